@@ -2,7 +2,9 @@ use std::error::Error;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum CompileError {
+    HeapLowering(fmm::analysis::HeapLoweringError),
     TypeCheck(fmm::analysis::TypeCheckError),
+    UnsupportedPrimitiveType(fmm::types::Primitive),
 }
 
 impl Error for CompileError {}
@@ -10,11 +12,25 @@ impl Error for CompileError {}
 impl std::fmt::Display for CompileError {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            Self::HeapLowering(error) => write!(formatter, "{}", error),
             Self::TypeCheck(error) => write!(formatter, "{}", error),
+            Self::UnsupportedPrimitiveType(primitive) => {
+                write!(
+                    formatter,
+                    "primitive type not supported by target: {:?}",
+                    primitive
+                )
+            }
         }
     }
 }
 
+impl From<fmm::analysis::HeapLoweringError> for CompileError {
+    fn from(error: fmm::analysis::HeapLoweringError) -> Self {
+        Self::HeapLowering(error)
+    }
+}
+
 impl From<fmm::analysis::TypeCheckError> for CompileError {
     fn from(error: fmm::analysis::TypeCheckError) -> Self {
         Self::TypeCheck(error)
@@ -12,6 +12,11 @@ pub fn compile_expression(
         |expression| compile_expression(expression, global_variables, type_ids);
 
     match expression {
+        Expression::AbsoluteAddress(address) => format!(
+            "(({})({}))",
+            compile_type_id(address.type_(), type_ids),
+            compile_primitive(address.address())
+        ),
         Expression::AlignOf(align_of) => {
             format!("alignof({})", compile_type_id(align_of.type_(), type_ids))
         }
@@ -29,6 +34,9 @@ pub fn compile_expression(
                 compile_expression(bit_cast.expression()),
             )
         }
+        Expression::BitManipulationOperation(operation) => {
+            compile_bit_manipulation_operation(operation, &compile_expression(operation.value()))
+        }
         Expression::BitwiseNotOperation(operation) => {
             format!("(~({}))", compile_expression(operation.value()))
         }
@@ -44,18 +52,56 @@ pub fn compile_expression(
                 compile_expression(operation.rhs()),
             )
         }
-        Expression::ComparisonOperation(operation) => format!(
-            "{}{}{}",
-            compile_expression(operation.lhs()),
-            compile_comparison_operator(operation.operator()),
-            compile_expression(operation.rhs()),
+        Expression::ByteOffset(offset) => format!(
+            "(({})((char*)({})+({})))",
+            compile_type_id(&offset.type_().clone().into(), type_ids),
+            compile_expression(offset.pointer()),
+            compile_expression(offset.offset()),
         ),
+        Expression::ComparisonOperation(operation) => compile_comparison_operation(
+            operation,
+            &compile_expression(operation.lhs()),
+            &compile_expression(operation.rhs()),
+        ),
+        Expression::ExtractBits(extract) => format!(
+            "(({})((({})>>{})&{}))",
+            compile_primitive_type_id(extract.type_()),
+            compile_expression(extract.value()),
+            extract.offset(),
+            compile_bit_mask(extract.type_(), extract.width()),
+        ),
+        Expression::InsertBits(insert) => {
+            let mask = format!(
+                "({}<<{})",
+                compile_bit_mask(insert.type_(), insert.width()),
+                insert.offset()
+            );
+
+            format!(
+                "(({})((({})&~{})|((({})<<{})&{})))",
+                compile_primitive_type_id(insert.type_()),
+                compile_expression(insert.value()),
+                mask,
+                compile_expression(insert.field()),
+                insert.offset(),
+                mask,
+            )
+        }
         Expression::PointerAddress(address) => format!(
             "(({})+({}))",
             compile_expression(address.pointer()),
             compile_expression(address.offset()),
         ),
         Expression::Primitive(primitive) => compile_primitive(*primitive),
+        Expression::PureCall(call) => format!(
+            "{}({})",
+            compile_expression(call.function()),
+            call.arguments()
+                .iter()
+                .map(&compile_expression)
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
         Expression::Record(record) => {
             format!(
                 "({}){{{}}}",
@@ -73,6 +119,12 @@ pub fn compile_expression(
             compile_expression(address.pointer()),
             generate_record_element_name(address.element_index()),
         ),
+        Expression::Select(select) => format!(
+            "(({})?({}):({}))",
+            compile_expression(select.condition()),
+            compile_expression(select.then()),
+            compile_expression(select.else_()),
+        ),
         Expression::SizeOf(size_of) => {
             format!("sizeof({})", compile_type_id(size_of.type_(), type_ids))
         }
@@ -104,12 +156,93 @@ pub fn compile_expression(
     }
 }
 
+fn compile_bit_manipulation_operation(operation: &BitManipulationOperation, value: &str) -> String {
+    let type_ = operation.type_();
+
+    format!(
+        "(({})({}))",
+        compile_primitive_type_id(type_),
+        if type_ == types::Primitive::Integer128 {
+            compile_wide_bit_manipulation(operation.operator(), value)
+        } else {
+            let bits = compile_bit_width(type_);
+
+            match operation.operator() {
+                BitManipulationOperator::ByteSwap => match type_ {
+                    types::Primitive::Integer16 => format!("__builtin_bswap16({})", value),
+                    types::Primitive::Integer32 => format!("__builtin_bswap32({})", value),
+                    types::Primitive::Integer64 => format!("__builtin_bswap64({})", value),
+                    _ => format!(
+                        "sizeof(size_t)==8?__builtin_bswap64({}):__builtin_bswap32({})",
+                        value, value
+                    ),
+                },
+                BitManipulationOperator::CountLeadingZeros => format!(
+                    "({})==0?{}:__builtin_clzll((uint64_t)({}))-(64-{})",
+                    value, bits, value, bits
+                ),
+                BitManipulationOperator::CountTrailingZeros => format!(
+                    "({})==0?{}:__builtin_ctzll((uint64_t)({}))",
+                    value, bits, value
+                ),
+                BitManipulationOperator::PopulationCount => {
+                    format!("__builtin_popcountll((uint64_t)({}))", value)
+                }
+            }
+        }
+    )
+}
+
+// Builtins for 128-bit integers are composed of ones for their 64-bit halves.
+fn compile_wide_bit_manipulation(operator: BitManipulationOperator, value: &str) -> String {
+    let low = format!("(uint64_t)({})", value);
+    let high = format!("(uint64_t)(({})>>64)", value);
+
+    match operator {
+        BitManipulationOperator::ByteSwap => format!("__builtin_bswap128({})", value),
+        BitManipulationOperator::CountLeadingZeros => format!(
+            "{}!=0?__builtin_clzll({}):{}!=0?64+__builtin_clzll({}):128",
+            high, high, low, low
+        ),
+        BitManipulationOperator::CountTrailingZeros => format!(
+            "{}!=0?__builtin_ctzll({}):{}!=0?64+__builtin_ctzll({}):128",
+            low, low, high, high
+        ),
+        BitManipulationOperator::PopulationCount => format!(
+            "__builtin_popcountll({})+__builtin_popcountll({})",
+            low, high
+        ),
+    }
+}
+
+fn compile_bit_width(type_: types::Primitive) -> String {
+    match type_ {
+        types::Primitive::Integer8 => "8".into(),
+        types::Primitive::Integer16 => "16".into(),
+        types::Primitive::Integer32 => "32".into(),
+        types::Primitive::Integer64 => "64".into(),
+        types::Primitive::Integer128 => "128".into(),
+        _ => format!("(sizeof({})*8)", compile_primitive_type_id(type_)),
+    }
+}
+
+// A mask is computed with a shift by less than a bit width of a type to avoid
+// undefined behavior on full-width masks.
+fn compile_bit_mask(type_: types::Primitive, width: u32) -> String {
+    format!(
+        "((({})2<<{})-1)",
+        compile_primitive_type_id(type_),
+        width - 1
+    )
+}
+
 fn compile_undefined(
     undefined: &Undefined,
     type_ids: &HashMap<fmm::types::Type, String>,
 ) -> String {
     match undefined.type_() {
         types::Type::Function(_) => "NULL".into(),
+        types::Type::Opaque(_) => unreachable!("undefined value of opaque type"),
         types::Type::Primitive(primitive) => compile_undefined_primitive(*primitive).into(),
         types::Type::Pointer(_) => {
             format!("({})NULL", compile_type_id(undefined.type_(), type_ids))
@@ -124,11 +257,19 @@ fn compile_undefined(
 fn compile_primitive(primitive: Primitive) -> String {
     match primitive {
         Primitive::Boolean(boolean) => format!("{}", boolean),
+        Primitive::Float16(number) => format!("((_Float16){})", number),
         Primitive::Float32(number) => format!("{}", number),
         Primitive::Float64(number) => format!("{}", number),
+        Primitive::Float128(number) => format!("((__float128){})", number),
         Primitive::Integer8(number) => format!("{}", number),
+        Primitive::Integer16(number) => format!("{}", number),
         Primitive::Integer32(number) => format!("{}", number),
         Primitive::Integer64(number) => format!("{}", number),
+        Primitive::Integer128(number) => format!(
+            "((unsigned __int128){}u<<64|{}u)",
+            (number >> 64) as u64,
+            number as u64
+        ),
         Primitive::PointerInteger(number) => format!("{}", number),
     }
 }
@@ -136,10 +277,15 @@ fn compile_primitive(primitive: Primitive) -> String {
 fn compile_undefined_primitive(primitive: types::Primitive) -> &'static str {
     match primitive {
         types::Primitive::Boolean => "false",
-        types::Primitive::Float32 | types::Primitive::Float64 => "0.0",
+        types::Primitive::Float16
+        | types::Primitive::Float32
+        | types::Primitive::Float64
+        | types::Primitive::Float128 => "0.0",
         types::Primitive::Integer8
+        | types::Primitive::Integer16
         | types::Primitive::Integer32
         | types::Primitive::Integer64
+        | types::Primitive::Integer128
         | types::Primitive::PointerInteger => "0",
     }
 }
@@ -153,13 +299,32 @@ fn compile_arithmetic_operator(operator: ArithmeticOperator) -> &'static str {
     }
 }
 
-fn compile_comparison_operator(operator: ComparisonOperator) -> &'static str {
-    match operator {
-        ComparisonOperator::Equal => "==",
-        ComparisonOperator::NotEqual => "!=",
-        ComparisonOperator::LessThan => "<",
-        ComparisonOperator::LessThanOrEqual => "<=",
-        ComparisonOperator::GreaterThan => ">",
-        ComparisonOperator::GreaterThanOrEqual => ">=",
+fn compile_comparison_operation(operation: &ComparisonOperation, lhs: &str, rhs: &str) -> String {
+    let is_float = matches!(
+        operation.type_(),
+        types::Primitive::Float32 | types::Primitive::Float64
+    );
+
+    match operation.operator() {
+        ComparisonOperator::Equal => format!("{}=={}", lhs, rhs),
+        // C's != is true for NaN operands.
+        ComparisonOperator::NotEqual if is_float => {
+            format!("__builtin_islessgreater({},{})", lhs, rhs)
+        }
+        ComparisonOperator::NotEqual => format!("{}!={}", lhs, rhs),
+        ComparisonOperator::LessThan => format!("{}<{}", lhs, rhs),
+        ComparisonOperator::LessThanOrEqual => format!("{}<={}", lhs, rhs),
+        ComparisonOperator::GreaterThan => format!("{}>{}", lhs, rhs),
+        ComparisonOperator::GreaterThanOrEqual => format!("{}>={}", lhs, rhs),
+        ComparisonOperator::Ordered => format!("!__builtin_isunordered({},{})", lhs, rhs),
+        ComparisonOperator::Unordered => format!("__builtin_isunordered({},{})", lhs, rhs),
+        ComparisonOperator::UnorderedEqual => {
+            format!("!__builtin_islessgreater({},{})", lhs, rhs)
+        }
+        ComparisonOperator::UnorderedNotEqual => format!("{}!={}", lhs, rhs),
+        ComparisonOperator::UnorderedLessThan => format!("!({}>={})", lhs, rhs),
+        ComparisonOperator::UnorderedLessThanOrEqual => format!("!({}>{})", lhs, rhs),
+        ComparisonOperator::UnorderedGreaterThan => format!("!({}<={})", lhs, rhs),
+        ComparisonOperator::UnorderedGreaterThanOrEqual => format!("!({}<{})", lhs, rhs),
     }
 }
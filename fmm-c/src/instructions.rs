@@ -94,6 +94,30 @@ fn compile_instruction(
                 .collect::<Vec<_>>()
                 .join(",")
         ),
+        Instruction::CheckedArithmeticOperation(operation) => {
+            let name = "_checked_".to_owned() + operation.name();
+
+            format!(
+                "{} {};{};{}.{}={};{}.{}=({}){};",
+                compile_overflow_type_id(operation.type_(), operation.signed()),
+                name,
+                compile_typed_name(&operation.result_type().into(), operation.name()),
+                operation.name(),
+                generate_record_element_name(1),
+                compile_overflow_builtin(
+                    operation.type_(),
+                    operation.operator(),
+                    operation.signed(),
+                    &compile_expression(operation.lhs()),
+                    &compile_expression(operation.rhs()),
+                    &name,
+                ),
+                operation.name(),
+                generate_record_element_name(0),
+                compile_primitive_type_id(operation.type_()),
+                name,
+            )
+        }
         Instruction::CompareAndSwap(cas) => {
             let name = "_cas_".to_owned() + cas.name();
 
@@ -134,9 +158,19 @@ fn compile_instruction(
                 compile_atomic_ordering(fence.ordering())
             )
         }
+        Instruction::Fma(fma) => format!(
+            "{}=__builtin_fma{}({},{},{});",
+            compile_typed_name(&fma.type_().into(), fma.name()),
+            compile_float_function_suffix(fma.type_()),
+            compile_expression(fma.lhs()),
+            compile_expression(fma.rhs()),
+            compile_expression(fma.addend()),
+        ),
         Instruction::FreeHeap(free) => {
             format!("free((void *)({}));", compile_expression(free.pointer()))
         }
+        // C has no explicit lifetimes of stack variables.
+        Instruction::LifetimeEnd(_) | Instruction::LifetimeStart(_) => "".into(),
         Instruction::If(if_) => {
             let compile_block =
                 |block| compile_block(block, Some(if_.name()), global_variables, type_ids);
@@ -149,11 +183,92 @@ fn compile_instruction(
                 compile_block(if_.else_())
             )
         }
+        // Stack alignment is not configurable in GCC-style inline assembly.
+        Instruction::InlineAssembly(assembly) => {
+            let constraints = assembly
+                .constraints()
+                .split(',')
+                .map(str::trim)
+                .filter(|constraint| !constraint.is_empty())
+                .collect::<Vec<_>>();
+            let compile_constraints = |constraints: Vec<(&str, String)>| {
+                constraints
+                    .iter()
+                    .map(|(constraint, value)| format!("{:?}({})", constraint, value))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            };
+
+            format!(
+                "{};__asm__{}({:?}:{}:{}:{});",
+                compile_typed_name(assembly.type_(), assembly.name()),
+                if assembly.side_effects() {
+                    " volatile"
+                } else {
+                    ""
+                },
+                compile_assembly_template(assembly.template()),
+                compile_constraints(
+                    constraints
+                        .iter()
+                        .filter(|constraint| constraint.starts_with('='))
+                        .map(|constraint| (*constraint, assembly.name().into()))
+                        .collect()
+                ),
+                compile_constraints(
+                    constraints
+                        .iter()
+                        .filter(|constraint| !constraint.starts_with(&['=', '~'][..]))
+                        .zip(assembly.operands())
+                        .map(|(constraint, operand)| (*constraint, compile_expression(operand)))
+                        .collect()
+                ),
+                constraints
+                    .iter()
+                    .filter_map(|constraint| constraint.strip_prefix('~'))
+                    .map(|clobber| format!("{:?}", clobber.trim_matches(&['{', '}'][..])))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        }
         Instruction::Load(load) => format!(
             "{}=*{};",
             compile_typed_name(load.type_(), load.name()),
             compile_expression(load.pointer()),
         ),
+        Instruction::MathOperation(operation) => format!(
+            "{}=__builtin_{}{}({});",
+            compile_typed_name(&operation.type_().into(), operation.name()),
+            match operation.operator() {
+                MathOperator::Absolute => "fabs",
+                MathOperator::Ceiling => "ceil",
+                MathOperator::Cosine => "cos",
+                MathOperator::Exponential => "exp",
+                MathOperator::Floor => "floor",
+                MathOperator::Logarithm => "log",
+                MathOperator::Sine => "sin",
+                MathOperator::SquareRoot => "sqrt",
+            },
+            compile_float_function_suffix(operation.type_()),
+            compile_expression(operation.value()),
+        ),
+        Instruction::MemoryCopy(copy) => format!(
+            "__builtin_{}((void *)({}),(void *)({}),{});",
+            if copy.overlapping() {
+                "memmove"
+            } else {
+                "memcpy"
+            },
+            compile_expression(copy.destination()),
+            compile_expression(copy.source()),
+            compile_expression(copy.size()),
+        ),
+        Instruction::MemorySet(set) => format!(
+            "__builtin_memset((void *)({}),{},{});",
+            compile_expression(set.pointer()),
+            compile_expression(set.value()),
+            compile_expression(set.size()),
+        ),
         Instruction::PassThrough(pass) => format!(
             "{}={};",
             compile_typed_name(pass.type_(), pass.name()),
@@ -167,6 +282,29 @@ fn compile_instruction(
                 compile_expression(reallocate.size()),
             )
         }
+        Instruction::SaturatingArithmeticOperation(operation) => {
+            let name = "_saturating_".to_owned() + operation.name();
+            let lhs = compile_expression(operation.lhs());
+            let rhs = compile_expression(operation.rhs());
+
+            format!(
+                "{} {};{}={}?{}:({}){};",
+                compile_overflow_type_id(operation.type_(), operation.signed()),
+                name,
+                compile_typed_name(&operation.type_().into(), operation.name()),
+                compile_overflow_builtin(
+                    operation.type_(),
+                    operation.operator(),
+                    operation.signed(),
+                    &lhs,
+                    &rhs,
+                    &name,
+                ),
+                compile_saturated_value(operation, &lhs, &rhs),
+                compile_primitive_type_id(operation.type_()),
+                name,
+            )
+        }
         Instruction::Store(store) => format!(
             "*{}={};",
             compile_expression(store.pointer()),
@@ -175,6 +313,105 @@ fn compile_instruction(
     }
 }
 
+// Half-precision floats are computed in single precision.
+fn compile_float_function_suffix(type_: types::Primitive) -> &'static str {
+    match type_ {
+        types::Primitive::Float16 | types::Primitive::Float32 => "f",
+        types::Primitive::Float128 => "f128",
+        _ => "",
+    }
+}
+
+fn compile_overflow_type_id(type_: types::Primitive, signed: bool) -> String {
+    if signed {
+        compile_signed_primitive_type_id(type_)
+    } else {
+        compile_primitive_type_id(type_)
+    }
+}
+
+fn compile_overflow_builtin(
+    type_: types::Primitive,
+    operator: ArithmeticOperator,
+    signed: bool,
+    lhs: &str,
+    rhs: &str,
+    name: &str,
+) -> String {
+    let type_id = compile_overflow_type_id(type_, signed);
+
+    format!(
+        "__builtin_{}_overflow(({})({}),({})({}),&{})",
+        match operator {
+            ArithmeticOperator::Add => "add",
+            ArithmeticOperator::Subtract => "sub",
+            ArithmeticOperator::Multiply => "mul",
+            ArithmeticOperator::Divide => unreachable!(),
+        },
+        type_id,
+        lhs,
+        type_id,
+        rhs,
+        name,
+    )
+}
+
+// A saturated value is chosen by a direction of overflow which depends on
+// signs of operands.
+fn compile_saturated_value(
+    operation: &SaturatingArithmeticOperation,
+    lhs: &str,
+    rhs: &str,
+) -> String {
+    let type_id = compile_primitive_type_id(operation.type_());
+    let maximum = format!("(({})-1)", type_id);
+    let minimum = format!("(({})0)", type_id);
+
+    if !operation.signed() {
+        return match operation.operator() {
+            ArithmeticOperator::Subtract => minimum,
+            _ => maximum,
+        };
+    }
+
+    let maximum = format!("(({})({}>>1))", type_id, maximum);
+    let minimum = format!("(({})~{})", type_id, maximum);
+    let signed_type_id = compile_signed_primitive_type_id(operation.type_());
+    let is_negative = |value| format!("(({})({})<0)", signed_type_id, value);
+
+    format!(
+        "({}?{}:{})",
+        match operation.operator() {
+            ArithmeticOperator::Add => is_negative(rhs),
+            ArithmeticOperator::Subtract => format!("!{}", is_negative(rhs)),
+            ArithmeticOperator::Multiply => format!("({}!={})", is_negative(lhs), is_negative(rhs)),
+            ArithmeticOperator::Divide => unreachable!(),
+        },
+        minimum,
+        maximum,
+    )
+}
+
+// Operands are referred to by `$n` in LLVM and `%n` in GCC.
+fn compile_assembly_template(template: &str) -> String {
+    let mut string = String::new();
+    let mut characters = template.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '%' => string.push_str("%%"),
+            '$' if characters.peek() == Some(&'$') => {
+                characters.next();
+                string.push('$');
+            }
+            '$' => string.push('%'),
+            _ => string.push(character),
+        }
+    }
+
+    string
+}
+
 fn compile_terminal_instruction(
     instruction: &TerminalInstruction,
     block_variable_name: Option<&str>,
@@ -191,6 +428,7 @@ fn compile_terminal_instruction(
         TerminalInstruction::Return(return_) => {
             format!("return {};", compile_expression(return_.expression(),))
         }
+        TerminalInstruction::Trap => "__builtin_trap();".into(),
         TerminalInstruction::Unreachable => "abort();".into(),
     }
 }
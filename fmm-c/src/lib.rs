@@ -4,6 +4,7 @@ mod instructions;
 mod malloc_configuration;
 mod names;
 mod renaming;
+mod target_configuration;
 mod types;
 
 pub use error::*;
@@ -14,6 +15,7 @@ pub use malloc_configuration::MallocConfiguration;
 use names::*;
 use renaming::rename_names;
 use std::collections::{HashMap, HashSet};
+pub use target_configuration::TargetConfiguration;
 use types::*;
 
 const INCLUDES: &[&str] = &[
@@ -28,9 +30,27 @@ pub fn compile(
     module: &Module,
     malloc_configuration: Option<MallocConfiguration>,
 ) -> Result<String, CompileError> {
-    fmm::analysis::check_types(module)?;
+    compile_with_target(
+        module,
+        malloc_configuration,
+        &TargetConfiguration::default(),
+    )
+}
 
-    let module = rename_names(module);
+pub fn compile_with_target(
+    module: &Module,
+    malloc_configuration: Option<MallocConfiguration>,
+    target_configuration: &TargetConfiguration,
+) -> Result<String, CompileError> {
+    fmm::analysis::check_types(module)?;
+    fmm::analysis::check_atomic_types(module, &target_configuration.data_layout)?;
+
+    let module = if let Some(configuration) = &malloc_configuration {
+        fmm::analysis::lower_heap_allocations(module, &configuration.heap_allocation_strategy)?
+    } else {
+        module.clone()
+    };
+    let module = rename_names(&module);
     let global_variables = module
         .variable_declarations()
         .iter()
@@ -43,6 +63,9 @@ pub fn compile(
         )
         .collect();
     let types = collect_types(&module);
+
+    check_primitive_types(&types, target_configuration)?;
+
     let type_ids = compile_type_ids(&types);
 
     Ok(INCLUDES
@@ -69,6 +92,9 @@ pub fn compile(
             collect_types(&module)
                 .iter()
                 .filter_map(|type_| match type_ {
+                    fmm::types::Type::Opaque(opaque) => {
+                        Some(compile_opaque_type_declaration(opaque, &type_ids))
+                    }
                     fmm::types::Type::Record(record) => {
                         Some(compile_record_type_definition(record, &type_ids))
                     }
@@ -115,6 +141,13 @@ pub fn compile(
         .join("\n"))
 }
 
+fn compile_opaque_type_declaration(
+    opaque: &fmm::types::Opaque,
+    type_ids: &HashMap<fmm::types::Type, String>,
+) -> String {
+    format!("struct {};", type_ids[&opaque.clone().into()])
+}
+
 fn compile_record_type_definition(
     record: &fmm::types::Record,
     type_ids: &HashMap<fmm::types::Type, String>,
@@ -225,6 +258,7 @@ fn compile_function_definition(
         + "\n}"
 }
 
+// Types are unique and sorted already so that names are deterministic.
 fn compile_type_ids(types: &[fmm::types::Type]) -> HashMap<fmm::types::Type, String> {
     types
         .iter()
@@ -235,8 +269,6 @@ fn compile_type_ids(types: &[fmm::types::Type]) -> HashMap<fmm::types::Type, Str
                 None
             }
         })
-        .collect::<HashSet<_>>()
-        .into_iter()
         .enumerate()
         .map(|(index, record)| (record.clone().into(), generate_record_type_name(index)))
         .chain(
@@ -249,11 +281,22 @@ fn compile_type_ids(types: &[fmm::types::Type]) -> HashMap<fmm::types::Type, Str
                         None
                     }
                 })
-                .collect::<HashSet<_>>()
-                .into_iter()
                 .enumerate()
                 .map(|(index, union)| (union.clone().into(), generate_union_type_name(index))),
         )
+        .chain(
+            types
+                .iter()
+                .filter_map(|type_| {
+                    if let fmm::types::Type::Opaque(opaque) = type_ {
+                        Some(opaque)
+                    } else {
+                        None
+                    }
+                })
+                .enumerate()
+                .map(|(index, opaque)| (opaque.clone().into(), generate_opaque_type_name(index))),
+        )
         .collect()
 }
 
@@ -268,9 +311,27 @@ fn compile_linkage(linkage: Linkage) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use fmm::types::{self, CallingConvention, Type};
+    use fmm::{
+        analysis::{
+            BumpPointerConfiguration, HeapAllocationStrategy, TargetDataLayout, TypeCheckError,
+        },
+        types::{self, CallingConvention, Type},
+    };
+
+    const TARGET_CONFIGURATION: TargetConfiguration = TargetConfiguration {
+        data_layout: TargetDataLayout::new(8, 8),
+        float16_supported: true,
+        float128_supported: true,
+    };
 
     fn compile_final_module(module: &Module) {
+        compile_final_module_with_strategy(module, HeapAllocationStrategy::Malloc);
+    }
+
+    fn compile_final_module_with_strategy(
+        module: &Module,
+        heap_allocation_strategy: HeapAllocationStrategy,
+    ) {
         let directory = tempfile::tempdir().unwrap();
         let file_path = directory.path().join("foo.c");
         let source = compile(
@@ -278,6 +339,7 @@ mod tests {
             Some(MallocConfiguration {
                 malloc_function_name: "my_malloc".into(),
                 realloc_function_name: "my_realloc".into(),
+                heap_allocation_strategy,
             }),
         )
         .unwrap();
@@ -379,6 +441,19 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_opaque_pointer() {
+            compile_module(&Module::new(
+                vec![VariableDeclaration::new(
+                    "x",
+                    types::Pointer::new(types::Opaque::new("foo")),
+                )],
+                vec![],
+                vec![],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_function_pointer() {
             compile_module(&Module::new(
@@ -699,6 +774,25 @@ mod tests {
     mod expressions {
         use super::*;
 
+        #[test]
+        fn compile_absolute_address() {
+            let type_ = types::Pointer::new(types::Primitive::Integer16);
+
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    AbsoluteAddress::new(type_.clone(), Primitive::PointerInteger(0xB8000)),
+                    type_,
+                    false,
+                    Linkage::Internal,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_size_of() {
             compile_module(&Module::new(
@@ -716,6 +810,27 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_size_of_record_of_small_integers() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    fmm::ir::SizeOf::new(types::Record::new(vec![
+                        types::Primitive::Integer8.into(),
+                        types::Primitive::Integer16.into(),
+                        types::Primitive::Integer32.into(),
+                    ])),
+                    types::Primitive::PointerInteger,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_align_of() {
             compile_module(&Module::new(
@@ -754,6 +869,245 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_population_count() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    BitManipulationOperation::new(
+                        types::Primitive::Integer32,
+                        BitManipulationOperator::PopulationCount,
+                        Primitive::Integer32(0x1234_5678),
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_count_leading_zeros() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    BitManipulationOperation::new(
+                        types::Primitive::Integer64,
+                        BitManipulationOperator::CountLeadingZeros,
+                        Primitive::Integer64(0x00ff_0000),
+                    ),
+                    types::Primitive::Integer64,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_count_trailing_zeros() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    BitManipulationOperation::new(
+                        types::Primitive::Integer128,
+                        BitManipulationOperator::CountTrailingZeros,
+                        Primitive::Integer128(0),
+                    ),
+                    types::Primitive::Integer128,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_byte_swap() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    BitManipulationOperation::new(
+                        types::Primitive::PointerInteger,
+                        BitManipulationOperator::ByteSwap,
+                        Primitive::PointerInteger(0x1234),
+                    ),
+                    types::Primitive::PointerInteger,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_float_comparisons() {
+            for operator in [
+                ComparisonOperator::Equal,
+                ComparisonOperator::NotEqual,
+                ComparisonOperator::LessThan,
+                ComparisonOperator::Ordered,
+                ComparisonOperator::Unordered,
+                ComparisonOperator::UnorderedEqual,
+                ComparisonOperator::UnorderedNotEqual,
+                ComparisonOperator::UnorderedLessThan,
+                ComparisonOperator::UnorderedLessThanOrEqual,
+                ComparisonOperator::UnorderedGreaterThan,
+                ComparisonOperator::UnorderedGreaterThanOrEqual,
+            ] {
+                compile_module(&Module::new(
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![create_function_definition(
+                        "f",
+                        vec![
+                            Argument::new("x", types::Primitive::Float64),
+                            Argument::new("y", types::Primitive::Float64),
+                        ],
+                        Block::new(
+                            vec![],
+                            Return::new(
+                                types::Primitive::Boolean,
+                                ComparisonOperation::new(
+                                    types::Primitive::Float64,
+                                    operator,
+                                    Variable::new("x"),
+                                    Variable::new("y"),
+                                ),
+                            ),
+                        ),
+                        types::Primitive::Boolean,
+                        Linkage::External,
+                    )],
+                ));
+            }
+        }
+
+        #[test]
+        fn compile_extract_bits() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    ExtractBits::new(
+                        types::Primitive::Integer32,
+                        Primitive::Integer32(0b1010_1101_0000),
+                        3,
+                        5,
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_insert_bits() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    InsertBits::new(
+                        types::Primitive::Integer32,
+                        Primitive::Integer32(0xffff_ffff),
+                        Primitive::Integer32(0b10),
+                        3,
+                        5,
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_pure_call() {
+            let function_type = types::Function::new(
+                vec![types::Primitive::PointerInteger.into()],
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+            );
+
+            compile_module(&Module::new(
+                vec![],
+                vec![FunctionDeclaration::with_attributes(
+                    "f",
+                    function_type.clone(),
+                    FunctionAttributes {
+                        read_none: true,
+                        ..Default::default()
+                    },
+                )],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![Argument::new("x", types::Primitive::PointerInteger)],
+                    Block::new(
+                        vec![],
+                        Return::new(
+                            types::Primitive::PointerInteger,
+                            PureCall::new(
+                                function_type.clone(),
+                                Variable::new("f"),
+                                vec![PureCall::new(
+                                    function_type,
+                                    Variable::new("f"),
+                                    vec![Variable::new("x").into()],
+                                )
+                                .into()],
+                            ),
+                        ),
+                    ),
+                    types::Primitive::PointerInteger,
+                    Linkage::External,
+                )],
+            ));
+        }
+
+        #[test]
+        fn compile_select() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    Select::new(
+                        types::Primitive::Integer32,
+                        Primitive::Boolean(true),
+                        Primitive::Integer32(1),
+                        Primitive::Integer32(2),
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_bitwise_and() {
             compile_module(&Module::new(
@@ -869,6 +1223,28 @@ mod tests {
             }
         }
 
+        #[test]
+        fn compile_integer128_arithmetic_operation() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    ArithmeticOperation::new(
+                        types::Primitive::Integer128,
+                        ArithmeticOperator::Add,
+                        Primitive::Integer128(u128::MAX),
+                        Primitive::Integer128(1 << 64),
+                    ),
+                    types::Primitive::Integer128,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_comparison_operation() {
             for &operator in &[
@@ -996,6 +1372,52 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_byte_offset() {
+            let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", pointer_type.clone())],
+                Block::new(
+                    vec![],
+                    Return::new(
+                        pointer_type.clone(),
+                        ByteOffset::new(
+                            pointer_type.clone(),
+                            Variable::new("x"),
+                            Primitive::PointerInteger(1),
+                        ),
+                    ),
+                ),
+                pointer_type,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_constant_byte_offset() {
+            let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    ByteOffset::new(
+                        pointer_type.clone(),
+                        Undefined::new(pointer_type.clone()),
+                        Primitive::PointerInteger(1),
+                    ),
+                    pointer_type,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_constant_pointer_address() {
             compile_module(&Module::new(
@@ -1098,6 +1520,33 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_allocate_heap_with_bump_pointer() {
+            compile_final_module_with_strategy(
+                &Module::new(
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![create_function_definition(
+                        "f",
+                        vec![],
+                        Block::new(
+                            vec![AllocateHeap::new(Primitive::PointerInteger(42), "y").into()],
+                            Return::new(types::GENERIC_POINTER_TYPE.clone(), Variable::new("y")),
+                        ),
+                        types::GENERIC_POINTER_TYPE.clone(),
+                        Linkage::External,
+                    )],
+                ),
+                HeapAllocationStrategy::BumpPointer(BumpPointerConfiguration {
+                    pointer_variable_name: "heap_pointer".into(),
+                    limit_variable_name: "heap_limit".into(),
+                    slow_path_function_name: "collect".into(),
+                    alignment: 8,
+                }),
+            );
+        }
+
         #[test]
         fn compile_reallocate_heap() {
             compile_function_definition(create_function_definition(
@@ -1156,6 +1605,73 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn fail_to_compile_unsupported_float_type() {
+            assert_eq!(
+                compile_with_target(
+                    &Module::new(
+                        vec![],
+                        vec![],
+                        vec![VariableDefinition::new(
+                            "x",
+                            Primitive::Float128(42.0),
+                            types::Primitive::Float128,
+                            false,
+                            Linkage::External,
+                            None,
+                        )],
+                        vec![],
+                    ),
+                    None,
+                    &TargetConfiguration {
+                        float128_supported: false,
+                        ..TARGET_CONFIGURATION
+                    },
+                ),
+                Err(CompileError::UnsupportedPrimitiveType(
+                    types::Primitive::Float128
+                ))
+            );
+        }
+
+        #[test]
+        fn fail_to_compile_atomic_load_wider_than_target_supports() {
+            assert!(matches!(
+                compile_with_target(
+                    &Module::new(
+                        vec![],
+                        vec![],
+                        vec![],
+                        vec![create_function_definition(
+                            "f",
+                            vec![Argument::new(
+                                "x",
+                                types::Pointer::new(types::Primitive::Integer128),
+                            )],
+                            Block::new(
+                                vec![AtomicLoad::new(
+                                    types::Primitive::Integer128,
+                                    Variable::new("x"),
+                                    AtomicOrdering::Relaxed,
+                                    "y",
+                                )
+                                .into()],
+                                Return::new(types::Primitive::Integer128, Variable::new("y")),
+                            ),
+                            types::Primitive::Integer128,
+                            Linkage::External,
+                        )],
+                    ),
+                    None,
+                    &TARGET_CONFIGURATION,
+                ),
+                Err(CompileError::TypeCheck(TypeCheckError::InvalidAtomicType(
+                    _,
+                    _
+                )))
+            ));
+        }
+
         #[test]
         fn compile_atomic_load_with_function_pointer() {
             let function_type = create_function_type(
@@ -1255,6 +1771,113 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_inline_assembly() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![InlineAssembly::new(
+                        types::Primitive::PointerInteger,
+                        "",
+                        "=r,0,~{memory}",
+                        vec![Variable::new("x").into()],
+                        true,
+                        false,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_inline_assembly_without_output() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![InlineAssembly::new(
+                        types::Primitive::PointerInteger,
+                        "",
+                        "r",
+                        vec![Variable::new("x").into()],
+                        true,
+                        false,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_memory_copy() {
+            for &overlapping in &[false, true] {
+                compile_function_definition(create_function_definition(
+                    "f",
+                    vec![
+                        Argument::new("x", types::GENERIC_POINTER_TYPE.clone()),
+                        Argument::new("y", types::GENERIC_POINTER_TYPE.clone()),
+                    ],
+                    Block::new(
+                        vec![MemoryCopy::new(
+                            Variable::new("x"),
+                            Variable::new("y"),
+                            Primitive::PointerInteger(8),
+                            overlapping,
+                        )
+                        .into()],
+                        Return::new(
+                            types::Primitive::PointerInteger,
+                            Primitive::PointerInteger(0),
+                        ),
+                    ),
+                    types::Primitive::PointerInteger,
+                    Linkage::External,
+                ));
+            }
+        }
+
+        #[test]
+        fn compile_memory_set() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", types::GENERIC_POINTER_TYPE.clone())],
+                Block::new(
+                    vec![MemorySet::new(
+                        Variable::new("x"),
+                        Primitive::Integer8(0),
+                        Primitive::PointerInteger(8),
+                    )
+                    .into()],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(0),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_trap() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![],
+                Block::new(vec![], TerminalInstruction::Trap),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
         #[test]
         fn compile_if() {
             compile_function_definition(create_function_definition(
@@ -1495,6 +2118,126 @@ mod tests {
             }
         }
 
+        #[test]
+        fn compile_saturating_arithmetic_operation() {
+            for &type_ in &[
+                types::Primitive::Integer8,
+                types::Primitive::Integer64,
+                types::Primitive::Integer128,
+                types::Primitive::PointerInteger,
+            ] {
+                for &operator in &[
+                    ArithmeticOperator::Add,
+                    ArithmeticOperator::Subtract,
+                    ArithmeticOperator::Multiply,
+                ] {
+                    for &signed in &[false, true] {
+                        compile_function_definition(create_function_definition(
+                            "f",
+                            vec![Argument::new("x", type_)],
+                            Block::new(
+                                vec![SaturatingArithmeticOperation::new(
+                                    type_,
+                                    operator,
+                                    signed,
+                                    Variable::new("x"),
+                                    Variable::new("x"),
+                                    "y",
+                                )
+                                .into()],
+                                Return::new(type_, Variable::new("y")),
+                            ),
+                            type_,
+                            Linkage::External,
+                        ));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn compile_checked_arithmetic_operation() {
+            for &operator in &[
+                ArithmeticOperator::Add,
+                ArithmeticOperator::Subtract,
+                ArithmeticOperator::Multiply,
+            ] {
+                for &signed in &[false, true] {
+                    let operation = CheckedArithmeticOperation::new(
+                        types::Primitive::Integer32,
+                        operator,
+                        signed,
+                        Variable::new("x"),
+                        Primitive::Integer32(42),
+                        "y",
+                    );
+
+                    compile_function_definition(create_function_definition(
+                        "f",
+                        vec![Argument::new("x", types::Primitive::Integer32)],
+                        Block::new(
+                            vec![operation.clone().into()],
+                            Return::new(operation.result_type(), Variable::new("y")),
+                        ),
+                        operation.result_type(),
+                        Linkage::External,
+                    ));
+                }
+            }
+        }
+
+        #[test]
+        fn compile_fma() {
+            for &type_ in &[types::Primitive::Float32, types::Primitive::Float64] {
+                compile_function_definition(create_function_definition(
+                    "f",
+                    vec![Argument::new("x", type_)],
+                    Block::new(
+                        vec![Fma::new(
+                            type_,
+                            Variable::new("x"),
+                            Variable::new("x"),
+                            Variable::new("x"),
+                            "y",
+                        )
+                        .into()],
+                        Return::new(type_, Variable::new("y")),
+                    ),
+                    type_,
+                    Linkage::External,
+                ));
+            }
+        }
+
+        #[test]
+        fn compile_math_operation() {
+            for &operator in &[
+                MathOperator::Absolute,
+                MathOperator::Ceiling,
+                MathOperator::Cosine,
+                MathOperator::Exponential,
+                MathOperator::Floor,
+                MathOperator::Logarithm,
+                MathOperator::Sine,
+                MathOperator::SquareRoot,
+            ] {
+                for &type_ in &[types::Primitive::Float32, types::Primitive::Float64] {
+                    compile_function_definition(create_function_definition(
+                        "f",
+                        vec![Argument::new("x", type_)],
+                        Block::new(
+                            vec![
+                                MathOperation::new(type_, operator, Variable::new("x"), "y").into()
+                            ],
+                            Return::new(type_, Variable::new("y")),
+                        ),
+                        type_,
+                        Linkage::External,
+                    ));
+                }
+            }
+        }
+
         #[test]
         fn compile_free_heap() {
             compile_function_definition(create_function_definition(
@@ -1511,5 +2254,26 @@ mod tests {
                 Linkage::External,
             ));
         }
+
+        #[test]
+        fn compile_lifetime_markers() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![
+                        AllocateStack::new(types::Primitive::PointerInteger, "x").into(),
+                        LifetimeStart::new(Variable::new("x")).into(),
+                        LifetimeEnd::new(Variable::new("x")).into(),
+                    ],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(0),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
     }
 }
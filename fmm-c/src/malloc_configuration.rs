@@ -1,4 +1,7 @@
+use fmm::analysis::HeapAllocationStrategy;
+
 pub struct MallocConfiguration {
     pub malloc_function_name: String,
     pub realloc_function_name: String,
+    pub heap_allocation_strategy: HeapAllocationStrategy,
 }
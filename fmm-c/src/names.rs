@@ -10,6 +10,10 @@ pub fn generate_union_member_name(index: usize) -> String {
     format!("m{}", index)
 }
 
+pub fn generate_opaque_type_name(index: usize) -> String {
+    format!("o{}", index)
+}
+
 pub fn generate_record_type_name(index: usize) -> String {
     format!("r{}", index)
 }
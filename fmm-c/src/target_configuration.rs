@@ -0,0 +1,18 @@
+use fmm::analysis::TargetDataLayout;
+
+pub struct TargetConfiguration {
+    pub data_layout: TargetDataLayout,
+    pub float16_supported: bool,
+    pub float128_supported: bool,
+}
+
+// The default configuration is for 64-bit targets.
+impl Default for TargetConfiguration {
+    fn default() -> Self {
+        Self {
+            data_layout: TargetDataLayout::new(8, 16),
+            float16_supported: true,
+            float128_supported: true,
+        }
+    }
+}
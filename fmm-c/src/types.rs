@@ -1,4 +1,4 @@
-use crate::names::*;
+use crate::{names::*, CompileError, TargetConfiguration};
 use fmm::types::{self, Type};
 use std::collections::HashMap;
 
@@ -7,6 +7,7 @@ pub fn compile_typed_name(type_: &Type, name: &str, type_ids: &HashMap<Type, Str
         Type::Function(function) => {
             compile_function_name(function, &format!("(*{})", name), type_ids)
         }
+        Type::Opaque(opaque) => compile_opaque_type_id(opaque, type_ids) + " " + name,
         Type::Primitive(primitive) => compile_primitive_type_id(*primitive) + " " + name,
         Type::Record(record) => compile_record_type_id(record, type_ids) + " " + name,
         Type::Pointer(pointer) => {
@@ -45,6 +46,7 @@ pub fn compile_function_name(
 pub fn compile_type_id(type_: &Type, type_ids: &HashMap<Type, String>) -> String {
     match type_ {
         Type::Function(function) => compile_function_name(function, "(*)", type_ids),
+        Type::Opaque(opaque) => compile_opaque_type_id(opaque, type_ids),
         Type::Primitive(primitive) => compile_primitive_type_id(*primitive),
         Type::Record(record) => compile_record_type_id(record, type_ids),
         Type::Pointer(pointer) => compile_typed_name(pointer.element(), "*", type_ids),
@@ -56,7 +58,7 @@ pub fn compile_atomic_pointer_type_id(type_: &Type, type_ids: &HashMap<Type, Str
     match type_ {
         Type::Function(function) => compile_function_name(function, "(*_Atomic *)", type_ids),
         Type::Pointer(pointer) => compile_typed_name(pointer.element(), "_Atomic *", type_ids),
-        Type::Primitive(_) | Type::Record(_) | Type::Union(_) => {
+        Type::Opaque(_) | Type::Primitive(_) | Type::Record(_) | Type::Union(_) => {
             "_Atomic ".to_owned() + &compile_type_id(type_, type_ids) + " *"
         }
     }
@@ -65,16 +67,60 @@ pub fn compile_atomic_pointer_type_id(type_: &Type, type_ids: &HashMap<Type, Str
 pub fn compile_primitive_type_id(primitive: types::Primitive) -> String {
     match primitive {
         types::Primitive::Boolean => "bool",
+        types::Primitive::Float16 => "_Float16",
         types::Primitive::Float32 => "float",
         types::Primitive::Float64 => "double",
+        types::Primitive::Float128 => "__float128",
         types::Primitive::Integer8 => "uint8_t",
+        types::Primitive::Integer16 => "uint16_t",
         types::Primitive::Integer32 => "uint32_t",
         types::Primitive::Integer64 => "uint64_t",
+        types::Primitive::Integer128 => "unsigned __int128",
         types::Primitive::PointerInteger => "size_t",
     }
     .into()
 }
 
+// C compilers cannot represent floating-point numbers of some widths on some
+// targets.
+pub fn check_primitive_types(
+    types: &[Type],
+    configuration: &TargetConfiguration,
+) -> Result<(), CompileError> {
+    for type_ in types {
+        if let Type::Primitive(primitive) = type_ {
+            let supported = match primitive {
+                types::Primitive::Float16 => configuration.float16_supported,
+                types::Primitive::Float128 => configuration.float128_supported,
+                _ => true,
+            };
+
+            if !supported {
+                return Err(CompileError::UnsupportedPrimitiveType(*primitive));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn compile_signed_primitive_type_id(primitive: types::Primitive) -> String {
+    match primitive {
+        types::Primitive::Integer8 => "int8_t",
+        types::Primitive::Integer16 => "int16_t",
+        types::Primitive::Integer32 => "int32_t",
+        types::Primitive::Integer64 => "int64_t",
+        types::Primitive::Integer128 => "__int128",
+        types::Primitive::PointerInteger => "intptr_t",
+        _ => return compile_primitive_type_id(primitive),
+    }
+    .into()
+}
+
+pub fn compile_opaque_type_id(opaque: &types::Opaque, type_ids: &HashMap<Type, String>) -> String {
+    "struct ".to_owned() + &type_ids[&opaque.clone().into()]
+}
+
 pub fn compile_record_type_id(record: &types::Record, type_ids: &HashMap<Type, String>) -> String {
     "struct ".to_owned() + &type_ids[&record.clone().into()]
 }
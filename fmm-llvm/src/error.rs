@@ -2,6 +2,7 @@ use std::error::Error;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum CompileError {
+    HeapLowering(fmm::analysis::HeapLoweringError),
     Llvm(String),
     TargetMachineNotCreated,
     TypeCheck(fmm::analysis::TypeCheckError),
@@ -12,6 +13,7 @@ impl Error for CompileError {}
 impl std::fmt::Display for CompileError {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            Self::HeapLowering(error) => write!(formatter, "{}", error),
             CompileError::Llvm(string) => {
                 write!(formatter, "{}", string)
             }
@@ -35,6 +37,12 @@ impl From<&str> for CompileError {
     }
 }
 
+impl From<fmm::analysis::HeapLoweringError> for CompileError {
+    fn from(error: fmm::analysis::HeapLoweringError) -> Self {
+        Self::HeapLowering(error)
+    }
+}
+
 impl From<fmm::analysis::TypeCheckError> for CompileError {
     fn from(error: fmm::analysis::TypeCheckError) -> Self {
         Self::TypeCheck(error)
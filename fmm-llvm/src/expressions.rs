@@ -1,10 +1,14 @@
 use super::types;
-use crate::union::compile_union_cast;
+use crate::{
+    calling_convention::compile_calling_convention, intrinsics::get_bit_manipulation_function,
+    union::compile_union_cast,
+};
 use fmm::ir::*;
 use inkwell::values::BasicValue;
 use std::collections::HashMap;
 
 pub fn compile_expression<'c>(
+    module: &inkwell::module::Module<'c>,
     builder: &inkwell::builder::Builder<'c>,
     expression: &Expression,
     variables: &HashMap<String, inkwell::values::BasicValueEnum<'c>>,
@@ -12,10 +16,13 @@ pub fn compile_expression<'c>(
     target_data: &inkwell::targets::TargetData,
 ) -> inkwell::values::BasicValueEnum<'c> {
     let compile_expression = |expression: &Expression| {
-        compile_expression(builder, expression, variables, context, target_data)
+        compile_expression(module, builder, expression, variables, context, target_data)
     };
 
     match expression {
+        Expression::AbsoluteAddress(address) => {
+            compile_absolute_address(address, context, target_data)
+        }
         Expression::AlignOf(align_of) => compile_align_of(align_of, context, target_data).into(),
         Expression::ArithmeticOperation(operation) => {
             compile_arithmetic_operation(builder, operation, &compile_expression)
@@ -23,19 +30,51 @@ pub fn compile_expression<'c>(
         Expression::BitCast(bit_cast) => {
             compile_bit_cast(builder, bit_cast, context, target_data, &compile_expression)
         }
+        Expression::BitManipulationOperation(operation) => {
+            compile_bit_manipulation_operation(module, builder, operation, &compile_expression)
+        }
         Expression::BitwiseNotOperation(operation) => {
             compile_bitwise_not_operation(builder, operation, &compile_expression).into()
         }
         Expression::BitwiseOperation(operation) => {
             compile_bitwise_operation(builder, operation, &compile_expression).into()
         }
+        Expression::ByteOffset(offset) => {
+            compile_byte_offset(builder, offset, context, target_data, &compile_expression).into()
+        }
         Expression::ComparisonOperation(operation) => {
             compile_comparison_operation(builder, operation, &compile_expression)
         }
+        Expression::ExtractBits(extract) => {
+            compile_extract_bits(builder, extract, &compile_expression).into()
+        }
+        Expression::InsertBits(insert) => {
+            compile_insert_bits(builder, insert, &compile_expression).into()
+        }
         Expression::PointerAddress(address) => {
             compile_pointer_address(builder, address, &compile_expression).into()
         }
         Expression::Primitive(primitive) => compile_primitive(*primitive, context, target_data),
+        Expression::PureCall(call) => {
+            let value = builder.build_call(
+                inkwell::values::CallableValue::try_from(
+                    compile_expression(call.function()).into_pointer_value(),
+                )
+                .unwrap(),
+                &call
+                    .arguments()
+                    .iter()
+                    .map(compile_expression)
+                    .collect::<Vec<_>>(),
+                "",
+            );
+
+            value.set_call_convention(compile_calling_convention(
+                call.type_().calling_convention(),
+            ));
+
+            value.try_as_basic_value().left().unwrap()
+        }
         Expression::Record(record) => {
             let mut value =
                 types::compile_record(record.type_(), context, target_data).const_zero();
@@ -52,6 +91,7 @@ pub fn compile_expression<'c>(
         Expression::RecordAddress(address) => {
             compile_record_address(builder, address, context, &compile_expression).into()
         }
+        Expression::Select(select) => compile_select(builder, select, &compile_expression),
         Expression::SizeOf(size_of) => compile_size_of(size_of, context, target_data).into(),
         Expression::Undefined(undefined) => compile_undefined(undefined, context, target_data),
         Expression::Union(union) => {
@@ -100,6 +140,9 @@ pub fn compile_constant_expression<'c>(
     };
 
     match expression {
+        Expression::AbsoluteAddress(address) => {
+            compile_absolute_address(address, context, target_data)
+        }
         Expression::AlignOf(align_of) => compile_align_of(align_of, context, target_data).into(),
         Expression::ArithmeticOperation(operation) => {
             compile_arithmetic_operation(&context.create_builder(), operation, &compile_expression)
@@ -111,6 +154,16 @@ pub fn compile_constant_expression<'c>(
             target_data,
             &compile_expression,
         ),
+        // Intrinsic calls are not constant expressions.
+        Expression::BitManipulationOperation(_) => compile_primitive(
+            fmm::analysis::evaluate_constant_expression(
+                expression,
+                target_data.get_pointer_byte_size(None) as usize,
+            )
+            .unwrap(),
+            context,
+            target_data,
+        ),
         Expression::BitwiseNotOperation(operation) => {
             compile_bitwise_not_operation(&context.create_builder(), operation, &compile_expression)
                 .into()
@@ -119,13 +172,28 @@ pub fn compile_constant_expression<'c>(
             compile_bitwise_operation(&context.create_builder(), operation, &compile_expression)
                 .into()
         }
+        Expression::ByteOffset(offset) => compile_byte_offset(
+            &context.create_builder(),
+            offset,
+            context,
+            target_data,
+            &compile_expression,
+        )
+        .into(),
         Expression::ComparisonOperation(operation) => {
             compile_comparison_operation(&context.create_builder(), operation, &compile_expression)
         }
+        Expression::ExtractBits(extract) => {
+            compile_extract_bits(&context.create_builder(), extract, &compile_expression).into()
+        }
+        Expression::InsertBits(insert) => {
+            compile_insert_bits(&context.create_builder(), insert, &compile_expression).into()
+        }
         Expression::PointerAddress(address) => {
             compile_pointer_address(&context.create_builder(), address, &compile_expression).into()
         }
         Expression::Primitive(primitive) => compile_primitive(*primitive, context, target_data),
+        Expression::PureCall(_) => unreachable!("pure calls are not constant expressions"),
         Expression::Record(record) => context
             .const_struct(
                 &record
@@ -143,6 +211,9 @@ pub fn compile_constant_expression<'c>(
             &compile_expression,
         )
         .into(),
+        Expression::Select(select) => {
+            compile_select(&context.create_builder(), select, &compile_expression)
+        }
         Expression::SizeOf(size_of) => compile_size_of(size_of, context, target_data).into(),
         Expression::Undefined(undefined) => compile_undefined(undefined, context, target_data),
         Expression::Union(union) => context
@@ -173,6 +244,19 @@ pub fn compile_constant_expression<'c>(
     }
 }
 
+fn compile_select<'c>(
+    builder: &inkwell::builder::Builder<'c>,
+    select: &Select,
+    compile_expression: &impl Fn(&Expression) -> inkwell::values::BasicValueEnum<'c>,
+) -> inkwell::values::BasicValueEnum<'c> {
+    builder.build_select(
+        compile_expression(select.condition()).into_int_value(),
+        compile_expression(select.then()),
+        compile_expression(select.else_()),
+        "",
+    )
+}
+
 fn compile_align_of<'c>(
     align_of: &AlignOf,
     context: &'c inkwell::context::Context,
@@ -197,24 +281,51 @@ fn compile_arithmetic_operation<'c>(
     match operation.type_() {
         fmm::types::Primitive::Boolean
         | fmm::types::Primitive::Integer8
+        | fmm::types::Primitive::Integer16
         | fmm::types::Primitive::Integer32
         | fmm::types::Primitive::Integer64
+        | fmm::types::Primitive::Integer128
         | fmm::types::Primitive::PointerInteger => {
             let lhs = lhs.into_int_value();
             let rhs = rhs.into_int_value();
 
+            let flags = operation.overflow_flags();
+
+            // Only one of the flags can be set with the C API of LLVM 12.
             match operation.operator() {
+                fmm::ir::ArithmeticOperator::Add if flags.no_signed_wrap => {
+                    builder.build_int_nsw_add(lhs, rhs, "")
+                }
+                fmm::ir::ArithmeticOperator::Add if flags.no_unsigned_wrap => {
+                    builder.build_int_nuw_add(lhs, rhs, "")
+                }
                 fmm::ir::ArithmeticOperator::Add => builder.build_int_add(lhs, rhs, ""),
+                fmm::ir::ArithmeticOperator::Subtract if flags.no_signed_wrap => {
+                    builder.build_int_nsw_sub(lhs, rhs, "")
+                }
+                fmm::ir::ArithmeticOperator::Subtract if flags.no_unsigned_wrap => {
+                    builder.build_int_nuw_sub(lhs, rhs, "")
+                }
                 fmm::ir::ArithmeticOperator::Subtract => builder.build_int_sub(lhs, rhs, ""),
+                fmm::ir::ArithmeticOperator::Multiply if flags.no_signed_wrap => {
+                    builder.build_int_nsw_mul(lhs, rhs, "")
+                }
+                fmm::ir::ArithmeticOperator::Multiply if flags.no_unsigned_wrap => {
+                    builder.build_int_nuw_mul(lhs, rhs, "")
+                }
                 fmm::ir::ArithmeticOperator::Multiply => builder.build_int_mul(lhs, rhs, ""),
                 fmm::ir::ArithmeticOperator::Divide => builder.build_int_unsigned_div(lhs, rhs, ""),
             }
             .into()
         }
-        fmm::types::Primitive::Float32 | fmm::types::Primitive::Float64 => {
+        fmm::types::Primitive::Float16
+        | fmm::types::Primitive::Float32
+        | fmm::types::Primitive::Float64
+        | fmm::types::Primitive::Float128 => {
             let lhs = lhs.into_float_value();
             let rhs = rhs.into_float_value();
 
+            // Fast-math flags are dropped as the C API of LLVM 12 cannot set them.
             match operation.operator() {
                 fmm::ir::ArithmeticOperator::Add => builder.build_float_add(lhs, rhs, ""),
                 fmm::ir::ArithmeticOperator::Subtract => builder.build_float_sub(lhs, rhs, ""),
@@ -308,6 +419,41 @@ fn is_constant_bit_cast_supported(type_: &fmm::types::Type) -> bool {
     )
 }
 
+fn compile_bit_manipulation_operation<'c>(
+    module: &inkwell::module::Module<'c>,
+    builder: &inkwell::builder::Builder<'c>,
+    operation: &BitManipulationOperation,
+    compile_expression: &impl Fn(&Expression) -> inkwell::values::BasicValueEnum<'c>,
+) -> inkwell::values::BasicValueEnum<'c> {
+    let value = compile_expression(operation.value()).into_int_value();
+    let function = get_bit_manipulation_function(module, operation.operator(), value.get_type());
+
+    builder
+        .build_call(
+            function,
+            &match operation.operator() {
+                BitManipulationOperator::ByteSwap | BitManipulationOperator::PopulationCount => {
+                    vec![value.into()]
+                }
+                // Zero operands are not poison.
+                BitManipulationOperator::CountLeadingZeros
+                | BitManipulationOperator::CountTrailingZeros => vec![
+                    value.into(),
+                    value
+                        .get_type()
+                        .get_context()
+                        .bool_type()
+                        .const_zero()
+                        .into(),
+                ],
+            },
+            "",
+        )
+        .try_as_basic_value()
+        .left()
+        .unwrap()
+}
+
 fn compile_bitwise_not_operation<'c>(
     builder: &inkwell::builder::Builder<'c>,
     operation: &BitwiseNotOperation,
@@ -331,6 +477,93 @@ fn compile_bitwise_operation<'c>(
     }
 }
 
+fn compile_extract_bits<'c>(
+    builder: &inkwell::builder::Builder<'c>,
+    extract: &ExtractBits,
+    compile_expression: &impl Fn(&Expression) -> inkwell::values::BasicValueEnum<'c>,
+) -> inkwell::values::IntValue<'c> {
+    let value = compile_expression(extract.value()).into_int_value();
+    let type_ = value.get_type();
+
+    builder.build_and(
+        builder.build_right_shift(
+            value,
+            type_.const_int(extract.offset() as u64, false),
+            false,
+            "",
+        ),
+        compile_bit_mask(builder, type_, 0, extract.width()),
+        "",
+    )
+}
+
+fn compile_insert_bits<'c>(
+    builder: &inkwell::builder::Builder<'c>,
+    insert: &InsertBits,
+    compile_expression: &impl Fn(&Expression) -> inkwell::values::BasicValueEnum<'c>,
+) -> inkwell::values::IntValue<'c> {
+    let value = compile_expression(insert.value()).into_int_value();
+    let field = compile_expression(insert.field()).into_int_value();
+    let type_ = value.get_type();
+    let mask = compile_bit_mask(builder, type_, insert.offset(), insert.width());
+
+    builder.build_or(
+        builder.build_and(value, builder.build_not(mask, ""), ""),
+        builder.build_and(
+            builder.build_left_shift(field, type_.const_int(insert.offset() as u64, false), ""),
+            mask,
+            "",
+        ),
+        "",
+    )
+}
+
+fn compile_bit_mask<'c>(
+    builder: &inkwell::builder::Builder<'c>,
+    type_: inkwell::types::IntType<'c>,
+    offset: u32,
+    width: u32,
+) -> inkwell::values::IntValue<'c> {
+    builder.build_left_shift(
+        builder.build_right_shift(
+            type_.const_all_ones(),
+            type_.const_int((type_.get_bit_width() - width) as u64, false),
+            false,
+            "",
+        ),
+        type_.const_int(offset as u64, false),
+        "",
+    )
+}
+
+fn compile_byte_offset<'c>(
+    builder: &inkwell::builder::Builder<'c>,
+    offset: &ByteOffset,
+    context: &'c inkwell::context::Context,
+    target_data: &inkwell::targets::TargetData,
+    compile_expression: &impl Fn(&Expression) -> inkwell::values::BasicValueEnum<'c>,
+) -> inkwell::values::PointerValue<'c> {
+    builder
+        .build_bitcast(
+            unsafe {
+                builder.build_gep(
+                    builder
+                        .build_bitcast(
+                            compile_expression(offset.pointer()),
+                            context.i8_type().ptr_type(types::DEFAULT_ADDRESS_SPACE),
+                            "",
+                        )
+                        .into_pointer_value(),
+                    &[compile_expression(offset.offset()).into_int_value()],
+                    "",
+                )
+            },
+            types::compile_pointer(offset.type_(), context, target_data),
+            "",
+        )
+        .into_pointer_value()
+}
+
 fn compile_comparison_operation<'c>(
     builder: &inkwell::builder::Builder<'c>,
     operation: &ComparisonOperation,
@@ -339,21 +572,25 @@ fn compile_comparison_operation<'c>(
     match operation.type_() {
         fmm::types::Primitive::Boolean
         | fmm::types::Primitive::Integer8
+        | fmm::types::Primitive::Integer16
         | fmm::types::Primitive::Integer32
         | fmm::types::Primitive::Integer64
+        | fmm::types::Primitive::Integer128
         | fmm::types::Primitive::PointerInteger => builder.build_int_compare(
             compile_integer_comparison_operator(operation.operator()),
             compile_expression(operation.lhs()).into_int_value(),
             compile_expression(operation.rhs()).into_int_value(),
             "",
         ),
-        fmm::types::Primitive::Float32 | fmm::types::Primitive::Float64 => builder
-            .build_float_compare(
-                compile_float_comparison_operator(operation.operator()),
-                compile_expression(operation.lhs()).into_float_value(),
-                compile_expression(operation.rhs()).into_float_value(),
-                "",
-            ),
+        fmm::types::Primitive::Float16
+        | fmm::types::Primitive::Float32
+        | fmm::types::Primitive::Float64
+        | fmm::types::Primitive::Float128 => builder.build_float_compare(
+            compile_float_comparison_operator(operation.operator()),
+            compile_expression(operation.lhs()).into_float_value(),
+            compile_expression(operation.rhs()).into_float_value(),
+            "",
+        ),
     }
     .into()
 }
@@ -390,6 +627,17 @@ fn compile_size_of<'c>(
     )
 }
 
+fn compile_absolute_address<'c>(
+    address: &AbsoluteAddress,
+    context: &'c inkwell::context::Context,
+    target_data: &inkwell::targets::TargetData,
+) -> inkwell::values::BasicValueEnum<'c> {
+    compile_primitive(address.address(), context, target_data)
+        .into_int_value()
+        .const_to_pointer(types::compile(address.type_(), context, target_data).into_pointer_type())
+        .into()
+}
+
 fn compile_undefined<'c>(
     undefined: &Undefined,
     context: &'c inkwell::context::Context,
@@ -401,6 +649,7 @@ fn compile_undefined<'c>(
                 .const_zero()
                 .into()
         }
+        fmm::types::Type::Opaque(_) => unreachable!("undefined value of opaque type"),
         fmm::types::Type::Primitive(primitive) => {
             compile_undefined_primitive(*primitive, context, target_data)
         }
@@ -424,11 +673,18 @@ fn compile_primitive<'c>(
 ) -> inkwell::values::BasicValueEnum<'c> {
     match primitive {
         Primitive::Boolean(boolean) => context.bool_type().const_int(boolean as u64, false).into(),
+        Primitive::Float16(number) => context.f16_type().const_float(number as f64).into(),
         Primitive::Float32(number) => context.f32_type().const_float(number as f64).into(),
         Primitive::Float64(number) => context.f64_type().const_float(number as f64).into(),
+        Primitive::Float128(number) => context.f128_type().const_float(number).into(),
         Primitive::Integer8(number) => context.i8_type().const_int(number as u64, false).into(),
+        Primitive::Integer16(number) => context.i16_type().const_int(number as u64, false).into(),
         Primitive::Integer32(number) => context.i32_type().const_int(number as u64, false).into(),
         Primitive::Integer64(number) => context.i64_type().const_int(number, false).into(),
+        Primitive::Integer128(number) => context
+            .i128_type()
+            .const_int_arbitrary_precision(&[number as u64, (number >> 64) as u64])
+            .into(),
         Primitive::PointerInteger(number) => {
             compile_pointer_integer(number as u64, context, target_data).into()
         }
@@ -510,6 +766,16 @@ fn compile_integer_comparison_operator(operator: ComparisonOperator) -> inkwell:
         ComparisonOperator::LessThanOrEqual => inkwell::IntPredicate::ULE,
         ComparisonOperator::GreaterThan => inkwell::IntPredicate::UGT,
         ComparisonOperator::GreaterThanOrEqual => inkwell::IntPredicate::UGE,
+        ComparisonOperator::Ordered
+        | ComparisonOperator::Unordered
+        | ComparisonOperator::UnorderedEqual
+        | ComparisonOperator::UnorderedNotEqual
+        | ComparisonOperator::UnorderedLessThan
+        | ComparisonOperator::UnorderedLessThanOrEqual
+        | ComparisonOperator::UnorderedGreaterThan
+        | ComparisonOperator::UnorderedGreaterThanOrEqual => {
+            unreachable!("unordered comparison of integers")
+        }
     }
 }
 
@@ -521,5 +787,13 @@ fn compile_float_comparison_operator(operator: ComparisonOperator) -> inkwell::F
         ComparisonOperator::LessThanOrEqual => inkwell::FloatPredicate::OLE,
         ComparisonOperator::GreaterThan => inkwell::FloatPredicate::OGT,
         ComparisonOperator::GreaterThanOrEqual => inkwell::FloatPredicate::OGE,
+        ComparisonOperator::Ordered => inkwell::FloatPredicate::ORD,
+        ComparisonOperator::Unordered => inkwell::FloatPredicate::UNO,
+        ComparisonOperator::UnorderedEqual => inkwell::FloatPredicate::UEQ,
+        ComparisonOperator::UnorderedNotEqual => inkwell::FloatPredicate::UNE,
+        ComparisonOperator::UnorderedLessThan => inkwell::FloatPredicate::ULT,
+        ComparisonOperator::UnorderedLessThanOrEqual => inkwell::FloatPredicate::ULE,
+        ComparisonOperator::UnorderedGreaterThan => inkwell::FloatPredicate::UGT,
+        ComparisonOperator::UnorderedGreaterThanOrEqual => inkwell::FloatPredicate::UGE,
     }
 }
@@ -1,3 +1,4 @@
+use fmm::analysis::HeapAllocationStrategy;
 #[cfg(test)]
 use once_cell::sync::Lazy;
 
@@ -6,6 +7,7 @@ pub struct InstructionConfiguration {
     pub reallocate_function_name: String,
     pub free_function_name: String,
     pub unreachable_function_name: Option<String>,
+    pub heap_allocation_strategy: HeapAllocationStrategy,
 }
 
 pub struct InstructionFunctionSet<'c> {
@@ -22,4 +24,5 @@ pub static DUMMY_INSTRUCTION_CONFIGURATION: Lazy<InstructionConfiguration> =
         reallocate_function_name: "my_realloc".into(),
         free_function_name: "my_free".into(),
         unreachable_function_name: None,
+        heap_allocation_strategy: HeapAllocationStrategy::Malloc,
     });
@@ -1,12 +1,14 @@
 use crate::{
     calling_convention::compile_calling_convention, error::CompileError, expressions::*,
-    instruction_configuration::InstructionFunctionSet, types, union::compile_union_cast,
+    instruction_configuration::InstructionFunctionSet, intrinsics::*, types,
+    union::compile_union_cast,
 };
 use fmm::ir::*;
 use inkwell::values::BasicValue;
 use std::{collections::HashMap, convert::TryFrom};
 
 pub fn compile_block<'c>(
+    module: &inkwell::module::Module<'c>,
     builder: &inkwell::builder::Builder<'c>,
     block: &Block,
     destination: Option<inkwell::basic_block::BasicBlock<'c>>,
@@ -19,6 +21,7 @@ pub fn compile_block<'c>(
 
     for instruction in block.instructions() {
         let value = compile_instruction(
+            module,
             builder,
             instruction,
             &variables,
@@ -35,6 +38,7 @@ pub fn compile_block<'c>(
     }
 
     Ok(compile_terminal_instruction(
+        module,
         builder,
         block.terminal_instruction(),
         destination,
@@ -46,6 +50,7 @@ pub fn compile_block<'c>(
 }
 
 fn compile_instruction<'c>(
+    module: &inkwell::module::Module<'c>,
     builder: &inkwell::builder::Builder<'c>,
     instruction: &Instruction,
     variables: &HashMap<String, inkwell::values::BasicValueEnum<'c>>,
@@ -53,9 +58,25 @@ fn compile_instruction<'c>(
     target_data: &inkwell::targets::TargetData,
     instruction_function_set: &InstructionFunctionSet<'c>,
 ) -> Result<Option<inkwell::values::BasicValueEnum<'c>>, CompileError> {
-    let compile_expression =
-        |expression| compile_expression(builder, expression, variables, context, target_data);
+    let compile_expression = |expression| {
+        compile_expression(module, builder, expression, variables, context, target_data)
+    };
     let compile_type = |type_| types::compile(type_, context, target_data);
+    // A size of -1 covers whole stack allocations.
+    let compile_lifetime_marker = |function, pointer| {
+        builder.build_call(
+            function,
+            &[
+                context.i64_type().const_all_ones().into(),
+                builder.build_bitcast(
+                    compile_expression(pointer),
+                    context.i8_type().ptr_type(types::DEFAULT_ADDRESS_SPACE),
+                    "",
+                ),
+            ],
+            "",
+        );
+    };
 
     Ok(match instruction {
         Instruction::AllocateHeap(allocate) => Some(
@@ -131,6 +152,23 @@ fn compile_instruction<'c>(
 
             Some(value.try_as_basic_value().left().unwrap())
         }
+        Instruction::CheckedArithmeticOperation(operation) => {
+            let lhs = compile_expression(operation.lhs()).into_int_value();
+
+            builder
+                .build_call(
+                    get_checked_arithmetic_function(
+                        module,
+                        operation.operator(),
+                        operation.signed(),
+                        lhs.get_type(),
+                    ),
+                    &[lhs.into(), compile_expression(operation.rhs())],
+                    operation.name(),
+                )
+                .try_as_basic_value()
+                .left()
+        }
         Instruction::CompareAndSwap(cas) => Some(
             builder
                 .build_extract_value(
@@ -172,6 +210,18 @@ fn compile_instruction<'c>(
 
             None
         }
+        Instruction::Fma(fma) => builder
+            .build_call(
+                get_fma_function(module, fma.type_(), context, target_data),
+                &[
+                    compile_expression(fma.lhs()),
+                    compile_expression(fma.rhs()),
+                    compile_expression(fma.addend()),
+                ],
+                fma.name(),
+            )
+            .try_as_basic_value()
+            .left(),
         Instruction::FreeHeap(free) => {
             builder.build_call(
                 instruction_function_set.free_function,
@@ -185,6 +235,19 @@ fn compile_instruction<'c>(
 
             None
         }
+        Instruction::LifetimeEnd(end) => {
+            compile_lifetime_marker(get_lifetime_end_function(module, context), end.pointer());
+
+            None
+        }
+        Instruction::LifetimeStart(start) => {
+            compile_lifetime_marker(
+                get_lifetime_start_function(module, context),
+                start.pointer(),
+            );
+
+            None
+        }
         Instruction::If(if_) => {
             let current = builder.get_insert_block().unwrap();
             let function = current.get_parent().unwrap();
@@ -205,6 +268,7 @@ fn compile_instruction<'c>(
                 builder.position_at_end(*llvm_block);
 
                 let value = compile_block(
+                    module,
                     builder,
                     block,
                     Some(phi),
@@ -236,10 +300,91 @@ fn compile_instruction<'c>(
                 Some(phi.as_basic_value())
             }
         }
+        Instruction::InlineAssembly(assembly) => {
+            let arguments = assembly
+                .operands()
+                .iter()
+                .map(|operand| compile_expression(operand))
+                .collect::<Vec<_>>();
+            let argument_types = arguments
+                .iter()
+                .map(|argument| argument.get_type())
+                .collect::<Vec<_>>();
+            // Inline assembly without outputs has no result value.
+            let has_output = assembly
+                .constraints()
+                .split(',')
+                .any(|constraint| constraint.trim().starts_with('='));
+
+            let value = builder.build_call(
+                inkwell::values::CallableValue::try_from(context.create_inline_asm(
+                    if has_output {
+                        compile_type(assembly.type_()).fn_type(&argument_types, false)
+                    } else {
+                        context.void_type().fn_type(&argument_types, false)
+                    },
+                    assembly.template().into(),
+                    assembly.constraints().into(),
+                    assembly.side_effects(),
+                    assembly.align_stack(),
+                    None,
+                ))
+                .unwrap(),
+                &arguments,
+                assembly.name(),
+            );
+
+            Some(if has_output {
+                value.try_as_basic_value().left().unwrap()
+            } else {
+                compile_expression(&Undefined::new(assembly.type_().clone()).into())
+            })
+        }
         Instruction::Load(load) => Some(builder.build_load(
             compile_expression(load.pointer()).into_pointer_value(),
             load.name(),
         )),
+        Instruction::MathOperation(operation) => builder
+            .build_call(
+                get_math_function(
+                    module,
+                    operation.operator(),
+                    operation.type_(),
+                    context,
+                    target_data,
+                ),
+                &[compile_expression(operation.value())],
+                operation.name(),
+            )
+            .try_as_basic_value()
+            .left(),
+        // The alignment of 1 byte is assumed as pointers can be unaligned.
+        Instruction::MemoryCopy(copy) => {
+            let destination = compile_expression(copy.destination()).into_pointer_value();
+            let source = compile_expression(copy.source()).into_pointer_value();
+            let size = compile_expression(copy.size()).into_int_value();
+
+            if copy.overlapping() {
+                builder.build_memmove(destination, 1, source, 1, size)
+            } else {
+                builder.build_memcpy(destination, 1, source, 1, size)
+            }
+            .unwrap();
+
+            None
+        }
+        Instruction::MemorySet(set) => {
+            builder
+                .build_memset(
+                    compile_expression(set.pointer()).into_pointer_value(),
+                    1,
+                    compile_expression(set.value()).into_int_value(),
+                    compile_expression(set.size()).into_int_value(),
+                )
+                .unwrap();
+
+            None
+        }
         Instruction::PassThrough(pass) => Some(builder.build_select(
             context.bool_type().const_int(1, false),
             compile_expression(pass.expression()),
@@ -257,6 +402,28 @@ fn compile_instruction<'c>(
             )
             .try_as_basic_value()
             .left(),
+        Instruction::SaturatingArithmeticOperation(operation) => {
+            let lhs = compile_expression(operation.lhs()).into_int_value();
+            let mut arguments = vec![lhs.into(), compile_expression(operation.rhs())];
+
+            if operation.operator() == ArithmeticOperator::Multiply {
+                arguments.push(context.i32_type().const_zero().into());
+            }
+
+            builder
+                .build_call(
+                    get_saturating_arithmetic_function(
+                        module,
+                        operation.operator(),
+                        operation.signed(),
+                        lhs.get_type(),
+                    ),
+                    &arguments,
+                    operation.name(),
+                )
+                .try_as_basic_value()
+                .left()
+        }
         Instruction::Store(store) => {
             builder.build_store(
                 compile_expression(store.pointer()).into_pointer_value(),
@@ -269,6 +436,7 @@ fn compile_instruction<'c>(
 }
 
 fn compile_terminal_instruction<'c>(
+    module: &inkwell::module::Module<'c>,
     builder: &inkwell::builder::Builder<'c>,
     instruction: &TerminalInstruction,
     destination: Option<inkwell::basic_block::BasicBlock<'c>>,
@@ -277,8 +445,9 @@ fn compile_terminal_instruction<'c>(
     target_data: &inkwell::targets::TargetData,
     instruction_function_set: &InstructionFunctionSet<'c>,
 ) -> Option<inkwell::values::BasicValueEnum<'c>> {
-    let compile_expression =
-        |expression| compile_expression(builder, expression, variables, context, target_data);
+    let compile_expression = |expression| {
+        compile_expression(module, builder, expression, variables, context, target_data)
+    };
 
     match instruction {
         TerminalInstruction::Branch(branch) => {
@@ -292,6 +461,12 @@ fn compile_terminal_instruction<'c>(
             builder.build_return(Some(&compile_expression(return_.expression())));
             None
         }
+        TerminalInstruction::Trap => {
+            builder.build_call(get_trap_function(module, context), &[], "");
+            builder.build_unreachable();
+
+            None
+        }
         TerminalInstruction::Unreachable => {
             if let Some(function) = instruction_function_set.unreachable_function {
                 builder.build_call(function, &[], "");
@@ -0,0 +1,219 @@
+use crate::types;
+use fmm::ir::{ArithmeticOperator, BitManipulationOperator, MathOperator};
+
+pub fn get_trap_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    context: &'c inkwell::context::Context,
+) -> inkwell::values::FunctionValue<'c> {
+    get_intrinsic(module, "llvm.trap", context.void_type().fn_type(&[], false))
+}
+
+pub fn get_lifetime_start_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    context: &'c inkwell::context::Context,
+) -> inkwell::values::FunctionValue<'c> {
+    get_lifetime_marker_function(module, "start", context)
+}
+
+pub fn get_lifetime_end_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    context: &'c inkwell::context::Context,
+) -> inkwell::values::FunctionValue<'c> {
+    get_lifetime_marker_function(module, "end", context)
+}
+
+fn get_lifetime_marker_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    name: &str,
+    context: &'c inkwell::context::Context,
+) -> inkwell::values::FunctionValue<'c> {
+    get_intrinsic(
+        module,
+        &format!("llvm.lifetime.{}.p0i8", name),
+        context.void_type().fn_type(
+            &[
+                context.i64_type().into(),
+                context
+                    .i8_type()
+                    .ptr_type(types::DEFAULT_ADDRESS_SPACE)
+                    .into(),
+            ],
+            false,
+        ),
+    )
+}
+
+pub fn get_bit_manipulation_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    operator: BitManipulationOperator,
+    type_: inkwell::types::IntType<'c>,
+) -> inkwell::values::FunctionValue<'c> {
+    let (name, function_type) = match operator {
+        BitManipulationOperator::ByteSwap => ("bswap", type_.fn_type(&[type_.into()], false)),
+        BitManipulationOperator::CountLeadingZeros => (
+            "ctlz",
+            type_.fn_type(
+                &[type_.into(), type_.get_context().bool_type().into()],
+                false,
+            ),
+        ),
+        BitManipulationOperator::CountTrailingZeros => (
+            "cttz",
+            type_.fn_type(
+                &[type_.into(), type_.get_context().bool_type().into()],
+                false,
+            ),
+        ),
+        BitManipulationOperator::PopulationCount => {
+            ("ctpop", type_.fn_type(&[type_.into()], false))
+        }
+    };
+
+    get_intrinsic(
+        module,
+        &format!("llvm.{}.i{}", name, type_.get_bit_width()),
+        function_type,
+    )
+}
+
+pub fn get_saturating_arithmetic_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    operator: ArithmeticOperator,
+    signed: bool,
+    type_: inkwell::types::IntType<'c>,
+) -> inkwell::values::FunctionValue<'c> {
+    let prefix = if signed { "s" } else { "u" };
+    let (name, function_type) = match operator {
+        ArithmeticOperator::Add => (
+            format!("{}add.sat", prefix),
+            type_.fn_type(&[type_.into(), type_.into()], false),
+        ),
+        ArithmeticOperator::Subtract => (
+            format!("{}sub.sat", prefix),
+            type_.fn_type(&[type_.into(), type_.into()], false),
+        ),
+        // Fixed-point multiplication with a scale of 0 is integer
+        // multiplication.
+        ArithmeticOperator::Multiply => (
+            format!("{}mul.fix.sat", prefix),
+            type_.fn_type(
+                &[
+                    type_.into(),
+                    type_.into(),
+                    type_.get_context().i32_type().into(),
+                ],
+                false,
+            ),
+        ),
+        ArithmeticOperator::Divide => unreachable!(),
+    };
+
+    get_intrinsic(
+        module,
+        &format!("llvm.{}.i{}", name, type_.get_bit_width()),
+        function_type,
+    )
+}
+
+pub fn get_checked_arithmetic_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    operator: ArithmeticOperator,
+    signed: bool,
+    type_: inkwell::types::IntType<'c>,
+) -> inkwell::values::FunctionValue<'c> {
+    get_intrinsic(
+        module,
+        &format!(
+            "llvm.{}{}.with.overflow.i{}",
+            if signed { "s" } else { "u" },
+            match operator {
+                ArithmeticOperator::Add => "add",
+                ArithmeticOperator::Subtract => "sub",
+                ArithmeticOperator::Multiply => "mul",
+                ArithmeticOperator::Divide => unreachable!(),
+            },
+            type_.get_bit_width()
+        ),
+        type_
+            .get_context()
+            .struct_type(
+                &[type_.into(), type_.get_context().bool_type().into()],
+                false,
+            )
+            .fn_type(&[type_.into(), type_.into()], false),
+    )
+}
+
+pub fn get_fma_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    type_: fmm::types::Primitive,
+    context: &'c inkwell::context::Context,
+    target_data: &inkwell::targets::TargetData,
+) -> inkwell::values::FunctionValue<'c> {
+    let llvm_type = types::compile_primitive(type_, context, target_data).into_float_type();
+
+    get_intrinsic(
+        module,
+        &format!("llvm.fma.{}", compile_float_type_suffix(type_)),
+        llvm_type.fn_type(
+            &[llvm_type.into(), llvm_type.into(), llvm_type.into()],
+            false,
+        ),
+    )
+}
+
+pub fn get_math_function<'c>(
+    module: &inkwell::module::Module<'c>,
+    operator: MathOperator,
+    type_: fmm::types::Primitive,
+    context: &'c inkwell::context::Context,
+    target_data: &inkwell::targets::TargetData,
+) -> inkwell::values::FunctionValue<'c> {
+    let llvm_type = types::compile_primitive(type_, context, target_data).into_float_type();
+
+    get_intrinsic(
+        module,
+        &format!(
+            "llvm.{}.{}",
+            match operator {
+                MathOperator::Absolute => "fabs",
+                MathOperator::Ceiling => "ceil",
+                MathOperator::Cosine => "cos",
+                MathOperator::Exponential => "exp",
+                MathOperator::Floor => "floor",
+                MathOperator::Logarithm => "log",
+                MathOperator::Sine => "sin",
+                MathOperator::SquareRoot => "sqrt",
+            },
+            compile_float_type_suffix(type_)
+        ),
+        llvm_type.fn_type(&[llvm_type.into()], false),
+    )
+}
+
+fn compile_float_type_suffix(type_: fmm::types::Primitive) -> &'static str {
+    match type_ {
+        fmm::types::Primitive::Float16 => "f16",
+        fmm::types::Primitive::Float32 => "f32",
+        fmm::types::Primitive::Float64 => "f64",
+        fmm::types::Primitive::Float128 => "f128",
+        fmm::types::Primitive::Boolean
+        | fmm::types::Primitive::Integer8
+        | fmm::types::Primitive::Integer16
+        | fmm::types::Primitive::Integer32
+        | fmm::types::Primitive::Integer64
+        | fmm::types::Primitive::Integer128
+        | fmm::types::Primitive::PointerInteger => unreachable!(),
+    }
+}
+
+// Intrinsics are declared only when they are used.
+fn get_intrinsic<'c>(
+    module: &inkwell::module::Module<'c>,
+    name: &str,
+    type_: inkwell::types::FunctionType<'c>,
+) -> inkwell::values::FunctionValue<'c> {
+    module
+        .get_function(name)
+        .unwrap_or_else(|| module.add_function(name, type_, None))
+}
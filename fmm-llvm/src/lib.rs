@@ -3,6 +3,7 @@ mod error;
 mod expressions;
 mod instruction_configuration;
 mod instructions;
+mod intrinsics;
 mod types;
 mod union;
 
@@ -80,16 +81,30 @@ fn compile_module<'c>(
     module: &Module,
     instruction_configuration: &InstructionConfiguration,
 ) -> Result<inkwell::module::Module<'c>, CompileError> {
+    let target_data = target_machine.get_target_data();
+    let pointer_size = target_data.get_pointer_byte_size(None) as usize;
+
+    // Target machines are created without CPU features, such as double-word
+    // compare-and-swap, which atomic operations wider than pointers need.
+    let layout = fmm::analysis::TargetDataLayout::new(pointer_size, pointer_size);
+
     fmm::analysis::check_types(module)?;
 
-    let target_data = target_machine.get_target_data();
+    let module = &fmm::analysis::lower_wide_atomics(module, &layout);
+
+    fmm::analysis::check_atomic_types(module, &layout)?;
+
+    let module = &fmm::analysis::lower_heap_allocations(
+        module,
+        &instruction_configuration.heap_allocation_strategy,
+    )?;
 
     let llvm_module = context.create_module("");
     llvm_module.set_triple(&target_machine.get_triple());
 
     let mut variables = HashMap::new();
 
-    let instruction_function_set = compile_heap_functions(
+    let instruction_function_set = compile_runtime_functions(
         &llvm_module,
         instruction_configuration,
         context,
@@ -147,7 +162,7 @@ fn compile_module<'c>(
     Ok(llvm_module)
 }
 
-fn compile_heap_functions<'c>(
+fn compile_runtime_functions<'c>(
     module: &inkwell::module::Module<'c>,
     instruction_configuration: &InstructionConfiguration,
     context: &'c inkwell::context::Context,
@@ -284,6 +299,7 @@ fn compile_function_definition<'c>(
     builder.position_at_end(context.append_basic_block(function, "entry"));
 
     compile_block(
+        module,
         &builder,
         definition.body(),
         None,
@@ -324,7 +340,10 @@ fn compile_linkage(linkage: fmm::ir::Linkage) -> inkwell::module::Linkage {
 #[cfg(test)]
 mod tests {
     use super::{instruction_configuration::DUMMY_INSTRUCTION_CONFIGURATION, *};
-    use fmm::types::{self, CallingConvention, Type};
+    use fmm::{
+        analysis::{BumpPointerConfiguration, HeapAllocationStrategy},
+        types::{self, CallingConvention, Type},
+    };
 
     fn compile_final_module(module: &Module) {
         compile_to_object(module, &DUMMY_INSTRUCTION_CONFIGURATION, None).unwrap();
@@ -377,6 +396,20 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn declare_no_unused_intrinsics() {
+        let context = inkwell::context::Context::create();
+        let module = super::compile_module(
+            &context,
+            &create_target_machine(None).unwrap(),
+            &Module::new(vec![], vec![], vec![], vec![]),
+            &DUMMY_INSTRUCTION_CONFIGURATION,
+        )
+        .unwrap();
+
+        assert!(module.get_function("llvm.trap").is_none());
+    }
+
     mod variable_declarations {
         use super::*;
 
@@ -406,6 +439,19 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_opaque_pointer() {
+            compile_module(&Module::new(
+                vec![VariableDeclaration::new(
+                    "x",
+                    types::Pointer::new(types::Opaque::new("foo")),
+                )],
+                vec![],
+                vec![],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_function_pointer() {
             compile_module(&Module::new(
@@ -741,6 +787,25 @@ mod tests {
     mod expressions {
         use super::*;
 
+        #[test]
+        fn compile_absolute_address() {
+            let type_ = types::Pointer::new(types::Primitive::Integer16);
+
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    AbsoluteAddress::new(type_.clone(), Primitive::PointerInteger(0xB8000)),
+                    type_,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_size_of() {
             compile_module(&Module::new(
@@ -758,6 +823,27 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_size_of_record_of_small_integers() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    fmm::ir::SizeOf::new(types::Record::new(vec![
+                        types::Primitive::Integer8.into(),
+                        types::Primitive::Integer16.into(),
+                        types::Primitive::Integer32.into(),
+                    ])),
+                    types::Primitive::PointerInteger,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_align_of() {
             compile_module(&Module::new(
@@ -894,6 +980,117 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_extract_bits() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    ExtractBits::new(
+                        types::Primitive::Integer32,
+                        Primitive::Integer32(0b1010_1101_0000),
+                        3,
+                        5,
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_insert_bits() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    InsertBits::new(
+                        types::Primitive::Integer32,
+                        Primitive::Integer32(0xffff_ffff),
+                        Primitive::Integer32(0b10),
+                        3,
+                        5,
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
+        #[test]
+        fn compile_pure_call() {
+            let function_type = types::Function::new(
+                vec![types::Primitive::PointerInteger.into()],
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+            );
+
+            compile_module(&Module::new(
+                vec![],
+                vec![FunctionDeclaration::with_attributes(
+                    "f",
+                    function_type.clone(),
+                    FunctionAttributes {
+                        read_none: true,
+                        ..Default::default()
+                    },
+                )],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![Argument::new("x", types::Primitive::PointerInteger)],
+                    Block::new(
+                        vec![],
+                        Return::new(
+                            types::Primitive::PointerInteger,
+                            PureCall::new(
+                                function_type.clone(),
+                                Variable::new("f"),
+                                vec![PureCall::new(
+                                    function_type,
+                                    Variable::new("f"),
+                                    vec![Variable::new("x").into()],
+                                )
+                                .into()],
+                            ),
+                        ),
+                    ),
+                    types::Primitive::PointerInteger,
+                    Linkage::External,
+                )],
+            ));
+        }
+
+        #[test]
+        fn compile_select() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    Select::new(
+                        types::Primitive::Integer32,
+                        Primitive::Boolean(true),
+                        Primitive::Integer32(1),
+                        Primitive::Integer32(2),
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_bitwise_and() {
             compile_module(&Module::new(
@@ -1009,6 +1206,28 @@ mod tests {
             }
         }
 
+        #[test]
+        fn compile_integer128_arithmetic_operation() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    ArithmeticOperation::new(
+                        types::Primitive::Integer128,
+                        ArithmeticOperator::Add,
+                        Primitive::Integer128(u128::MAX),
+                        Primitive::Integer128(1 << 64),
+                    ),
+                    types::Primitive::Integer128,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_comparison_operation() {
             for &operator in &[
@@ -1136,6 +1355,52 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_byte_offset() {
+            let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", pointer_type.clone())],
+                Block::new(
+                    vec![],
+                    Return::new(
+                        pointer_type.clone(),
+                        ByteOffset::new(
+                            pointer_type.clone(),
+                            Variable::new("x"),
+                            Primitive::PointerInteger(1),
+                        ),
+                    ),
+                ),
+                pointer_type,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_constant_byte_offset() {
+            let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    ByteOffset::new(
+                        pointer_type.clone(),
+                        Undefined::new(pointer_type.clone()),
+                        Primitive::PointerInteger(1),
+                    ),
+                    pointer_type,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
+
         #[test]
         fn compile_constant_pointer_address() {
             compile_module(&Module::new(
@@ -1244,6 +1509,7 @@ mod tests {
                     reallocate_function_name: "my_realloc".into(),
                     free_function_name: "my_free".into(),
                     unreachable_function_name: Some("my_unreachable".into()),
+                    heap_allocation_strategy: HeapAllocationStrategy::Malloc,
                 },
                 None,
             )
@@ -1264,6 +1530,43 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_allocate_heap_with_bump_pointer() {
+            compile_to_object(
+                &Module::new(
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![create_function_definition(
+                        "f",
+                        vec![],
+                        Block::new(
+                            vec![AllocateHeap::new(Primitive::PointerInteger(42), "y").into()],
+                            Return::new(types::GENERIC_POINTER_TYPE.clone(), Variable::new("y")),
+                        ),
+                        types::GENERIC_POINTER_TYPE.clone(),
+                        Linkage::External,
+                    )],
+                ),
+                &InstructionConfiguration {
+                    allocate_function_name: "my_malloc".into(),
+                    reallocate_function_name: "my_realloc".into(),
+                    free_function_name: "my_free".into(),
+                    unreachable_function_name: None,
+                    heap_allocation_strategy: HeapAllocationStrategy::BumpPointer(
+                        BumpPointerConfiguration {
+                            pointer_variable_name: "heap_pointer".into(),
+                            limit_variable_name: "heap_limit".into(),
+                            slow_path_function_name: "collect".into(),
+                            alignment: 8,
+                        },
+                    ),
+                },
+                None,
+            )
+            .unwrap();
+        }
+
         #[test]
         fn compile_reallocate_heap() {
             compile_function_definition(create_function_definition(
@@ -1322,6 +1625,51 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_atomic_load_wider_than_pointer() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new(
+                    "x",
+                    types::Pointer::new(types::Primitive::Integer128),
+                )],
+                Block::new(
+                    vec![AtomicLoad::new(
+                        types::Primitive::Integer128,
+                        Variable::new("x"),
+                        AtomicOrdering::Relaxed,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::Integer128, Variable::new("y")),
+                ),
+                types::Primitive::Integer128,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_atomic_load_of_record() {
+            let record_type = types::Record::new(vec![types::Primitive::Integer8.into()]);
+
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Pointer::new(record_type.clone()))],
+                Block::new(
+                    vec![AtomicLoad::new(
+                        record_type.clone(),
+                        Variable::new("x"),
+                        AtomicOrdering::Relaxed,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(record_type.clone(), Variable::new("y")),
+                ),
+                record_type,
+                Linkage::External,
+            ));
+        }
+
         #[test]
         fn compile_atomic_load_with_function_pointer() {
             let function_type = create_function_type(
@@ -1421,6 +1769,113 @@ mod tests {
             ));
         }
 
+        #[test]
+        fn compile_inline_assembly() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![InlineAssembly::new(
+                        types::Primitive::PointerInteger,
+                        "",
+                        "=r,0,~{memory}",
+                        vec![Variable::new("x").into()],
+                        true,
+                        false,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_inline_assembly_without_output() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![InlineAssembly::new(
+                        types::Primitive::PointerInteger,
+                        "",
+                        "r",
+                        vec![Variable::new("x").into()],
+                        true,
+                        true,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_memory_copy() {
+            for &overlapping in &[false, true] {
+                compile_function_definition(create_function_definition(
+                    "f",
+                    vec![
+                        Argument::new("x", types::GENERIC_POINTER_TYPE.clone()),
+                        Argument::new("y", types::GENERIC_POINTER_TYPE.clone()),
+                    ],
+                    Block::new(
+                        vec![MemoryCopy::new(
+                            Variable::new("x"),
+                            Variable::new("y"),
+                            Primitive::PointerInteger(8),
+                            overlapping,
+                        )
+                        .into()],
+                        Return::new(
+                            types::Primitive::PointerInteger,
+                            Primitive::PointerInteger(0),
+                        ),
+                    ),
+                    types::Primitive::PointerInteger,
+                    Linkage::External,
+                ));
+            }
+        }
+
+        #[test]
+        fn compile_memory_set() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![Argument::new("x", types::GENERIC_POINTER_TYPE.clone())],
+                Block::new(
+                    vec![MemorySet::new(
+                        Variable::new("x"),
+                        Primitive::Integer8(0),
+                        Primitive::PointerInteger(8),
+                    )
+                    .into()],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(0),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_trap() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![],
+                Block::new(vec![], TerminalInstruction::Trap),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
         #[test]
         fn compile_if() {
             compile_function_definition(create_function_definition(
@@ -1694,6 +2149,140 @@ mod tests {
             }
         }
 
+        #[test]
+        fn compile_saturating_arithmetic_operation() {
+            for type_ in [
+                types::Primitive::Integer8,
+                types::Primitive::Integer64,
+                types::Primitive::PointerInteger,
+            ]
+            .iter()
+            {
+                for operator in [
+                    ArithmeticOperator::Add,
+                    ArithmeticOperator::Subtract,
+                    ArithmeticOperator::Multiply,
+                ]
+                .iter()
+                {
+                    for signed in [false, true].iter() {
+                        compile_function_definition(create_function_definition(
+                            "f",
+                            vec![Argument::new("x", *type_)],
+                            Block::new(
+                                vec![SaturatingArithmeticOperation::new(
+                                    *type_,
+                                    *operator,
+                                    *signed,
+                                    Variable::new("x"),
+                                    Variable::new("x"),
+                                    "y",
+                                )
+                                .into()],
+                                Return::new(*type_, Variable::new("y")),
+                            ),
+                            *type_,
+                            Linkage::External,
+                        ));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn compile_checked_arithmetic_operation() {
+            for operator in [
+                ArithmeticOperator::Add,
+                ArithmeticOperator::Subtract,
+                ArithmeticOperator::Multiply,
+            ]
+            .iter()
+            {
+                for signed in [false, true].iter() {
+                    let operation = CheckedArithmeticOperation::new(
+                        types::Primitive::Integer32,
+                        *operator,
+                        *signed,
+                        Variable::new("x"),
+                        Primitive::Integer32(42),
+                        "y",
+                    );
+
+                    compile_function_definition(create_function_definition(
+                        "f",
+                        vec![Argument::new("x", types::Primitive::Integer32)],
+                        Block::new(
+                            vec![operation.clone().into()],
+                            Return::new(operation.result_type(), Variable::new("y")),
+                        ),
+                        operation.result_type(),
+                        Linkage::External,
+                    ));
+                }
+            }
+        }
+
+        #[test]
+        fn compile_fma() {
+            for type_ in [
+                types::Primitive::Float32,
+                types::Primitive::Float64,
+                types::Primitive::Float128,
+            ]
+            .iter()
+            {
+                compile_function_definition(create_function_definition(
+                    "f",
+                    vec![Argument::new("x", *type_)],
+                    Block::new(
+                        vec![Fma::new(
+                            *type_,
+                            Variable::new("x"),
+                            Variable::new("x"),
+                            Variable::new("x"),
+                            "y",
+                        )
+                        .into()],
+                        Return::new(*type_, Variable::new("y")),
+                    ),
+                    *type_,
+                    Linkage::External,
+                ));
+            }
+        }
+
+        #[test]
+        fn compile_math_operation() {
+            for operator in [
+                MathOperator::Absolute,
+                MathOperator::Ceiling,
+                MathOperator::Cosine,
+                MathOperator::Exponential,
+                MathOperator::Floor,
+                MathOperator::Logarithm,
+                MathOperator::Sine,
+                MathOperator::SquareRoot,
+            ]
+            .iter()
+            {
+                for type_ in [types::Primitive::Float32, types::Primitive::Float64].iter() {
+                    compile_function_definition(create_function_definition(
+                        "f",
+                        vec![Argument::new("x", *type_)],
+                        Block::new(
+                            vec![
+                                MathOperation::new(*type_, *operator, Variable::new("x"), "y")
+                                    .into(),
+                            ],
+                            Return::new(*type_, Variable::new("y")),
+                        ),
+                        *type_,
+                        Linkage::External,
+                    ));
+                }
+            }
+        }
+
         #[test]
         fn compile_free_heap() {
             compile_function_definition(create_function_definition(
@@ -1710,5 +2299,85 @@ mod tests {
                 Linkage::External,
             ));
         }
+
+        #[test]
+        fn compile_lifetime_markers() {
+            compile_function_definition(create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![
+                        AllocateStack::new(types::Primitive::PointerInteger, "x").into(),
+                        LifetimeStart::new(Variable::new("x")).into(),
+                        LifetimeEnd::new(Variable::new("x")).into(),
+                    ],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(0),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+                Linkage::External,
+            ));
+        }
+
+        #[test]
+        fn compile_bit_manipulation_operations() {
+            for operator in [
+                BitManipulationOperator::ByteSwap,
+                BitManipulationOperator::CountLeadingZeros,
+                BitManipulationOperator::CountTrailingZeros,
+                BitManipulationOperator::PopulationCount,
+            ]
+            .iter()
+            {
+                for type_ in [
+                    types::Primitive::Integer32,
+                    types::Primitive::Integer128,
+                    types::Primitive::PointerInteger,
+                ]
+                .iter()
+                {
+                    compile_function_definition(create_function_definition(
+                        "f",
+                        vec![Argument::new("x", *type_)],
+                        Block::new(
+                            vec![],
+                            Return::new(
+                                *type_,
+                                BitManipulationOperation::new(
+                                    *type_,
+                                    *operator,
+                                    Variable::new("x"),
+                                ),
+                            ),
+                        ),
+                        *type_,
+                        Linkage::External,
+                    ));
+                }
+            }
+        }
+
+        #[test]
+        fn compile_constant_population_count() {
+            compile_module(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    BitManipulationOperation::new(
+                        types::Primitive::Integer32,
+                        BitManipulationOperator::PopulationCount,
+                        Primitive::Integer32(0x1234_5678),
+                    ),
+                    types::Primitive::Integer32,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            ));
+        }
     }
 }
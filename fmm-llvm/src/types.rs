@@ -10,6 +10,7 @@ pub fn compile<'c>(
 ) -> inkwell::types::BasicTypeEnum<'c> {
     match type_ {
         Type::Function(function) => compile_function_pointer(function, context, target_data).into(),
+        Type::Opaque(opaque) => compile_opaque(opaque, context).into(),
         Type::Primitive(primitive) => compile_primitive(*primitive, context, target_data),
         Type::Record(record) => compile_record(record, context, target_data).into(),
         Type::Pointer(pointer) => compile_pointer(pointer, context, target_data).into(),
@@ -57,11 +58,15 @@ pub fn compile_primitive<'c>(
 ) -> inkwell::types::BasicTypeEnum<'c> {
     match primitive {
         types::Primitive::Boolean => context.bool_type().into(),
+        types::Primitive::Float16 => context.f16_type().into(),
         types::Primitive::Float32 => context.f32_type().into(),
         types::Primitive::Float64 => context.f64_type().into(),
+        types::Primitive::Float128 => context.f128_type().into(),
         types::Primitive::Integer8 => context.i8_type().into(),
+        types::Primitive::Integer16 => context.i16_type().into(),
         types::Primitive::Integer32 => context.i32_type().into(),
         types::Primitive::Integer64 => context.i64_type().into(),
+        types::Primitive::Integer128 => context.i128_type().into(),
         types::Primitive::PointerInteger => compile_pointer_integer(context, target_data).into(),
     }
 }
@@ -73,6 +78,21 @@ pub fn compile_pointer_integer<'c>(
     context.ptr_sized_int_type(target_data, None)
 }
 
+// Opaque types are named with a reserved prefix so that they never resolve to
+// other named struct types in the same context.
+const OPAQUE_TYPE_PREFIX: &str = "fmm.opaque.";
+
+pub fn compile_opaque<'c>(
+    opaque: &types::Opaque,
+    context: &'c inkwell::context::Context,
+) -> inkwell::types::StructType<'c> {
+    let name = format!("{}{}", OPAQUE_TYPE_PREFIX, opaque.name());
+
+    context
+        .get_struct_type(&name)
+        .unwrap_or_else(|| context.opaque_struct_type(&name))
+}
+
 pub fn compile_record<'c>(
     record: &types::Record,
     context: &'c inkwell::context::Context,
@@ -179,4 +199,41 @@ mod tests {
         assert_eq!(get_pointer_integer_array_size(8, 8), 1);
         assert_eq!(get_pointer_integer_array_size(9, 8), 2);
     }
+
+    #[test]
+    fn compile_record_of_small_integers() {
+        let context = inkwell::context::Context::create();
+        let target_data = inkwell::targets::TargetData::create("e-i64:64-n8:16:32:64-S128");
+        let record = compile_record(
+            &types::Record::new(vec![
+                types::Primitive::Integer8.into(),
+                types::Primitive::Integer16.into(),
+                types::Primitive::Integer32.into(),
+            ]),
+            &context,
+            &target_data,
+        );
+
+        assert_eq!(target_data.get_store_size(&record), 8);
+        assert_eq!(target_data.get_abi_alignment(&record), 4);
+        assert_eq!(target_data.offset_of_element(&record, 0), Some(0));
+        assert_eq!(target_data.offset_of_element(&record, 1), Some(2));
+        assert_eq!(target_data.offset_of_element(&record, 2), Some(4));
+    }
+
+    #[test]
+    fn compile_extended_floats() {
+        let context = inkwell::context::Context::create();
+        let target_data = inkwell::targets::TargetData::create("e-i64:64-n8:16:32:64-S128");
+
+        for (primitive, size) in [
+            (types::Primitive::Float16, 2),
+            (types::Primitive::Float128, 16),
+        ] {
+            assert_eq!(
+                target_data.get_store_size(&compile_primitive(primitive, &context, &target_data)),
+                size
+            );
+        }
+    }
 }
@@ -1,15 +1,72 @@
+mod alias;
+mod atomic_lowering;
+mod bounds_check;
+mod common_subexpression;
+mod commutative_normalization;
+mod constant_evaluation;
+mod cost;
 mod cps;
+mod dead_code;
+mod dead_store;
+mod explicit_padding;
+mod expression_call_flattening;
 mod expression_conversion;
 mod format;
+mod free_variables;
+mod heap_lowering;
+mod if_conversion;
+mod intrinsics;
+mod jump_threading;
+mod load_forwarding;
+mod memory_copy_expansion;
+mod opaque_resolution;
+mod peephole;
 mod renaming;
+mod safepoint;
+mod sinking;
+mod stack_map;
+mod tail_call_merging;
+mod tail_duplication;
+mod target_data_layout;
 mod type_check;
 mod type_collection;
 mod type_conversion;
+mod type_size;
+mod unreachable_code;
+mod validation;
 
+pub use alias::*;
+pub use atomic_lowering::*;
+pub use bounds_check::*;
+pub use common_subexpression::*;
+pub use commutative_normalization::*;
+pub use constant_evaluation::*;
+pub use cost::*;
 pub use cps::*;
+pub use dead_code::*;
+pub use dead_store::*;
+pub use explicit_padding::*;
+pub use expression_call_flattening::*;
 pub use expression_conversion::*;
 pub use format::*;
+pub use heap_lowering::*;
+pub use if_conversion::*;
+pub use intrinsics::*;
+pub use jump_threading::*;
+pub use load_forwarding::*;
+pub use memory_copy_expansion::*;
+pub use opaque_resolution::*;
+pub use peephole::*;
 pub use renaming::*;
+pub use safepoint::*;
+pub use sinking::*;
+pub use stack_map::*;
+pub use tail_call_merging::*;
+pub use tail_duplication::*;
+pub use target_data_layout::*;
 pub use type_check::*;
 pub use type_collection::*;
 pub use type_conversion::*;
+pub use type_size::*;
+pub use unreachable_code::*;
+pub use validation::*;
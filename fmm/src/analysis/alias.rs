@@ -0,0 +1,354 @@
+use super::type_size::{field_offset, type_size};
+use crate::{ir::*, types};
+use std::collections::HashSet;
+
+#[derive(Clone, Debug, Default)]
+pub struct AliasContext {
+    allocations: HashSet<String>,
+}
+
+impl AliasContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Allocations are pointers which are not derived from any other pointers.
+    pub fn add_allocation(&mut self, name: impl Into<String>) {
+        self.allocations.insert(name.into());
+    }
+
+    // Names rebound by instructions other than allocations are not allocations
+    // anymore.
+    pub fn bind(&mut self, instruction: &Instruction) {
+        match instruction {
+            Instruction::AllocateHeap(allocate) => self.add_allocation(allocate.name()),
+            Instruction::AllocateStack(allocate) => self.add_allocation(allocate.name()),
+            _ => {
+                if let Some(name) = instruction.name() {
+                    self.allocations.remove(name);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn is_allocation(&self, expression: &Expression) -> bool {
+        matches!(expression, Expression::Variable(variable) if self.allocations.contains(variable.name()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Step<'a> {
+    Element(&'a types::Pointer, i64),
+    Field(&'a types::Record, usize),
+    Unknown,
+}
+
+// Pointers are decomposed into their bases and paths of constant offsets from
+// the bases. Paths of different shapes are assumed to alias as pointers can be
+// offset out of elements or fields.
+pub fn may_alias(one: &Expression, other: &Expression, context: &AliasContext) -> bool {
+    let (one_base, one_steps) = decompose(one);
+    let (other_base, other_steps) = decompose(other);
+
+    if one_base != other_base {
+        return !(context.is_allocation(one_base) && context.is_allocation(other_base));
+    } else if one_steps.len() != other_steps.len() {
+        return true;
+    }
+
+    for (index, (one, other)) in one_steps.iter().zip(&other_steps).enumerate() {
+        let distinct = match (one, other) {
+            (Step::Unknown, _) | (_, Step::Unknown) => return true,
+            _ if one == other => continue,
+            (Step::Element(one_type, _), Step::Element(other_type, _)) => one_type == other_type,
+            (Step::Field(one_type, _), Step::Field(other_type, _)) => one_type == other_type,
+            _ => return true,
+        };
+
+        // Different elements or fields with identical prefixes do not overlap
+        // unless pointers are offset out of them later.
+        return !(distinct
+            && one_steps[index + 1..]
+                .iter()
+                .chain(&other_steps[index + 1..])
+                .all(|step| matches!(step, Step::Field(_, _))));
+    }
+
+    true
+}
+
+pub(crate) fn pointer_base(pointer: &Expression) -> &Expression {
+    decompose(pointer).0
+}
+
+// Pointers are decomposed into their bases and constant byte offsets from the
+// bases if any.
+pub(crate) fn decompose_bytes(
+    pointer: &Expression,
+    pointer_size: usize,
+) -> (&Expression, Option<i64>) {
+    let (inner, offset) = match pointer {
+        Expression::BitCast(bit_cast) => (bit_cast.expression(), Some(0)),
+        Expression::ByteOffset(offset) => (
+            offset.pointer(),
+            match offset.offset() {
+                Expression::Primitive(Primitive::PointerInteger(offset)) => Some(*offset),
+                _ => None,
+            },
+        ),
+        Expression::PointerAddress(address) => (
+            address.pointer(),
+            match address.offset() {
+                Expression::Primitive(Primitive::PointerInteger(offset)) => {
+                    type_size(address.type_().element(), pointer_size)
+                        .ok()
+                        .and_then(|size| offset.checked_mul(size as i64))
+                }
+                _ => None,
+            },
+        ),
+        Expression::RecordAddress(address) => (
+            address.pointer(),
+            field_offset(address.type_(), address.element_index(), pointer_size)
+                .ok()
+                .map(|offset| offset as i64),
+        ),
+        Expression::UnionAddress(address) => (address.pointer(), Some(0)),
+        _ => return (pointer, Some(0)),
+    };
+    let (base, inner_offset) = decompose_bytes(inner, pointer_size);
+
+    (
+        base,
+        inner_offset
+            .zip(offset)
+            .and_then(|(one, other)| one.checked_add(other)),
+    )
+}
+
+fn decompose(pointer: &Expression) -> (&Expression, Vec<Step<'_>>) {
+    let (inner, step) = match pointer {
+        Expression::BitCast(bit_cast) => (bit_cast.expression(), Step::Unknown),
+        Expression::ByteOffset(offset) => (offset.pointer(), Step::Unknown),
+        Expression::PointerAddress(address) => (
+            address.pointer(),
+            match address.offset() {
+                Expression::Primitive(Primitive::PointerInteger(offset)) => {
+                    Step::Element(address.type_(), *offset)
+                }
+                _ => Step::Unknown,
+            },
+        ),
+        Expression::RecordAddress(address) => (
+            address.pointer(),
+            Step::Field(address.type_(), address.element_index()),
+        ),
+        // Members of unions overlap with each other.
+        Expression::UnionAddress(address) => (address.pointer(), Step::Unknown),
+        _ => return (pointer, vec![]),
+    };
+    let (base, mut steps) = decompose(inner);
+
+    // Offsets of chained element addresses are added up.
+    match (steps.last_mut(), step) {
+        (Some(Step::Element(type_, offset)), Step::Element(other_type, other_offset))
+            if *type_ == other_type =>
+        {
+            if let Some(sum) = offset.checked_add(other_offset) {
+                *offset = sum;
+            } else {
+                *steps.last_mut().unwrap() = Step::Unknown;
+            }
+        }
+        (_, step) => steps.push(step),
+    }
+
+    if let Some(Step::Element(_, 0)) = steps.last() {
+        steps.pop();
+    }
+
+    (base, steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_context() -> AliasContext {
+        let mut context = AliasContext::new();
+
+        context.add_allocation("a");
+        context.add_allocation("b");
+
+        context
+    }
+
+    fn create_record_type() -> types::Record {
+        types::Record::new(vec![
+            types::Primitive::Integer64.into(),
+            types::Primitive::Integer64.into(),
+        ])
+    }
+
+    fn create_element_address(pointer: &str, offset: i64) -> Expression {
+        PointerAddress::new(
+            types::Pointer::new(types::Primitive::Integer64),
+            Variable::new(pointer),
+            Primitive::PointerInteger(offset),
+        )
+        .into()
+    }
+
+    #[test]
+    fn distinct_allocations_do_not_alias() {
+        assert!(!may_alias(
+            &Variable::new("a").into(),
+            &RecordAddress::new(create_record_type(), Variable::new("b"), 0).into(),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn allocation_and_unknown_pointer_may_alias() {
+        assert!(may_alias(
+            &Variable::new("a").into(),
+            &Variable::new("p").into(),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn same_pointers_may_alias() {
+        assert!(may_alias(
+            &create_element_address("p", 1),
+            &create_element_address("p", 1),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn different_elements_do_not_alias() {
+        assert!(!may_alias(
+            &create_element_address("p", 1),
+            &create_element_address("p", 2),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn different_fields_do_not_alias() {
+        assert!(!may_alias(
+            &RecordAddress::new(create_record_type(), Variable::new("a"), 0).into(),
+            &RecordAddress::new(create_record_type(), Variable::new("a"), 1).into(),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn nested_element_addresses_may_alias() {
+        assert!(may_alias(
+            &create_element_address("p", 1),
+            &PointerAddress::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                create_element_address("p", 0),
+                Primitive::PointerInteger(1),
+            )
+            .into(),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn nested_element_addresses_do_not_alias() {
+        assert!(!may_alias(
+            &create_element_address("p", 3),
+            &PointerAddress::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                create_element_address("p", 1),
+                Primitive::PointerInteger(1),
+            )
+            .into(),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn element_of_field_and_other_field_may_alias() {
+        assert!(may_alias(
+            &PointerAddress::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                RecordAddress::new(create_record_type(), Variable::new("a"), 0),
+                Primitive::PointerInteger(1),
+            )
+            .into(),
+            &RecordAddress::new(create_record_type(), Variable::new("a"), 1).into(),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn elements_of_different_fields_may_alias() {
+        let create_address = |index, offset| {
+            PointerAddress::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                RecordAddress::new(create_record_type(), Variable::new("a"), index),
+                Primitive::PointerInteger(offset),
+            )
+            .into()
+        };
+
+        assert!(may_alias(
+            &create_address(0, 2),
+            &create_address(1, 1),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn rebound_allocation_may_alias() {
+        let mut context = create_context();
+
+        context.bind(
+            &PassThrough::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                Variable::new("p"),
+                "a",
+            )
+            .into(),
+        );
+
+        assert!(may_alias(
+            &Variable::new("a").into(),
+            &Variable::new("b").into(),
+            &context
+        ));
+    }
+
+    #[test]
+    fn field_and_its_record_may_alias() {
+        assert!(may_alias(
+            &RecordAddress::new(create_record_type(), Variable::new("a"), 1).into(),
+            &Variable::new("a").into(),
+            &create_context()
+        ));
+    }
+
+    #[test]
+    fn byte_offsets_may_alias() {
+        assert!(may_alias(
+            &ByteOffset::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                Variable::new("a"),
+                Primitive::PointerInteger(0)
+            )
+            .into(),
+            &ByteOffset::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                Variable::new("a"),
+                Primitive::PointerInteger(8)
+            )
+            .into(),
+            &create_context()
+        ));
+    }
+}
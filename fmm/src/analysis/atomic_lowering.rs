@@ -0,0 +1,633 @@
+use super::{
+    renaming::create_name_generator, target_data_layout::TargetDataLayout, type_size::type_size,
+};
+use crate::{
+    ir::*,
+    types::{self, Type, GENERIC_POINTER_TYPE, VOID_TYPE},
+};
+use std::collections::BTreeMap;
+
+const ATOMIC_LOAD_FUNCTION_NAME: &str = "__atomic_load";
+const ATOMIC_STORE_FUNCTION_NAME: &str = "__atomic_store";
+const ATOMIC_COMPARE_EXCHANGE_FUNCTION_NAME: &str = "__atomic_compare_exchange";
+
+struct Context<'a, G: FnMut() -> String> {
+    layout: &'a TargetDataLayout,
+    function_declarations: BTreeMap<String, types::Function>,
+    generate_name: G,
+}
+
+// Atomic operations on types wider than the target supports or on aggregate
+// types are lowered into calls to the `__atomic_*` functions of libatomic.
+pub fn lower_wide_atomics(module: &Module, layout: &TargetDataLayout) -> Module {
+    let mut context = Context {
+        layout,
+        function_declarations: Default::default(),
+        generate_name: create_name_generator(module, "_atomic_"),
+    };
+
+    let function_definitions = module
+        .function_definitions()
+        .iter()
+        .map(|definition| {
+            FunctionDefinition::new(
+                definition.name(),
+                definition.arguments().to_vec(),
+                lower_block(&mut context, definition.body()),
+                definition.result_type().clone(),
+                definition.calling_convention(),
+                definition.linkage(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module
+            .function_declarations()
+            .iter()
+            .cloned()
+            .chain(
+                context
+                    .function_declarations
+                    .into_iter()
+                    .filter(|(name, _)| {
+                        !module
+                            .function_declarations()
+                            .iter()
+                            .any(|declaration| declaration.name() == name)
+                            && !module
+                                .function_definitions()
+                                .iter()
+                                .any(|definition| definition.name() == name)
+                    })
+                    .map(|(name, type_)| FunctionDeclaration::new(name, type_)),
+            )
+            .collect(),
+        module.variable_definitions().to_vec(),
+        function_definitions,
+    )
+}
+
+fn lower_block(context: &mut Context<impl FnMut() -> String>, block: &Block) -> Block {
+    Block::new(
+        block
+            .instructions()
+            .iter()
+            .flat_map(|instruction| lower_instruction(context, instruction))
+            .collect(),
+        block.terminal_instruction().clone(),
+    )
+}
+
+fn lower_instruction(
+    context: &mut Context<impl FnMut() -> String>,
+    instruction: &Instruction,
+) -> Vec<Instruction> {
+    match instruction {
+        Instruction::AtomicLoad(load) if is_wide(context, load.type_()) => {
+            let pointer = (context.generate_name)();
+            let call = (context.generate_name)();
+            let function_type = types::Function::new(
+                vec![
+                    types::Primitive::PointerInteger.into(),
+                    GENERIC_POINTER_TYPE.clone(),
+                    GENERIC_POINTER_TYPE.clone(),
+                    types::Primitive::Integer32.into(),
+                ],
+                VOID_TYPE.clone(),
+                types::CallingConvention::Target,
+            );
+
+            context
+                .function_declarations
+                .insert(ATOMIC_LOAD_FUNCTION_NAME.into(), function_type.clone());
+
+            vec![
+                AllocateStack::new(load.type_().clone(), &pointer).into(),
+                Call::new(
+                    function_type,
+                    Variable::new(ATOMIC_LOAD_FUNCTION_NAME),
+                    vec![
+                        compile_size(context, load.type_()),
+                        compile_generic_pointer(load.type_(), load.pointer().clone()),
+                        compile_generic_pointer(load.type_(), Variable::new(&pointer)),
+                        compile_ordering(load.ordering()),
+                    ],
+                    call,
+                )
+                .into(),
+                Load::new(load.type_().clone(), Variable::new(pointer), load.name()).into(),
+            ]
+        }
+        Instruction::AtomicStore(store) if is_wide(context, store.type_()) => {
+            let pointer = (context.generate_name)();
+            let call = (context.generate_name)();
+            let function_type = types::Function::new(
+                vec![
+                    types::Primitive::PointerInteger.into(),
+                    GENERIC_POINTER_TYPE.clone(),
+                    GENERIC_POINTER_TYPE.clone(),
+                    types::Primitive::Integer32.into(),
+                ],
+                VOID_TYPE.clone(),
+                types::CallingConvention::Target,
+            );
+
+            context
+                .function_declarations
+                .insert(ATOMIC_STORE_FUNCTION_NAME.into(), function_type.clone());
+
+            vec![
+                AllocateStack::new(store.type_().clone(), &pointer).into(),
+                Store::new(
+                    store.type_().clone(),
+                    store.value().clone(),
+                    Variable::new(&pointer),
+                )
+                .into(),
+                Call::new(
+                    function_type,
+                    Variable::new(ATOMIC_STORE_FUNCTION_NAME),
+                    vec![
+                        compile_size(context, store.type_()),
+                        compile_generic_pointer(store.type_(), store.pointer().clone()),
+                        compile_generic_pointer(store.type_(), Variable::new(pointer)),
+                        compile_ordering(store.ordering()),
+                    ],
+                    call,
+                )
+                .into(),
+            ]
+        }
+        Instruction::CompareAndSwap(cas) if is_wide(context, cas.type_()) => {
+            let expected = (context.generate_name)();
+            let desired = (context.generate_name)();
+            let function_type = types::Function::new(
+                vec![
+                    types::Primitive::PointerInteger.into(),
+                    GENERIC_POINTER_TYPE.clone(),
+                    GENERIC_POINTER_TYPE.clone(),
+                    GENERIC_POINTER_TYPE.clone(),
+                    types::Primitive::Integer32.into(),
+                    types::Primitive::Integer32.into(),
+                ],
+                types::Primitive::Boolean,
+                types::CallingConvention::Target,
+            );
+
+            context.function_declarations.insert(
+                ATOMIC_COMPARE_EXCHANGE_FUNCTION_NAME.into(),
+                function_type.clone(),
+            );
+
+            vec![
+                AllocateStack::new(cas.type_().clone(), &expected).into(),
+                Store::new(
+                    cas.type_().clone(),
+                    cas.old_value().clone(),
+                    Variable::new(&expected),
+                )
+                .into(),
+                AllocateStack::new(cas.type_().clone(), &desired).into(),
+                Store::new(
+                    cas.type_().clone(),
+                    cas.new_value().clone(),
+                    Variable::new(&desired),
+                )
+                .into(),
+                Call::new(
+                    function_type,
+                    Variable::new(ATOMIC_COMPARE_EXCHANGE_FUNCTION_NAME),
+                    vec![
+                        compile_size(context, cas.type_()),
+                        compile_generic_pointer(cas.type_(), cas.pointer().clone()),
+                        compile_generic_pointer(cas.type_(), Variable::new(expected)),
+                        compile_generic_pointer(cas.type_(), Variable::new(desired)),
+                        compile_ordering(cas.success_ordering()),
+                        compile_ordering(cas.failure_ordering()),
+                    ],
+                    cas.name(),
+                )
+                .into(),
+            ]
+        }
+        Instruction::AtomicOperation(operation) if is_wide(context, &operation.type_().into()) => {
+            let type_ = Type::from(operation.type_());
+            // Only sized variants of fetch-and-op functions are available.
+            let name = format!(
+                "__atomic_fetch_{}_{}",
+                match operation.operator() {
+                    AtomicOperator::Add => "add",
+                    AtomicOperator::Subtract => "sub",
+                },
+                type_size(&type_, context.layout.pointer_size()).unwrap_or_default()
+            );
+            let function_type = types::Function::new(
+                vec![
+                    GENERIC_POINTER_TYPE.clone(),
+                    type_.clone(),
+                    types::Primitive::Integer32.into(),
+                ],
+                type_.clone(),
+                types::CallingConvention::Target,
+            );
+
+            context
+                .function_declarations
+                .insert(name.clone(), function_type.clone());
+
+            vec![Call::new(
+                function_type,
+                Variable::new(name),
+                vec![
+                    compile_generic_pointer(&type_, operation.pointer().clone()),
+                    operation.value().clone(),
+                    compile_ordering(operation.ordering()),
+                ],
+                operation.name(),
+            )
+            .into()]
+        }
+        Instruction::If(if_) => vec![If::new(
+            if_.type_().clone(),
+            if_.condition().clone(),
+            lower_block(context, if_.then()),
+            lower_block(context, if_.else_()),
+            if_.name(),
+        )
+        .into()],
+        _ => vec![instruction.clone()],
+    }
+}
+
+// Types of unknown sizes are left to type checking.
+fn is_wide(context: &Context<impl FnMut() -> String>, type_: &Type) -> bool {
+    type_size(type_, context.layout.pointer_size())
+        .map(|size| {
+            matches!(type_, Type::Record(_) | Type::Union(_))
+                || size > context.layout.max_atomic_width()
+        })
+        .unwrap_or_default()
+}
+
+fn compile_size(context: &Context<impl FnMut() -> String>, type_: &Type) -> Expression {
+    Primitive::PointerInteger(
+        type_size(type_, context.layout.pointer_size()).unwrap_or_default() as i64,
+    )
+    .into()
+}
+
+fn compile_generic_pointer(type_: &Type, pointer: impl Into<Expression>) -> Expression {
+    BitCast::new(
+        types::Pointer::new(type_.clone()),
+        GENERIC_POINTER_TYPE.clone(),
+        pointer,
+    )
+    .into()
+}
+
+// Memory orders follow the C11 `memory_order` enumeration.
+fn compile_ordering(ordering: AtomicOrdering) -> Expression {
+    Primitive::Integer32(match ordering {
+        AtomicOrdering::Relaxed => 0,
+        AtomicOrdering::Acquire => 2,
+        AtomicOrdering::Release => 3,
+        AtomicOrdering::AcquireRelease => 4,
+        AtomicOrdering::SequentiallyConsistent => 5,
+    })
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT: TargetDataLayout = TargetDataLayout::new(8, 8);
+
+    fn to_call(instruction: &Instruction) -> &Call {
+        match instruction {
+            Instruction::Call(call) => call,
+            _ => panic!("call expected"),
+        }
+    }
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new(
+                    "x",
+                    types::Pointer::new(types::Primitive::Integer128),
+                )],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::Boolean, Primitive::Boolean(true)),
+                ),
+                types::Primitive::Boolean,
+                types::CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    #[test]
+    fn keep_narrow_atomic_load() {
+        let module = Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new(
+                    "x",
+                    types::Pointer::new(types::Primitive::Integer64),
+                )],
+                Block::new(
+                    vec![AtomicLoad::new(
+                        types::Primitive::Integer64,
+                        Variable::new("x"),
+                        AtomicOrdering::SequentiallyConsistent,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::Integer64, Variable::new("y")),
+                ),
+                types::Primitive::Integer64,
+                types::CallingConvention::Target,
+                Linkage::External,
+            )],
+        );
+
+        assert_eq!(lower_wide_atomics(&module, &LAYOUT), module);
+    }
+
+    #[test]
+    fn lower_compare_and_swap() {
+        let module = lower_wide_atomics(
+            &create_module(vec![CompareAndSwap::new(
+                types::Primitive::Integer128,
+                Variable::new("x"),
+                Primitive::Integer128(0),
+                Primitive::Integer128(1),
+                AtomicOrdering::AcquireRelease,
+                AtomicOrdering::Acquire,
+                "y",
+            )
+            .into()]),
+            &LAYOUT,
+        );
+
+        assert_eq!(
+            module.function_declarations(),
+            &[FunctionDeclaration::new(
+                ATOMIC_COMPARE_EXCHANGE_FUNCTION_NAME,
+                types::Function::new(
+                    vec![
+                        types::Primitive::PointerInteger.into(),
+                        GENERIC_POINTER_TYPE.clone(),
+                        GENERIC_POINTER_TYPE.clone(),
+                        GENERIC_POINTER_TYPE.clone(),
+                        types::Primitive::Integer32.into(),
+                        types::Primitive::Integer32.into(),
+                    ],
+                    types::Primitive::Boolean,
+                    types::CallingConvention::Target,
+                )
+            )]
+        );
+
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert_eq!(instructions.len(), 5);
+
+        let call = to_call(&instructions[4]);
+
+        assert_eq!(call.name(), "y");
+        assert_eq!(
+            call.function(),
+            &Variable::new(ATOMIC_COMPARE_EXCHANGE_FUNCTION_NAME).into()
+        );
+        assert_eq!(call.arguments()[0], Primitive::PointerInteger(16).into());
+        assert_eq!(call.arguments()[4], Primitive::Integer32(4).into());
+        assert_eq!(call.arguments()[5], Primitive::Integer32(2).into());
+    }
+
+    #[test]
+    fn lower_atomic_load() {
+        let module = lower_wide_atomics(
+            &create_module(vec![AtomicLoad::new(
+                types::Primitive::Integer128,
+                Variable::new("x"),
+                AtomicOrdering::Relaxed,
+                "y",
+            )
+            .into()]),
+            &LAYOUT,
+        );
+
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            to_call(&instructions[1]).function(),
+            &Variable::new(ATOMIC_LOAD_FUNCTION_NAME).into()
+        );
+        assert_eq!(instructions[2].name(), Some("y"));
+    }
+
+    #[test]
+    fn lower_atomic_store() {
+        let module = lower_wide_atomics(
+            &create_module(vec![AtomicStore::new(
+                types::Primitive::Integer128,
+                Primitive::Integer128(42),
+                Variable::new("x"),
+                AtomicOrdering::Release,
+            )
+            .into()]),
+            &LAYOUT,
+        );
+
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            to_call(&instructions[2]).function(),
+            &Variable::new(ATOMIC_STORE_FUNCTION_NAME).into()
+        );
+    }
+
+    #[test]
+    fn lower_atomic_load_of_record() {
+        let record_type = types::Record::new(vec![types::Primitive::Integer8.into()]);
+        let module = lower_wide_atomics(
+            &Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![FunctionDefinition::new(
+                    "f",
+                    vec![Argument::new("x", types::Pointer::new(record_type.clone()))],
+                    Block::new(
+                        vec![AtomicLoad::new(
+                            record_type.clone(),
+                            Variable::new("x"),
+                            AtomicOrdering::Relaxed,
+                            "y",
+                        )
+                        .into()],
+                        Return::new(record_type.clone(), Variable::new("y")),
+                    ),
+                    record_type,
+                    types::CallingConvention::Target,
+                    Linkage::External,
+                )],
+            ),
+            &LAYOUT,
+        );
+
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            to_call(&instructions[1]).function(),
+            &Variable::new(ATOMIC_LOAD_FUNCTION_NAME).into()
+        );
+    }
+
+    #[test]
+    fn lower_atomic_operation() {
+        let module = lower_wide_atomics(
+            &create_module(vec![AtomicOperation::new(
+                types::Primitive::Integer128,
+                AtomicOperator::Subtract,
+                Variable::new("x"),
+                Primitive::Integer128(1),
+                AtomicOrdering::SequentiallyConsistent,
+                "y",
+            )
+            .into()]),
+            &LAYOUT,
+        );
+
+        assert_eq!(
+            module.function_declarations(),
+            &[FunctionDeclaration::new(
+                "__atomic_fetch_sub_16",
+                types::Function::new(
+                    vec![
+                        GENERIC_POINTER_TYPE.clone(),
+                        types::Primitive::Integer128.into(),
+                        types::Primitive::Integer32.into(),
+                    ],
+                    types::Primitive::Integer128,
+                    types::CallingConvention::Target,
+                )
+            )]
+        );
+
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert_eq!(instructions.len(), 1);
+
+        let call = to_call(&instructions[0]);
+
+        assert_eq!(call.name(), "y");
+        assert_eq!(call.arguments()[1], Primitive::Integer128(1).into());
+        assert_eq!(call.arguments()[2], Primitive::Integer32(5).into());
+    }
+
+    #[test]
+    fn generate_names_not_colliding_with_existing_ones() {
+        let module = lower_wide_atomics(
+            &create_module(vec![
+                PassThrough::new(
+                    types::Primitive::Boolean,
+                    Primitive::Boolean(true),
+                    "_atomic_0",
+                )
+                .into(),
+                AtomicLoad::new(
+                    types::Primitive::Integer128,
+                    Variable::new("x"),
+                    AtomicOrdering::Relaxed,
+                    "y",
+                )
+                .into(),
+            ]),
+            &LAYOUT,
+        );
+
+        assert_eq!(
+            module.function_definitions()[0].body().instructions()[1].name(),
+            Some("_atomic_1")
+        );
+    }
+
+    #[test]
+    fn declare_runtime_function_once() {
+        let instruction: Instruction = CompareAndSwap::new(
+            types::Primitive::Integer128,
+            Variable::new("x"),
+            Primitive::Integer128(0),
+            Primitive::Integer128(1),
+            AtomicOrdering::SequentiallyConsistent,
+            AtomicOrdering::SequentiallyConsistent,
+            "y",
+        )
+        .into();
+
+        assert_eq!(
+            lower_wide_atomics(
+                &create_module(vec![instruction.clone(), instruction]),
+                &LAYOUT
+            )
+            .function_declarations()
+            .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn skip_declaration_of_defined_runtime_function() {
+        let module = create_module(vec![AtomicLoad::new(
+            types::Primitive::Integer128,
+            Variable::new("x"),
+            AtomicOrdering::Relaxed,
+            "y",
+        )
+        .into()]);
+        let module = Module::new(
+            vec![],
+            vec![],
+            vec![],
+            module
+                .function_definitions()
+                .iter()
+                .cloned()
+                .chain([FunctionDefinition::new(
+                    ATOMIC_LOAD_FUNCTION_NAME,
+                    vec![
+                        Argument::new("size", types::Primitive::PointerInteger),
+                        Argument::new("source", GENERIC_POINTER_TYPE.clone()),
+                        Argument::new("destination", GENERIC_POINTER_TYPE.clone()),
+                        Argument::new("ordering", types::Primitive::Integer32),
+                    ],
+                    Block::new(vec![], Return::new(VOID_TYPE.clone(), VOID_VALUE.clone())),
+                    VOID_TYPE.clone(),
+                    types::CallingConvention::Target,
+                    Linkage::External,
+                )])
+                .collect(),
+        );
+
+        assert_eq!(
+            lower_wide_atomics(&module, &LAYOUT).function_declarations(),
+            &[]
+        );
+    }
+}
@@ -0,0 +1,276 @@
+mod error;
+
+use super::renaming::create_name_generator;
+use crate::{
+    build,
+    ir::*,
+    types::{self, VOID_TYPE},
+};
+pub use error::*;
+
+pub fn check_bounds(module: &Module) -> Result<(), BoundsCheckError> {
+    for definition in module.function_definitions() {
+        check_block(definition.body())?;
+    }
+
+    Ok(())
+}
+
+fn check_block(block: &Block) -> Result<(), BoundsCheckError> {
+    for instruction in block.instructions() {
+        match instruction {
+            Instruction::AtomicLoad(load) => check_pointer(load.pointer())?,
+            Instruction::AtomicStore(store) => check_pointer(store.pointer())?,
+            Instruction::If(if_) => {
+                check_block(if_.then())?;
+                check_block(if_.else_())?;
+            }
+            Instruction::Load(load) => check_pointer(load.pointer())?,
+            Instruction::Store(store) => check_pointer(store.pointer())?,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn check_pointer(pointer: &Expression) -> Result<(), BoundsCheckError> {
+    if let Expression::PointerAddress(address) = pointer {
+        if let (Some(length), Some(Primitive::PointerInteger(index))) =
+            (address.type_().length(), address.offset().to_primitive())
+        {
+            if index < 0 || index as usize >= length {
+                return Err(BoundsCheckError::OutOfBounds(address.clone()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Loads and stores through pointer addresses of bounded pointers trap at runtime
+// if their offsets are out of bounds. Offsets known to be in bounds are not
+// checked.
+pub fn insert_bounds_checks(module: &Module) -> Module {
+    let mut generate_name = create_name_generator(module, "_bounds_");
+
+    module.clone().map_function_definitions(|definition| {
+        FunctionDefinition::new(
+            definition.name(),
+            definition.arguments().to_vec(),
+            insert_in_block(definition.body(), &mut generate_name),
+            definition.result_type().clone(),
+            definition.calling_convention(),
+            definition.linkage(),
+        )
+    })
+}
+
+fn insert_in_block(block: &Block, generate_name: &mut impl FnMut() -> String) -> Block {
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        let pointer = match instruction {
+            Instruction::AtomicLoad(load) => Some(load.pointer()),
+            Instruction::AtomicStore(store) => Some(store.pointer()),
+            Instruction::Load(load) => Some(load.pointer()),
+            Instruction::Store(store) => Some(store.pointer()),
+            _ => None,
+        };
+
+        if let Some(Expression::PointerAddress(address)) = pointer {
+            if let Some(length) = address.type_().length() {
+                if !is_in_bounds(address.offset(), length) {
+                    instructions.push(create_bounds_check(
+                        address.offset(),
+                        length,
+                        generate_name(),
+                    ));
+                }
+            }
+        }
+
+        instructions.push(match instruction {
+            Instruction::If(if_) => If::new(
+                if_.type_().clone(),
+                if_.condition().clone(),
+                insert_in_block(if_.then(), generate_name),
+                insert_in_block(if_.else_(), generate_name),
+                if_.name(),
+            )
+            .into(),
+            _ => instruction.clone(),
+        });
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn is_in_bounds(offset: &Expression, length: usize) -> bool {
+    matches!(
+        offset,
+        Expression::Primitive(Primitive::PointerInteger(index))
+            if *index >= 0 && (*index as usize) < length
+    )
+}
+
+// Comparisons are unsigned and so negative offsets are out of bounds too.
+fn create_bounds_check(offset: &Expression, length: usize, name: String) -> Instruction {
+    If::new(
+        VOID_TYPE.clone(),
+        ComparisonOperation::new(
+            types::Primitive::PointerInteger,
+            ComparisonOperator::LessThan,
+            offset.clone(),
+            Primitive::PointerInteger(length as i64),
+        ),
+        Block::new(vec![], Branch::new(VOID_TYPE.clone(), build::void_value())),
+        Block::new(vec![], TerminalInstruction::Trap),
+        name,
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_module(index: i64) -> Module {
+        let pointer_type = types::Pointer::bounded(types::Primitive::PointerInteger, 4);
+
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("x", pointer_type.clone())],
+                Block::new(
+                    vec![Load::new(
+                        types::Primitive::PointerInteger,
+                        PointerAddress::new(
+                            pointer_type,
+                            Variable::new("x"),
+                            Primitive::PointerInteger(index),
+                        ),
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                types::Primitive::PointerInteger,
+                types::CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    #[test]
+    fn check_in_bounds_access() {
+        assert_eq!(check_bounds(&create_module(3)), Ok(()));
+    }
+
+    #[test]
+    fn fail_to_check_out_of_bounds_access() {
+        assert!(matches!(
+            check_bounds(&create_module(4)),
+            Err(BoundsCheckError::OutOfBounds(_))
+        ));
+    }
+
+    #[test]
+    fn insert_bounds_check() {
+        let module = create_module(0);
+        let definition = &module.function_definitions()[0];
+        let module = Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    definition.arguments()[0].clone(),
+                    Argument::new("i", types::Primitive::PointerInteger),
+                ],
+                Block::new(
+                    vec![Load::new(
+                        types::Primitive::PointerInteger,
+                        PointerAddress::new(
+                            types::Pointer::bounded(types::Primitive::PointerInteger, 4),
+                            Variable::new("x"),
+                            Variable::new("i"),
+                        ),
+                        "y",
+                    )
+                    .into()],
+                    definition.body().terminal_instruction().clone(),
+                ),
+                types::Primitive::PointerInteger,
+                types::CallingConvention::Target,
+                Linkage::External,
+            )],
+        );
+
+        assert_eq!(
+            insert_bounds_checks(&module).function_definitions()[0]
+                .body()
+                .instructions(),
+            &[
+                create_bounds_check(&Variable::new("i").into(), 4, "_bounds_0".into()),
+                module.function_definitions()[0].body().instructions()[0].clone(),
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_bounds_check_for_out_of_bounds_constant_offset() {
+        assert_eq!(
+            insert_bounds_checks(&create_module(4)).function_definitions()[0]
+                .body()
+                .instructions()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn do_not_insert_bounds_check_for_in_bounds_access() {
+        let module = create_module(3);
+
+        assert_eq!(insert_bounds_checks(&module), module);
+    }
+
+    #[test]
+    fn check_unbounded_pointer() {
+        let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+        assert_eq!(
+            check_bounds(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![FunctionDefinition::new(
+                    "f",
+                    vec![Argument::new("x", pointer_type.clone())],
+                    Block::new(
+                        vec![Store::new(
+                            types::Primitive::PointerInteger,
+                            Primitive::PointerInteger(42),
+                            PointerAddress::new(
+                                pointer_type,
+                                Variable::new("x"),
+                                Primitive::PointerInteger(42),
+                            ),
+                        )
+                        .into()],
+                        Return::new(build::void_type(), build::void_value()),
+                    ),
+                    build::void_type(),
+                    types::CallingConvention::Target,
+                    Linkage::External,
+                )],
+            )),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::{analysis::format_expression, ir::*};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoundsCheckError {
+    OutOfBounds(PointerAddress),
+}
+
+impl Display for BoundsCheckError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfBounds(address) => write!(
+                formatter,
+                "pointer address out of bounds: {}",
+                format_expression(&address.clone().into())
+            ),
+        }
+    }
+}
+
+impl Error for BoundsCheckError {}
@@ -0,0 +1,387 @@
+use super::{
+    commutative_normalization::normalize_commutative_operations,
+    dead_code::{collect_pure_functions, is_pure_call, shadow_pure_functions},
+    free_variables::collect_from_expression,
+};
+use crate::{ir::*, types::Type};
+use std::collections::HashSet;
+
+// Expressions bound by pass-through instructions and calls to read-none
+// functions are reused if the same ones are bound already in the same or outer
+// blocks.
+pub fn eliminate_common_subexpressions(module: &Module) -> Module {
+    let module = normalize_commutative_operations(module);
+    let pure_functions = collect_pure_functions(&module);
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    eliminate_in_block(
+                        definition.body(),
+                        &shadow_pure_functions(&pure_functions, definition),
+                        &[],
+                    ),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn eliminate_in_block(
+    block: &Block,
+    pure_functions: &HashSet<&str>,
+    expressions: &[(Expression, String)],
+) -> Block {
+    let mut pure_functions = pure_functions.clone();
+    let mut expressions = expressions.to_vec();
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        let (instruction, expression) = match instruction {
+            Instruction::If(if_) => (
+                If::new(
+                    if_.type_().clone(),
+                    if_.condition().clone(),
+                    eliminate_in_block(if_.then(), &pure_functions, &expressions),
+                    eliminate_in_block(if_.else_(), &pure_functions, &expressions),
+                    if_.name(),
+                )
+                .into(),
+                None,
+            ),
+            Instruction::Call(call) if is_pure_call(instruction, &pure_functions) => {
+                // Calls to read-none functions are equivalent to pure call
+                // expressions.
+                let call_expression = PureCall::new(
+                    call.type_().clone(),
+                    call.function().clone(),
+                    call.arguments().to_vec(),
+                )
+                .into();
+
+                reuse_expression(
+                    instruction,
+                    call.type_().result(),
+                    call_expression,
+                    &expressions,
+                )
+            }
+            Instruction::PassThrough(pass) => reuse_expression(
+                instruction,
+                pass.type_(),
+                pass.expression().clone(),
+                &expressions,
+            ),
+            _ => (instruction.clone(), None),
+        };
+
+        // Expressions referring to rebound names are not available anymore.
+        if let Some(name) = instruction.name() {
+            expressions.retain(|(expression, other)| {
+                other != name && !collect_from_expression(expression).contains(name)
+            });
+
+            if let Some(expression) = expression {
+                if !collect_from_expression(&expression).contains(name) {
+                    expressions.push((expression, name.into()));
+                }
+            }
+
+            pure_functions.remove(name);
+        }
+
+        instructions.push(instruction);
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn reuse_expression(
+    instruction: &Instruction,
+    type_: &Type,
+    expression: Expression,
+    expressions: &[(Expression, String)],
+) -> (Instruction, Option<Expression>) {
+    if let Some((_, name)) = expressions.iter().find(|(other, _)| other == &expression) {
+        (
+            PassThrough::new(
+                type_.clone(),
+                Variable::new(name),
+                instruction.name().unwrap(),
+            )
+            .into(),
+            None,
+        )
+    } else {
+        (instruction.clone(), Some(expression))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn eliminate_in_function(instructions: Vec<Instruction>) -> Block {
+        eliminate_common_subexpressions(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("a", types::Primitive::Integer64),
+                    Argument::new("b", types::Primitive::Integer64),
+                ],
+                Block::new(instructions, TerminalInstruction::Unreachable),
+                types::Primitive::Integer64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        ))
+        .function_definitions()[0]
+            .body()
+            .clone()
+    }
+
+    fn add(lhs: &str, rhs: &str) -> ArithmeticOperation {
+        ArithmeticOperation::new(
+            types::Primitive::Integer64,
+            ArithmeticOperator::Add,
+            Variable::new(lhs),
+            Variable::new(rhs),
+        )
+    }
+
+    #[test]
+    fn eliminate_commuted_addition() {
+        let block = eliminate_in_function(vec![
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "x").into(),
+            PassThrough::new(types::Primitive::Integer64, add("b", "a"), "y").into(),
+        ]);
+
+        assert_eq!(
+            block.instructions()[1],
+            PassThrough::new(types::Primitive::Integer64, Variable::new("x"), "y").into()
+        );
+    }
+
+    #[test]
+    fn keep_different_expressions() {
+        let instructions = vec![
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "x").into(),
+            PassThrough::new(types::Primitive::Integer64, add("a", "a"), "y").into(),
+        ];
+
+        assert_eq!(
+            eliminate_in_function(instructions.clone()).instructions(),
+            instructions
+        );
+    }
+
+    #[test]
+    fn eliminate_expression_in_if() {
+        let block = eliminate_in_function(vec![
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "x").into(),
+            If::new(
+                types::Primitive::Integer64,
+                Primitive::Boolean(true),
+                Block::new(
+                    vec![PassThrough::new(types::Primitive::Integer64, add("b", "a"), "y").into()],
+                    Branch::new(types::Primitive::Integer64, Variable::new("y")),
+                ),
+                Block::new(vec![], TerminalInstruction::Unreachable),
+                "z",
+            )
+            .into(),
+        ]);
+
+        assert_eq!(
+            block.instructions()[1],
+            If::new(
+                types::Primitive::Integer64,
+                Primitive::Boolean(true),
+                Block::new(
+                    vec![
+                        PassThrough::new(types::Primitive::Integer64, Variable::new("x"), "y")
+                            .into()
+                    ],
+                    Branch::new(types::Primitive::Integer64, Variable::new("y")),
+                ),
+                Block::new(vec![], TerminalInstruction::Unreachable),
+                "z",
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn keep_expression_with_rebound_free_variable() {
+        let instructions = vec![
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "x").into(),
+            PassThrough::new(types::Primitive::Integer64, Primitive::Integer64(0), "a").into(),
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "y").into(),
+        ];
+
+        assert_eq!(
+            eliminate_in_function(instructions.clone()).instructions(),
+            instructions
+        );
+    }
+
+    #[test]
+    fn keep_expression_with_rebound_name() {
+        let instructions = vec![
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "x").into(),
+            PassThrough::new(types::Primitive::Integer64, Primitive::Integer64(0), "x").into(),
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "y").into(),
+        ];
+
+        assert_eq!(
+            eliminate_in_function(instructions.clone()).instructions(),
+            instructions
+        );
+    }
+
+    #[test]
+    fn keep_expression_referring_to_its_own_name() {
+        let instructions = vec![
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "a").into(),
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "y").into(),
+        ];
+
+        assert_eq!(
+            eliminate_in_function(instructions.clone()).instructions(),
+            instructions
+        );
+    }
+
+    fn eliminate_calls(attributes: FunctionAttributes, instructions: Vec<Instruction>) -> Block {
+        eliminate_common_subexpressions(&Module::new(
+            vec![],
+            vec![FunctionDeclaration::with_attributes(
+                "g",
+                create_function_type(),
+                attributes,
+            )],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("a", types::Primitive::Integer64)],
+                Block::new(instructions, TerminalInstruction::Unreachable),
+                types::Primitive::Integer64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        ))
+        .function_definitions()[0]
+            .body()
+            .clone()
+    }
+
+    fn create_function_type() -> types::Function {
+        types::Function::new(
+            vec![types::Primitive::Integer64.into()],
+            types::Primitive::Integer64,
+            CallingConvention::Target,
+        )
+    }
+
+    fn create_call(name: &str) -> Instruction {
+        Call::new(
+            create_function_type(),
+            Variable::new("g"),
+            vec![Variable::new("a").into()],
+            name,
+        )
+        .into()
+    }
+
+    #[test]
+    fn eliminate_read_none_call() {
+        let block = eliminate_calls(
+            FunctionAttributes {
+                read_none: true,
+                ..Default::default()
+            },
+            vec![create_call("x"), create_call("y")],
+        );
+
+        assert_eq!(
+            block.instructions()[1],
+            PassThrough::new(types::Primitive::Integer64, Variable::new("x"), "y").into()
+        );
+    }
+
+    #[test]
+    fn keep_read_only_call() {
+        let instructions = vec![create_call("x"), create_call("y")];
+
+        assert_eq!(
+            eliminate_calls(
+                FunctionAttributes {
+                    read_only: true,
+                    ..Default::default()
+                },
+                instructions.clone()
+            )
+            .instructions(),
+            instructions
+        );
+    }
+
+    #[test]
+    fn keep_call_of_shadowing_local() {
+        let instructions = vec![
+            create_call("x"),
+            PassThrough::new(create_function_type(), Variable::new("h"), "g").into(),
+            create_call("y"),
+        ];
+
+        assert_eq!(
+            eliminate_calls(
+                FunctionAttributes {
+                    read_none: true,
+                    ..Default::default()
+                },
+                instructions.clone()
+            )
+            .instructions(),
+            instructions
+        );
+    }
+
+    #[test]
+    fn keep_expression_out_of_if() {
+        let instructions = vec![
+            If::new(
+                types::Primitive::Integer64,
+                Primitive::Boolean(true),
+                Block::new(
+                    vec![PassThrough::new(types::Primitive::Integer64, add("a", "b"), "x").into()],
+                    Branch::new(types::Primitive::Integer64, Variable::new("x")),
+                ),
+                Block::new(vec![], TerminalInstruction::Unreachable),
+                "y",
+            )
+            .into(),
+            PassThrough::new(types::Primitive::Integer64, add("a", "b"), "z").into(),
+        ];
+
+        assert_eq!(
+            eliminate_in_function(instructions.clone()).instructions(),
+            instructions
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use super::expression_conversion::convert_expressions;
+use crate::ir::*;
+use std::cmp::Ordering;
+
+// Operands of commutative operations are sorted by their structures so that
+// equivalent expressions become identical.
+pub fn normalize_commutative_operations(module: &Module) -> Module {
+    convert_expressions(module, &normalize_expression)
+}
+
+fn normalize_expression(expression: &Expression) -> Expression {
+    match expression {
+        Expression::ArithmeticOperation(operation)
+            if matches!(
+                operation.operator(),
+                ArithmeticOperator::Add | ArithmeticOperator::Multiply
+            ) =>
+        {
+            let (lhs, rhs) = sort_operands(operation.lhs(), operation.rhs());
+
+            ArithmeticOperation::new(operation.type_(), operation.operator(), lhs, rhs)
+                .with_fast_math_flags(operation.fast_math_flags())
+                .with_overflow_flags(operation.overflow_flags())
+                .into()
+        }
+        Expression::BitwiseOperation(operation) => {
+            let (lhs, rhs) = sort_operands(operation.lhs(), operation.rhs());
+
+            BitwiseOperation::new(operation.type_(), operation.operator(), lhs, rhs).into()
+        }
+        Expression::ComparisonOperation(operation)
+            if matches!(
+                operation.operator(),
+                ComparisonOperator::Equal
+                    | ComparisonOperator::NotEqual
+                    | ComparisonOperator::Ordered
+                    | ComparisonOperator::Unordered
+                    | ComparisonOperator::UnorderedEqual
+                    | ComparisonOperator::UnorderedNotEqual
+            ) =>
+        {
+            let (lhs, rhs) = sort_operands(operation.lhs(), operation.rhs());
+
+            ComparisonOperation::new(operation.type_(), operation.operator(), lhs, rhs).into()
+        }
+        _ => expression.clone(),
+    }
+}
+
+fn sort_operands(lhs: &Expression, rhs: &Expression) -> (Expression, Expression) {
+    if compare_expressions(lhs, rhs) == Ordering::Greater {
+        (rhs.clone(), lhs.clone())
+    } else {
+        (lhs.clone(), rhs.clone())
+    }
+}
+
+// Variables come before constants and constants before other expressions.
+// Variables and constants are ordered by their names and values respectively.
+fn compare_expressions(one: &Expression, other: &Expression) -> Ordering {
+    match (one, other) {
+        (Expression::Variable(one), Expression::Variable(other)) => one.name().cmp(other.name()),
+        (Expression::Primitive(one), Expression::Primitive(other)) => {
+            primitive_key(*one).cmp(&primitive_key(*other))
+        }
+        (Expression::ArithmeticOperation(one), Expression::ArithmeticOperation(other)) => {
+            compare_operands((one.lhs(), one.rhs()), (other.lhs(), other.rhs()))
+        }
+        (Expression::BitwiseOperation(one), Expression::BitwiseOperation(other)) => {
+            compare_operands((one.lhs(), one.rhs()), (other.lhs(), other.rhs()))
+        }
+        (Expression::ComparisonOperation(one), Expression::ComparisonOperation(other)) => {
+            compare_operands((one.lhs(), one.rhs()), (other.lhs(), other.rhs()))
+        }
+        _ => expression_rank(one).cmp(&expression_rank(other)),
+    }
+}
+
+fn compare_operands(
+    (one_lhs, one_rhs): (&Expression, &Expression),
+    (other_lhs, other_rhs): (&Expression, &Expression),
+) -> Ordering {
+    compare_expressions(one_lhs, other_lhs).then_with(|| compare_expressions(one_rhs, other_rhs))
+}
+
+fn expression_rank(expression: &Expression) -> usize {
+    match expression {
+        Expression::Variable(_) => 0,
+        Expression::Primitive(_) => 1,
+        Expression::ArithmeticOperation(_) => 2,
+        Expression::BitwiseOperation(_) => 3,
+        Expression::ComparisonOperation(_) => 4,
+        _ => 5,
+    }
+}
+
+fn primitive_key(primitive: Primitive) -> (usize, u128) {
+    match primitive {
+        Primitive::Boolean(boolean) => (0, boolean as u128),
+        Primitive::Float16(number) => (1, number.to_bits() as u128),
+        Primitive::Float32(number) => (2, number.to_bits() as u128),
+        Primitive::Float64(number) => (3, number.to_bits() as u128),
+        Primitive::Float128(number) => (4, number.to_bits() as u128),
+        Primitive::Integer8(number) => (5, number as u128),
+        Primitive::Integer16(number) => (6, number as u128),
+        Primitive::Integer32(number) => (7, number as u128),
+        Primitive::Integer64(number) => (8, number as u128),
+        Primitive::Integer128(number) => (9, number),
+        Primitive::PointerInteger(number) => (10, number as u128),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types;
+
+    fn normalize(expression: impl Into<Expression>) -> Expression {
+        normalize_commutative_operations(&Module::new(
+            vec![],
+            vec![],
+            vec![VariableDefinition::new(
+                "x",
+                expression,
+                types::Primitive::Integer64,
+                false,
+                Linkage::Internal,
+                None,
+            )],
+            vec![],
+        ))
+        .variable_definitions()[0]
+            .body()
+            .clone()
+    }
+
+    #[test]
+    fn normalize_addition() {
+        let operation = |lhs: &str, rhs: &str| {
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Variable::new(lhs),
+                Variable::new(rhs),
+            )
+        };
+
+        assert_eq!(
+            normalize(operation("a", "b")),
+            normalize(operation("b", "a"))
+        );
+    }
+
+    #[test]
+    fn keep_subtraction() {
+        let operation = ArithmeticOperation::new(
+            types::Primitive::Integer64,
+            ArithmeticOperator::Subtract,
+            Variable::new("b"),
+            Variable::new("a"),
+        );
+
+        assert_eq!(normalize(operation.clone()), operation.into());
+    }
+
+    #[test]
+    fn normalize_bitwise_operation() {
+        let operation = |lhs: Expression, rhs: Expression| {
+            BitwiseOperation::new(types::Primitive::Integer64, BitwiseOperator::Xor, lhs, rhs)
+        };
+
+        assert_eq!(
+            normalize(operation(
+                Variable::new("a").into(),
+                Primitive::Integer64(1).into()
+            )),
+            normalize(operation(
+                Primitive::Integer64(1).into(),
+                Variable::new("a").into()
+            ))
+        );
+    }
+
+    #[test]
+    fn normalize_equality() {
+        let operation = |lhs: &str, rhs: &str| {
+            ComparisonOperation::new(
+                types::Primitive::Integer64,
+                ComparisonOperator::Equal,
+                Variable::new(lhs),
+                Variable::new(rhs),
+            )
+        };
+
+        assert_eq!(
+            normalize(operation("a", "b")),
+            normalize(operation("b", "a"))
+        );
+    }
+
+    #[test]
+    fn keep_ordered_comparison() {
+        let operation = ComparisonOperation::new(
+            types::Primitive::Integer64,
+            ComparisonOperator::LessThan,
+            Variable::new("b"),
+            Variable::new("a"),
+        );
+
+        assert_eq!(normalize(operation.clone()), operation.into());
+    }
+
+    #[test]
+    fn put_variable_before_constant() {
+        assert_eq!(
+            normalize(ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Primitive::Integer64(1),
+                Variable::new("a"),
+            )),
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Variable::new("a"),
+                Primitive::Integer64(1),
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn sort_constants_by_values() {
+        assert_eq!(
+            normalize(ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Primitive::Integer64(10),
+                Primitive::Integer64(2),
+            )),
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Primitive::Integer64(2),
+                Primitive::Integer64(10),
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn normalize_nested_operations() {
+        let operation = |lhs: Expression, rhs: Expression| {
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Multiply,
+                lhs,
+                rhs,
+            )
+        };
+
+        assert_eq!(
+            normalize(operation(
+                operation(Variable::new("b").into(), Variable::new("a").into()).into(),
+                Variable::new("c").into()
+            )),
+            normalize(operation(
+                Variable::new("c").into(),
+                operation(Variable::new("a").into(), Variable::new("b").into()).into()
+            ))
+        );
+    }
+}
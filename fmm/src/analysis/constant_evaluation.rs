@@ -0,0 +1,933 @@
+use super::type_size::{type_alignment, type_size};
+use crate::{ir::*, types};
+use std::cmp::Ordering;
+
+pub fn evaluate_fma(fma: &Fma, pointer_size: usize) -> Option<Primitive> {
+    let evaluate = |expression| evaluate_constant_expression(expression, pointer_size);
+
+    Some(
+        match (
+            evaluate(fma.lhs())?,
+            evaluate(fma.rhs())?,
+            evaluate(fma.addend())?,
+        ) {
+            (Primitive::Float32(lhs), Primitive::Float32(rhs), Primitive::Float32(addend)) => {
+                Primitive::Float32(lhs.mul_add(rhs, addend))
+            }
+            (lhs, rhs, addend) => from_float(
+                fma.type_(),
+                to_float(lhs)?.mul_add(to_float(rhs)?, to_float(addend)?),
+            ),
+        },
+    )
+}
+
+pub fn evaluate_math_operation(
+    operation: &MathOperation,
+    pointer_size: usize,
+) -> Option<Primitive> {
+    let value = to_float(evaluate_constant_expression(
+        operation.value(),
+        pointer_size,
+    )?)?;
+
+    Some(from_float(
+        operation.type_(),
+        match operation.operator() {
+            MathOperator::Absolute => value.abs(),
+            MathOperator::Ceiling => value.ceil(),
+            MathOperator::Cosine => value.cos(),
+            MathOperator::Exponential => value.exp(),
+            MathOperator::Floor => value.floor(),
+            MathOperator::Logarithm => value.ln(),
+            MathOperator::Sine => value.sin(),
+            MathOperator::SquareRoot => value.sqrt(),
+        },
+    ))
+}
+
+pub fn evaluate_saturating_arithmetic_operation(
+    operation: &SaturatingArithmeticOperation,
+    pointer_size: usize,
+) -> Option<Primitive> {
+    let (wrapped, overflow, saturated) = evaluate_overflowing_arithmetic_operation(
+        operation.type_(),
+        operation.operator(),
+        operation.signed(),
+        evaluate_constant_expression(operation.lhs(), pointer_size)?,
+        evaluate_constant_expression(operation.rhs(), pointer_size)?,
+        pointer_size,
+    )?;
+
+    Some(if overflow { saturated } else { wrapped })
+}
+
+pub fn evaluate_checked_arithmetic_operation(
+    operation: &CheckedArithmeticOperation,
+    pointer_size: usize,
+) -> Option<Record> {
+    let (wrapped, overflow, _) = evaluate_overflowing_arithmetic_operation(
+        operation.type_(),
+        operation.operator(),
+        operation.signed(),
+        evaluate_constant_expression(operation.lhs(), pointer_size)?,
+        evaluate_constant_expression(operation.rhs(), pointer_size)?,
+        pointer_size,
+    )?;
+
+    Some(Record::new(
+        operation.result_type(),
+        vec![wrapped.into(), Primitive::Boolean(overflow).into()],
+    ))
+}
+
+pub fn evaluate_constant_expression(
+    expression: &Expression,
+    pointer_size: usize,
+) -> Option<Primitive> {
+    let evaluate = |expression| evaluate_constant_expression(expression, pointer_size);
+
+    match expression {
+        Expression::AlignOf(align_of) => Some(Primitive::PointerInteger(
+            type_alignment(align_of.type_(), pointer_size).ok()? as i64,
+        )),
+        Expression::ArithmeticOperation(operation) => evaluate_arithmetic_operation(
+            operation.type_(),
+            operation.operator(),
+            evaluate(operation.lhs())?,
+            evaluate(operation.rhs())?,
+            pointer_size,
+        ),
+        Expression::BitCast(bit_cast) => {
+            if bit_cast.from() == bit_cast.to() {
+                evaluate(bit_cast.expression())
+            } else {
+                None
+            }
+        }
+        Expression::BitManipulationOperation(operation) => evaluate_bit_manipulation_operation(
+            operation.type_(),
+            operation.operator(),
+            evaluate(operation.value())?,
+            pointer_size,
+        ),
+        Expression::BitwiseNotOperation(operation) => {
+            let value = to_integer(evaluate(operation.value())?, pointer_size)?;
+
+            Some(from_integer(operation.type_(), !value, pointer_size))
+        }
+        Expression::BitwiseOperation(operation) => {
+            let lhs = to_integer(evaluate(operation.lhs())?, pointer_size)?;
+            let rhs = to_integer(evaluate(operation.rhs())?, pointer_size)?;
+
+            Some(from_integer(
+                operation.type_(),
+                match operation.operator() {
+                    BitwiseOperator::And => lhs & rhs,
+                    BitwiseOperator::Or => lhs | rhs,
+                    BitwiseOperator::Xor => lhs ^ rhs,
+                },
+                pointer_size,
+            ))
+        }
+        Expression::ComparisonOperation(operation) => evaluate_comparison_operation(
+            operation.operator(),
+            evaluate(operation.lhs())?,
+            evaluate(operation.rhs())?,
+            pointer_size,
+        ),
+        Expression::ExtractBits(extract) => {
+            let value = to_integer(evaluate(extract.value())?, pointer_size)?;
+
+            Some(from_integer(
+                extract.type_(),
+                truncate(
+                    value.checked_shr(extract.offset())?,
+                    extract.width() as usize,
+                ),
+                pointer_size,
+            ))
+        }
+        Expression::InsertBits(insert) => {
+            let value = to_integer(evaluate(insert.value())?, pointer_size)?;
+            let field = to_integer(evaluate(insert.field())?, pointer_size)?;
+            let mask = truncate(u128::MAX, insert.width() as usize).checked_shl(insert.offset())?;
+
+            Some(from_integer(
+                insert.type_(),
+                value & !mask | field.checked_shl(insert.offset())? & mask,
+                pointer_size,
+            ))
+        }
+        Expression::Primitive(primitive) => Some(*primitive),
+        Expression::Select(select) => match evaluate(select.condition())? {
+            Primitive::Boolean(true) => evaluate(select.then()),
+            Primitive::Boolean(false) => evaluate(select.else_()),
+            _ => None,
+        },
+        Expression::SizeOf(size_of) => Some(Primitive::PointerInteger(
+            type_size(size_of.type_(), pointer_size).ok()? as i64,
+        )),
+        Expression::AbsoluteAddress(_)
+        | Expression::ByteOffset(_)
+        | Expression::PointerAddress(_)
+        | Expression::PureCall(_)
+        | Expression::Record(_)
+        | Expression::RecordAddress(_)
+        | Expression::Undefined(_)
+        | Expression::Union(_)
+        | Expression::UnionAddress(_)
+        | Expression::Variable(_) => None,
+    }
+}
+
+fn evaluate_arithmetic_operation(
+    type_: types::Primitive,
+    operator: ArithmeticOperator,
+    lhs: Primitive,
+    rhs: Primitive,
+    pointer_size: usize,
+) -> Option<Primitive> {
+    if let (Some(lhs), Some(rhs)) = (to_float(lhs), to_float(rhs)) {
+        Some(from_float(
+            type_,
+            match operator {
+                ArithmeticOperator::Add => lhs + rhs,
+                ArithmeticOperator::Subtract => lhs - rhs,
+                ArithmeticOperator::Multiply => lhs * rhs,
+                ArithmeticOperator::Divide => lhs / rhs,
+            },
+        ))
+    } else {
+        let lhs = to_integer(lhs, pointer_size)?;
+        let rhs = to_integer(rhs, pointer_size)?;
+
+        Some(from_integer(
+            type_,
+            match operator {
+                ArithmeticOperator::Add => lhs.wrapping_add(rhs),
+                ArithmeticOperator::Subtract => lhs.wrapping_sub(rhs),
+                ArithmeticOperator::Multiply => lhs.wrapping_mul(rhs),
+                ArithmeticOperator::Divide => lhs.checked_div(rhs)?,
+            },
+            pointer_size,
+        ))
+    }
+}
+
+fn evaluate_bit_manipulation_operation(
+    type_: types::Primitive,
+    operator: BitManipulationOperator,
+    value: Primitive,
+    pointer_size: usize,
+) -> Option<Primitive> {
+    let bits = integer_bit_width(type_, pointer_size)?;
+    let value = to_integer(value, pointer_size)?;
+
+    Some(from_integer(
+        type_,
+        match operator {
+            BitManipulationOperator::ByteSwap => value.swap_bytes() >> (128 - bits),
+            BitManipulationOperator::CountLeadingZeros => {
+                (value.leading_zeros() - (128 - bits)) as u128
+            }
+            BitManipulationOperator::CountTrailingZeros => value.trailing_zeros().min(bits) as u128,
+            BitManipulationOperator::PopulationCount => value.count_ones() as u128,
+        },
+        pointer_size,
+    ))
+}
+
+// This returns wrapped results, overflow flags, and saturated results.
+fn evaluate_overflowing_arithmetic_operation(
+    type_: types::Primitive,
+    operator: ArithmeticOperator,
+    signed: bool,
+    lhs: Primitive,
+    rhs: Primitive,
+    pointer_size: usize,
+) -> Option<(Primitive, bool, Primitive)> {
+    let bits = integer_bit_width(type_, pointer_size)?;
+    let lhs = to_integer(lhs, pointer_size)?;
+    let rhs = to_integer(rhs, pointer_size)?;
+    let wrapped = match operator {
+        ArithmeticOperator::Add => lhs.wrapping_add(rhs),
+        ArithmeticOperator::Subtract => lhs.wrapping_sub(rhs),
+        ArithmeticOperator::Multiply => lhs.wrapping_mul(rhs),
+        ArithmeticOperator::Divide => return None,
+    };
+
+    let (overflow, saturated) = if signed {
+        let extend = |number: u128| ((number << (128 - bits)) as i128) >> (128 - bits);
+        let (lhs, rhs) = (extend(lhs), extend(rhs));
+        let maximum = i128::MAX >> (128 - bits);
+        let result = match operator {
+            ArithmeticOperator::Add => lhs.checked_add(rhs),
+            ArithmeticOperator::Subtract => lhs.checked_sub(rhs),
+            ArithmeticOperator::Multiply => lhs.checked_mul(rhs),
+            ArithmeticOperator::Divide => return None,
+        };
+        let positive = match operator {
+            ArithmeticOperator::Add => rhs > 0,
+            ArithmeticOperator::Subtract => rhs < 0,
+            ArithmeticOperator::Multiply => (lhs < 0) == (rhs < 0),
+            ArithmeticOperator::Divide => return None,
+        };
+
+        (
+            result.map(|result| result < !maximum || result > maximum) != Some(false),
+            if positive { maximum } else { !maximum } as u128,
+        )
+    } else {
+        let maximum = truncate(u128::MAX, bits as usize);
+        let result = match operator {
+            ArithmeticOperator::Add => lhs.checked_add(rhs),
+            ArithmeticOperator::Subtract => lhs.checked_sub(rhs),
+            ArithmeticOperator::Multiply => lhs.checked_mul(rhs),
+            ArithmeticOperator::Divide => return None,
+        };
+
+        (
+            result.map(|result| result > maximum) != Some(false),
+            if operator == ArithmeticOperator::Subtract {
+                0
+            } else {
+                maximum
+            },
+        )
+    };
+
+    Some((
+        from_integer(type_, wrapped, pointer_size),
+        overflow,
+        from_integer(type_, saturated, pointer_size),
+    ))
+}
+
+fn evaluate_comparison_operation(
+    operator: ComparisonOperator,
+    lhs: Primitive,
+    rhs: Primitive,
+    pointer_size: usize,
+) -> Option<Primitive> {
+    let ordering = if let (Some(lhs), Some(rhs)) = (to_float(lhs), to_float(rhs)) {
+        lhs.partial_cmp(&rhs)
+    } else {
+        Some(to_integer(lhs, pointer_size)?.cmp(&to_integer(rhs, pointer_size)?))
+    };
+
+    // Orderings are absent only if any of operands is NaN.
+    let ordered = |predicate: fn(Ordering) -> bool| ordering.map(predicate).unwrap_or(false);
+    let unordered = |predicate: fn(Ordering) -> bool| ordering.map(predicate).unwrap_or(true);
+
+    Some(Primitive::Boolean(match operator {
+        ComparisonOperator::Equal => ordered(Ordering::is_eq),
+        ComparisonOperator::NotEqual => ordered(Ordering::is_ne),
+        ComparisonOperator::LessThan => ordered(Ordering::is_lt),
+        ComparisonOperator::LessThanOrEqual => ordered(Ordering::is_le),
+        ComparisonOperator::GreaterThan => ordered(Ordering::is_gt),
+        ComparisonOperator::GreaterThanOrEqual => ordered(Ordering::is_ge),
+        ComparisonOperator::Ordered => ordering.is_some(),
+        ComparisonOperator::Unordered => ordering.is_none(),
+        ComparisonOperator::UnorderedEqual => unordered(Ordering::is_eq),
+        ComparisonOperator::UnorderedNotEqual => unordered(Ordering::is_ne),
+        ComparisonOperator::UnorderedLessThan => unordered(Ordering::is_lt),
+        ComparisonOperator::UnorderedLessThanOrEqual => unordered(Ordering::is_le),
+        ComparisonOperator::UnorderedGreaterThan => unordered(Ordering::is_gt),
+        ComparisonOperator::UnorderedGreaterThanOrEqual => unordered(Ordering::is_ge),
+    }))
+}
+
+fn integer_bit_width(type_: types::Primitive, pointer_size: usize) -> Option<u32> {
+    match type_ {
+        types::Primitive::Integer8 => Some(8),
+        types::Primitive::Integer16 => Some(16),
+        types::Primitive::Integer32 => Some(32),
+        types::Primitive::Integer64 => Some(64),
+        types::Primitive::Integer128 => Some(128),
+        types::Primitive::PointerInteger => Some(pointer_size as u32 * 8),
+        types::Primitive::Boolean
+        | types::Primitive::Float16
+        | types::Primitive::Float32
+        | types::Primitive::Float64
+        | types::Primitive::Float128 => None,
+    }
+}
+
+// Integers are unsigned and pointer integers have the sizes of pointers.
+fn to_integer(primitive: Primitive, pointer_size: usize) -> Option<u128> {
+    match primitive {
+        Primitive::Boolean(boolean) => Some(boolean as u128),
+        Primitive::Integer8(number) => Some(number as u128),
+        Primitive::Integer16(number) => Some(number as u128),
+        Primitive::Integer32(number) => Some(number as u128),
+        Primitive::Integer64(number) => Some(number as u128),
+        Primitive::Integer128(number) => Some(number),
+        Primitive::PointerInteger(number) => Some(truncate(number as u128, pointer_size * 8)),
+        Primitive::Float16(_)
+        | Primitive::Float32(_)
+        | Primitive::Float64(_)
+        | Primitive::Float128(_) => None,
+    }
+}
+
+fn from_integer(type_: types::Primitive, number: u128, pointer_size: usize) -> Primitive {
+    match type_ {
+        types::Primitive::Boolean => Primitive::Boolean(number & 1 == 1),
+        types::Primitive::Integer8 => Primitive::Integer8(number as u8),
+        types::Primitive::Integer16 => Primitive::Integer16(number as u16),
+        types::Primitive::Integer32 => Primitive::Integer32(number as u32),
+        types::Primitive::Integer64 => Primitive::Integer64(number as u64),
+        types::Primitive::Integer128 => Primitive::Integer128(number),
+        types::Primitive::PointerInteger => {
+            Primitive::PointerInteger(truncate(number, pointer_size * 8) as i64)
+        }
+        types::Primitive::Float16 => Primitive::Float16(number as f32),
+        types::Primitive::Float32 => Primitive::Float32(number as f32),
+        types::Primitive::Float64 => Primitive::Float64(number as f64),
+        types::Primitive::Float128 => Primitive::Float128(number as f64),
+    }
+}
+
+// Half and quadruple precision floats are not evaluated as they are not
+// represented exactly by Rust floats.
+fn to_float(primitive: Primitive) -> Option<f64> {
+    match primitive {
+        Primitive::Float32(number) => Some(number as f64),
+        Primitive::Float64(number) => Some(number),
+        _ => None,
+    }
+}
+
+fn from_float(type_: types::Primitive, number: f64) -> Primitive {
+    match type_ {
+        types::Primitive::Float16 => Primitive::Float16(number as f32),
+        types::Primitive::Float32 => Primitive::Float32(number as f32),
+        types::Primitive::Float128 => Primitive::Float128(number),
+        _ => Primitive::Float64(number),
+    }
+}
+
+fn truncate(number: u128, bits: usize) -> u128 {
+    if bits >= 128 {
+        number
+    } else {
+        number & ((1 << bits) - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_size_of_record() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &SizeOf::new(types::Record::new(vec![
+                    types::Primitive::Integer8.into(),
+                    types::Primitive::PointerInteger.into(),
+                ]))
+                .into(),
+                8
+            ),
+            Some(Primitive::PointerInteger(16))
+        );
+    }
+
+    #[test]
+    fn evaluate_nested_arithmetic_operations() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::PointerInteger,
+                    ArithmeticOperator::Multiply,
+                    ArithmeticOperation::new(
+                        types::Primitive::PointerInteger,
+                        ArithmeticOperator::Add,
+                        SizeOf::new(types::Primitive::Float64),
+                        AlignOf::new(types::Primitive::Integer32),
+                    ),
+                    Primitive::PointerInteger(3),
+                )
+                .into(),
+                8
+            ),
+            Some(Primitive::PointerInteger(36))
+        );
+    }
+
+    #[test]
+    fn evaluate_integer_overflow() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::Integer8,
+                    ArithmeticOperator::Add,
+                    Primitive::Integer8(255),
+                    Primitive::Integer8(2),
+                )
+                .into(),
+                8
+            ),
+            Some(Primitive::Integer8(1))
+        );
+    }
+
+    #[test]
+    fn evaluate_extract_bits() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ExtractBits::new(
+                    types::Primitive::Integer32,
+                    Primitive::Integer32(0b1010_1101_0000),
+                    3,
+                    5,
+                )
+                .into(),
+                8
+            ),
+            Some(Primitive::Integer32(0b1_1010))
+        );
+    }
+
+    #[test]
+    fn evaluate_insert_bits() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &InsertBits::new(
+                    types::Primitive::Integer32,
+                    Primitive::Integer32(0xffff_ffff),
+                    Primitive::Integer32(0b10),
+                    3,
+                    5,
+                )
+                .into(),
+                8
+            ),
+            Some(Primitive::Integer32(0xffff_ff17))
+        );
+    }
+
+    #[test]
+    fn evaluate_select() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &Select::new(
+                    types::Primitive::Integer32,
+                    Primitive::Boolean(false),
+                    Primitive::Integer32(1),
+                    Primitive::Integer32(2),
+                )
+                .into(),
+                8
+            ),
+            Some(Primitive::Integer32(2))
+        );
+    }
+
+    fn evaluate_bit_manipulation(
+        operator: BitManipulationOperator,
+        value: u32,
+    ) -> Option<Primitive> {
+        evaluate_constant_expression(
+            &BitManipulationOperation::new(
+                types::Primitive::Integer32,
+                operator,
+                Primitive::Integer32(value),
+            )
+            .into(),
+            8,
+        )
+    }
+
+    #[test]
+    fn evaluate_population_count() {
+        assert_eq!(
+            evaluate_bit_manipulation(BitManipulationOperator::PopulationCount, 0x1234_5678),
+            Some(Primitive::Integer32(13))
+        );
+    }
+
+    #[test]
+    fn evaluate_count_leading_zeros() {
+        assert_eq!(
+            evaluate_bit_manipulation(BitManipulationOperator::CountLeadingZeros, 0x00ff_0000),
+            Some(Primitive::Integer32(8))
+        );
+        assert_eq!(
+            evaluate_bit_manipulation(BitManipulationOperator::CountLeadingZeros, 0),
+            Some(Primitive::Integer32(32))
+        );
+    }
+
+    #[test]
+    fn evaluate_count_trailing_zeros() {
+        assert_eq!(
+            evaluate_bit_manipulation(BitManipulationOperator::CountTrailingZeros, 0x00ff_0000),
+            Some(Primitive::Integer32(16))
+        );
+        assert_eq!(
+            evaluate_bit_manipulation(BitManipulationOperator::CountTrailingZeros, 0),
+            Some(Primitive::Integer32(32))
+        );
+    }
+
+    #[test]
+    fn evaluate_byte_swap() {
+        assert_eq!(
+            evaluate_bit_manipulation(BitManipulationOperator::ByteSwap, 0x1234_5678),
+            Some(Primitive::Integer32(0x7856_3412))
+        );
+    }
+
+    #[test]
+    fn evaluate_byte_swap_of_pointer_integer() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &BitManipulationOperation::new(
+                    types::Primitive::PointerInteger,
+                    BitManipulationOperator::ByteSwap,
+                    Primitive::PointerInteger(0x1234),
+                )
+                .into(),
+                4,
+            ),
+            Some(Primitive::PointerInteger(0x3412_0000))
+        );
+    }
+
+    fn evaluate_saturating_addition(signed: bool, lhs: u8, rhs: u8) -> Option<Primitive> {
+        evaluate_saturating_arithmetic_operation(
+            &SaturatingArithmeticOperation::new(
+                types::Primitive::Integer8,
+                ArithmeticOperator::Add,
+                signed,
+                Primitive::Integer8(lhs),
+                Primitive::Integer8(rhs),
+                "x",
+            ),
+            8,
+        )
+    }
+
+    fn evaluate_checked_addition(signed: bool, lhs: u8, rhs: u8) -> Option<Record> {
+        evaluate_checked_arithmetic_operation(
+            &CheckedArithmeticOperation::new(
+                types::Primitive::Integer8,
+                ArithmeticOperator::Add,
+                signed,
+                Primitive::Integer8(lhs),
+                Primitive::Integer8(rhs),
+                "x",
+            ),
+            8,
+        )
+    }
+
+    fn create_checked_result(value: u8, overflow: bool) -> Record {
+        Record::new(
+            types::Record::new(vec![
+                types::Primitive::Integer8.into(),
+                types::Primitive::Boolean.into(),
+            ]),
+            vec![
+                Primitive::Integer8(value).into(),
+                Primitive::Boolean(overflow).into(),
+            ],
+        )
+    }
+
+    #[test]
+    fn evaluate_saturating_addition_at_maximum() {
+        assert_eq!(
+            evaluate_saturating_addition(false, 255, 1),
+            Some(Primitive::Integer8(255))
+        );
+        assert_eq!(
+            evaluate_saturating_addition(false, 254, 1),
+            Some(Primitive::Integer8(255))
+        );
+        assert_eq!(
+            evaluate_saturating_addition(true, 127, 1),
+            Some(Primitive::Integer8(127))
+        );
+        assert_eq!(
+            evaluate_saturating_addition(true, 128, 255),
+            Some(Primitive::Integer8(128))
+        );
+    }
+
+    #[test]
+    fn evaluate_saturating_subtraction_at_minimum() {
+        assert_eq!(
+            evaluate_saturating_arithmetic_operation(
+                &SaturatingArithmeticOperation::new(
+                    types::Primitive::Integer32,
+                    ArithmeticOperator::Subtract,
+                    false,
+                    Primitive::Integer32(1),
+                    Primitive::Integer32(2),
+                    "x",
+                ),
+                8,
+            ),
+            Some(Primitive::Integer32(0))
+        );
+    }
+
+    #[test]
+    fn evaluate_checked_addition_with_overflow() {
+        assert_eq!(
+            evaluate_checked_addition(false, 255, 1),
+            Some(create_checked_result(0, true))
+        );
+        assert_eq!(
+            evaluate_checked_addition(true, 127, 1),
+            Some(create_checked_result(128, true))
+        );
+    }
+
+    #[test]
+    fn evaluate_checked_addition_without_overflow() {
+        assert_eq!(
+            evaluate_checked_addition(false, 127, 1),
+            Some(create_checked_result(128, false))
+        );
+        assert_eq!(
+            evaluate_checked_addition(true, 255, 1),
+            Some(create_checked_result(0, false))
+        );
+    }
+
+    #[test]
+    fn evaluate_signed_checked_multiplication_of_128_bit_integers() {
+        assert_eq!(
+            evaluate_checked_arithmetic_operation(
+                &CheckedArithmeticOperation::new(
+                    types::Primitive::Integer128,
+                    ArithmeticOperator::Multiply,
+                    true,
+                    Primitive::Integer128(1 << 126),
+                    Primitive::Integer128(2),
+                    "x",
+                ),
+                8,
+            )
+            .unwrap()
+            .elements()[1],
+            Primitive::Boolean(true).into()
+        );
+    }
+
+    #[test]
+    fn evaluate_fma() {
+        assert_eq!(
+            super::evaluate_fma(
+                &Fma::new(
+                    types::Primitive::Float64,
+                    Primitive::Float64(2.0),
+                    Primitive::Float64(3.0),
+                    Primitive::Float64(4.0),
+                    "x",
+                ),
+                8,
+            ),
+            Some(Primitive::Float64(10.0))
+        );
+    }
+
+    #[test]
+    fn evaluate_fma_of_32_bit_floats() {
+        assert_eq!(
+            super::evaluate_fma(
+                &Fma::new(
+                    types::Primitive::Float32,
+                    Primitive::Float32(0.5),
+                    Primitive::Float32(4.0),
+                    Primitive::Float32(-1.0),
+                    "x",
+                ),
+                8,
+            ),
+            Some(Primitive::Float32(1.0))
+        );
+    }
+
+    #[test]
+    fn evaluate_fma_with_non_constant_operand() {
+        assert_eq!(
+            super::evaluate_fma(
+                &Fma::new(
+                    types::Primitive::Float64,
+                    Variable::new("y"),
+                    Primitive::Float64(3.0),
+                    Primitive::Float64(4.0),
+                    "x",
+                ),
+                8,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn evaluate_floor() {
+        for &(value, result) in &[(1.5, 1.0), (-1.5, -2.0), (2.0, 2.0)] {
+            assert_eq!(
+                evaluate_math_operation(
+                    &MathOperation::new(
+                        types::Primitive::Float64,
+                        MathOperator::Floor,
+                        Primitive::Float64(value),
+                        "x",
+                    ),
+                    8,
+                ),
+                Some(Primitive::Float64(result))
+            );
+        }
+    }
+
+    #[test]
+    fn evaluate_square_root_of_32_bit_float() {
+        assert_eq!(
+            evaluate_math_operation(
+                &MathOperation::new(
+                    types::Primitive::Float32,
+                    MathOperator::SquareRoot,
+                    Primitive::Float32(4.0),
+                    "x",
+                ),
+                8,
+            ),
+            Some(Primitive::Float32(2.0))
+        );
+    }
+
+    #[test]
+    fn evaluate_float_arithmetic_operation() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::Float64,
+                    ArithmeticOperator::Divide,
+                    Primitive::Float64(1.0),
+                    Primitive::Float64(4.0),
+                )
+                .into(),
+                8
+            ),
+            Some(Primitive::Float64(0.25))
+        );
+    }
+
+    #[test]
+    fn do_not_evaluate_arithmetic_operation_of_128_bit_floats() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::Float128,
+                    ArithmeticOperator::Divide,
+                    Primitive::Float128(1.0),
+                    Primitive::Float128(3.0),
+                )
+                .into(),
+                8
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn do_not_evaluate_arithmetic_operation_of_16_bit_floats() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::Float16,
+                    ArithmeticOperator::Add,
+                    Primitive::Float16(1.0),
+                    Primitive::Float16(0.001),
+                )
+                .into(),
+                8
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn evaluate_comparison_operation() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ComparisonOperation::new(
+                    types::Primitive::PointerInteger,
+                    ComparisonOperator::LessThan,
+                    SizeOf::new(types::Primitive::Integer32),
+                    SizeOf::new(types::Primitive::Integer64),
+                )
+                .into(),
+                8
+            ),
+            Some(Primitive::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn evaluate_comparison_operations_with_nan() {
+        let evaluate = |operator| {
+            evaluate_constant_expression(
+                &ComparisonOperation::new(
+                    types::Primitive::Float64,
+                    operator,
+                    Primitive::Float64(f64::NAN),
+                    Primitive::Float64(0.0),
+                )
+                .into(),
+                8,
+            )
+        };
+
+        assert_eq!(
+            evaluate(ComparisonOperator::Equal),
+            Some(Primitive::Boolean(false))
+        );
+        assert_eq!(
+            evaluate(ComparisonOperator::NotEqual),
+            Some(Primitive::Boolean(false))
+        );
+        assert_eq!(
+            evaluate(ComparisonOperator::UnorderedEqual),
+            Some(Primitive::Boolean(true))
+        );
+        assert_eq!(
+            evaluate(ComparisonOperator::Unordered),
+            Some(Primitive::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn fail_to_evaluate_division_by_zero() {
+        assert_eq!(
+            evaluate_constant_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::PointerInteger,
+                    ArithmeticOperator::Divide,
+                    Primitive::PointerInteger(1),
+                    Primitive::PointerInteger(0),
+                )
+                .into(),
+                8
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn fail_to_evaluate_variable() {
+        assert_eq!(
+            evaluate_constant_expression(&Variable::new("x").into(), 8),
+            None
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use super::{target_data_layout::TargetDataLayout, type_size::type_size};
+use crate::{ir::*, types::Type};
+
+// Costs are rough estimates of cycles and code sizes relative to a simple
+// integer operation.
+const OPERATION_COST: u32 = 1;
+const DIVISION_COST: u32 = 20;
+const MEMORY_COST: u32 = 4;
+const ATOMIC_COST: u32 = 20;
+const CALL_COST: u32 = 10;
+const HEAP_COST: u32 = 50;
+const MATH_COST: u32 = 20;
+
+pub fn block_cost(block: &Block, layout: &TargetDataLayout) -> u32 {
+    block
+        .instructions()
+        .iter()
+        .map(|instruction| instruction_cost(instruction, layout))
+        .sum::<u32>()
+        + terminal_instruction_cost(block.terminal_instruction())
+}
+
+pub fn instruction_cost(instruction: &Instruction, layout: &TargetDataLayout) -> u32 {
+    let memory_cost = |type_| MEMORY_COST * word_count(type_, layout);
+
+    match instruction {
+        Instruction::AllocateHeap(allocate) => HEAP_COST + expression_cost(allocate.size()),
+        Instruction::AllocateStack(_) => OPERATION_COST,
+        Instruction::AtomicLoad(load) => ATOMIC_COST + expression_cost(load.pointer()),
+        Instruction::AtomicOperation(operation) => {
+            ATOMIC_COST + expression_cost(operation.pointer()) + expression_cost(operation.value())
+        }
+        Instruction::AtomicStore(store) => {
+            ATOMIC_COST + expression_cost(store.pointer()) + expression_cost(store.value())
+        }
+        Instruction::Call(call) => {
+            CALL_COST
+                + expression_cost(call.function())
+                + call
+                    .arguments()
+                    .iter()
+                    .map(|argument| OPERATION_COST + expression_cost(argument))
+                    .sum::<u32>()
+        }
+        Instruction::CheckedArithmeticOperation(operation) => {
+            2 * OPERATION_COST + expression_cost(operation.lhs()) + expression_cost(operation.rhs())
+        }
+        Instruction::CompareAndSwap(cas) => {
+            ATOMIC_COST
+                + expression_cost(cas.pointer())
+                + expression_cost(cas.old_value())
+                + expression_cost(cas.new_value())
+        }
+        Instruction::DeconstructRecord(deconstruct) => {
+            OPERATION_COST + expression_cost(deconstruct.record())
+        }
+        Instruction::DeconstructUnion(deconstruct) => {
+            OPERATION_COST + expression_cost(deconstruct.union())
+        }
+        Instruction::Fence(_) => ATOMIC_COST,
+        Instruction::Fma(fma) => {
+            OPERATION_COST
+                + expression_cost(fma.lhs())
+                + expression_cost(fma.rhs())
+                + expression_cost(fma.addend())
+        }
+        Instruction::FreeHeap(free) => HEAP_COST + expression_cost(free.pointer()),
+        Instruction::If(if_) => {
+            OPERATION_COST
+                + expression_cost(if_.condition())
+                + block_cost(if_.then(), layout)
+                + block_cost(if_.else_(), layout)
+        }
+        // Inline assembly is as opaque as function calls.
+        Instruction::InlineAssembly(assembly) => {
+            CALL_COST
+                + assembly
+                    .operands()
+                    .iter()
+                    .map(|operand| OPERATION_COST + expression_cost(operand))
+                    .sum::<u32>()
+        }
+        Instruction::LifetimeEnd(_) | Instruction::LifetimeStart(_) => 0,
+        Instruction::Load(load) => memory_cost(load.type_()) + expression_cost(load.pointer()),
+        Instruction::MathOperation(operation) => {
+            (match operation.operator() {
+                MathOperator::Absolute | MathOperator::Ceiling | MathOperator::Floor => {
+                    OPERATION_COST
+                }
+                MathOperator::Cosine
+                | MathOperator::Exponential
+                | MathOperator::Logarithm
+                | MathOperator::Sine
+                | MathOperator::SquareRoot => MATH_COST,
+            }) + expression_cost(operation.value())
+        }
+        Instruction::MemoryCopy(copy) => {
+            CALL_COST
+                + expression_cost(copy.source())
+                + expression_cost(copy.destination())
+                + expression_cost(copy.size())
+        }
+        Instruction::MemorySet(set) => {
+            CALL_COST
+                + expression_cost(set.pointer())
+                + expression_cost(set.value())
+                + expression_cost(set.size())
+        }
+        Instruction::PassThrough(pass) => expression_cost(pass.expression()),
+        Instruction::ReallocateHeap(reallocate) => {
+            HEAP_COST + expression_cost(reallocate.pointer()) + expression_cost(reallocate.size())
+        }
+        Instruction::SaturatingArithmeticOperation(operation) => {
+            2 * OPERATION_COST + expression_cost(operation.lhs()) + expression_cost(operation.rhs())
+        }
+        Instruction::Store(store) => {
+            memory_cost(store.type_())
+                + expression_cost(store.pointer())
+                + expression_cost(store.value())
+        }
+    }
+}
+
+fn terminal_instruction_cost(instruction: &TerminalInstruction) -> u32 {
+    match instruction {
+        TerminalInstruction::Branch(branch) => {
+            OPERATION_COST + expression_cost(branch.expression())
+        }
+        TerminalInstruction::Return(return_) => {
+            OPERATION_COST + expression_cost(return_.expression())
+        }
+        TerminalInstruction::Trap | TerminalInstruction::Unreachable => OPERATION_COST,
+    }
+}
+
+fn expression_cost(expression: &Expression) -> u32 {
+    match expression {
+        Expression::ArithmeticOperation(operation) => {
+            (match operation.operator() {
+                ArithmeticOperator::Divide => DIVISION_COST,
+                ArithmeticOperator::Add
+                | ArithmeticOperator::Subtract
+                | ArithmeticOperator::Multiply => OPERATION_COST,
+            }) + expression_cost(operation.lhs())
+                + expression_cost(operation.rhs())
+        }
+        Expression::BitCast(bit_cast) => expression_cost(bit_cast.expression()),
+        Expression::BitManipulationOperation(operation) => {
+            OPERATION_COST + expression_cost(operation.value())
+        }
+        Expression::BitwiseNotOperation(operation) => {
+            OPERATION_COST + expression_cost(operation.value())
+        }
+        Expression::BitwiseOperation(operation) => {
+            OPERATION_COST + expression_cost(operation.lhs()) + expression_cost(operation.rhs())
+        }
+        Expression::ByteOffset(offset) => {
+            OPERATION_COST + expression_cost(offset.pointer()) + expression_cost(offset.offset())
+        }
+        Expression::ComparisonOperation(operation) => {
+            OPERATION_COST + expression_cost(operation.lhs()) + expression_cost(operation.rhs())
+        }
+        Expression::ExtractBits(extract) => 2 * OPERATION_COST + expression_cost(extract.value()),
+        Expression::InsertBits(insert) => {
+            4 * OPERATION_COST + expression_cost(insert.value()) + expression_cost(insert.field())
+        }
+        Expression::PointerAddress(address) => {
+            OPERATION_COST + expression_cost(address.pointer()) + expression_cost(address.offset())
+        }
+        Expression::PureCall(call) => {
+            CALL_COST
+                + expression_cost(call.function())
+                + call
+                    .arguments()
+                    .iter()
+                    .map(|argument| OPERATION_COST + expression_cost(argument))
+                    .sum::<u32>()
+        }
+        Expression::Record(record) => record.elements().iter().map(expression_cost).sum(),
+        Expression::RecordAddress(address) => OPERATION_COST + expression_cost(address.pointer()),
+        Expression::Select(select) => {
+            OPERATION_COST
+                + expression_cost(select.condition())
+                + expression_cost(select.then())
+                + expression_cost(select.else_())
+        }
+        Expression::Union(union) => expression_cost(union.member()),
+        Expression::UnionAddress(address) => expression_cost(address.pointer()),
+        Expression::AbsoluteAddress(_)
+        | Expression::AlignOf(_)
+        | Expression::Primitive(_)
+        | Expression::SizeOf(_)
+        | Expression::Undefined(_)
+        | Expression::Variable(_) => 0,
+    }
+}
+
+// Types of unknown sizes are regarded as single words.
+fn word_count(type_: &Type, layout: &TargetDataLayout) -> u32 {
+    let size = type_size(type_, layout.pointer_size()).unwrap_or(0);
+
+    size.div_ceil(layout.pointer_size()).max(1) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    const LAYOUT: TargetDataLayout = TargetDataLayout::new(8, 8);
+
+    fn create_addition(type_: types::Primitive) -> Instruction {
+        PassThrough::new(
+            type_,
+            ArithmeticOperation::new(
+                type_,
+                ArithmeticOperator::Add,
+                Variable::new("x"),
+                Variable::new("y"),
+            ),
+            "z",
+        )
+        .into()
+    }
+
+    #[test]
+    fn call_costs_more_than_addition() {
+        let function_type = types::Function::new(
+            vec![types::Primitive::Integer64.into()],
+            types::Primitive::Integer64,
+            CallingConvention::Target,
+        );
+
+        assert!(
+            instruction_cost(
+                &Call::new(
+                    function_type,
+                    Variable::new("f"),
+                    vec![Variable::new("x").into()],
+                    "y",
+                )
+                .into(),
+                &LAYOUT
+            ) > instruction_cost(&create_addition(types::Primitive::Integer64), &LAYOUT)
+        );
+    }
+
+    #[test]
+    fn division_costs_more_than_addition() {
+        assert!(
+            instruction_cost(
+                &PassThrough::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperation::new(
+                        types::Primitive::Integer64,
+                        ArithmeticOperator::Divide,
+                        Variable::new("x"),
+                        Variable::new("y"),
+                    ),
+                    "z",
+                )
+                .into(),
+                &LAYOUT
+            ) > instruction_cost(&create_addition(types::Primitive::Integer64), &LAYOUT)
+        );
+    }
+
+    #[test]
+    fn load_costs_more_than_addition() {
+        assert!(
+            instruction_cost(
+                &Load::new(types::Primitive::Integer64, Variable::new("x"), "y").into(),
+                &LAYOUT
+            ) > instruction_cost(&create_addition(types::Primitive::Integer64), &LAYOUT)
+        );
+    }
+
+    #[test]
+    fn load_of_record_costs_more_than_load_of_word() {
+        let load = |type_: Type| {
+            instruction_cost(&Load::new(type_, Variable::new("x"), "y").into(), &LAYOUT)
+        };
+
+        assert!(
+            load(
+                types::Record::new(vec![
+                    types::Primitive::Integer64.into(),
+                    types::Primitive::Integer64.into()
+                ])
+                .into()
+            ) > load(types::Primitive::Integer64.into())
+        );
+    }
+
+    #[test]
+    fn calculate_if_cost_from_both_blocks() {
+        let block = Block::new(
+            vec![create_addition(types::Primitive::Integer64)],
+            Branch::new(types::Primitive::Integer64, Variable::new("z")),
+        );
+
+        assert_eq!(
+            instruction_cost(
+                &If::new(
+                    types::Primitive::Integer64,
+                    Variable::new("c"),
+                    block.clone(),
+                    block.clone(),
+                    "w",
+                )
+                .into(),
+                &LAYOUT
+            ),
+            OPERATION_COST + 2 * block_cost(&block, &LAYOUT)
+        );
+    }
+}
@@ -1,23 +1,33 @@
+mod continuation_type;
 mod cps_transformer;
 mod error;
-mod free_variables;
 mod if_flattener;
 mod stack;
 mod target_functions;
 
-use super::check_types;
+use super::{check_types, merge_tail_calls, prune_unreachable_code};
 use crate::{ir::*, types::Type};
+pub use continuation_type::create_continuation_type;
 use cps_transformer::*;
 use error::CpsTransformationError;
+pub use stack::{PopFromStack, PushToStack, StackConfiguration};
 
 pub fn transform_to_cps(
     module: &Module,
     result_type: impl Into<Type>,
+) -> Result<Module, CpsTransformationError> {
+    transform_to_cps_with_stack(module, result_type, StackConfiguration::default())
+}
+
+pub fn transform_to_cps_with_stack(
+    module: &Module,
+    result_type: impl Into<Type>,
+    stack: StackConfiguration,
 ) -> Result<Module, CpsTransformationError> {
     check_types(module)?;
 
-    let module = if_flattener::flatten(module);
-    let module = CpsTransformer::new(result_type).transform(&module)?;
+    let module = if_flattener::flatten(&prune_unreachable_code(module));
+    let module = merge_tail_calls(&CpsTransformer::new(result_type, stack).transform(&module)?);
 
     check_types(&module)?;
 
@@ -28,8 +38,10 @@ pub fn transform_to_cps(
 mod tests {
     use super::*;
     use crate::{
-        analysis::check_types,
-        types::{self, CallingConvention, Type, VOID_TYPE},
+        analysis::{check_types, duplicate_tails, format_module, TargetDataLayout},
+        build,
+        test_support::{assert_idempotent, assert_preserves_types},
+        types::{self, CallingConvention, Type, GENERIC_POINTER_TYPE, VOID_TYPE},
     };
     use stack::STACK_TYPE;
 
@@ -73,7 +85,111 @@ mod tests {
     }
 
     fn test_transformation(module: &Module) {
-        check_types(&transform_to_cps(module, VOID_TYPE.clone()).unwrap()).unwrap();
+        assert_preserves_types(
+            |module| transform_to_cps(module, VOID_TYPE.clone()).unwrap(),
+            module,
+        );
+    }
+
+    #[test]
+    fn transform_transformed_module() {
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+        assert_idempotent(
+            |module| transform_to_cps(module, VOID_TYPE.clone()).unwrap(),
+            &Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type.clone())],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![Argument::new("x", types::Primitive::Float64)],
+                    Block::new(
+                        vec![
+                            Call::new(
+                                function_type,
+                                Variable::new("f"),
+                                vec![Primitive::Float64(42.0).into()],
+                                "y",
+                            )
+                            .into(),
+                            If::new(
+                                types::Primitive::Float64,
+                                Primitive::Boolean(true),
+                                Block::new(
+                                    vec![],
+                                    Branch::new(types::Primitive::Float64, Variable::new("x")),
+                                ),
+                                Block::new(
+                                    vec![],
+                                    Branch::new(types::Primitive::Float64, Variable::new("y")),
+                                ),
+                                "z",
+                            )
+                            .into(),
+                        ],
+                        Return::new(types::Primitive::Float64, Variable::new("z")),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ),
+        );
+    }
+
+    #[test]
+    fn transform_deterministically() {
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h"];
+        let module = Module::new(
+            vec![],
+            vec![FunctionDeclaration::new("k", function_type.clone())],
+            vec![],
+            vec![create_function_definition(
+                "l",
+                names
+                    .iter()
+                    .map(|name| Argument::new(*name, types::Primitive::Float64))
+                    .collect(),
+                Block::new(
+                    vec![Call::new(
+                        function_type,
+                        Variable::new("k"),
+                        vec![Primitive::Float64(42.0).into()],
+                        "x",
+                    )
+                    .into()],
+                    Return::new(
+                        types::Primitive::Float64,
+                        names.iter().fold(
+                            Expression::from(Variable::new("x")),
+                            |expression, name| {
+                                ArithmeticOperation::new(
+                                    types::Primitive::Float64,
+                                    ArithmeticOperator::Add,
+                                    expression,
+                                    Variable::new(*name),
+                                )
+                                .into()
+                            },
+                        ),
+                    ),
+                ),
+                types::Primitive::Float64,
+            )],
+        );
+        let format = |module| {
+            crate::analysis::format_module(&transform_to_cps(module, VOID_TYPE.clone()).unwrap())
+        };
+        let string = format(&module);
+
+        for _ in 0..100 {
+            assert_eq!(format(&module), string);
+        }
     }
 
     #[test]
@@ -128,6 +244,170 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn keep_lifetime_markers() {
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+
+        let module = transform_to_cps(
+            &Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type.clone())],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![],
+                    Block::new(
+                        vec![
+                            AllocateStack::new(types::Primitive::Float64, "p").into(),
+                            LifetimeStart::new(Variable::new("p")).into(),
+                            Store::new(
+                                types::Primitive::Float64,
+                                Primitive::Float64(42.0),
+                                Variable::new("p"),
+                            )
+                            .into(),
+                            Load::new(types::Primitive::Float64, Variable::new("p"), "x").into(),
+                            LifetimeEnd::new(Variable::new("p")).into(),
+                            Call::new(
+                                function_type,
+                                Variable::new("f"),
+                                vec![Variable::new("x").into()],
+                                "y",
+                            )
+                            .into(),
+                        ],
+                        Return::new(types::Primitive::Float64, Variable::new("y")),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ),
+            VOID_TYPE.clone(),
+        )
+        .unwrap();
+        let string = crate::analysis::format_module(&module);
+
+        assert!(string.contains("(lifetime-start p)"));
+        assert!(string.contains("(lifetime-end p)"));
+    }
+
+    #[test]
+    fn keep_fast_math_flags() {
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+
+        let module = transform_to_cps(
+            &Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type.clone())],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![Argument::new("x", types::Primitive::Float64)],
+                    Block::new(
+                        vec![Call::new(
+                            function_type,
+                            Variable::new("f"),
+                            vec![Variable::new("x").into()],
+                            "y",
+                        )
+                        .into()],
+                        Return::new(
+                            types::Primitive::Float64,
+                            ArithmeticOperation::new(
+                                types::Primitive::Float64,
+                                ArithmeticOperator::Add,
+                                Variable::new("x"),
+                                Variable::new("y"),
+                            )
+                            .with_fast_math_flags(FastMathFlags::fast()),
+                        ),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ),
+            VOID_TYPE.clone(),
+        )
+        .unwrap();
+
+        assert!(crate::analysis::format_module(&module).contains("(+ fast "));
+    }
+
+    #[test]
+    fn transform_with_custom_stack() {
+        let stack_type: Type = types::Pointer::new(GENERIC_POINTER_TYPE.clone()).into();
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+
+        let module = transform_to_cps_with_stack(
+            &Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type.clone())],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![Argument::new("x", types::Primitive::Float64)],
+                    Block::new(
+                        vec![Call::new(
+                            function_type,
+                            Variable::new("f"),
+                            vec![Variable::new("x").into()],
+                            "y",
+                        )
+                        .into()],
+                        Return::new(
+                            types::Primitive::Float64,
+                            ArithmeticOperation::new(
+                                types::Primitive::Float64,
+                                ArithmeticOperator::Add,
+                                Variable::new("x"),
+                                Variable::new("y"),
+                            ),
+                        ),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ),
+            VOID_TYPE.clone(),
+            StackConfiguration {
+                type_: stack_type.clone(),
+                push: |builder, stack, element| {
+                    let pointer = builder.allocate_heap(build::size_of(element.type_().clone()));
+
+                    builder.store(
+                        element.clone(),
+                        build::bit_cast(
+                            types::Pointer::new(element.type_().clone()),
+                            pointer.clone(),
+                        ),
+                    );
+                    builder.store(pointer, stack);
+
+                    Ok(())
+                },
+                pop: |builder, stack, type_| {
+                    builder.load(build::bit_cast(
+                        types::Pointer::new(type_.clone()),
+                        builder.load(stack)?,
+                    ))
+                },
+            },
+        )
+        .unwrap();
+
+        check_types(&module).unwrap();
+
+        for definition in module.function_definitions() {
+            assert_eq!(definition.arguments()[0].type_(), &stack_type);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn transform_call_in_function_of_target_calling_convention() {
@@ -160,6 +440,80 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn report_function_name_on_error() {
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+        let call = Call::new(
+            function_type.clone(),
+            Variable::new("f"),
+            vec![Primitive::Float64(42.0).into()],
+            "x",
+        );
+
+        let error = transform_to_cps(
+            &Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type)],
+                vec![],
+                vec![FunctionDefinition::new(
+                    "g",
+                    vec![],
+                    Block::new(
+                        vec![call.clone().into()],
+                        Return::new(types::Primitive::Float64, Variable::new("x")),
+                    ),
+                    types::Primitive::Float64,
+                    CallingConvention::Target,
+                    Linkage::Internal,
+                )],
+            ),
+            VOID_TYPE.clone(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error,
+            CpsTransformationError::FunctionDefinition(
+                "g".into(),
+                CpsTransformationError::InvalidCallingConvention(call).into()
+            )
+        );
+        assert!(error.to_string().ends_with("in function g"));
+    }
+
+    #[test]
+    fn transform_instruction_after_no_return_call() {
+        let function_type = create_function_type(vec![], types::Primitive::Float64);
+
+        test_transformation(&Module::new(
+            vec![],
+            vec![FunctionDeclaration::with_attributes(
+                "f",
+                function_type.clone(),
+                FunctionAttributes {
+                    no_return: true,
+                    ..Default::default()
+                },
+            )],
+            vec![],
+            vec![create_function_definition(
+                "g",
+                vec![],
+                Block::new(
+                    vec![
+                        Call::new(function_type, Variable::new("f"), vec![], "x").into(),
+                        PassThrough::new(types::Primitive::Float64, Variable::new("x"), "y").into(),
+                    ],
+                    Return::new(types::Primitive::Float64, Variable::new("y")),
+                ),
+                types::Primitive::Float64,
+            )],
+        ));
+    }
+
     #[test]
     fn transform_instruction_after_call() {
         let function_type = create_function_type(
@@ -231,6 +585,92 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn transform_if_with_returns_in_both_arms() {
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+        let create_block = |value| {
+            Block::new(
+                vec![Call::new(
+                    function_type.clone(),
+                    Variable::new("f"),
+                    vec![Primitive::Float64(value).into()],
+                    "x",
+                )
+                .into()],
+                Return::new(types::Primitive::Float64, Variable::new("x")),
+            )
+        };
+
+        let module = assert_preserves_types(
+            |module| transform_to_cps(module, VOID_TYPE.clone()).unwrap(),
+            &Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type.clone())],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![Argument::new("y", types::Primitive::Boolean)],
+                    Block::new(
+                        vec![If::new(
+                            VOID_TYPE.clone(),
+                            Variable::new("y"),
+                            create_block(1.0),
+                            create_block(2.0),
+                            "_",
+                        )
+                        .into()],
+                        TerminalInstruction::Unreachable,
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ),
+        );
+
+        assert_eq!(module.function_definitions().len(), 1);
+    }
+
+    #[test]
+    fn transform_if_with_trap() {
+        let function_type = create_function_type(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+        );
+
+        test_transformation(&Module::new(
+            vec![],
+            vec![FunctionDeclaration::new("f", function_type.clone())],
+            vec![],
+            vec![create_function_definition(
+                "g",
+                vec![],
+                Block::new(
+                    vec![If::new(
+                        VOID_TYPE.clone(),
+                        Primitive::Boolean(true),
+                        Block::new(
+                            vec![Call::new(
+                                function_type,
+                                Variable::new("f"),
+                                vec![Primitive::Float64(42.0).into()],
+                                "x",
+                            )
+                            .into()],
+                            Return::new(types::Primitive::Float64, Variable::new("x")),
+                        ),
+                        Block::new(vec![], TerminalInstruction::Trap),
+                        "_",
+                    )
+                    .into()],
+                    TerminalInstruction::Trap,
+                ),
+                types::Primitive::Float64,
+            )],
+        ));
+    }
+
     #[test]
     fn transform_if_with_branch() {
         let function_type = create_function_type(
@@ -506,4 +946,169 @@ mod tests {
             )],
         ));
     }
+
+    #[test]
+    fn transform_module_with_duplicated_tails() {
+        let module = duplicate_tails(
+            &Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![
+                        Argument::new("c", types::Primitive::Boolean),
+                        Argument::new("d", types::Primitive::Boolean),
+                        Argument::new("x", types::Primitive::Float64),
+                    ],
+                    Block::new(
+                        vec![
+                            If::new(
+                                types::Primitive::Float64,
+                                Variable::new("c"),
+                                Block::new(
+                                    vec![
+                                        PassThrough::new(
+                                            types::Primitive::Float64,
+                                            Variable::new("x"),
+                                            "a",
+                                        )
+                                        .into(),
+                                        If::new(
+                                            types::Primitive::Float64,
+                                            Variable::new("d"),
+                                            Block::new(
+                                                vec![],
+                                                Return::new(
+                                                    types::Primitive::Float64,
+                                                    Variable::new("a"),
+                                                ),
+                                            ),
+                                            Block::new(
+                                                vec![],
+                                                Return::new(
+                                                    types::Primitive::Float64,
+                                                    Variable::new("a"),
+                                                ),
+                                            ),
+                                            "b",
+                                        )
+                                        .into(),
+                                    ],
+                                    Branch::new(types::Primitive::Float64, Variable::new("b")),
+                                ),
+                                Block::new(
+                                    vec![],
+                                    Branch::new(types::Primitive::Float64, Variable::new("x")),
+                                ),
+                                "y",
+                            )
+                            .into(),
+                            PassThrough::new(types::Primitive::Float64, Variable::new("y"), "z")
+                                .into(),
+                        ],
+                        Return::new(types::Primitive::Float64, Variable::new("z")),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ),
+            &TargetDataLayout::new(8, 8),
+            10,
+        );
+
+        assert!(format_module(&module).contains("_tail_0"));
+
+        test_transformation(&module);
+    }
+
+    mod non_void_result_type {
+        use super::*;
+
+        const RESULT_TYPE: types::Primitive = types::Primitive::Integer64;
+
+        fn transform(module: &Module) -> Module {
+            assert_preserves_types(
+                |module| transform_to_cps(module, RESULT_TYPE).unwrap(),
+                module,
+            )
+        }
+
+        #[test]
+        fn transform_function_definition() {
+            let module = transform(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![],
+                        Return::new(types::Primitive::Float64, Primitive::Float64(42.0)),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ));
+            let definition = &module.function_definitions()[0];
+
+            assert_eq!(
+                definition.type_(),
+                &types::Function::new(
+                    vec![
+                        STACK_TYPE.clone(),
+                        create_continuation_type(types::Primitive::Float64, RESULT_TYPE).into(),
+                    ],
+                    RESULT_TYPE,
+                    CallingConvention::Tail,
+                )
+            );
+            assert_eq!(
+                definition.body().terminal_instruction(),
+                &Return::new(RESULT_TYPE, Variable::new("_result")).into()
+            );
+        }
+
+        #[test]
+        fn transform_call() {
+            let function_type = create_function_type(
+                vec![types::Primitive::Float64.into()],
+                types::Primitive::Float64,
+            );
+
+            let module = transform(&Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type.clone())],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![],
+                    Block::new(
+                        vec![Call::new(
+                            function_type,
+                            Variable::new("f"),
+                            vec![Primitive::Float64(42.0).into()],
+                            "x",
+                        )
+                        .into()],
+                        Return::new(types::Primitive::Float64, Variable::new("x")),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            ));
+
+            assert_eq!(
+                module.function_declarations()[0].type_().result(),
+                &RESULT_TYPE.into()
+            );
+
+            assert_eq!(
+                module.function_definitions()[0].type_().arguments()[1],
+                create_continuation_type(types::Primitive::Float64, RESULT_TYPE).into()
+            );
+
+            for definition in module.function_definitions() {
+                assert_eq!(definition.result_type(), &RESULT_TYPE.into());
+            }
+        }
+    }
 }
@@ -0,0 +1,39 @@
+use super::stack::STACK_TYPE;
+use crate::types::{self, CallingConvention, Type};
+
+pub fn create_continuation_type(
+    result_type: impl Into<Type>,
+    overall_result_type: impl Into<Type>,
+) -> types::Function {
+    create_continuation_type_with_stack(STACK_TYPE.clone(), result_type, overall_result_type)
+}
+
+pub fn create_continuation_type_with_stack(
+    stack_type: impl Into<Type>,
+    result_type: impl Into<Type>,
+    overall_result_type: impl Into<Type>,
+) -> types::Function {
+    types::Function::new(
+        vec![stack_type.into(), result_type.into()],
+        overall_result_type,
+        CallingConvention::Tail,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VOID_TYPE;
+
+    #[test]
+    fn create_type() {
+        assert_eq!(
+            create_continuation_type(types::Primitive::Float64, VOID_TYPE.clone()),
+            types::Function::new(
+                vec![STACK_TYPE.clone(), types::Primitive::Float64.into()],
+                VOID_TYPE.clone(),
+                CallingConvention::Tail,
+            )
+        );
+    }
+}
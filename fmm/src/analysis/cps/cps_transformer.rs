@@ -1,11 +1,9 @@
 use super::{
-    error::CpsTransformationError,
-    free_variables::collect_free_variables,
-    stack::{pop_from_stack, push_to_stack, STACK_TYPE},
-    target_functions::validate_target_function_definition,
+    continuation_type::create_continuation_type_with_stack, error::CpsTransformationError,
+    stack::StackConfiguration, target_functions::validate_target_function_definition,
 };
 use crate::{
-    analysis::convert_types,
+    analysis::{convert_types, free_variables::collect_free_variables},
     build::{self, BuildError, InstructionBuilder, NameGenerator},
     ir::*,
     types::{self, CallingConvention, Type},
@@ -21,15 +19,19 @@ pub struct CpsTransformer {
     continuation_index: usize,
     function_definitions: Vec<FunctionDefinition>,
     result_type: Type,
+    stack: StackConfiguration,
+    function_types: RefCell<HashMap<types::Function, types::Function>>,
 }
 
 impl CpsTransformer {
-    pub fn new(result_type: impl Into<Type>) -> Self {
+    pub fn new(result_type: impl Into<Type>, stack: StackConfiguration) -> Self {
         Self {
             name_generator: Rc::new(NameGenerator::new("_cps_").into()),
             continuation_index: 0,
             function_definitions: vec![],
             result_type: result_type.into(),
+            stack,
+            function_types: Default::default(),
         }
     }
 
@@ -42,7 +44,15 @@ impl CpsTransformer {
                 module
                     .function_definitions()
                     .iter()
-                    .map(|definition| self.transform_function_definition(definition))
+                    .map(|definition| {
+                        self.transform_function_definition(definition)
+                            .map_err(|error| {
+                                CpsTransformationError::FunctionDefinition(
+                                    definition.name().into(),
+                                    error.into(),
+                                )
+                            })
+                    })
                     .collect::<Vec<_>>()
                     .into_iter()
                     .chain(self.function_definitions.drain(..).map(Ok))
@@ -66,7 +76,7 @@ impl CpsTransformer {
                 FunctionDefinition::new(
                     definition.name(),
                     vec![
-                        Argument::new(STACK_ARGUMENT_NAME, STACK_TYPE.clone()),
+                        Argument::new(STACK_ARGUMENT_NAME, self.stack.type_.clone()),
                         Argument::new(CONTINUATION_ARGUMENT_NAME, continuation_type.clone()),
                     ]
                     .into_iter()
@@ -134,6 +144,7 @@ impl CpsTransformer {
                     .into()],
                     Return::new(self.result_type.clone(), Variable::new(RESULT_NAME)).into(),
                 ),
+                TerminalInstruction::Trap => (vec![], TerminalInstruction::Trap),
                 TerminalInstruction::Unreachable => (vec![], TerminalInstruction::Unreachable),
             },
             [instruction, ..] => {
@@ -167,10 +178,10 @@ impl CpsTransformer {
                         let builder = InstructionBuilder::new(self.name_generator.clone());
 
                         if !is_tail_call {
-                            push_to_stack(
+                            (self.stack.push)(
                                 &builder,
-                                build::variable(STACK_ARGUMENT_NAME, STACK_TYPE.clone()),
-                                self.get_environment_record(&environment),
+                                build::variable(STACK_ARGUMENT_NAME, self.stack.type_.clone()),
+                                self.get_environment_record(&environment).into(),
                             )?;
                         }
 
@@ -260,7 +271,7 @@ impl CpsTransformer {
         self.function_definitions.push(FunctionDefinition::new(
             &name,
             vec![
-                Argument::new(STACK_ARGUMENT_NAME, STACK_TYPE.clone()),
+                Argument::new(STACK_ARGUMENT_NAME, self.stack.type_.clone()),
                 Argument::new(call.name(), call.type_().result().clone()),
             ],
             Block::new(
@@ -269,9 +280,9 @@ impl CpsTransformer {
 
                     let environment_record_type =
                         self.get_environment_record(environment).type_().clone();
-                    let environment_record = pop_from_stack(
+                    let environment_record = (self.stack.pop)(
                         &builder,
-                        build::variable(STACK_ARGUMENT_NAME, STACK_TYPE.clone()),
+                        build::variable(STACK_ARGUMENT_NAME, self.stack.type_.clone()),
                         &environment_record_type.clone().into(),
                     )?;
 
@@ -310,46 +321,60 @@ impl CpsTransformer {
         terminal_instruction: &TerminalInstruction,
         local_variables: &HashMap<String, Type>,
     ) -> Vec<(String, Type)> {
+        // Variables are sorted to make environments deterministic.
+        let mut names = collect_free_variables(instructions, terminal_instruction)
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        names.sort();
+
         vec![(
             CONTINUATION_ARGUMENT_NAME.into(),
             local_variables[CONTINUATION_ARGUMENT_NAME].clone(),
         )]
         .into_iter()
-        .chain(
-            collect_free_variables(instructions, terminal_instruction)
-                .iter()
-                .flat_map(|name| {
-                    local_variables
-                        .get(name)
-                        .map(|type_| (name.clone(), type_.clone()))
-                }),
-        )
+        .chain(names.iter().flat_map(|name| {
+            local_variables
+                .get(name)
+                .map(|type_| (name.clone(), type_.clone()))
+        }))
         .collect()
     }
 
     fn transform_function_type(&self, type_: &types::Function) -> types::Function {
-        if type_.calling_convention() == CallingConvention::Source {
-            types::Function::new(
-                vec![
-                    STACK_TYPE.clone(),
-                    self.create_continuation_type(type_.result()).into(),
-                ]
-                .into_iter()
-                .chain(type_.arguments().iter().cloned())
-                .collect(),
-                self.result_type.clone(),
-                CallingConvention::Tail,
-            )
-        } else {
-            type_.clone()
+        if type_.calling_convention() != CallingConvention::Source {
+            return type_.clone();
         }
+
+        // The same function types appear repeatedly in modules.
+        if let Some(type_) = self.function_types.borrow().get(type_) {
+            return type_.clone();
+        }
+
+        let transformed = types::Function::new(
+            vec![
+                self.stack.type_.clone(),
+                self.create_continuation_type(type_.result()).into(),
+            ]
+            .into_iter()
+            .chain(type_.arguments().iter().cloned())
+            .collect(),
+            self.result_type.clone(),
+            CallingConvention::Tail,
+        );
+
+        self.function_types
+            .borrow_mut()
+            .insert(type_.clone(), transformed.clone());
+
+        transformed
     }
 
     fn create_continuation_type(&self, result_type: &Type) -> types::Function {
-        types::Function::new(
-            vec![STACK_TYPE.clone(), result_type.clone()],
+        create_continuation_type_with_stack(
+            self.stack.type_.clone(),
+            result_type.clone(),
             self.result_type.clone(),
-            CallingConvention::Tail,
         )
     }
 
@@ -361,3 +386,37 @@ impl CpsTransformer {
         name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VOID_TYPE;
+
+    #[test]
+    fn transform_function_type_with_cache() {
+        let transformer = CpsTransformer::new(VOID_TYPE.clone(), Default::default());
+        let type_ = types::Function::new(
+            vec![types::Primitive::Integer64.into()],
+            types::Primitive::Float64,
+            CallingConvention::Source,
+        );
+        let transformed = types::Function::new(
+            vec![
+                transformer.stack.type_.clone(),
+                create_continuation_type_with_stack(
+                    transformer.stack.type_.clone(),
+                    types::Primitive::Float64,
+                    VOID_TYPE.clone(),
+                )
+                .into(),
+                types::Primitive::Integer64.into(),
+            ],
+            VOID_TYPE.clone(),
+            CallingConvention::Tail,
+        );
+
+        assert_eq!(transformer.transform_function_type(&type_), transformed);
+        assert_eq!(transformer.transform_function_type(&type_), transformed);
+        assert_eq!(transformer.function_types.borrow().len(), 1);
+    }
+}
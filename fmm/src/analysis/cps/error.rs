@@ -1,5 +1,5 @@
 use super::super::TypeCheckError;
-use crate::{build::BuildError, ir::*};
+use crate::{analysis::format_expression, build::BuildError, ir::*};
 use std::{
     error::Error,
     fmt::{self, Display, Formatter},
@@ -8,13 +8,25 @@ use std::{
 #[derive(Clone, Debug, PartialEq)]
 pub enum CpsTransformationError {
     Build(BuildError),
+    FunctionDefinition(String, Box<CpsTransformationError>),
     InvalidCallingConvention(Call),
     TypeCheck(TypeCheckError),
 }
 
 impl Display for CpsTransformationError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        write!(formatter, "{:?}", self)
+        match self {
+            Self::Build(error) => write!(formatter, "{}", error),
+            Self::FunctionDefinition(name, error) => {
+                write!(formatter, "{} in function {}", error, name)
+            }
+            Self::InvalidCallingConvention(call) => write!(
+                formatter,
+                "call to {} of source calling convention in target function",
+                format_expression(call.function())
+            ),
+            Self::TypeCheck(error) => write!(formatter, "{}", error),
+        }
     }
 }
 
@@ -31,3 +43,31 @@ impl From<TypeCheckError> for CpsTransformationError {
         Self::TypeCheck(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    #[test]
+    fn display_invalid_calling_convention() {
+        assert_eq!(
+            CpsTransformationError::FunctionDefinition(
+                "f".into(),
+                CpsTransformationError::InvalidCallingConvention(Call::new(
+                    types::Function::new(
+                        vec![],
+                        types::Primitive::Float64,
+                        CallingConvention::Source
+                    ),
+                    Variable::new("g"),
+                    vec![],
+                    "x",
+                ))
+                .into()
+            )
+            .to_string(),
+            "call to g of source calling convention in target function in function f"
+        );
+    }
+}
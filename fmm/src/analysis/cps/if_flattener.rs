@@ -1,5 +1,5 @@
-use super::free_variables::collect_free_variables;
 use crate::{
+    analysis::free_variables::collect_free_variables,
     build::NameGenerator,
     ir::*,
     types::{self, Type, VOID_TYPE},
@@ -91,6 +91,22 @@ fn transform_instructions(
             let instructions = &instructions[1..];
 
             if let Instruction::If(if_) = instruction {
+                // Instructions after if instructions never branching to them are
+                // unreachable.
+                if !is_branched(if_.then()) && !is_branched(if_.else_()) {
+                    return (
+                        vec![If::new(
+                            VOID_TYPE.clone(),
+                            if_.condition().clone(),
+                            transform_block(context, if_.then(), result_type, local_variables),
+                            transform_block(context, if_.else_(), result_type, local_variables),
+                            "",
+                        )
+                        .into()],
+                        TerminalInstruction::Unreachable,
+                    );
+                }
+
                 let environment = get_continuation_environment(
                     instructions,
                     terminal_instruction,
@@ -243,12 +259,23 @@ fn create_continuation(
     Variable::new(name)
 }
 
+fn is_branched(block: &Block) -> bool {
+    matches!(block.terminal_instruction(), TerminalInstruction::Branch(_))
+}
+
 fn get_continuation_environment(
     instructions: &[Instruction],
     terminal_instruction: &TerminalInstruction,
     local_variables: &HashMap<String, Type>,
 ) -> Vec<(String, Type)> {
-    collect_free_variables(instructions, terminal_instruction)
+    // The order of hash sets differs across runs.
+    let mut names = collect_free_variables(instructions, terminal_instruction)
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    names.sort();
+
+    names
         .iter()
         .flat_map(|name| {
             local_variables
@@ -14,6 +14,30 @@ pub static STACK_TYPE: Lazy<Type> = Lazy::new(|| {
     .into()
 });
 
+pub type PushToStack =
+    fn(&InstructionBuilder, TypedExpression, TypedExpression) -> Result<(), BuildError>;
+pub type PopFromStack =
+    fn(&InstructionBuilder, TypedExpression, &Type) -> Result<TypedExpression, BuildError>;
+
+// A stack representation which continuation environments are pushed to and
+// popped from.
+#[derive(Clone)]
+pub struct StackConfiguration {
+    pub type_: Type,
+    pub push: PushToStack,
+    pub pop: PopFromStack,
+}
+
+impl Default for StackConfiguration {
+    fn default() -> Self {
+        Self {
+            type_: STACK_TYPE.clone(),
+            push: |builder, stack, element| push_to_stack(builder, stack, element),
+            pop: |builder, stack, type_| pop_from_stack(builder, stack, type_),
+        }
+    }
+}
+
 pub fn push_to_stack(
     builder: &InstructionBuilder,
     stack: impl Into<TypedExpression>,
@@ -0,0 +1,292 @@
+use super::free_variables::{collect_free_variables, collect_from_instruction};
+use crate::ir::*;
+use std::collections::HashSet;
+
+// Calls to functions without any memory access are eliminated if their results
+// are not used.
+pub fn eliminate_dead_code(module: &Module) -> Module {
+    let pure_functions = collect_pure_functions(module);
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    eliminate_in_block(
+                        definition.body(),
+                        &shadow_pure_functions(&pure_functions, definition),
+                    ),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+pub(crate) fn collect_pure_functions(module: &Module) -> HashSet<&str> {
+    module
+        .function_declarations()
+        .iter()
+        .filter(|declaration| declaration.attributes().read_none)
+        .map(|declaration| declaration.name())
+        .collect()
+}
+
+// Arguments can shadow the pure functions.
+pub(crate) fn shadow_pure_functions<'a>(
+    pure_functions: &HashSet<&'a str>,
+    definition: &FunctionDefinition,
+) -> HashSet<&'a str> {
+    let mut pure_functions = pure_functions.clone();
+
+    for argument in definition.arguments() {
+        pure_functions.remove(argument.name());
+    }
+
+    pure_functions
+}
+
+fn eliminate_in_block(block: &Block, pure_functions: &HashSet<&str>) -> Block {
+    let mut pure_functions = pure_functions.clone();
+    let mut pure_calls = vec![];
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        pure_calls.push(is_pure_call(instruction, &pure_functions));
+        instructions.push(eliminate_in_instruction(instruction, &pure_functions));
+
+        if let Some(name) = instruction.name() {
+            pure_functions.remove(name);
+        }
+    }
+
+    let mut variables = collect_free_variables(&[], block.terminal_instruction());
+    let mut live_instructions = vec![];
+
+    for (instruction, pure) in instructions.into_iter().zip(pure_calls).rev() {
+        if pure && !variables.contains(instruction.name().unwrap()) {
+            continue;
+        }
+
+        variables.extend(collect_from_instruction(&instruction));
+        live_instructions.push(instruction);
+    }
+
+    live_instructions.reverse();
+
+    Block::new(live_instructions, block.terminal_instruction().clone())
+}
+
+fn eliminate_in_instruction(
+    instruction: &Instruction,
+    pure_functions: &HashSet<&str>,
+) -> Instruction {
+    match instruction {
+        Instruction::If(if_) => If::new(
+            if_.type_().clone(),
+            if_.condition().clone(),
+            eliminate_in_block(if_.then(), pure_functions),
+            eliminate_in_block(if_.else_(), pure_functions),
+            if_.name(),
+        )
+        .into(),
+        _ => instruction.clone(),
+    }
+}
+
+pub(crate) fn is_pure_call(instruction: &Instruction, pure_functions: &HashSet<&str>) -> bool {
+    if let Instruction::Call(call) = instruction {
+        if let Expression::Variable(variable) = call.function() {
+            return pure_functions.contains(variable.name());
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_function_type() -> types::Function {
+        types::Function::new(vec![], types::Primitive::Float64, CallingConvention::Target)
+    }
+
+    fn create_module(attributes: FunctionAttributes, body: Block) -> Module {
+        Module::new(
+            vec![],
+            vec![FunctionDeclaration::with_attributes(
+                "f",
+                create_function_type(),
+                attributes,
+            )],
+            vec![],
+            vec![FunctionDefinition::new(
+                "g",
+                vec![],
+                body,
+                types::Primitive::Float64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_call(name: &str) -> Instruction {
+        Call::new(create_function_type(), Variable::new("f"), vec![], name).into()
+    }
+
+    const READ_NONE: FunctionAttributes = FunctionAttributes {
+        no_return: false,
+        read_only: false,
+        read_none: true,
+    };
+
+    #[test]
+    fn eliminate_read_none_call_with_dead_result() {
+        let return_ = Return::new(types::Primitive::Float64, Primitive::Float64(42.0));
+
+        assert_eq!(
+            eliminate_dead_code(&create_module(
+                READ_NONE,
+                Block::new(vec![create_call("x")], return_.clone()),
+            )),
+            create_module(READ_NONE, Block::new(vec![], return_)),
+        );
+    }
+
+    #[test]
+    fn eliminate_read_none_calls_used_only_by_dead_calls() {
+        let function_type = types::Function::new(
+            vec![types::Primitive::Float64.into()],
+            types::Primitive::Float64,
+            CallingConvention::Target,
+        );
+        let return_ = Return::new(types::Primitive::Float64, Primitive::Float64(42.0));
+        let module = Module::new(
+            vec![],
+            vec![FunctionDeclaration::with_attributes(
+                "f",
+                function_type.clone(),
+                READ_NONE,
+            )],
+            vec![],
+            vec![FunctionDefinition::new(
+                "g",
+                vec![Argument::new("x", types::Primitive::Float64)],
+                Block::new(
+                    vec![
+                        Call::new(
+                            function_type.clone(),
+                            Variable::new("f"),
+                            vec![Variable::new("x").into()],
+                            "y",
+                        )
+                        .into(),
+                        Call::new(
+                            function_type,
+                            Variable::new("f"),
+                            vec![Variable::new("y").into()],
+                            "z",
+                        )
+                        .into(),
+                    ],
+                    return_.clone(),
+                ),
+                types::Primitive::Float64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        );
+
+        assert_eq!(
+            eliminate_dead_code(&module).function_definitions()[0].body(),
+            &Block::new(vec![], return_)
+        );
+    }
+
+    #[test]
+    fn keep_read_none_call_with_used_result() {
+        let module = create_module(
+            READ_NONE,
+            Block::new(
+                vec![create_call("x")],
+                Return::new(types::Primitive::Float64, Variable::new("x")),
+            ),
+        );
+
+        assert_eq!(eliminate_dead_code(&module), module);
+    }
+
+    #[test]
+    fn keep_call_of_shadowing_local_with_dead_result() {
+        let module = create_module(
+            READ_NONE,
+            Block::new(
+                vec![
+                    PassThrough::new(create_function_type(), Variable::new("h"), "f").into(),
+                    create_call("x"),
+                ],
+                Return::new(types::Primitive::Float64, Primitive::Float64(42.0)),
+            ),
+        );
+
+        assert_eq!(eliminate_dead_code(&module), module);
+    }
+
+    #[test]
+    fn keep_call_of_shadowing_argument_with_dead_result() {
+        let module = create_module(
+            READ_NONE,
+            Block::new(
+                vec![create_call("x")],
+                Return::new(types::Primitive::Float64, Primitive::Float64(42.0)),
+            ),
+        );
+        let module = Module::new(
+            vec![],
+            module.function_declarations().to_vec(),
+            vec![],
+            vec![FunctionDefinition::new(
+                "g",
+                vec![Argument::new("f", create_function_type())],
+                module.function_definitions()[0].body().clone(),
+                types::Primitive::Float64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        );
+
+        assert_eq!(eliminate_dead_code(&module), module);
+    }
+
+    #[test]
+    fn keep_impure_call_with_dead_result() {
+        for attributes in [
+            Default::default(),
+            FunctionAttributes {
+                read_only: true,
+                ..Default::default()
+            },
+        ] {
+            let module = create_module(
+                attributes,
+                Block::new(
+                    vec![create_call("x")],
+                    Return::new(types::Primitive::Float64, Primitive::Float64(42.0)),
+                ),
+            );
+
+            assert_eq!(eliminate_dead_code(&module), module);
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use super::{
+    alias::{may_alias, pointer_base, AliasContext},
+    free_variables::{collect_free_variables, collect_from_expression, collect_from_instruction},
+    sinking::is_pure,
+};
+use crate::ir::*;
+use std::collections::HashSet;
+
+// Stores overwritten by later stores to the same locations are eliminated if
+// nothing observes them in between. Only stores to allocations not escaping
+// from functions are eliminated as other locations might be observed
+// elsewhere. Atomic stores are never eliminated.
+pub fn eliminate_dead_stores(module: &Module) -> Module {
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    eliminate_in_block(
+                        definition.body(),
+                        &AliasContext::new(),
+                        &collect_escaped_names(definition.body()),
+                    ),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn eliminate_in_block(
+    block: &Block,
+    context: &AliasContext,
+    escaped_names: &HashSet<String>,
+) -> Block {
+    let mut context = context.clone();
+    // Stores not observed yet with their indices
+    let mut stores = Vec::<(usize, &Store)>::new();
+    let mut dead_indices = HashSet::new();
+    let mut instructions = vec![];
+
+    for (index, instruction) in block.instructions().iter().enumerate() {
+        match instruction {
+            Instruction::AllocateHeap(_) | Instruction::AllocateStack(_) => {}
+            Instruction::Load(load) => {
+                stores.retain(|(_, store)| !may_alias(store.pointer(), load.pointer(), &context))
+            }
+            Instruction::Store(store) => {
+                let local = is_local(store.pointer(), &context, escaped_names);
+
+                stores.retain(|(index, other)| {
+                    if local && other.pointer() == store.pointer() && other.type_() == store.type_()
+                    {
+                        dead_indices.insert(*index);
+                        false
+                    } else {
+                        true
+                    }
+                });
+                stores.push((index, store));
+            }
+            _ if is_pure(instruction) => {}
+            // Any other instructions including calls and atomic operations
+            // might read memory.
+            _ => stores.clear(),
+        }
+
+        if let Some(name) = instruction.name() {
+            stores.retain(|(_, store)| {
+                !collect_from_instruction(&(*store).clone().into()).contains(name)
+            });
+        }
+
+        let instruction = match instruction {
+            Instruction::If(if_) => If::new(
+                if_.type_().clone(),
+                if_.condition().clone(),
+                eliminate_in_block(if_.then(), &context, escaped_names),
+                eliminate_in_block(if_.else_(), &context, escaped_names),
+                if_.name(),
+            )
+            .into(),
+            _ => instruction.clone(),
+        };
+
+        context.bind(&instruction);
+        instructions.push(instruction);
+    }
+
+    Block::new(
+        instructions
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !dead_indices.contains(index))
+            .map(|(_, instruction)| instruction)
+            .collect(),
+        block.terminal_instruction().clone(),
+    )
+}
+
+fn is_local(pointer: &Expression, context: &AliasContext, escaped_names: &HashSet<String>) -> bool {
+    let base = pointer_base(pointer);
+
+    context.is_allocation(base)
+        && matches!(base, Expression::Variable(variable) if !escaped_names.contains(variable.name()))
+}
+
+// Allocations escape if their pointers are used other than as locations of
+// loads and stores.
+fn collect_escaped_names(block: &Block) -> HashSet<String> {
+    let mut names = collect_free_variables(&[], block.terminal_instruction());
+
+    for instruction in block.instructions() {
+        names.extend(match instruction {
+            Instruction::If(if_) => collect_from_expression(if_.condition())
+                .into_iter()
+                .chain(collect_escaped_names(if_.then()))
+                .chain(collect_escaped_names(if_.else_()))
+                .collect(),
+            Instruction::Load(load) => collect_from_location(load.pointer()),
+            Instruction::Store(store) => collect_from_expression(store.value())
+                .into_iter()
+                .chain(collect_from_location(store.pointer()))
+                .collect(),
+            _ => collect_from_instruction(instruction),
+        });
+    }
+
+    names
+}
+
+fn collect_from_location(pointer: &Expression) -> HashSet<String> {
+    let mut names = collect_from_expression(pointer);
+
+    if let Expression::Variable(variable) = pointer_base(pointer) {
+        names.remove(variable.name());
+    }
+
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("p", types::Pointer::new(types::Primitive::Integer64)),
+                    Argument::new("q", types::Pointer::new(types::Primitive::Integer64)),
+                ],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+                ),
+                types::Primitive::Integer64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_store(value: u64, pointer: &str) -> Instruction {
+        Store::new(
+            types::Primitive::Integer64,
+            Primitive::Integer64(value),
+            Variable::new(pointer),
+        )
+        .into()
+    }
+
+    fn create_load(pointer: &str) -> Instruction {
+        Load::new(types::Primitive::Integer64, Variable::new(pointer), "x").into()
+    }
+
+    fn create_allocation(name: &str) -> Instruction {
+        AllocateStack::new(types::Primitive::Integer64, name).into()
+    }
+
+    #[test]
+    fn eliminate_overwritten_store() {
+        assert_eq!(
+            eliminate_dead_stores(&create_module(vec![
+                create_allocation("a"),
+                create_store(1, "a"),
+                create_store(2, "a")
+            ])),
+            create_module(vec![create_allocation("a"), create_store(2, "a")])
+        );
+    }
+
+    #[test]
+    fn keep_overwritten_store_to_argument() {
+        let module = create_module(vec![create_store(1, "p"), create_store(2, "p")]);
+
+        assert_eq!(eliminate_dead_stores(&module), module);
+    }
+
+    #[test]
+    fn keep_overwritten_store_to_escaping_allocation() {
+        let module = create_module(vec![
+            create_allocation("a"),
+            Store::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                Variable::new("a"),
+                Variable::new("p"),
+            )
+            .into(),
+            create_store(1, "a"),
+            create_store(2, "a"),
+        ]);
+
+        assert_eq!(eliminate_dead_stores(&module), module);
+    }
+
+    #[test]
+    fn keep_store_observed_by_load_of_nested_element() {
+        let create_element_address = |pointer: Expression, offset| -> Expression {
+            PointerAddress::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                pointer,
+                Primitive::PointerInteger(offset),
+            )
+            .into()
+        };
+        let create_element_store = |value| -> Instruction {
+            Store::new(
+                types::Primitive::Integer64,
+                Primitive::Integer64(value),
+                create_element_address(Variable::new("a").into(), 1),
+            )
+            .into()
+        };
+        let module = create_module(vec![
+            create_allocation("a"),
+            create_element_store(1),
+            Load::new(
+                types::Primitive::Integer64,
+                create_element_address(create_element_address(Variable::new("a").into(), 0), 1),
+                "x",
+            )
+            .into(),
+            create_element_store(2),
+        ]);
+
+        assert_eq!(eliminate_dead_stores(&module), module);
+    }
+
+    #[test]
+    fn eliminate_store_overwritten_after_store_to_another_allocation() {
+        assert_eq!(
+            eliminate_dead_stores(&create_module(vec![
+                create_allocation("a"),
+                create_allocation("b"),
+                create_store(1, "a"),
+                create_store(2, "b"),
+                create_load("b"),
+                create_store(3, "a"),
+            ])),
+            create_module(vec![
+                create_allocation("a"),
+                create_allocation("b"),
+                create_store(2, "b"),
+                create_load("b"),
+                create_store(3, "a"),
+            ])
+        );
+    }
+
+    #[test]
+    fn keep_store_observed_by_load() {
+        let module = create_module(vec![
+            create_allocation("a"),
+            create_store(1, "a"),
+            create_load("a"),
+            create_store(2, "a"),
+        ]);
+
+        assert_eq!(eliminate_dead_stores(&module), module);
+    }
+
+    #[test]
+    fn keep_store_observed_by_aliased_load() {
+        let module = create_module(vec![
+            create_allocation("a"),
+            create_store(1, "a"),
+            create_load("q"),
+            create_store(2, "a"),
+        ]);
+
+        assert_eq!(eliminate_dead_stores(&module), module);
+    }
+
+    #[test]
+    fn keep_store_across_call() {
+        let module = create_module(vec![
+            create_allocation("a"),
+            create_store(1, "a"),
+            Call::new(
+                types::Function::new(
+                    vec![],
+                    types::Primitive::Integer64,
+                    CallingConvention::Target,
+                ),
+                Variable::new("g"),
+                vec![],
+                "y",
+            )
+            .into(),
+            create_store(2, "a"),
+        ]);
+
+        assert_eq!(eliminate_dead_stores(&module), module);
+    }
+
+    #[test]
+    fn keep_atomic_store() {
+        let module = create_module(vec![
+            create_allocation("a"),
+            AtomicStore::new(
+                types::Primitive::Integer64,
+                Primitive::Integer64(1),
+                Variable::new("a"),
+                AtomicOrdering::Release,
+            )
+            .into(),
+            create_store(2, "a"),
+        ]);
+
+        assert_eq!(eliminate_dead_stores(&module), module);
+    }
+}
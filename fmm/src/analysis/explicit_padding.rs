@@ -0,0 +1,338 @@
+use super::{
+    expression_conversion::convert_expressions,
+    target_data_layout::TargetDataLayout,
+    type_conversion::convert_types,
+    type_size::{type_alignment, type_size},
+};
+use crate::{
+    ir::*,
+    types::{self, Type},
+};
+
+// Records are rewritten to have explicit padding elements of byte records so
+// that their layouts have no gaps. Records of unknown sizes are kept as they
+// are.
+pub fn insert_explicit_padding(module: &Module, layout: &TargetDataLayout) -> Module {
+    let pointer_size = layout.pointer_size();
+
+    let module = convert_expressions(module, &|expression| {
+        convert_expression(expression, pointer_size)
+    })
+    .map_function_definitions(|definition| {
+        FunctionDefinition::new(
+            definition.name(),
+            definition.arguments().to_vec(),
+            convert_block(definition.body(), pointer_size),
+            definition.result_type().clone(),
+            definition.calling_convention(),
+            definition.linkage(),
+        )
+    });
+
+    convert_types(&module, &|type_| match type_ {
+        Type::Record(record) => {
+            if let Some((elements, _)) = pad_record(record, pointer_size) {
+                if let Some(name) = record.name() {
+                    types::Record::named(name, elements)
+                } else {
+                    types::Record::new(elements)
+                }
+                .into()
+            } else {
+                type_.clone()
+            }
+        }
+        _ => type_.clone(),
+    })
+}
+
+fn convert_block(block: &Block, pointer_size: usize) -> Block {
+    Block::new(
+        block
+            .instructions()
+            .iter()
+            .map(|instruction| convert_instruction(instruction, pointer_size))
+            .collect(),
+        block.terminal_instruction().clone(),
+    )
+}
+
+fn convert_instruction(instruction: &Instruction, pointer_size: usize) -> Instruction {
+    match instruction {
+        Instruction::DeconstructRecord(deconstruct) => DeconstructRecord::new(
+            deconstruct.type_().clone(),
+            deconstruct.record().clone(),
+            convert_element_index(
+                deconstruct.type_(),
+                deconstruct.element_index(),
+                pointer_size,
+            ),
+            deconstruct.name(),
+        )
+        .into(),
+        Instruction::If(if_) => If::new(
+            if_.type_().clone(),
+            if_.condition().clone(),
+            convert_block(if_.then(), pointer_size),
+            convert_block(if_.else_(), pointer_size),
+            if_.name(),
+        )
+        .into(),
+        _ => instruction.clone(),
+    }
+}
+
+fn convert_expression(expression: &Expression, pointer_size: usize) -> Expression {
+    match expression {
+        Expression::Record(record) => {
+            if let Some((elements, indices)) = pad_record(record.type_(), pointer_size) {
+                let mut values = elements
+                    .into_iter()
+                    .map(|type_| Undefined::new(type_).into())
+                    .collect::<Vec<_>>();
+
+                for (index, element) in indices.into_iter().zip(record.elements()) {
+                    values[index] = element.clone();
+                }
+
+                Record::new(record.type_().clone(), values).into()
+            } else {
+                expression.clone()
+            }
+        }
+        Expression::RecordAddress(address) => RecordAddress::new(
+            address.type_().clone(),
+            address.pointer().clone(),
+            convert_element_index(address.type_(), address.element_index(), pointer_size),
+        )
+        .into(),
+        _ => expression.clone(),
+    }
+}
+
+fn convert_element_index(record: &types::Record, index: usize, pointer_size: usize) -> usize {
+    pad_record(record, pointer_size)
+        .map(|(_, indices)| indices[index])
+        .unwrap_or(index)
+}
+
+// Returns padded elements and new indices of original elements.
+fn pad_record(record: &types::Record, pointer_size: usize) -> Option<(Vec<Type>, Vec<usize>)> {
+    let mut elements = vec![];
+    let mut indices = vec![];
+    let mut offset = 0usize;
+
+    for element in record.elements() {
+        let alignment = type_alignment(element, pointer_size).ok()?;
+        let aligned_offset = offset.div_ceil(alignment) * alignment;
+
+        if aligned_offset > offset {
+            elements.push(padding_type(aligned_offset - offset));
+        }
+
+        indices.push(elements.len());
+        elements.push(element.clone());
+        offset = aligned_offset + type_size(element, pointer_size).ok()?;
+    }
+
+    let size = type_size(&record.clone().into(), pointer_size).ok()?;
+
+    if size > offset {
+        elements.push(padding_type(size - offset));
+    }
+
+    Some((elements, indices))
+}
+
+fn padding_type(size: usize) -> Type {
+    types::Record::new(vec![types::Primitive::Integer8.into(); size]).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CallingConvention;
+
+    const LAYOUT: TargetDataLayout = TargetDataLayout::new(8, 8);
+
+    fn create_record_type() -> types::Record {
+        types::Record::new(vec![
+            types::Primitive::Integer8.into(),
+            types::Primitive::Integer64.into(),
+        ])
+    }
+
+    fn create_padded_record_type() -> types::Record {
+        types::Record::new(vec![
+            types::Primitive::Integer8.into(),
+            padding_type(7),
+            types::Primitive::Integer64.into(),
+        ])
+    }
+
+    #[test]
+    fn insert_padding_between_elements() {
+        assert_eq!(
+            insert_explicit_padding(
+                &Module::new(
+                    vec![],
+                    vec![],
+                    vec![VariableDefinition::new(
+                        "x",
+                        Record::new(
+                            create_record_type(),
+                            vec![
+                                Primitive::Integer8(1).into(),
+                                Primitive::Integer64(2).into()
+                            ],
+                        ),
+                        create_record_type(),
+                        false,
+                        Linkage::External,
+                        None,
+                    )],
+                    vec![],
+                ),
+                &LAYOUT,
+            ),
+            Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    Record::new(
+                        create_padded_record_type(),
+                        vec![
+                            Primitive::Integer8(1).into(),
+                            Undefined::new(padding_type(7)).into(),
+                            Primitive::Integer64(2).into(),
+                        ],
+                    ),
+                    create_padded_record_type(),
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            )
+        );
+    }
+
+    #[test]
+    fn insert_tail_padding() {
+        let record_type = types::Record::new(vec![
+            types::Primitive::Integer64.into(),
+            types::Primitive::Integer8.into(),
+        ]);
+
+        assert_eq!(
+            insert_explicit_padding(
+                &Module::new(
+                    vec![VariableDeclaration::new("x", record_type)],
+                    vec![],
+                    vec![],
+                    vec![]
+                ),
+                &LAYOUT,
+            ),
+            Module::new(
+                vec![VariableDeclaration::new(
+                    "x",
+                    types::Record::new(vec![
+                        types::Primitive::Integer64.into(),
+                        types::Primitive::Integer8.into(),
+                        padding_type(7),
+                    ])
+                )],
+                vec![],
+                vec![],
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn convert_element_indices() {
+        let pointer_type = types::Pointer::new(create_record_type());
+
+        assert_eq!(
+            insert_explicit_padding(
+                &Module::new(
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![FunctionDefinition::new(
+                        "f",
+                        vec![
+                            Argument::new("x", create_record_type()),
+                            Argument::new("p", pointer_type.clone()),
+                        ],
+                        Block::new(
+                            vec![DeconstructRecord::new(
+                                create_record_type(),
+                                Variable::new("x"),
+                                1,
+                                "y",
+                            )
+                            .into()],
+                            Return::new(
+                                types::Pointer::new(types::Primitive::Integer64),
+                                RecordAddress::new(create_record_type(), Variable::new("p"), 1),
+                            ),
+                        ),
+                        types::Pointer::new(types::Primitive::Integer64),
+                        CallingConvention::Target,
+                        Linkage::External,
+                    )],
+                ),
+                &LAYOUT,
+            ),
+            Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![FunctionDefinition::new(
+                    "f",
+                    vec![
+                        Argument::new("x", create_padded_record_type()),
+                        Argument::new("p", types::Pointer::new(create_padded_record_type())),
+                    ],
+                    Block::new(
+                        vec![DeconstructRecord::new(
+                            create_padded_record_type(),
+                            Variable::new("x"),
+                            2,
+                            "y",
+                        )
+                        .into()],
+                        Return::new(
+                            types::Pointer::new(types::Primitive::Integer64),
+                            RecordAddress::new(create_padded_record_type(), Variable::new("p"), 2),
+                        ),
+                    ),
+                    types::Pointer::new(types::Primitive::Integer64),
+                    CallingConvention::Target,
+                    Linkage::External,
+                )],
+            )
+        );
+    }
+
+    #[test]
+    fn keep_record_without_gaps() {
+        let module = Module::new(
+            vec![VariableDeclaration::new(
+                "x",
+                types::Record::new(vec![
+                    types::Primitive::Integer32.into(),
+                    types::Primitive::Integer32.into(),
+                ]),
+            )],
+            vec![],
+            vec![],
+            vec![],
+        );
+
+        assert_eq!(insert_explicit_padding(&module, &LAYOUT), module);
+    }
+}
@@ -0,0 +1,228 @@
+use super::{
+    expression_conversion::{
+        convert_expressions_in_expression, convert_expressions_in_instruction,
+        convert_expressions_in_terminal_instruction,
+    },
+    renaming::create_name_generator,
+};
+use crate::ir::*;
+use std::cell::RefCell;
+
+// Calls in expressions are converted into call instructions preceding their
+// users. Inner calls are evaluated first.
+pub fn flatten_expression_calls(module: &Module) -> Module {
+    let generate_name = RefCell::new(create_name_generator(module, "_pure_call_"));
+
+    module.clone().map_function_definitions(|definition| {
+        FunctionDefinition::new(
+            definition.name(),
+            definition.arguments().to_vec(),
+            flatten_block(&generate_name, definition.body()),
+            definition.result_type().clone(),
+            definition.calling_convention(),
+            definition.linkage(),
+        )
+    })
+}
+
+fn flatten_block(generate_name: &RefCell<impl FnMut() -> String>, block: &Block) -> Block {
+    let instructions = RefCell::new(vec![]);
+    let flatten = |expression: &Expression| {
+        if let Expression::PureCall(call) = expression {
+            let name = (generate_name.borrow_mut())();
+
+            instructions.borrow_mut().push(
+                Call::new(
+                    call.type_().clone(),
+                    call.function().clone(),
+                    call.arguments().to_vec(),
+                    &name,
+                )
+                .into(),
+            );
+
+            Variable::new(name).into()
+        } else {
+            expression.clone()
+        }
+    };
+
+    for instruction in block.instructions() {
+        let instruction = if let Instruction::If(if_) = instruction {
+            If::new(
+                if_.type_().clone(),
+                convert_expressions_in_expression(if_.condition(), &flatten),
+                flatten_block(generate_name, if_.then()),
+                flatten_block(generate_name, if_.else_()),
+                if_.name(),
+            )
+            .into()
+        } else {
+            convert_expressions_in_instruction(instruction, &flatten)
+        };
+
+        instructions.borrow_mut().push(instruction);
+    }
+
+    let terminal_instruction =
+        convert_expressions_in_terminal_instruction(block.terminal_instruction(), &flatten);
+
+    Block::new(instructions.into_inner(), terminal_instruction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        analysis::check_types,
+        types::{self, CallingConvention},
+    };
+
+    fn create_function_type() -> types::Function {
+        types::Function::new(
+            vec![types::Primitive::PointerInteger.into()],
+            types::Primitive::PointerInteger,
+            CallingConvention::Target,
+        )
+    }
+
+    fn create_module(instructions: Vec<Instruction>, result: impl Into<Expression>) -> Module {
+        Module::new(
+            vec![],
+            ["f", "g"]
+                .iter()
+                .map(|name| {
+                    FunctionDeclaration::with_attributes(
+                        *name,
+                        create_function_type(),
+                        FunctionAttributes {
+                            read_none: true,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            vec![],
+            vec![FunctionDefinition::new(
+                "h",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::PointerInteger, result),
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_pure_call(name: &str, argument: impl Into<Expression>) -> PureCall {
+        PureCall::new(
+            create_function_type(),
+            Variable::new(name),
+            vec![argument.into()],
+        )
+    }
+
+    fn create_call(name: &str, argument: impl Into<Expression>, result: &str) -> Instruction {
+        Call::new(
+            create_function_type(),
+            Variable::new(name),
+            vec![argument.into()],
+            result,
+        )
+        .into()
+    }
+
+    #[test]
+    fn flatten_nested_pure_call() {
+        let module = create_module(
+            vec![],
+            create_pure_call("f", create_pure_call("g", Variable::new("x"))),
+        );
+
+        check_types(&module).unwrap();
+
+        let module = flatten_expression_calls(&module);
+
+        check_types(&module).unwrap();
+        assert_eq!(
+            module,
+            create_module(
+                vec![
+                    create_call("g", Variable::new("x"), "_pure_call_0"),
+                    create_call("f", Variable::new("_pure_call_0"), "_pure_call_1"),
+                ],
+                Variable::new("_pure_call_1"),
+            )
+        );
+    }
+
+    #[test]
+    fn flatten_pure_call_in_instruction() {
+        assert_eq!(
+            flatten_expression_calls(&create_module(
+                vec![PassThrough::new(
+                    types::Primitive::PointerInteger,
+                    create_pure_call("f", Variable::new("x")),
+                    "y",
+                )
+                .into()],
+                Variable::new("y"),
+            )),
+            create_module(
+                vec![
+                    create_call("f", Variable::new("x"), "_pure_call_0"),
+                    PassThrough::new(
+                        types::Primitive::PointerInteger,
+                        Variable::new("_pure_call_0"),
+                        "y",
+                    )
+                    .into(),
+                ],
+                Variable::new("y"),
+            )
+        );
+    }
+
+    #[test]
+    fn flatten_pure_call_in_if_arm() {
+        let if_ = |then: Block| -> Instruction {
+            If::new(
+                types::Primitive::PointerInteger,
+                Primitive::Boolean(true),
+                then,
+                Block::new(
+                    vec![],
+                    Branch::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                "y",
+            )
+            .into()
+        };
+
+        assert_eq!(
+            flatten_expression_calls(&create_module(
+                vec![if_(Block::new(
+                    vec![],
+                    Branch::new(
+                        types::Primitive::PointerInteger,
+                        create_pure_call("f", Variable::new("x")),
+                    ),
+                ))],
+                Variable::new("y"),
+            )),
+            create_module(
+                vec![if_(Block::new(
+                    vec![create_call("f", Variable::new("x"), "_pure_call_0")],
+                    Branch::new(
+                        types::Primitive::PointerInteger,
+                        Variable::new("_pure_call_0"),
+                    ),
+                ))],
+                Variable::new("y"),
+            )
+        );
+    }
+}
@@ -23,6 +23,13 @@ pub fn convert_expressions(
     )
 }
 
+pub fn convert_expressions_in_expression(
+    expression: &Expression,
+    convert: &impl Fn(&Expression) -> Expression,
+) -> Expression {
+    convert_expression(expression, convert)
+}
+
 pub fn convert_expressions_in_instruction(
     instruction: &Instruction,
     convert: &impl Fn(&Expression) -> Expression,
@@ -121,6 +128,15 @@ fn convert_instruction(
             call.name(),
         )
         .into(),
+        Instruction::CheckedArithmeticOperation(operation) => CheckedArithmeticOperation::new(
+            operation.type_(),
+            operation.operator(),
+            operation.signed(),
+            convert(operation.lhs()),
+            convert(operation.rhs()),
+            operation.name(),
+        )
+        .into(),
         Instruction::CompareAndSwap(cas) => CompareAndSwap::new(
             cas.type_().clone(),
             convert(cas.pointer()),
@@ -146,7 +162,17 @@ fn convert_instruction(
         )
         .into(),
         Instruction::Fence(fence) => fence.clone().into(),
+        Instruction::Fma(fma) => Fma::new(
+            fma.type_(),
+            convert(fma.lhs()),
+            convert(fma.rhs()),
+            convert(fma.addend()),
+            fma.name(),
+        )
+        .into(),
         Instruction::FreeHeap(free) => FreeHeap::new(convert(free.pointer())).into(),
+        Instruction::LifetimeEnd(end) => LifetimeEnd::new(convert(end.pointer())).into(),
+        Instruction::LifetimeStart(start) => LifetimeStart::new(convert(start.pointer())).into(),
         Instruction::If(if_) => If::new(
             if_.type_().clone(),
             convert(if_.condition()),
@@ -155,9 +181,39 @@ fn convert_instruction(
             if_.name(),
         )
         .into(),
+        Instruction::InlineAssembly(assembly) => InlineAssembly::new(
+            assembly.type_().clone(),
+            assembly.template(),
+            assembly.constraints(),
+            assembly.operands().iter().map(convert).collect(),
+            assembly.side_effects(),
+            assembly.align_stack(),
+            assembly.name(),
+        )
+        .into(),
         Instruction::Load(load) => {
             Load::new(load.type_().clone(), convert(load.pointer()), load.name()).into()
         }
+        Instruction::MathOperation(operation) => MathOperation::new(
+            operation.type_(),
+            operation.operator(),
+            convert(operation.value()),
+            operation.name(),
+        )
+        .into(),
+        Instruction::MemoryCopy(copy) => MemoryCopy::new(
+            convert(copy.source()),
+            convert(copy.destination()),
+            convert(copy.size()),
+            copy.overlapping(),
+        )
+        .into(),
+        Instruction::MemorySet(set) => MemorySet::new(
+            convert(set.pointer()),
+            convert(set.value()),
+            convert(set.size()),
+        )
+        .into(),
         Instruction::PassThrough(pass) => PassThrough::new(
             pass.type_().clone(),
             convert(pass.expression()),
@@ -170,6 +226,17 @@ fn convert_instruction(
             reallocate.name(),
         )
         .into(),
+        Instruction::SaturatingArithmeticOperation(operation) => {
+            SaturatingArithmeticOperation::new(
+                operation.type_(),
+                operation.operator(),
+                operation.signed(),
+                convert(operation.lhs()),
+                convert(operation.rhs()),
+                operation.name(),
+            )
+            .into()
+        }
         Instruction::Store(store) => Store::new(
             store.type_().clone(),
             convert(store.value()),
@@ -190,6 +257,7 @@ fn convert_terminal_instruction(
         TerminalInstruction::Return(return_) => {
             Return::new(return_.type_().clone(), convert(return_.expression())).into()
         }
+        TerminalInstruction::Trap => TerminalInstruction::Trap,
         TerminalInstruction::Unreachable => TerminalInstruction::Unreachable,
     }
 }
@@ -208,6 +276,8 @@ fn convert_expression(
                 convert(operation.lhs()),
                 convert(operation.rhs()),
             )
+            .with_fast_math_flags(operation.fast_math_flags())
+            .with_overflow_flags(operation.overflow_flags())
             .into(),
             Expression::BitCast(bit_cast) => BitCast::new(
                 bit_cast.from().clone(),
@@ -215,6 +285,12 @@ fn convert_expression(
                 convert(bit_cast.expression()),
             )
             .into(),
+            Expression::BitManipulationOperation(operation) => BitManipulationOperation::new(
+                operation.type_(),
+                operation.operator(),
+                convert(operation.value()),
+            )
+            .into(),
             Expression::BitwiseNotOperation(operation) => {
                 BitwiseNotOperation::new(operation.type_(), convert(operation.value())).into()
             }
@@ -225,6 +301,12 @@ fn convert_expression(
                 convert(operation.rhs()),
             )
             .into(),
+            Expression::ByteOffset(offset) => ByteOffset::new(
+                offset.type_().clone(),
+                convert(offset.pointer()),
+                convert(offset.offset()),
+            )
+            .into(),
             Expression::ComparisonOperation(operation) => ComparisonOperation::new(
                 operation.type_(),
                 operation.operator(),
@@ -232,12 +314,33 @@ fn convert_expression(
                 convert(operation.rhs()),
             )
             .into(),
+            Expression::ExtractBits(extract) => ExtractBits::new(
+                extract.type_(),
+                convert(extract.value()),
+                extract.offset(),
+                extract.width(),
+            )
+            .into(),
+            Expression::InsertBits(insert) => InsertBits::new(
+                insert.type_(),
+                convert(insert.value()),
+                convert(insert.field()),
+                insert.offset(),
+                insert.width(),
+            )
+            .into(),
             Expression::PointerAddress(address) => PointerAddress::new(
                 address.type_().clone(),
                 convert(address.pointer()),
                 convert(address.offset()),
             )
             .into(),
+            Expression::PureCall(call) => PureCall::new(
+                call.type_().clone(),
+                convert(call.function()),
+                call.arguments().iter().map(convert).collect(),
+            )
+            .into(),
             Expression::Record(record) => Record::new(
                 record.type_().clone(),
                 record.elements().iter().map(convert).collect(),
@@ -249,6 +352,13 @@ fn convert_expression(
                 address.element_index(),
             )
             .into(),
+            Expression::Select(select) => Select::new(
+                select.type_().clone(),
+                convert(select.condition()),
+                convert(select.then()),
+                convert(select.else_()),
+            )
+            .into(),
             Expression::Union(union) => Union::new(
                 union.type_().clone(),
                 union.member_index(),
@@ -261,7 +371,8 @@ fn convert_expression(
                 address.member_index(),
             )
             .into(),
-            Expression::AlignOf(_)
+            Expression::AbsoluteAddress(_)
+            | Expression::AlignOf(_)
             | Expression::Primitive(_)
             | Expression::SizeOf(_)
             | Expression::Undefined(_)
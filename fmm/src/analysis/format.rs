@@ -1,3 +1,4 @@
+use super::type_collection::collect_named_records;
 use crate::{
     ir::*,
     types::{self, Type},
@@ -6,15 +7,57 @@ use crate::{
 pub fn format_module(module: &Module) -> String {
     format!(
         "(module\n{})",
-        module
-            .function_definitions()
+        collect_named_records(module)
             .iter()
-            .map(|definition| indent(&format_function_definition(definition)))
+            .map(|record| indent(&format_record_definition(record)))
+            .chain(
+                module
+                    .function_declarations()
+                    .iter()
+                    .map(|declaration| indent(&format_function_declaration(declaration)))
+            )
+            .chain(
+                module
+                    .function_definitions()
+                    .iter()
+                    .map(|definition| indent(&format_function_definition(definition)))
+            )
             .collect::<Vec<_>>()
             .join("\n")
     )
 }
 
+fn format_record_definition(record: &types::Record) -> String {
+    format!(
+        "(type {} {})",
+        record.name().unwrap_or_default(),
+        format_record_elements(record)
+    )
+}
+
+fn format_function_declaration(declaration: &FunctionDeclaration) -> String {
+    let attributes = declaration.attributes();
+
+    format!(
+        "(declare {})",
+        [declaration.name()]
+            .iter()
+            .chain(
+                [
+                    (attributes.no_return, "no-return"),
+                    (attributes.read_only, "read-only"),
+                    (attributes.read_none, "read-none"),
+                ]
+                .iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, name)| name)
+            )
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
+
 fn format_function_definition(definition: &FunctionDefinition) -> String {
     format!(
         "(function {} {}\n{})",
@@ -22,13 +65,38 @@ fn format_function_definition(definition: &FunctionDefinition) -> String {
         definition
             .arguments()
             .iter()
-            .map(|argument| argument.name())
+            .map(format_argument)
             .collect::<Vec<_>>()
             .join(" "),
         indent(&format_block(definition.body())),
     )
 }
 
+fn format_argument(argument: &Argument) -> String {
+    let attributes = argument.attributes();
+    let names = [
+        (attributes.by_value, "by-value"),
+        (attributes.no_alias, "no-alias"),
+        (attributes.non_null, "non-null"),
+        (attributes.struct_return, "struct-return"),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, name)| name.to_string())
+    .chain(
+        attributes
+            .alignment
+            .map(|alignment| format!("(align {})", alignment)),
+    )
+    .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        argument.name().into()
+    } else {
+        format_list(argument.name(), names)
+    }
+}
+
 fn format_block(block: &Block) -> String {
     let instructions = block
         .instructions()
@@ -98,6 +166,14 @@ fn format_instruction(instruction: &Instruction) -> String {
                 .join(" "),
             call.name(),
         ),
+        Instruction::CheckedArithmeticOperation(operation) => format!(
+            "({}checked{} {} {} {})",
+            if operation.signed() { "signed-" } else { "" },
+            format_arithmetic_operator(operation.operator()),
+            format_expression(operation.lhs()),
+            format_expression(operation.rhs()),
+            operation.name(),
+        ),
         Instruction::CompareAndSwap(cas) => {
             format!(
                 "(compare-and-swap {} {} {} {})",
@@ -124,9 +200,22 @@ fn format_instruction(instruction: &Instruction) -> String {
             )
         }
         Instruction::Fence(_) => "(fence)".into(),
+        Instruction::Fma(fma) => format!(
+            "(fma {} {} {} {})",
+            format_expression(fma.lhs()),
+            format_expression(fma.rhs()),
+            format_expression(fma.addend()),
+            fma.name(),
+        ),
         Instruction::FreeHeap(free) => {
             format!("(free-heap {})", format_expression(free.pointer()))
         }
+        Instruction::LifetimeEnd(end) => {
+            format!("(lifetime-end {})", format_expression(end.pointer()))
+        }
+        Instruction::LifetimeStart(start) => {
+            format!("(lifetime-start {})", format_expression(start.pointer()))
+        }
         Instruction::If(if_) => format!(
             "(if {}\n{}\n{}\n{})",
             format_expression(if_.condition()),
@@ -134,6 +223,28 @@ fn format_instruction(instruction: &Instruction) -> String {
             indent(&format_block(if_.else_())),
             indent(if_.name()),
         ),
+        Instruction::InlineAssembly(assembly) => format!(
+            "(inline-assembly{}{} {:?} {:?} {} {})",
+            if assembly.side_effects() {
+                " sideeffect"
+            } else {
+                ""
+            },
+            if assembly.align_stack() {
+                " alignstack"
+            } else {
+                ""
+            },
+            assembly.template(),
+            assembly.constraints(),
+            assembly
+                .operands()
+                .iter()
+                .map(format_expression)
+                .collect::<Vec<_>>()
+                .join(" "),
+            assembly.name(),
+        ),
         Instruction::Load(load) => {
             format!(
                 "(load {} {})",
@@ -141,6 +252,29 @@ fn format_instruction(instruction: &Instruction) -> String {
                 load.name()
             )
         }
+        Instruction::MathOperation(operation) => format!(
+            "({} {} {})",
+            format_math_operator(operation.operator()),
+            format_expression(operation.value()),
+            operation.name(),
+        ),
+        Instruction::MemoryCopy(copy) => format!(
+            "({} {} {} {})",
+            if copy.overlapping() {
+                "memory-move"
+            } else {
+                "memory-copy"
+            },
+            format_expression(copy.source()),
+            format_expression(copy.destination()),
+            format_expression(copy.size()),
+        ),
+        Instruction::MemorySet(set) => format!(
+            "(memory-set {} {} {})",
+            format_expression(set.pointer()),
+            format_expression(set.value()),
+            format_expression(set.size()),
+        ),
         Instruction::PassThrough(pass) => {
             format!(
                 "(pass {} {})",
@@ -156,6 +290,14 @@ fn format_instruction(instruction: &Instruction) -> String {
                 allocate.name()
             )
         }
+        Instruction::SaturatingArithmeticOperation(operation) => format!(
+            "({}saturating{} {} {} {})",
+            if operation.signed() { "signed-" } else { "" },
+            format_arithmetic_operator(operation.operator()),
+            format_expression(operation.lhs()),
+            format_expression(operation.rhs()),
+            operation.name(),
+        ),
         Instruction::Store(store) => {
             format!(
                 "(store {} {})",
@@ -174,27 +316,39 @@ fn format_terminal_instruction(instruction: &TerminalInstruction) -> String {
         TerminalInstruction::Return(return_) => {
             format!("(return {})", format_expression(return_.expression()))
         }
+        TerminalInstruction::Trap => "(trap)".into(),
         TerminalInstruction::Unreachable => "(unreachable)".into(),
     }
 }
 
-fn format_expression(expression: &Expression) -> String {
+pub(crate) fn format_expression(expression: &Expression) -> String {
     match expression {
         Expression::AlignOf(align_of) => format!("(align-of {})", format_type(align_of.type_())),
         Expression::ArithmeticOperation(operation) => format!(
             "({} {} {})",
-            match operation.operator() {
-                ArithmeticOperator::Add => "+",
-                ArithmeticOperator::Subtract => "-",
-                ArithmeticOperator::Multiply => "*",
-                ArithmeticOperator::Divide => "/",
-            },
+            [format_arithmetic_operator(operation.operator())]
+                .iter()
+                .copied()
+                .chain(format_fast_math_flags(operation.fast_math_flags()))
+                .chain(format_overflow_flags(operation.overflow_flags()))
+                .collect::<Vec<_>>()
+                .join(" "),
             format_expression(operation.lhs()),
             format_expression(operation.rhs())
         ),
         Expression::BitCast(bit_cast) => {
             format!("(bit-cast {})", format_expression(bit_cast.expression()))
         }
+        Expression::BitManipulationOperation(operation) => format!(
+            "({} {})",
+            match operation.operator() {
+                BitManipulationOperator::ByteSwap => "bswap",
+                BitManipulationOperator::CountLeadingZeros => "clz",
+                BitManipulationOperator::CountTrailingZeros => "ctz",
+                BitManipulationOperator::PopulationCount => "popcount",
+            },
+            format_expression(operation.value())
+        ),
         Expression::BitwiseNotOperation(not) => {
             format!("(bit! {})", format_expression(not.value()))
         }
@@ -210,6 +364,11 @@ fn format_expression(expression: &Expression) -> String {
                 format_expression(operation.rhs()),
             )
         }
+        Expression::ByteOffset(offset) => format!(
+            "(byte-offset {} {})",
+            format_expression(offset.pointer()),
+            format_expression(offset.offset()),
+        ),
         Expression::ComparisonOperation(operation) => format!(
             "({} {} {})",
             match operation.operator() {
@@ -219,16 +378,44 @@ fn format_expression(expression: &Expression) -> String {
                 ComparisonOperator::LessThanOrEqual => "<=",
                 ComparisonOperator::GreaterThan => ">",
                 ComparisonOperator::GreaterThanOrEqual => ">=",
+                ComparisonOperator::Ordered => "ordered",
+                ComparisonOperator::Unordered => "unordered",
+                ComparisonOperator::UnorderedEqual => "unordered-==",
+                ComparisonOperator::UnorderedNotEqual => "unordered-!=",
+                ComparisonOperator::UnorderedLessThan => "unordered-<",
+                ComparisonOperator::UnorderedLessThanOrEqual => "unordered-<=",
+                ComparisonOperator::UnorderedGreaterThan => "unordered->",
+                ComparisonOperator::UnorderedGreaterThanOrEqual => "unordered->=",
             },
             format_expression(operation.lhs()),
             format_expression(operation.rhs())
         ),
+        Expression::ExtractBits(extract) => format!(
+            "(extract-bits {} {} {})",
+            format_expression(extract.value()),
+            extract.offset(),
+            extract.width(),
+        ),
+        Expression::InsertBits(insert) => format!(
+            "(insert-bits {} {} {} {})",
+            format_expression(insert.value()),
+            format_expression(insert.field()),
+            insert.offset(),
+            insert.width(),
+        ),
         Expression::PointerAddress(address) => format!(
             "(pointer-address {} {})",
             format_expression(address.pointer()),
             format_expression(address.offset()),
         ),
         Expression::Primitive(primitive) => format_primitive(primitive),
+        Expression::PureCall(call) => format_list(
+            "pure-call",
+            vec![call.function()]
+                .into_iter()
+                .chain(call.arguments())
+                .map(format_expression),
+        ),
         Expression::Record(record) => {
             let elements = record
                 .elements()
@@ -251,8 +438,19 @@ fn format_expression(expression: &Expression) -> String {
             format_expression(address.pointer()),
             address.element_index(),
         ),
+        Expression::Select(select) => format!(
+            "(select {} {} {})",
+            format_expression(select.condition()),
+            format_expression(select.then()),
+            format_expression(select.else_()),
+        ),
         Expression::SizeOf(size_of) => format!("(size-of {})", format_type(size_of.type_())),
         Expression::Undefined(_) => "undefined".into(),
+        Expression::AbsoluteAddress(address) => format!(
+            "(absolute-address {} {})",
+            format_type(address.type_()),
+            format_address(address.address()),
+        ),
         Expression::Union(union) => format!(
             "(union {} {})",
             union.member_index(),
@@ -267,37 +465,145 @@ fn format_expression(expression: &Expression) -> String {
     }
 }
 
+pub(crate) fn format_arithmetic_operator(operator: ArithmeticOperator) -> &'static str {
+    match operator {
+        ArithmeticOperator::Add => "+",
+        ArithmeticOperator::Subtract => "-",
+        ArithmeticOperator::Multiply => "*",
+        ArithmeticOperator::Divide => "/",
+    }
+}
+
+fn format_fast_math_flags(flags: FastMathFlags) -> Vec<&'static str> {
+    if flags.is_fast() {
+        return vec!["fast"];
+    }
+
+    [
+        (flags.no_nans, "nnan"),
+        (flags.no_infinities, "ninf"),
+        (flags.no_signed_zeros, "nsz"),
+        (flags.allow_reciprocal, "arcp"),
+        (flags.allow_contraction, "contract"),
+        (flags.allow_reassociation, "reassoc"),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, name)| *name)
+    .collect()
+}
+
+fn format_overflow_flags(flags: OverflowFlags) -> Vec<&'static str> {
+    [
+        (flags.no_signed_wrap, "nsw"),
+        (flags.no_unsigned_wrap, "nuw"),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, name)| *name)
+    .collect()
+}
+
+pub(crate) fn format_math_operator(operator: MathOperator) -> &'static str {
+    match operator {
+        MathOperator::Absolute => "abs",
+        MathOperator::Ceiling => "ceil",
+        MathOperator::Cosine => "cos",
+        MathOperator::Exponential => "exp",
+        MathOperator::Floor => "floor",
+        MathOperator::Logarithm => "log",
+        MathOperator::Sine => "sin",
+        MathOperator::SquareRoot => "sqrt",
+    }
+}
+
 fn format_primitive(primitive: &Primitive) -> String {
     match primitive {
         Primitive::Boolean(boolean) => format!("{}", boolean),
+        Primitive::Float16(number) => format!("{}", number),
         Primitive::Float32(number) => format!("{}", number),
         Primitive::Float64(number) => format!("{}", number),
+        Primitive::Float128(number) => format!("{}", number),
         Primitive::Integer8(number) => format!("{}", number),
+        Primitive::Integer16(number) => format!("{}", number),
         Primitive::Integer32(number) => format!("{}", number),
         Primitive::Integer64(number) => format!("{}", number),
+        Primitive::Integer128(number) => format!("{}", number),
         Primitive::PointerInteger(number) => format!("{}", number),
     }
 }
 
-fn format_type(type_: &Type) -> String {
+fn format_address(primitive: Primitive) -> String {
+    match primitive {
+        Primitive::Integer8(number) => format!("{:#x}", number),
+        Primitive::Integer16(number) => format!("{:#x}", number),
+        Primitive::Integer32(number) => format!("{:#x}", number),
+        Primitive::Integer64(number) => format!("{:#x}", number),
+        Primitive::Integer128(number) => format!("{:#x}", number),
+        Primitive::PointerInteger(number) => format!("{:#x}", number),
+        _ => format_primitive(&primitive),
+    }
+}
+
+pub(crate) fn format_type(type_: &Type) -> String {
     match type_ {
-        Type::Function(_) => "function".into(),
+        Type::Function(function) => format!(
+            "(function ({}) {} {})",
+            function
+                .arguments()
+                .iter()
+                .map(format_type)
+                .collect::<Vec<_>>()
+                .join(" "),
+            format_type(function.result()),
+            match function.calling_convention() {
+                types::CallingConvention::Source => "source",
+                types::CallingConvention::Tail => "tail",
+                types::CallingConvention::Target => "target",
+            }
+        ),
+        Type::Opaque(opaque) => format!("(opaque {})", opaque.name()),
         Type::Primitive(primitive) => match primitive {
             types::Primitive::Boolean => "boolean",
             types::Primitive::Integer8 => "integer8",
+            types::Primitive::Integer16 => "integer16",
             types::Primitive::Integer32 => "integer32",
             types::Primitive::Integer64 => "integer64",
+            types::Primitive::Integer128 => "integer128",
+            types::Primitive::Float16 => "float16",
             types::Primitive::Float32 => "float32",
             types::Primitive::Float64 => "float64",
+            types::Primitive::Float128 => "float128",
             types::Primitive::PointerInteger => "pointer-integer",
         }
         .into(),
-        Type::Pointer(pointer) => format!("(pointer {})", format_type(pointer.element())),
-        Type::Record(_) => "record".into(),
+        Type::Pointer(pointer) => {
+            if let Some(length) = pointer.length() {
+                format!("(pointer {} {})", format_type(pointer.element()), length)
+            } else {
+                format!("(pointer {})", format_type(pointer.element()))
+            }
+        }
+        Type::Record(record) => record.name().unwrap_or("record").into(),
         Type::Union(_) => "union".into(),
     }
 }
 
+fn format_record_elements(record: &types::Record) -> String {
+    format_list("record", record.elements().iter().map(format_type))
+}
+
+fn format_list(head: &str, elements: impl IntoIterator<Item = String>) -> String {
+    format!(
+        "({})",
+        vec![head.into()]
+            .into_iter()
+            .chain(elements)
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
+
 fn indent(string: &str) -> String {
     regex::Regex::new("^|\n")
         .unwrap()
@@ -416,6 +722,250 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn format_module_with_inline_assembly() {
+        insta::assert_snapshot!(format_module(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![InlineAssembly::new(
+                        types::Primitive::PointerInteger,
+                        "mov $1, $0",
+                        "=r,r",
+                        vec![Variable::new("x").into()],
+                        true,
+                        true,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                types::Primitive::PointerInteger,
+                types::CallingConvention::Source,
+                Linkage::Internal
+            )],
+        )));
+    }
+
+    #[test]
+    fn format_ordered_and_unordered_comparisons() {
+        let format = |operator| {
+            format_expression(
+                &ComparisonOperation::new(
+                    types::Primitive::Float64,
+                    operator,
+                    Variable::new("x"),
+                    Variable::new("y"),
+                )
+                .into(),
+            )
+        };
+
+        assert_eq!(format(ComparisonOperator::Equal), "(== x y)");
+        assert_eq!(
+            format(ComparisonOperator::UnorderedEqual),
+            "(unordered-== x y)"
+        );
+        assert_eq!(format(ComparisonOperator::Unordered), "(unordered x y)");
+    }
+
+    #[test]
+    fn format_argument_with_attributes() {
+        assert_eq!(
+            format_argument(&Argument::with_attributes(
+                "x",
+                types::Pointer::new(types::Primitive::PointerInteger),
+                ArgumentAttributes {
+                    alignment: Some(8),
+                    no_alias: true,
+                    struct_return: true,
+                    ..Default::default()
+                },
+            )),
+            "(x no-alias struct-return (align 8))"
+        );
+    }
+
+    #[test]
+    fn format_argument_without_attributes() {
+        assert_eq!(
+            format_argument(&Argument::new("x", types::Primitive::PointerInteger)),
+            "x"
+        );
+    }
+
+    #[test]
+    fn format_function_declaration_with_no_return() {
+        assert_eq!(
+            format_function_declaration(&FunctionDeclaration::with_attributes(
+                "f",
+                types::Function::new(
+                    vec![],
+                    types::Primitive::Boolean,
+                    types::CallingConvention::Target
+                ),
+                FunctionAttributes {
+                    no_return: true,
+                    ..Default::default()
+                },
+            )),
+            "(declare f no-return)"
+        );
+    }
+
+    #[test]
+    fn format_function_declaration_with_purity() {
+        assert_eq!(
+            format_function_declaration(&FunctionDeclaration::with_attributes(
+                "f",
+                types::Function::new(
+                    vec![],
+                    types::Primitive::Boolean,
+                    types::CallingConvention::Target
+                ),
+                FunctionAttributes {
+                    read_none: true,
+                    ..Default::default()
+                },
+            )),
+            "(declare f read-none)"
+        );
+    }
+
+    #[test]
+    fn format_arithmetic_operation_with_fast_math_flags() {
+        assert_eq!(
+            format_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::Float64,
+                    ArithmeticOperator::Add,
+                    Variable::new("x"),
+                    Variable::new("y"),
+                )
+                .with_fast_math_flags(FastMathFlags {
+                    no_nans: true,
+                    allow_reassociation: true,
+                    ..Default::default()
+                })
+                .into()
+            ),
+            "(+ nnan reassoc x y)"
+        );
+    }
+
+    #[test]
+    fn format_arithmetic_operation_with_fast_flag() {
+        assert_eq!(
+            format_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::Float64,
+                    ArithmeticOperator::Multiply,
+                    Variable::new("x"),
+                    Variable::new("y"),
+                )
+                .with_fast_math_flags(FastMathFlags::fast())
+                .into()
+            ),
+            "(* fast x y)"
+        );
+    }
+
+    #[test]
+    fn format_arithmetic_operation_with_overflow_flags() {
+        assert_eq!(
+            format_expression(
+                &ArithmeticOperation::new(
+                    types::Primitive::Integer32,
+                    ArithmeticOperator::Add,
+                    Variable::new("x"),
+                    Variable::new("y"),
+                )
+                .with_overflow_flags(OverflowFlags {
+                    no_signed_wrap: true,
+                    no_unsigned_wrap: true,
+                })
+                .into()
+            ),
+            "(+ nsw nuw x y)"
+        );
+    }
+
+    fn create_module_with_record_type(element_type: types::Record) -> Module {
+        let type_ = types::Record::named(
+            "bar",
+            vec![
+                element_type.clone().into(),
+                types::Pointer::new(element_type).into(),
+            ],
+        );
+
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![],
+                Block::new(
+                    vec![],
+                    Return::new(types::Primitive::PointerInteger, SizeOf::new(type_)),
+                ),
+                types::Primitive::PointerInteger,
+                types::CallingConvention::Source,
+                Linkage::Internal,
+            )],
+        )
+    }
+
+    #[test]
+    fn format_module_with_anonymous_record_type() {
+        insta::assert_snapshot!(format_module(&create_module_with_record_type(
+            types::Record::new(vec![
+                types::Primitive::Float64.into(),
+                types::Union::new(vec![
+                    types::Primitive::Integer8.into(),
+                    types::Primitive::Integer64.into()
+                ])
+                .into()
+            ])
+        )));
+    }
+
+    #[test]
+    fn format_module_with_named_record_type() {
+        insta::assert_snapshot!(format_module(&create_module_with_record_type(
+            types::Record::named(
+                "foo",
+                vec![
+                    types::Primitive::Float64.into(),
+                    types::Union::new(vec![
+                        types::Primitive::Integer8.into(),
+                        types::Primitive::Integer64.into()
+                    ])
+                    .into()
+                ]
+            )
+        )));
+    }
+
+    #[test]
+    fn format_absolute_address() {
+        assert_eq!(
+            format_expression(
+                &AbsoluteAddress::new(
+                    types::Pointer::new(types::Primitive::Integer16),
+                    Primitive::PointerInteger(0xB8000)
+                )
+                .into()
+            ),
+            "(absolute-address (pointer integer16) 0xb8000)"
+        );
+    }
+
     #[test]
     fn format_record_without_any_element() {
         assert_eq!(
@@ -424,6 +974,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn format_pure_call() {
+        assert_eq!(
+            format_expression(
+                &PureCall::new(
+                    types::Function::new(
+                        vec![types::Primitive::PointerInteger.into()],
+                        types::Primitive::PointerInteger,
+                        types::CallingConvention::Target,
+                    ),
+                    Variable::new("f"),
+                    vec![Primitive::PointerInteger(42).into()],
+                )
+                .into()
+            ),
+            "(pure-call f 42)"
+        );
+    }
+
+    #[test]
+    fn format_empty_record_type() {
+        assert_eq!(
+            format_record_definition(&types::Record::named("foo", vec![])),
+            "(type foo (record))"
+        );
+    }
+
+    #[test]
+    fn format_function_type() {
+        assert_eq!(
+            format_type(
+                &types::Function::new(
+                    vec![
+                        types::Primitive::Integer8.into(),
+                        types::Primitive::Float64.into()
+                    ],
+                    types::Primitive::Boolean,
+                    types::CallingConvention::Tail
+                )
+                .into()
+            ),
+            "(function (integer8 float64) boolean tail)"
+        );
+    }
+
     #[test]
     fn format_record_with_element() {
         assert_eq!(
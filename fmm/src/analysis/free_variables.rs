@@ -24,7 +24,7 @@ fn collect_from_block(block: &Block) -> HashSet<String> {
     collect_free_variables(block.instructions(), block.terminal_instruction())
 }
 
-fn collect_from_instruction(instruction: &Instruction) -> HashSet<String> {
+pub fn collect_from_instruction(instruction: &Instruction) -> HashSet<String> {
     match instruction {
         Instruction::AllocateHeap(allocate) => collect_from_expression(allocate.size()),
         Instruction::AtomicLoad(load) => collect_from_expression(load.pointer()),
@@ -40,6 +40,10 @@ fn collect_from_instruction(instruction: &Instruction) -> HashSet<String> {
             .into_iter()
             .chain(call.arguments().iter().flat_map(collect_from_expression))
             .collect(),
+        Instruction::CheckedArithmeticOperation(operation) => [operation.lhs(), operation.rhs()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(expression))
+            .collect(),
         Instruction::CompareAndSwap(cas) => [cas.pointer(), cas.old_value(), cas.new_value()]
             .iter()
             .flat_map(|expression| collect_from_expression(*expression))
@@ -48,7 +52,13 @@ fn collect_from_instruction(instruction: &Instruction) -> HashSet<String> {
             collect_from_expression(deconstruct.record())
         }
         Instruction::DeconstructUnion(deconstruct) => collect_from_expression(deconstruct.union()),
+        Instruction::Fma(fma) => [fma.lhs(), fma.rhs(), fma.addend()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(expression))
+            .collect(),
         Instruction::FreeHeap(free) => collect_from_expression(free.pointer()),
+        Instruction::LifetimeEnd(end) => collect_from_expression(end.pointer()),
+        Instruction::LifetimeStart(start) => collect_from_expression(start.pointer()),
         Instruction::If(if_) => vec![
             collect_from_expression(if_.condition()),
             collect_from_block(if_.then()),
@@ -57,12 +67,30 @@ fn collect_from_instruction(instruction: &Instruction) -> HashSet<String> {
         .into_iter()
         .flatten()
         .collect(),
+        Instruction::InlineAssembly(assembly) => assembly
+            .operands()
+            .iter()
+            .flat_map(collect_from_expression)
+            .collect(),
         Instruction::Load(load) => collect_from_expression(load.pointer()),
+        Instruction::MathOperation(operation) => collect_from_expression(operation.value()),
+        Instruction::MemoryCopy(copy) => [copy.source(), copy.destination(), copy.size()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(expression))
+            .collect(),
+        Instruction::MemorySet(set) => [set.pointer(), set.value(), set.size()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(expression))
+            .collect(),
         Instruction::PassThrough(pass) => collect_from_expression(pass.expression()),
         Instruction::ReallocateHeap(reallocate) => [reallocate.pointer(), reallocate.size()]
             .iter()
             .flat_map(|expression| collect_from_expression(*expression))
             .collect(),
+        Instruction::SaturatingArithmeticOperation(operation) => [operation.lhs(), operation.rhs()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(expression))
+            .collect(),
         Instruction::Store(store) => [store.value(), store.pointer()]
             .iter()
             .flat_map(|expression| collect_from_expression(*expression))
@@ -76,40 +104,62 @@ fn collect_from_terminal_instruction(instruction: &TerminalInstruction) -> HashS
     match instruction {
         TerminalInstruction::Branch(branch) => collect_from_expression(branch.expression()),
         TerminalInstruction::Return(return_) => collect_from_expression(return_.expression()),
-        TerminalInstruction::Unreachable => HashSet::new(),
+        TerminalInstruction::Trap | TerminalInstruction::Unreachable => HashSet::new(),
     }
 }
 
-fn collect_from_expression(expression: &Expression) -> HashSet<String> {
+pub fn collect_from_expression(expression: &Expression) -> HashSet<String> {
     match expression {
         Expression::ArithmeticOperation(operation) => [operation.lhs(), operation.rhs()]
             .iter()
             .flat_map(|expression| collect_from_expression(*expression))
             .collect(),
         Expression::BitCast(bit_cast) => collect_from_expression(bit_cast.expression()),
+        Expression::BitManipulationOperation(operation) => {
+            collect_from_expression(operation.value())
+        }
         Expression::BitwiseNotOperation(operation) => collect_from_expression(operation.value()),
         Expression::BitwiseOperation(operation) => collect_from_expression(operation.lhs())
             .into_iter()
             .chain(collect_from_expression(operation.rhs()))
             .collect(),
+        Expression::ByteOffset(offset) => [offset.pointer(), offset.offset()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(*expression))
+            .collect(),
         Expression::ComparisonOperation(operation) => [operation.lhs(), operation.rhs()]
             .iter()
             .flat_map(|expression| collect_from_expression(*expression))
             .collect(),
+        Expression::ExtractBits(extract) => collect_from_expression(extract.value()),
+        Expression::InsertBits(insert) => collect_from_expression(insert.value())
+            .into_iter()
+            .chain(collect_from_expression(insert.field()))
+            .collect(),
         Expression::PointerAddress(address) => [address.pointer(), address.offset()]
             .iter()
             .flat_map(|expression| collect_from_expression(*expression))
             .collect(),
+        Expression::PureCall(call) => collect_from_expression(call.function())
+            .into_iter()
+            .chain(call.arguments().iter().flat_map(collect_from_expression))
+            .collect(),
         Expression::Record(record) => record
             .elements()
             .iter()
             .flat_map(collect_from_expression)
             .collect(),
         Expression::RecordAddress(address) => collect_from_expression(address.pointer()),
+        Expression::Select(select) => collect_from_expression(select.condition())
+            .into_iter()
+            .chain(collect_from_expression(select.then()))
+            .chain(collect_from_expression(select.else_()))
+            .collect(),
         Expression::Union(union) => collect_from_expression(union.member()),
         Expression::UnionAddress(address) => collect_from_expression(address.pointer()),
         Expression::Variable(variable) => vec![variable.name().into()].into_iter().collect(),
-        Expression::AlignOf(_)
+        Expression::AbsoluteAddress(_)
+        | Expression::AlignOf(_)
         | Expression::Primitive(_)
         | Expression::SizeOf(_)
         | Expression::Undefined(_) => Default::default(),
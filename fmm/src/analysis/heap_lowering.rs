@@ -0,0 +1,416 @@
+mod error;
+
+use super::renaming::create_name_generator;
+use crate::{
+    ir::*,
+    types::{self, GENERIC_POINTER_TYPE},
+};
+pub use error::*;
+
+pub enum HeapAllocationStrategy {
+    Malloc,
+    BumpPointer(BumpPointerConfiguration),
+}
+
+// Bump pointers and limits are global variables of pointer integers. Slow path
+// functions receive sizes and return new allocations. Alignments must be powers
+// of two.
+pub struct BumpPointerConfiguration {
+    pub pointer_variable_name: String,
+    pub limit_variable_name: String,
+    pub slow_path_function_name: String,
+    pub alignment: u64,
+}
+
+struct Context<'a, G: FnMut() -> String> {
+    configuration: &'a BumpPointerConfiguration,
+    generate_name: G,
+}
+
+// Heap allocations are lowered into inline fast paths of bump allocators which
+// fall back to calls to slow path functions.
+pub fn lower_heap_allocations(
+    module: &Module,
+    strategy: &HeapAllocationStrategy,
+) -> Result<Module, HeapLoweringError> {
+    let configuration = match strategy {
+        HeapAllocationStrategy::Malloc => return Ok(module.clone()),
+        HeapAllocationStrategy::BumpPointer(configuration) => configuration,
+    };
+
+    if !configuration.alignment.is_power_of_two() {
+        return Err(HeapLoweringError::InvalidAlignment(configuration.alignment));
+    }
+
+    let mut context = Context {
+        configuration,
+        generate_name: create_name_generator(module, "_bump_"),
+    };
+
+    let function_definitions = module
+        .function_definitions()
+        .iter()
+        .map(|definition| {
+            FunctionDefinition::new(
+                definition.name(),
+                definition.arguments().to_vec(),
+                lower_block(&mut context, definition.body()),
+                definition.result_type().clone(),
+                definition.calling_convention(),
+                definition.linkage(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let is_declared = |name: &str| {
+        module
+            .variable_declarations()
+            .iter()
+            .any(|declaration| declaration.name() == name)
+            || module
+                .variable_definitions()
+                .iter()
+                .any(|definition| definition.name() == name)
+    };
+    let is_function_declared = |name: &str| {
+        module
+            .function_declarations()
+            .iter()
+            .any(|declaration| declaration.name() == name)
+            || module
+                .function_definitions()
+                .iter()
+                .any(|definition| definition.name() == name)
+    };
+
+    Ok(Module::new(
+        module
+            .variable_declarations()
+            .iter()
+            .cloned()
+            .chain(
+                [
+                    &configuration.pointer_variable_name,
+                    &configuration.limit_variable_name,
+                ]
+                .iter()
+                .filter(|name| !is_declared(name))
+                .map(|name| VariableDeclaration::new(*name, types::Primitive::PointerInteger)),
+            )
+            .collect(),
+        module
+            .function_declarations()
+            .iter()
+            .cloned()
+            .chain(
+                if is_function_declared(&configuration.slow_path_function_name) {
+                    None
+                } else {
+                    Some(FunctionDeclaration::new(
+                        &configuration.slow_path_function_name,
+                        slow_path_function_type(),
+                    ))
+                },
+            )
+            .collect(),
+        module.variable_definitions().to_vec(),
+        function_definitions,
+    ))
+}
+
+fn lower_block(context: &mut Context<impl FnMut() -> String>, block: &Block) -> Block {
+    Block::new(
+        block
+            .instructions()
+            .iter()
+            .flat_map(|instruction| lower_instruction(context, instruction))
+            .collect(),
+        block.terminal_instruction().clone(),
+    )
+}
+
+fn lower_instruction(
+    context: &mut Context<impl FnMut() -> String>,
+    instruction: &Instruction,
+) -> Vec<Instruction> {
+    match instruction {
+        Instruction::AllocateHeap(allocate) => lower_allocation(context, allocate),
+        Instruction::If(if_) => vec![If::new(
+            if_.type_().clone(),
+            if_.condition().clone(),
+            lower_block(context, if_.then()),
+            lower_block(context, if_.else_()),
+            if_.name(),
+        )
+        .into()],
+        _ => vec![instruction.clone()],
+    }
+}
+
+fn lower_allocation(
+    context: &mut Context<impl FnMut() -> String>,
+    allocate: &AllocateHeap,
+) -> Vec<Instruction> {
+    let configuration = context.configuration;
+    let size = (context.generate_name)();
+    let pointer = (context.generate_name)();
+    let limit = (context.generate_name)();
+    let aligned_size = (context.generate_name)();
+    let call = (context.generate_name)();
+    let alignment = Primitive::PointerInteger(configuration.alignment as i64 - 1);
+
+    vec![
+        PassThrough::new(
+            types::Primitive::PointerInteger,
+            allocate.size().clone(),
+            &size,
+        )
+        .into(),
+        Load::new(
+            types::Primitive::PointerInteger,
+            Variable::new(&configuration.pointer_variable_name),
+            &pointer,
+        )
+        .into(),
+        Load::new(
+            types::Primitive::PointerInteger,
+            Variable::new(&configuration.limit_variable_name),
+            &limit,
+        )
+        .into(),
+        // Sizes are rounded up to alignments.
+        PassThrough::new(
+            types::Primitive::PointerInteger,
+            BitwiseOperation::new(
+                types::Primitive::PointerInteger,
+                BitwiseOperator::And,
+                ArithmeticOperation::new(
+                    types::Primitive::PointerInteger,
+                    ArithmeticOperator::Add,
+                    Variable::new(&size),
+                    alignment,
+                ),
+                BitwiseNotOperation::new(types::Primitive::PointerInteger, alignment),
+            ),
+            &aligned_size,
+        )
+        .into(),
+        // Sizes are compared with remaining spaces rather than bumped pointers
+        // with limits so that nothing wraps around. Rounded sizes smaller than
+        // original ones have wrapped around.
+        If::new(
+            GENERIC_POINTER_TYPE.clone(),
+            Select::new(
+                types::Primitive::Boolean,
+                ComparisonOperation::new(
+                    types::Primitive::PointerInteger,
+                    ComparisonOperator::GreaterThanOrEqual,
+                    Variable::new(&aligned_size),
+                    Variable::new(&size),
+                ),
+                ComparisonOperation::new(
+                    types::Primitive::PointerInteger,
+                    ComparisonOperator::LessThanOrEqual,
+                    Variable::new(&aligned_size),
+                    ArithmeticOperation::new(
+                        types::Primitive::PointerInteger,
+                        ArithmeticOperator::Subtract,
+                        Variable::new(&limit),
+                        Variable::new(&pointer),
+                    ),
+                ),
+                Primitive::Boolean(false),
+            ),
+            Block::new(
+                vec![Store::new(
+                    types::Primitive::PointerInteger,
+                    ArithmeticOperation::new(
+                        types::Primitive::PointerInteger,
+                        ArithmeticOperator::Add,
+                        Variable::new(&pointer),
+                        Variable::new(&aligned_size),
+                    ),
+                    Variable::new(&configuration.pointer_variable_name),
+                )
+                .into()],
+                Branch::new(
+                    GENERIC_POINTER_TYPE.clone(),
+                    BitCast::new(
+                        types::Primitive::PointerInteger,
+                        GENERIC_POINTER_TYPE.clone(),
+                        Variable::new(&pointer),
+                    ),
+                ),
+            ),
+            Block::new(
+                vec![Call::new(
+                    slow_path_function_type(),
+                    Variable::new(&configuration.slow_path_function_name),
+                    vec![Variable::new(&size).into()],
+                    &call,
+                )
+                .into()],
+                Branch::new(GENERIC_POINTER_TYPE.clone(), Variable::new(&call)),
+            ),
+            allocate.name(),
+        )
+        .into(),
+    ]
+}
+
+fn slow_path_function_type() -> types::Function {
+    types::Function::new(
+        vec![types::Primitive::PointerInteger.into()],
+        GENERIC_POINTER_TYPE.clone(),
+        types::CallingConvention::Target,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::check_types;
+
+    fn create_strategy(alignment: u64) -> HeapAllocationStrategy {
+        HeapAllocationStrategy::BumpPointer(BumpPointerConfiguration {
+            pointer_variable_name: "heap_pointer".into(),
+            limit_variable_name: "heap_limit".into(),
+            slow_path_function_name: "collect".into(),
+            alignment,
+        })
+    }
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("n", types::Primitive::PointerInteger)],
+                Block::new(
+                    instructions,
+                    Return::new(GENERIC_POINTER_TYPE.clone(), Variable::new("x")),
+                ),
+                GENERIC_POINTER_TYPE.clone(),
+                types::CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    #[test]
+    fn lower_allocation_into_fast_path() {
+        let module = lower_heap_allocations(
+            &create_module(vec![AllocateHeap::new(Variable::new("n"), "x").into()]),
+            &create_strategy(8),
+        )
+        .unwrap();
+
+        check_types(&module).unwrap();
+
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert!(matches!(
+            &instructions[1],
+            Instruction::Load(load) if load.pointer() == &Variable::new("heap_pointer").into()
+        ));
+        assert!(matches!(
+            &instructions[2],
+            Instruction::Load(load) if load.pointer() == &Variable::new("heap_limit").into()
+        ));
+
+        let if_ = match &instructions[4] {
+            Instruction::If(if_) => if_,
+            _ => unreachable!(),
+        };
+
+        assert!(matches!(
+            if_.condition(),
+            Expression::Select(select) if matches!(
+                select.then(),
+                Expression::ComparisonOperation(operation)
+                    if operation.operator() == ComparisonOperator::LessThanOrEqual
+            )
+        ));
+        assert!(matches!(
+            &if_.then().instructions()[0],
+            Instruction::Store(store) if store.pointer() == &Variable::new("heap_pointer").into()
+        ));
+        assert!(matches!(
+            &if_.else_().instructions()[0],
+            Instruction::Call(call) if call.function() == &Variable::new("collect").into()
+        ));
+        assert_eq!(if_.name(), "x");
+    }
+
+    #[test]
+    fn generate_names_not_colliding_with_existing_ones() {
+        let module = lower_heap_allocations(
+            &create_module(vec![
+                PassThrough::new(
+                    types::Primitive::PointerInteger,
+                    Variable::new("n"),
+                    "_bump_0",
+                )
+                .into(),
+                AllocateHeap::new(Variable::new("_bump_0"), "x").into(),
+            ]),
+            &create_strategy(8),
+        )
+        .unwrap();
+
+        check_types(&module).unwrap();
+
+        assert_eq!(
+            module.function_definitions()[0].body().instructions()[1].name(),
+            Some("_bump_1")
+        );
+    }
+
+    #[test]
+    fn declare_bump_pointer_variables_and_slow_path_function() {
+        let module = lower_heap_allocations(
+            &create_module(vec![AllocateHeap::new(Variable::new("n"), "x").into()]),
+            &create_strategy(8),
+        )
+        .unwrap();
+
+        assert_eq!(
+            module.variable_declarations(),
+            &[
+                VariableDeclaration::new("heap_pointer", types::Primitive::PointerInteger),
+                VariableDeclaration::new("heap_limit", types::Primitive::PointerInteger),
+            ]
+        );
+        assert_eq!(
+            module.function_declarations(),
+            &[FunctionDeclaration::new(
+                "collect",
+                slow_path_function_type()
+            )]
+        );
+    }
+
+    #[test]
+    fn keep_allocation_with_malloc_strategy() {
+        let module = create_module(vec![AllocateHeap::new(Variable::new("n"), "x").into()]);
+
+        assert_eq!(
+            lower_heap_allocations(&module, &HeapAllocationStrategy::Malloc),
+            Ok(module)
+        );
+    }
+
+    #[test]
+    fn fail_to_lower_allocation_with_invalid_alignment() {
+        let module = create_module(vec![AllocateHeap::new(Variable::new("n"), "x").into()]);
+
+        for alignment in [0, 3, 12] {
+            assert_eq!(
+                lower_heap_allocations(&module, &create_strategy(alignment)),
+                Err(HeapLoweringError::InvalidAlignment(alignment))
+            );
+        }
+    }
+}
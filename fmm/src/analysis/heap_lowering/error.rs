@@ -0,0 +1,21 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeapLoweringError {
+    InvalidAlignment(u64),
+}
+
+impl Display for HeapLoweringError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidAlignment(alignment) => {
+                write!(formatter, "invalid heap alignment: {}", alignment)
+            }
+        }
+    }
+}
+
+impl Error for HeapLoweringError {}
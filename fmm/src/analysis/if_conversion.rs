@@ -0,0 +1,521 @@
+use super::{
+    cost::block_cost,
+    expression_conversion::{
+        convert_expressions_in_expression, convert_expressions_in_instruction,
+    },
+    renaming::{create_name_generator, rename_bindings_in_block},
+    target_data_layout::TargetDataLayout,
+};
+use crate::{ir::*, types};
+use std::cell::Cell;
+
+// `If` instructions whose arms are cheap and free of side effects are converted
+// into `Select` expressions so that both arms are computed without branches.
+pub fn convert_ifs_to_selects(
+    module: &Module,
+    layout: &TargetDataLayout,
+    cost_budget: u32,
+) -> Module {
+    let mut generate_name = create_name_generator(module, "_if_");
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    convert_block(definition.body(), layout, cost_budget, &mut generate_name),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn convert_block(
+    block: &Block,
+    layout: &TargetDataLayout,
+    cost_budget: u32,
+    generate_name: &mut impl FnMut() -> String,
+) -> Block {
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        if let Instruction::If(if_) = instruction {
+            // Inner `If` instructions are converted first so that nested ones
+            // can be flattened together.
+            let if_ = If::new(
+                if_.type_().clone(),
+                if_.condition().clone(),
+                convert_block(if_.then(), layout, cost_budget, generate_name),
+                convert_block(if_.else_(), layout, cost_budget, generate_name),
+                if_.name(),
+            );
+
+            if let Some(converted) = convert_if(&if_, layout, cost_budget, generate_name) {
+                instructions.extend(converted);
+            } else {
+                instructions.push(if_.into());
+            }
+        } else {
+            instructions.push(instruction.clone());
+        }
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn convert_if(
+    if_: &If,
+    layout: &TargetDataLayout,
+    cost_budget: u32,
+    generate_name: &mut impl FnMut() -> String,
+) -> Option<Vec<Instruction>> {
+    if_.then().terminal_instruction().to_branch()?;
+    if_.else_().terminal_instruction().to_branch()?;
+
+    if !is_speculatable(if_.then())
+        || !is_speculatable(if_.else_())
+        || block_cost(if_.then(), layout) + block_cost(if_.else_(), layout) > cost_budget
+    {
+        return None;
+    }
+
+    // Bindings in arms are renamed so that they shadow neither names in the
+    // enclosing block nor ones in the other arm after hoisting.
+    let then_block = rename_bindings_in_block(if_.then(), generate_name);
+    let else_block = rename_bindings_in_block(if_.else_(), generate_name);
+    let then = then_block.terminal_instruction().to_branch()?;
+    let else_ = else_block.terminal_instruction().to_branch()?;
+
+    Some(
+        then_block
+            .instructions()
+            .iter()
+            .chain(else_block.instructions())
+            .map(|instruction| {
+                convert_expressions_in_instruction(instruction, &clear_overflow_flags)
+            })
+            .chain([PassThrough::new(
+                if_.type_().clone(),
+                Select::new(
+                    if_.type_().clone(),
+                    if_.condition().clone(),
+                    convert_expressions_in_expression(then.expression(), &clear_overflow_flags),
+                    convert_expressions_in_expression(else_.expression(), &clear_overflow_flags),
+                ),
+                if_.name(),
+            )
+            .into()])
+            .collect(),
+    )
+}
+
+// Instructions in arms are executed unconditionally after conversion. So they
+// must neither have side effects nor trap.
+fn is_speculatable(block: &Block) -> bool {
+    block.instructions().iter().all(|instruction| {
+        matches!(
+            instruction,
+            Instruction::CheckedArithmeticOperation(_)
+                | Instruction::DeconstructRecord(_)
+                | Instruction::DeconstructUnion(_)
+                | Instruction::Fma(_)
+                | Instruction::MathOperation(_)
+                | Instruction::PassThrough(_)
+                | Instruction::SaturatingArithmeticOperation(_)
+        ) && !contains_integer_division(instruction)
+    })
+}
+
+// Overflow of speculated operations must not be undefined behavior because
+// they might not be executed in original programs.
+fn clear_overflow_flags(expression: &Expression) -> Expression {
+    if let Expression::ArithmeticOperation(operation) = expression {
+        operation
+            .clone()
+            .with_overflow_flags(Default::default())
+            .into()
+    } else {
+        expression.clone()
+    }
+}
+
+fn contains_integer_division(instruction: &Instruction) -> bool {
+    let found = Cell::new(false);
+
+    convert_expressions_in_instruction(instruction, &|expression| {
+        if let Expression::ArithmeticOperation(operation) = expression {
+            if operation.operator() == ArithmeticOperator::Divide
+                && !matches!(
+                    operation.type_(),
+                    types::Primitive::Float16
+                        | types::Primitive::Float32
+                        | types::Primitive::Float64
+                        | types::Primitive::Float128
+                )
+            {
+                found.set(true);
+            }
+        }
+
+        expression.clone()
+    });
+
+    found.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CallingConvention;
+
+    const LAYOUT: TargetDataLayout = TargetDataLayout::new(8, 8);
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("p", types::Pointer::new(types::Primitive::Integer64)),
+                    Argument::new("c", types::Primitive::Boolean),
+                    Argument::new("x", types::Primitive::Integer64),
+                ],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::Integer64, Variable::new("z")),
+                ),
+                types::Primitive::Integer64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_addition(name: &str) -> Instruction {
+        PassThrough::new(
+            types::Primitive::Integer64,
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Variable::new("x"),
+                Primitive::Integer64(1),
+            ),
+            name,
+        )
+        .into()
+    }
+
+    fn convert(module: &Module) -> Module {
+        convert_ifs_to_selects(module, &LAYOUT, 10)
+    }
+
+    #[test]
+    fn convert_if_selecting_values() {
+        assert_eq!(
+            convert(&create_module(vec![If::new(
+                types::Primitive::Integer64,
+                Variable::new("c"),
+                Block::new(
+                    vec![create_addition("y")],
+                    Branch::new(types::Primitive::Integer64, Variable::new("y")),
+                ),
+                Block::new(
+                    vec![],
+                    Branch::new(types::Primitive::Integer64, Variable::new("x")),
+                ),
+                "z",
+            )
+            .into()])),
+            create_module(vec![
+                create_addition("_if_0"),
+                PassThrough::new(
+                    types::Primitive::Integer64,
+                    Select::new(
+                        types::Primitive::Integer64,
+                        Variable::new("c"),
+                        Variable::new("_if_0"),
+                        Variable::new("x"),
+                    ),
+                    "z",
+                )
+                .into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn rename_shadowing_binding_in_arm() {
+        assert_eq!(
+            convert(&create_module(vec![
+                If::new(
+                    types::Primitive::Integer64,
+                    Variable::new("c"),
+                    Block::new(
+                        vec![PassThrough::new(
+                            types::Primitive::Integer64,
+                            Primitive::Integer64(2),
+                            "x",
+                        )
+                        .into()],
+                        Branch::new(types::Primitive::Integer64, Variable::new("x")),
+                    ),
+                    Block::new(
+                        vec![],
+                        Branch::new(types::Primitive::Integer64, Variable::new("x")),
+                    ),
+                    "y",
+                )
+                .into(),
+                PassThrough::new(types::Primitive::Integer64, Variable::new("x"), "z").into(),
+            ])),
+            create_module(vec![
+                PassThrough::new(
+                    types::Primitive::Integer64,
+                    Primitive::Integer64(2),
+                    "_if_0",
+                )
+                .into(),
+                PassThrough::new(
+                    types::Primitive::Integer64,
+                    Select::new(
+                        types::Primitive::Integer64,
+                        Variable::new("c"),
+                        Variable::new("_if_0"),
+                        Variable::new("x"),
+                    ),
+                    "y",
+                )
+                .into(),
+                PassThrough::new(types::Primitive::Integer64, Variable::new("x"), "z").into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn clear_overflow_flags_of_speculated_operations() {
+        let create_flagged_addition = |name| -> Instruction {
+            PassThrough::new(
+                types::Primitive::Integer64,
+                ArithmeticOperation::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperator::Add,
+                    Variable::new("x"),
+                    Primitive::Integer64(1),
+                )
+                .with_overflow_flags(OverflowFlags {
+                    no_signed_wrap: true,
+                    no_unsigned_wrap: false,
+                }),
+                name,
+            )
+            .into()
+        };
+
+        assert_eq!(
+            convert(&create_module(vec![If::new(
+                types::Primitive::Integer64,
+                Variable::new("c"),
+                Block::new(
+                    vec![create_flagged_addition("y")],
+                    Branch::new(types::Primitive::Integer64, Variable::new("y")),
+                ),
+                Block::new(
+                    vec![],
+                    Branch::new(types::Primitive::Integer64, Variable::new("x")),
+                ),
+                "z",
+            )
+            .into()])),
+            create_module(vec![
+                create_addition("_if_0"),
+                PassThrough::new(
+                    types::Primitive::Integer64,
+                    Select::new(
+                        types::Primitive::Integer64,
+                        Variable::new("c"),
+                        Variable::new("_if_0"),
+                        Variable::new("x"),
+                    ),
+                    "z",
+                )
+                .into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn do_not_convert_if_with_store() {
+        let module = create_module(vec![If::new(
+            types::Primitive::Integer64,
+            Variable::new("c"),
+            Block::new(
+                vec![Store::new(
+                    types::Primitive::Integer64,
+                    Variable::new("x"),
+                    Variable::new("p"),
+                )
+                .into()],
+                Branch::new(types::Primitive::Integer64, Variable::new("x")),
+            ),
+            Block::new(
+                vec![],
+                Branch::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+            ),
+            "z",
+        )
+        .into()]);
+
+        assert_eq!(convert(&module), module);
+    }
+
+    #[test]
+    fn do_not_convert_if_with_call() {
+        let module = create_module(vec![If::new(
+            types::Primitive::Integer64,
+            Variable::new("c"),
+            Block::new(
+                vec![Call::new(
+                    types::Function::new(
+                        vec![],
+                        types::Primitive::Integer64,
+                        CallingConvention::Target,
+                    ),
+                    Variable::new("g"),
+                    vec![],
+                    "y",
+                )
+                .into()],
+                Branch::new(types::Primitive::Integer64, Variable::new("y")),
+            ),
+            Block::new(
+                vec![],
+                Branch::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+            ),
+            "z",
+        )
+        .into()]);
+
+        assert_eq!(convert(&module), module);
+    }
+
+    #[test]
+    fn do_not_convert_if_with_integer_division() {
+        let module = create_module(vec![If::new(
+            types::Primitive::Integer64,
+            Variable::new("c"),
+            Block::new(
+                vec![],
+                Branch::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperation::new(
+                        types::Primitive::Integer64,
+                        ArithmeticOperator::Divide,
+                        Primitive::Integer64(1),
+                        Variable::new("x"),
+                    ),
+                ),
+            ),
+            Block::new(
+                vec![],
+                Branch::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+            ),
+            "z",
+        )
+        .into()]);
+
+        assert_eq!(convert(&module), module);
+    }
+
+    #[test]
+    fn do_not_convert_if_over_budget() {
+        let module = create_module(vec![If::new(
+            types::Primitive::Integer64,
+            Variable::new("c"),
+            Block::new(
+                vec![
+                    create_addition("a"),
+                    create_addition("b"),
+                    create_addition("y"),
+                ],
+                Branch::new(types::Primitive::Integer64, Variable::new("y")),
+            ),
+            Block::new(
+                vec![create_addition("w")],
+                Branch::new(types::Primitive::Integer64, Variable::new("w")),
+            ),
+            "z",
+        )
+        .into()]);
+
+        assert_eq!(convert_ifs_to_selects(&module, &LAYOUT, 4), module);
+    }
+
+    #[test]
+    fn convert_nested_ifs() {
+        let inner = If::new(
+            types::Primitive::Integer64,
+            Variable::new("c"),
+            Block::new(
+                vec![],
+                Branch::new(types::Primitive::Integer64, Variable::new("x")),
+            ),
+            Block::new(
+                vec![],
+                Branch::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+            ),
+            "y",
+        );
+
+        assert_eq!(
+            convert(&create_module(vec![If::new(
+                types::Primitive::Integer64,
+                Variable::new("c"),
+                Block::new(
+                    vec![inner.into()],
+                    Branch::new(types::Primitive::Integer64, Variable::new("y")),
+                ),
+                Block::new(
+                    vec![],
+                    Branch::new(types::Primitive::Integer64, Primitive::Integer64(1)),
+                ),
+                "z",
+            )
+            .into()])),
+            create_module(vec![
+                PassThrough::new(
+                    types::Primitive::Integer64,
+                    Select::new(
+                        types::Primitive::Integer64,
+                        Variable::new("c"),
+                        Variable::new("x"),
+                        Primitive::Integer64(0),
+                    ),
+                    "_if_0",
+                )
+                .into(),
+                PassThrough::new(
+                    types::Primitive::Integer64,
+                    Select::new(
+                        types::Primitive::Integer64,
+                        Variable::new("c"),
+                        Variable::new("_if_0"),
+                        Primitive::Integer64(1),
+                    ),
+                    "z",
+                )
+                .into(),
+            ])
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    ir::*,
+    types::{self, CallingConvention, GENERIC_POINTER_TYPE},
+};
+use std::collections::HashSet;
+
+const MEMORY_COPY_FUNCTION_NAME: &str = "memcpy";
+const MEMORY_MOVE_FUNCTION_NAME: &str = "memmove";
+const MEMORY_SET_FUNCTION_NAME: &str = "memset";
+
+// Calls to declared C runtime functions with their exact signatures are
+// converted into structured instructions.
+pub fn recognize_intrinsics(module: &Module) -> Module {
+    let names = module
+        .function_declarations()
+        .iter()
+        .filter(|declaration| is_intrinsic_declaration(declaration))
+        .map(|declaration| declaration.name())
+        .collect::<HashSet<_>>();
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                // Arguments can shadow the declarations.
+                let mut names = names.clone();
+
+                for argument in definition.arguments() {
+                    names.remove(argument.name());
+                }
+
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    recognize_in_block(definition.body(), &names),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn recognize_in_block(block: &Block, names: &HashSet<&str>) -> Block {
+    let mut names = names.clone();
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        match instruction {
+            Instruction::Call(call) => {
+                if let Some(intrinsic) = recognize_call(call, &names) {
+                    instructions.extend([
+                        intrinsic,
+                        PassThrough::new(
+                            GENERIC_POINTER_TYPE.clone(),
+                            call.arguments()[0].clone(),
+                            call.name(),
+                        )
+                        .into(),
+                    ]);
+                } else {
+                    instructions.push(instruction.clone());
+                }
+            }
+            Instruction::If(if_) => instructions.push(
+                If::new(
+                    if_.type_().clone(),
+                    if_.condition().clone(),
+                    recognize_in_block(if_.then(), &names),
+                    recognize_in_block(if_.else_(), &names),
+                    if_.name(),
+                )
+                .into(),
+            ),
+            _ => instructions.push(instruction.clone()),
+        }
+
+        if let Some(name) = instruction.name() {
+            names.remove(name);
+        }
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn recognize_call(call: &Call, names: &HashSet<&str>) -> Option<Instruction> {
+    let name = match call.function() {
+        Expression::Variable(variable) if names.contains(variable.name()) => variable.name(),
+        _ => return None,
+    };
+
+    if Some(call.type_()) != intrinsic_type(name).as_ref() {
+        return None;
+    }
+
+    let arguments = call.arguments();
+
+    Some(match name {
+        MEMORY_COPY_FUNCTION_NAME | MEMORY_MOVE_FUNCTION_NAME => MemoryCopy::new(
+            arguments[1].clone(),
+            arguments[0].clone(),
+            arguments[2].clone(),
+            name == MEMORY_MOVE_FUNCTION_NAME,
+        )
+        .into(),
+        MEMORY_SET_FUNCTION_NAME => MemorySet::new(
+            arguments[0].clone(),
+            // Only constant values are converted as the C function takes
+            // integers wider than bytes.
+            match &arguments[1] {
+                Expression::Primitive(Primitive::Integer32(value)) => {
+                    Primitive::Integer8(*value as u8)
+                }
+                _ => return None,
+            },
+            arguments[2].clone(),
+        )
+        .into(),
+        _ => return None,
+    })
+}
+
+fn is_intrinsic_declaration(declaration: &FunctionDeclaration) -> bool {
+    Some(declaration.type_()) == intrinsic_type(declaration.name()).as_ref()
+}
+
+fn intrinsic_type(name: &str) -> Option<types::Function> {
+    let value_type = match name {
+        MEMORY_COPY_FUNCTION_NAME | MEMORY_MOVE_FUNCTION_NAME => GENERIC_POINTER_TYPE.clone(),
+        MEMORY_SET_FUNCTION_NAME => types::Primitive::Integer32.into(),
+        _ => return None,
+    };
+
+    Some(types::Function::new(
+        vec![
+            GENERIC_POINTER_TYPE.clone(),
+            value_type,
+            types::Primitive::PointerInteger.into(),
+        ],
+        GENERIC_POINTER_TYPE.clone(),
+        CallingConvention::Target,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_module(declaration: FunctionDeclaration, instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![declaration],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("p", GENERIC_POINTER_TYPE.clone()),
+                    Argument::new("q", GENERIC_POINTER_TYPE.clone()),
+                ],
+                Block::new(
+                    instructions,
+                    Return::new(GENERIC_POINTER_TYPE.clone(), Variable::new("x")),
+                ),
+                GENERIC_POINTER_TYPE.clone(),
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_call(name: &str, type_: types::Function, value: impl Into<Expression>) -> Call {
+        Call::new(
+            type_,
+            Variable::new(name),
+            vec![
+                Variable::new("p").into(),
+                value.into(),
+                Primitive::PointerInteger(8).into(),
+            ],
+            "x",
+        )
+    }
+
+    #[test]
+    fn recognize_memory_copy() {
+        let type_ = intrinsic_type("memcpy").unwrap();
+
+        assert_eq!(
+            recognize_intrinsics(&create_module(
+                FunctionDeclaration::new("memcpy", type_.clone()),
+                vec![create_call("memcpy", type_.clone(), Variable::new("q")).into()],
+            )),
+            create_module(
+                FunctionDeclaration::new("memcpy", type_),
+                vec![
+                    MemoryCopy::new(
+                        Variable::new("q"),
+                        Variable::new("p"),
+                        Primitive::PointerInteger(8),
+                        false
+                    )
+                    .into(),
+                    PassThrough::new(GENERIC_POINTER_TYPE.clone(), Variable::new("p"), "x").into(),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn recognize_memory_set_of_constant() {
+        let type_ = intrinsic_type("memset").unwrap();
+
+        assert_eq!(
+            recognize_intrinsics(&create_module(
+                FunctionDeclaration::new("memset", type_.clone()),
+                vec![create_call("memset", type_.clone(), Primitive::Integer32(42)).into()],
+            )),
+            create_module(
+                FunctionDeclaration::new("memset", type_),
+                vec![
+                    MemorySet::new(
+                        Variable::new("p"),
+                        Primitive::Integer8(42),
+                        Primitive::PointerInteger(8),
+                    )
+                    .into(),
+                    PassThrough::new(GENERIC_POINTER_TYPE.clone(), Variable::new("p"), "x").into(),
+                ],
+            )
+        );
+    }
+
+    #[test]
+    fn keep_unrelated_call() {
+        let type_ = intrinsic_type("memcpy").unwrap();
+        let module = create_module(
+            FunctionDeclaration::new("copy", type_.clone()),
+            vec![create_call("copy", type_, Variable::new("q")).into()],
+        );
+
+        assert_eq!(recognize_intrinsics(&module), module);
+    }
+
+    #[test]
+    fn keep_call_with_different_signature() {
+        let type_ = types::Function::new(
+            vec![
+                GENERIC_POINTER_TYPE.clone(),
+                GENERIC_POINTER_TYPE.clone(),
+                types::Primitive::Integer32.into(),
+            ],
+            GENERIC_POINTER_TYPE.clone(),
+            CallingConvention::Target,
+        );
+        let module = create_module(
+            FunctionDeclaration::new("memcpy", type_.clone()),
+            vec![create_call("memcpy", type_, Variable::new("q")).into()],
+        );
+
+        assert_eq!(recognize_intrinsics(&module), module);
+    }
+
+    #[test]
+    fn keep_memory_set_of_variable() {
+        let type_ = intrinsic_type("memset").unwrap();
+        let module = create_module(
+            FunctionDeclaration::new("memset", type_.clone()),
+            vec![create_call("memset", type_, Variable::new("q")).into()],
+        );
+
+        assert_eq!(recognize_intrinsics(&module), module);
+    }
+}
@@ -0,0 +1,327 @@
+use super::{
+    expression_conversion::convert_expressions_in_terminal_instruction,
+    free_variables::collect_from_expression,
+    renaming::{create_name_generator, rename_bindings_in_block},
+};
+use crate::{ir::*, types};
+use std::cell::Cell;
+
+// Conditions of `If` instructions are known in their arms. So inner `If`
+// instructions on the same conditions are replaced with their taken arms.
+pub fn thread_jumps(module: &Module) -> Module {
+    let mut generate_name = create_name_generator(module, "_jump_");
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    thread_block(definition.body(), &[], &mut generate_name),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn thread_block(
+    block: &Block,
+    conditions: &[(Expression, bool)],
+    generate_name: &mut impl FnMut() -> String,
+) -> Block {
+    let mut conditions = conditions.to_vec();
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        if let Instruction::If(if_) = instruction {
+            if let Some(value) = find_condition(&conditions, if_.condition()) {
+                // Bindings in taken arms are renamed so that they do not shadow
+                // names in the enclosing block after splicing.
+                let block = rename_bindings_in_block(
+                    &thread_block(
+                        if value { if_.then() } else { if_.else_() },
+                        &conditions,
+                        generate_name,
+                    ),
+                    generate_name,
+                );
+
+                instructions.extend(block.instructions().iter().cloned());
+
+                if let TerminalInstruction::Branch(branch) = block.terminal_instruction() {
+                    instructions.push(
+                        PassThrough::new(
+                            if_.type_().clone(),
+                            branch.expression().clone(),
+                            if_.name(),
+                        )
+                        .into(),
+                    );
+                } else {
+                    // Instructions after the `If` instruction are unreachable.
+                    return Block::new(instructions, block.terminal_instruction().clone());
+                }
+            } else {
+                let mut then_conditions = conditions.clone();
+                let mut else_conditions = conditions.clone();
+
+                if is_trackable(if_.condition()) {
+                    then_conditions.push((if_.condition().clone(), true));
+                    else_conditions.push((if_.condition().clone(), false));
+                }
+
+                instructions.push(
+                    If::new(
+                        if_.type_().clone(),
+                        if_.condition().clone(),
+                        thread_block(if_.then(), &then_conditions, generate_name),
+                        thread_block(if_.else_(), &else_conditions, generate_name),
+                        if_.name(),
+                    )
+                    .into(),
+                );
+            }
+        } else {
+            instructions.push(instruction.clone());
+        }
+
+        // Conditions referring to shadowed variables are not known anymore.
+        if let Some(name) = instructions
+            .last()
+            .and_then(|instruction| instruction.name())
+        {
+            conditions.retain(|(condition, _)| !collect_from_expression(condition).contains(name));
+        }
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn find_condition(conditions: &[(Expression, bool)], condition: &Expression) -> Option<bool> {
+    conditions
+        .iter()
+        .rev()
+        .find(|(known, _)| known == condition)
+        .map(|(_, value)| *value)
+}
+
+// Undefined values can be different every time they are evaluated.
+fn is_trackable(condition: &Expression) -> bool {
+    let trackable = Cell::new(true);
+
+    convert_expressions_in_terminal_instruction(
+        &Branch::new(types::Primitive::Boolean, condition.clone()).into(),
+        &|expression| {
+            if let Expression::Undefined(_) = expression {
+                trackable.set(false);
+            }
+
+            expression.clone()
+        },
+    );
+
+    trackable.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CallingConvention;
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("c", types::Primitive::Boolean),
+                    Argument::new("d", types::Primitive::Boolean),
+                ],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::Integer64, Variable::new("z")),
+                ),
+                types::Primitive::Integer64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_branch(value: u64) -> Block {
+        Block::new(
+            vec![],
+            Branch::new(types::Primitive::Integer64, Primitive::Integer64(value)),
+        )
+    }
+
+    fn create_if(condition: &str, then: Block, else_: Block, name: &str) -> Instruction {
+        If::new(
+            types::Primitive::Integer64,
+            Variable::new(condition),
+            then,
+            else_,
+            name,
+        )
+        .into()
+    }
+
+    fn create_outer_if(then: Block, else_: Block) -> Module {
+        create_module(vec![create_if("c", then, else_, "z")])
+    }
+
+    fn create_inner_block(instructions: Vec<Instruction>) -> Block {
+        Block::new(
+            instructions,
+            Branch::new(types::Primitive::Integer64, Variable::new("y")),
+        )
+    }
+
+    #[test]
+    fn thread_jump_in_then_block() {
+        assert_eq!(
+            thread_jumps(&create_outer_if(
+                create_inner_block(vec![create_if(
+                    "c",
+                    create_branch(1),
+                    create_branch(2),
+                    "y"
+                )]),
+                create_branch(0),
+            )),
+            create_outer_if(
+                create_inner_block(vec![PassThrough::new(
+                    types::Primitive::Integer64,
+                    Primitive::Integer64(1),
+                    "y"
+                )
+                .into()]),
+                create_branch(0),
+            )
+        );
+    }
+
+    #[test]
+    fn rename_shadowing_binding_in_taken_arm() {
+        let create_pass_through = |expression: Expression, name| {
+            PassThrough::new(types::Primitive::Integer64, expression, name).into()
+        };
+
+        assert_eq!(
+            thread_jumps(&create_outer_if(
+                create_inner_block(vec![
+                    create_pass_through(Primitive::Integer64(1).into(), "x"),
+                    create_if(
+                        "c",
+                        Block::new(
+                            vec![create_pass_through(Primitive::Integer64(2).into(), "x")],
+                            Branch::new(types::Primitive::Integer64, Variable::new("x")),
+                        ),
+                        create_branch(0),
+                        "w",
+                    ),
+                    create_pass_through(Variable::new("x").into(), "y"),
+                ]),
+                create_branch(0),
+            )),
+            create_outer_if(
+                create_inner_block(vec![
+                    create_pass_through(Primitive::Integer64(1).into(), "x"),
+                    create_pass_through(Primitive::Integer64(2).into(), "_jump_0"),
+                    create_pass_through(Variable::new("_jump_0").into(), "w"),
+                    create_pass_through(Variable::new("x").into(), "y"),
+                ]),
+                create_branch(0),
+            )
+        );
+    }
+
+    #[test]
+    fn thread_jump_in_else_block() {
+        assert_eq!(
+            thread_jumps(&create_outer_if(
+                create_branch(0),
+                create_inner_block(vec![create_if(
+                    "c",
+                    create_branch(1),
+                    create_branch(2),
+                    "y"
+                )]),
+            )),
+            create_outer_if(
+                create_branch(0),
+                create_inner_block(vec![PassThrough::new(
+                    types::Primitive::Integer64,
+                    Primitive::Integer64(2),
+                    "y"
+                )
+                .into()]),
+            )
+        );
+    }
+
+    #[test]
+    fn thread_jump_to_return() {
+        let then = Block::new(
+            vec![],
+            Return::new(types::Primitive::Integer64, Primitive::Integer64(1)),
+        );
+
+        assert_eq!(
+            thread_jumps(&create_outer_if(
+                create_inner_block(vec![
+                    create_if("c", then.clone(), create_branch(2), "y"),
+                    create_if("d", create_branch(3), create_branch(4), "w"),
+                ]),
+                create_branch(0),
+            )),
+            create_outer_if(then, create_branch(0))
+        );
+    }
+
+    #[test]
+    fn keep_if_on_different_condition() {
+        let module = create_outer_if(
+            create_inner_block(vec![create_if(
+                "d",
+                create_branch(1),
+                create_branch(2),
+                "y",
+            )]),
+            create_branch(0),
+        );
+
+        assert_eq!(thread_jumps(&module), module);
+    }
+
+    #[test]
+    fn keep_if_on_undefined_condition() {
+        let create_if = |then, else_, name| -> Instruction {
+            If::new(
+                types::Primitive::Integer64,
+                Undefined::new(types::Primitive::Boolean),
+                then,
+                else_,
+                name,
+            )
+            .into()
+        };
+        let module = create_module(vec![create_if(
+            create_inner_block(vec![create_if(create_branch(1), create_branch(2), "y")]),
+            create_branch(0),
+            "z",
+        )]);
+
+        assert_eq!(thread_jumps(&module), module);
+    }
+}
@@ -0,0 +1,323 @@
+use super::{
+    alias::{may_alias, AliasContext},
+    free_variables::collect_from_instruction,
+    sinking::is_pure,
+};
+use crate::ir::*;
+
+// Loads from pointers are replaced with values of dominating stores to the same
+// locations.
+pub fn forward_loads(module: &Module) -> Module {
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    forward_block(definition.body(), &AliasContext::new(), &[]),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn forward_block(block: &Block, context: &AliasContext, stores: &[Store]) -> Block {
+    let mut context = context.clone();
+    let mut stores = stores.to_vec();
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        let instruction = match instruction {
+            Instruction::AllocateHeap(_) | Instruction::AllocateStack(_) => instruction.clone(),
+            Instruction::If(if_) => {
+                let if_ = If::new(
+                    if_.type_().clone(),
+                    if_.condition().clone(),
+                    forward_block(if_.then(), &context, &stores),
+                    forward_block(if_.else_(), &context, &stores),
+                    if_.name(),
+                );
+
+                // Stores in arms are not tracked after the `If` instruction.
+                stores.clear();
+
+                if_.into()
+            }
+            Instruction::Load(load) => stores
+                .iter()
+                .find(|store| store.pointer() == load.pointer() && store.type_() == load.type_())
+                .map(|store| {
+                    PassThrough::new(load.type_().clone(), store.value().clone(), load.name())
+                        .into()
+                })
+                .unwrap_or_else(|| instruction.clone()),
+            Instruction::Store(store) => {
+                stores.retain(|other| !may_alias(other.pointer(), store.pointer(), &context));
+                stores.push(store.clone());
+                instruction.clone()
+            }
+            _ if is_pure(instruction) => instruction.clone(),
+            // Any other instructions including calls and atomic operations
+            // might write memory.
+            _ => {
+                stores.clear();
+                instruction.clone()
+            }
+        };
+
+        if let Some(name) = instruction.name() {
+            stores.retain(|store| !collect_from_instruction(&store.clone().into()).contains(name));
+        }
+
+        context.bind(&instruction);
+        instructions.push(instruction);
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("p", types::Pointer::new(types::Primitive::Integer64)),
+                    Argument::new("q", types::Pointer::new(types::Primitive::Integer64)),
+                ],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::Integer64, Variable::new("x")),
+                ),
+                types::Primitive::Integer64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_allocation(name: &str) -> Instruction {
+        AllocateStack::new(types::Primitive::Integer64, name).into()
+    }
+
+    fn create_store(value: u64, pointer: &str) -> Instruction {
+        Store::new(
+            types::Primitive::Integer64,
+            Primitive::Integer64(value),
+            Variable::new(pointer),
+        )
+        .into()
+    }
+
+    fn create_load(pointer: &str) -> Instruction {
+        Load::new(types::Primitive::Integer64, Variable::new(pointer), "x").into()
+    }
+
+    fn create_forwarded_value(value: u64) -> Instruction {
+        PassThrough::new(
+            types::Primitive::Integer64,
+            Primitive::Integer64(value),
+            "x",
+        )
+        .into()
+    }
+
+    #[test]
+    fn forward_stored_value() {
+        assert_eq!(
+            forward_loads(&create_module(vec![
+                create_store(42, "p"),
+                create_load("p")
+            ])),
+            create_module(vec![create_store(42, "p"), create_forwarded_value(42)])
+        );
+    }
+
+    #[test]
+    fn forward_stored_value_across_store_to_another_allocation() {
+        assert_eq!(
+            forward_loads(&create_module(vec![
+                create_allocation("a"),
+                create_allocation("b"),
+                create_store(1, "a"),
+                create_store(2, "b"),
+                create_load("a"),
+            ])),
+            create_module(vec![
+                create_allocation("a"),
+                create_allocation("b"),
+                create_store(1, "a"),
+                create_store(2, "b"),
+                create_forwarded_value(1),
+            ])
+        );
+    }
+
+    #[test]
+    fn do_not_forward_stored_value_across_aliased_store() {
+        let module = create_module(vec![
+            create_store(1, "p"),
+            create_store(2, "q"),
+            create_load("p"),
+        ]);
+
+        assert_eq!(forward_loads(&module), module);
+    }
+
+    #[test]
+    fn do_not_forward_stored_value_across_store_to_nested_element() {
+        let create_address = |pointer: Expression, offset| {
+            PointerAddress::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                pointer,
+                Primitive::PointerInteger(offset),
+            )
+        };
+        let module = create_module(vec![
+            Store::new(
+                types::Primitive::Integer64,
+                Primitive::Integer64(1),
+                create_address(Variable::new("p").into(), 1),
+            )
+            .into(),
+            Store::new(
+                types::Primitive::Integer64,
+                Primitive::Integer64(2),
+                create_address(create_address(Variable::new("p").into(), 0).into(), 1),
+            )
+            .into(),
+            Load::new(
+                types::Primitive::Integer64,
+                create_address(Variable::new("p").into(), 1),
+                "x",
+            )
+            .into(),
+        ]);
+
+        assert_eq!(forward_loads(&module), module);
+    }
+
+    #[test]
+    fn do_not_forward_stored_value_across_store_to_rebound_allocation() {
+        let module = create_module(vec![
+            create_allocation("a"),
+            create_allocation("b"),
+            PassThrough::new(
+                types::Pointer::new(types::Primitive::Integer64),
+                Variable::new("a"),
+                "b",
+            )
+            .into(),
+            create_store(1, "a"),
+            create_store(2, "b"),
+            create_load("a"),
+        ]);
+
+        assert_eq!(forward_loads(&module), module);
+    }
+
+    #[test]
+    fn forward_dominating_stored_value() {
+        let create_module = |instruction| {
+            create_module(vec![
+                create_store(42, "p"),
+                If::new(
+                    types::Primitive::Integer64,
+                    Primitive::Boolean(true),
+                    Block::new(
+                        vec![instruction],
+                        Branch::new(types::Primitive::Integer64, Variable::new("x")),
+                    ),
+                    Block::new(
+                        vec![],
+                        Branch::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+                    ),
+                    "y",
+                )
+                .into(),
+            ])
+        };
+
+        assert_eq!(
+            forward_loads(&create_module(create_load("p"))),
+            create_module(create_forwarded_value(42))
+        );
+    }
+
+    #[test]
+    fn do_not_forward_stored_value_after_if() {
+        let module = create_module(vec![
+            create_store(1, "p"),
+            If::new(
+                types::Primitive::Integer64,
+                Primitive::Boolean(true),
+                Block::new(
+                    vec![create_store(2, "p")],
+                    Branch::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+                ),
+                Block::new(
+                    vec![],
+                    Branch::new(types::Primitive::Integer64, Primitive::Integer64(0)),
+                ),
+                "y",
+            )
+            .into(),
+            create_load("p"),
+        ]);
+
+        assert_eq!(forward_loads(&module), module);
+    }
+
+    #[test]
+    fn do_not_forward_stored_value_across_atomic_store() {
+        let module = create_module(vec![
+            create_store(1, "p"),
+            AtomicStore::new(
+                types::Primitive::Integer64,
+                Primitive::Integer64(2),
+                Variable::new("q"),
+                AtomicOrdering::Release,
+            )
+            .into(),
+            create_load("p"),
+        ]);
+
+        assert_eq!(forward_loads(&module), module);
+    }
+
+    #[test]
+    fn do_not_forward_stored_value_across_call() {
+        let module = create_module(vec![
+            create_store(1, "p"),
+            Call::new(
+                types::Function::new(
+                    vec![],
+                    types::Primitive::Integer64,
+                    CallingConvention::Target,
+                ),
+                Variable::new("g"),
+                vec![],
+                "y",
+            )
+            .into(),
+            create_load("p"),
+        ]);
+
+        assert_eq!(forward_loads(&module), module);
+    }
+}
@@ -0,0 +1,356 @@
+use super::{
+    renaming::create_name_generator,
+    target_data_layout::TargetDataLayout,
+    type_check::{check_expression, collect_global_variables},
+    type_size::{type_alignment, type_size},
+};
+use crate::{
+    ir::*,
+    types::{self, Type},
+};
+use std::collections::HashMap;
+
+const WORD_TYPES: [types::Primitive; 4] = [
+    types::Primitive::Integer64,
+    types::Primitive::Integer32,
+    types::Primitive::Integer16,
+    types::Primitive::Integer8,
+];
+
+// Memory copies of constant sizes up to thresholds are expanded into loads and
+// stores of words. Words are not wider than element alignments of source and
+// destination pointers so that they assume no stricter alignment than the
+// pointer types do.
+pub fn expand_small_memcpy(module: &Module, threshold: usize, layout: &TargetDataLayout) -> Module {
+    let variables = collect_global_variables(module);
+    let mut generate_name = create_name_generator(module, "_memcpy_");
+
+    module.clone().map_function_definitions(|definition| {
+        let mut variables = variables.clone();
+
+        variables.extend(
+            definition
+                .arguments()
+                .iter()
+                .map(|argument| (argument.name().into(), argument.type_().clone())),
+        );
+
+        FunctionDefinition::new(
+            definition.name(),
+            definition.arguments().to_vec(),
+            expand_block(
+                definition.body(),
+                &variables,
+                threshold,
+                layout.pointer_size(),
+                &mut generate_name,
+            ),
+            definition.result_type().clone(),
+            definition.calling_convention(),
+            definition.linkage(),
+        )
+    })
+}
+
+fn expand_block(
+    block: &Block,
+    variables: &HashMap<String, Type>,
+    threshold: usize,
+    pointer_size: usize,
+    generate_name: &mut impl FnMut() -> String,
+) -> Block {
+    let mut variables = variables.clone();
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        match instruction {
+            Instruction::If(if_) => instructions.push(
+                If::new(
+                    if_.type_().clone(),
+                    if_.condition().clone(),
+                    expand_block(
+                        if_.then(),
+                        &variables,
+                        threshold,
+                        pointer_size,
+                        generate_name,
+                    ),
+                    expand_block(
+                        if_.else_(),
+                        &variables,
+                        threshold,
+                        pointer_size,
+                        generate_name,
+                    ),
+                    if_.name(),
+                )
+                .into(),
+            ),
+            Instruction::MemoryCopy(copy) => {
+                if let Some(expanded) =
+                    expand_copy(copy, &variables, threshold, pointer_size, generate_name)
+                {
+                    instructions.extend(expanded);
+                } else {
+                    instructions.push(instruction.clone());
+                }
+            }
+            _ => instructions.push(instruction.clone()),
+        }
+
+        if let (Some(name), Some(type_)) = (instruction.name(), instruction.result_type()) {
+            variables.insert(name.into(), type_);
+        }
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn expand_copy(
+    copy: &MemoryCopy,
+    variables: &HashMap<String, Type>,
+    threshold: usize,
+    pointer_size: usize,
+    generate_name: &mut impl FnMut() -> String,
+) -> Option<Vec<Instruction>> {
+    let size = match copy.size() {
+        Expression::Primitive(Primitive::PointerInteger(size)) if *size >= 0 => *size as usize,
+        _ => return None,
+    };
+
+    if size > threshold {
+        return None;
+    }
+
+    let source_type = check_pointer_type(copy.source(), variables)?;
+    let destination_type = check_pointer_type(copy.destination(), variables)?;
+    let alignment = type_alignment(source_type.element(), pointer_size)
+        .ok()?
+        .min(type_alignment(destination_type.element(), pointer_size).ok()?)
+        .min(pointer_size);
+
+    let mut loads = vec![];
+    let mut stores = vec![];
+    let mut offset = 0;
+
+    for word_type in WORD_TYPES {
+        let word_size = type_size(&word_type.into(), pointer_size).ok()?;
+
+        if word_size > alignment {
+            continue;
+        }
+
+        while offset + word_size <= size {
+            let index = (offset / word_size) as i64;
+            let name = generate_name();
+
+            loads.push(
+                Load::new(
+                    word_type,
+                    create_word_address(copy.source(), &source_type, word_type, index),
+                    &name,
+                )
+                .into(),
+            );
+            stores.push(
+                Store::new(
+                    word_type,
+                    Variable::new(name),
+                    create_word_address(copy.destination(), &destination_type, word_type, index),
+                )
+                .into(),
+            );
+
+            offset += word_size;
+        }
+    }
+
+    // All words are loaded before stores in case regions overlap.
+    Some(loads.into_iter().chain(stores).collect())
+}
+
+fn check_pointer_type(
+    pointer: &Expression,
+    variables: &HashMap<String, Type>,
+) -> Option<types::Pointer> {
+    match check_expression(pointer, variables).ok()? {
+        Type::Pointer(pointer) => Some(pointer),
+        _ => None,
+    }
+}
+
+fn create_word_address(
+    pointer: &Expression,
+    pointer_type: &types::Pointer,
+    word_type: types::Primitive,
+    index: i64,
+) -> Expression {
+    let word_pointer_type = types::Pointer::new(word_type);
+
+    PointerAddress::new(
+        word_pointer_type.clone(),
+        BitCast::new(pointer_type.clone(), word_pointer_type, pointer.clone()),
+        Primitive::PointerInteger(index),
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CallingConvention;
+
+    const LAYOUT: TargetDataLayout = TargetDataLayout::new(8, 8);
+
+    fn create_module(element_type: types::Primitive, instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("p", types::Pointer::new(element_type)),
+                    Argument::new("q", types::Pointer::new(element_type)),
+                ],
+                Block::new(instructions, TerminalInstruction::Unreachable),
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_copy(size: i64) -> Instruction {
+        MemoryCopy::new(
+            Variable::new("p"),
+            Variable::new("q"),
+            Primitive::PointerInteger(size),
+            false,
+        )
+        .into()
+    }
+
+    fn create_word_copy(
+        element_type: types::Primitive,
+        words: &[(types::Primitive, i64)],
+    ) -> Vec<Instruction> {
+        let create_address = |pointer: &str, word_type, index| {
+            create_word_address(
+                &Variable::new(pointer).into(),
+                &types::Pointer::new(element_type),
+                word_type,
+                index,
+            )
+        };
+
+        words
+            .iter()
+            .enumerate()
+            .map(|(name, &(word_type, index))| {
+                Load::new(
+                    word_type,
+                    create_address("p", word_type, index),
+                    format!("_memcpy_{}", name),
+                )
+                .into()
+            })
+            .chain(words.iter().enumerate().map(|(name, &(word_type, index))| {
+                Store::new(
+                    word_type,
+                    Variable::new(format!("_memcpy_{}", name)),
+                    create_address("q", word_type, index),
+                )
+                .into()
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn expand_8_byte_copy() {
+        let element_type = types::Primitive::Integer64;
+
+        assert_eq!(
+            expand_small_memcpy(
+                &create_module(element_type, vec![create_copy(8)]),
+                32,
+                &LAYOUT
+            ),
+            create_module(
+                element_type,
+                create_word_copy(element_type, &[(types::Primitive::Integer64, 0)])
+            )
+        );
+    }
+
+    #[test]
+    fn expand_24_byte_copy() {
+        let element_type = types::Primitive::Integer64;
+
+        assert_eq!(
+            expand_small_memcpy(
+                &create_module(element_type, vec![create_copy(24)]),
+                32,
+                &LAYOUT
+            ),
+            create_module(
+                element_type,
+                create_word_copy(
+                    element_type,
+                    &[
+                        (types::Primitive::Integer64, 0),
+                        (types::Primitive::Integer64, 1),
+                        (types::Primitive::Integer64, 2),
+                    ]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn expand_copy_with_words_of_pointer_alignment() {
+        let element_type = types::Primitive::Integer16;
+
+        assert_eq!(
+            expand_small_memcpy(
+                &create_module(element_type, vec![create_copy(5)]),
+                32,
+                &LAYOUT
+            ),
+            create_module(
+                element_type,
+                create_word_copy(
+                    element_type,
+                    &[
+                        (types::Primitive::Integer16, 0),
+                        (types::Primitive::Integer16, 1),
+                        (types::Primitive::Integer8, 4),
+                    ]
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn keep_copy_larger_than_threshold() {
+        let module = create_module(types::Primitive::Integer64, vec![create_copy(40)]);
+
+        assert_eq!(expand_small_memcpy(&module, 32, &LAYOUT), module);
+    }
+
+    #[test]
+    fn keep_copy_of_variable_size() {
+        let module = create_module(
+            types::Primitive::Integer64,
+            vec![MemoryCopy::new(
+                Variable::new("p"),
+                Variable::new("q"),
+                Variable::new("n"),
+                false,
+            )
+            .into()],
+        );
+
+        assert_eq!(expand_small_memcpy(&module, 32, &LAYOUT), module);
+    }
+}
@@ -0,0 +1,103 @@
+use super::type_conversion::convert_types;
+use crate::{ir::*, types::Type};
+use std::collections::HashMap;
+
+// Bodies are not resolved recursively so that recursive types can refer to
+// themselves through opaque types.
+pub fn resolve_opaque_types(module: &Module, bodies: &HashMap<String, Type>) -> Module {
+    convert_types(module, &|type_| match type_ {
+        Type::Opaque(opaque) => bodies
+            .get(opaque.name())
+            .cloned()
+            .unwrap_or_else(|| type_.clone()),
+        _ => type_.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        analysis::check_types,
+        types::{self, CallingConvention},
+    };
+
+    fn create_module(type_: impl Into<Type>) -> Module {
+        let type_ = type_.into();
+        let pointer_type = types::Pointer::new(type_.clone());
+
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("x", pointer_type.clone())],
+                Block::new(
+                    vec![PassThrough::new(
+                        types::Pointer::new(types::Primitive::Integer8),
+                        BitCast::new(
+                            pointer_type.clone(),
+                            types::Pointer::new(types::Primitive::Integer8),
+                            Variable::new("x"),
+                        ),
+                        "y",
+                    )
+                    .into()],
+                    Return::new(
+                        pointer_type.clone(),
+                        BitCast::new(
+                            types::Pointer::new(types::Primitive::Integer8),
+                            pointer_type.clone(),
+                            Variable::new("y"),
+                        ),
+                    ),
+                ),
+                pointer_type,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    #[test]
+    fn resolve_opaque_type() {
+        let record_type = types::Record::new(vec![types::Primitive::Float64.into()]);
+        let module = create_module(types::Opaque::new("foo"));
+
+        check_types(&module).unwrap();
+
+        assert_eq!(
+            resolve_opaque_types(
+                &module,
+                &vec![("foo".into(), record_type.clone().into())]
+                    .into_iter()
+                    .collect()
+            ),
+            create_module(record_type)
+        );
+    }
+
+    #[test]
+    fn resolve_recursive_opaque_type() {
+        let record_type =
+            types::Record::new(vec![types::Pointer::new(types::Opaque::new("foo")).into()]);
+
+        assert_eq!(
+            resolve_opaque_types(
+                &create_module(types::Opaque::new("foo")),
+                &vec![("foo".into(), record_type.clone().into())]
+                    .into_iter()
+                    .collect()
+            ),
+            create_module(record_type)
+        );
+    }
+
+    #[test]
+    fn keep_unknown_opaque_type() {
+        let module = create_module(types::Opaque::new("foo"));
+
+        assert_eq!(resolve_opaque_types(&module, &Default::default()), module);
+    }
+}
@@ -0,0 +1,814 @@
+use super::{
+    constant_evaluation::evaluate_constant_expression, expression_conversion::convert_expressions,
+};
+use crate::{ir::*, types};
+
+pub fn simplify_peepholes(module: &Module) -> Module {
+    convert_expressions(module, &simplify_expression)
+}
+
+// Sub-expressions are already simplified as expression conversion runs bottom
+// up.
+fn simplify_expression(expression: &Expression) -> Expression {
+    match expression {
+        Expression::ArithmeticOperation(operation) => simplify_arithmetic_operation(operation),
+        Expression::BitCast(bit_cast) => simplify_bit_cast(bit_cast),
+        Expression::BitwiseNotOperation(operation) => match operation.value() {
+            Expression::BitwiseNotOperation(inner) => inner.value().clone(),
+            Expression::ComparisonOperation(comparison)
+                if operation.type_() == types::Primitive::Boolean =>
+            {
+                invert_comparison(comparison).into()
+            }
+            _ => expression.clone(),
+        },
+        Expression::ComparisonOperation(operation) => simplify_comparison_operation(operation),
+        Expression::ExtractBits(extract) => simplify_extract_bits(extract),
+        Expression::InsertBits(insert) => simplify_insert_bits(insert),
+        Expression::Select(select) => simplify_select(select),
+        _ => expression.clone(),
+    }
+}
+
+fn simplify_arithmetic_operation(operation: &ArithmeticOperation) -> Expression {
+    let lhs = operation.lhs();
+    let rhs = operation.rhs();
+
+    match operation.operator() {
+        ArithmeticOperator::Add if is_integer_zero(rhs) => lhs.clone(),
+        ArithmeticOperator::Add if is_integer_zero(lhs) => rhs.clone(),
+        ArithmeticOperator::Multiply if is_one(rhs) => lhs.clone(),
+        ArithmeticOperator::Multiply if is_one(lhs) => rhs.clone(),
+        ArithmeticOperator::Divide => simplify_division(operation),
+        _ => reassociate_constants(operation).unwrap_or_else(|| operation.clone().into()),
+    }
+}
+
+// `(x * c) / c` is reduced to `x` only if the multiplication does not wrap as
+// division is unsigned.
+fn simplify_division(operation: &ArithmeticOperation) -> Expression {
+    match operation.lhs() {
+        Expression::ArithmeticOperation(multiplication)
+            if multiplication.operator() == ArithmeticOperator::Multiply
+                && multiplication.overflow_flags().no_unsigned_wrap
+                && multiplication.rhs() == operation.rhs()
+                && matches!(operation.rhs(), Expression::Primitive(_))
+                && !is_integer_zero(operation.rhs()) =>
+        {
+            multiplication.lhs().clone()
+        }
+        _ => operation.clone().into(),
+    }
+}
+
+// `(x + c1) + c2` is rewritten into `x + (c1 + c2)`. Floating-point operations
+// are reassociated only if both of them allow it.
+fn reassociate_constants(operation: &ArithmeticOperation) -> Option<Expression> {
+    let inner = match operation.lhs() {
+        Expression::ArithmeticOperation(inner)
+            if matches!(
+                operation.operator(),
+                ArithmeticOperator::Add | ArithmeticOperator::Multiply
+            ) && inner.operator() == operation.operator()
+                && inner.type_() == operation.type_() =>
+        {
+            inner
+        }
+        _ => return None,
+    };
+
+    if !matches!(inner.rhs(), Expression::Primitive(_))
+        || !matches!(operation.rhs(), Expression::Primitive(_))
+        || is_float(operation.type_())
+            && !(inner.fast_math_flags().allow_reassociation
+                && operation.fast_math_flags().allow_reassociation)
+    {
+        return None;
+    }
+
+    let constant: Expression = ArithmeticOperation::new(
+        operation.type_(),
+        operation.operator(),
+        inner.rhs().clone(),
+        operation.rhs().clone(),
+    )
+    .with_fast_math_flags(operation.fast_math_flags())
+    .into();
+
+    Some(
+        ArithmeticOperation::new(
+            operation.type_(),
+            operation.operator(),
+            inner.lhs().clone(),
+            // Pointer integers are not folded as their sizes depend on targets.
+            if operation.type_() == types::Primitive::PointerInteger {
+                constant
+            } else {
+                evaluate_constant_expression(&constant, 0)?.into()
+            },
+        )
+        .with_fast_math_flags(operation.fast_math_flags())
+        .into(),
+    )
+}
+
+fn is_float(type_: types::Primitive) -> bool {
+    matches!(
+        type_,
+        types::Primitive::Float16
+            | types::Primitive::Float32
+            | types::Primitive::Float64
+            | types::Primitive::Float128
+    )
+}
+
+fn simplify_bit_cast(bit_cast: &BitCast) -> Expression {
+    let (from, expression) = match bit_cast.expression() {
+        Expression::BitCast(inner) => (inner.from(), inner.expression()),
+        expression => (bit_cast.from(), expression),
+    };
+
+    if from == bit_cast.to() {
+        expression.clone()
+    } else {
+        BitCast::new(from.clone(), bit_cast.to().clone(), expression.clone()).into()
+    }
+}
+
+// Comparisons of booleans with literals are reduced to the booleans themselves
+// or equalities with `false`.
+fn simplify_comparison_operation(operation: &ComparisonOperation) -> Expression {
+    if operation.type_() != types::Primitive::Boolean {
+        return operation.clone().into();
+    }
+
+    let (value, literal) = match (operation.lhs(), operation.rhs()) {
+        (value, Expression::Primitive(Primitive::Boolean(literal)))
+        | (Expression::Primitive(Primitive::Boolean(literal)), value) => (value, *literal),
+        _ => return operation.clone().into(),
+    };
+
+    match (operation.operator(), literal) {
+        (ComparisonOperator::Equal, true) | (ComparisonOperator::NotEqual, false) => value.clone(),
+        (ComparisonOperator::Equal, false) | (ComparisonOperator::NotEqual, true) => match value {
+            Expression::ComparisonOperation(comparison) => invert_comparison(comparison),
+            _ => ComparisonOperation::new(
+                types::Primitive::Boolean,
+                ComparisonOperator::Equal,
+                value.clone(),
+                Primitive::Boolean(false),
+            ),
+        }
+        .into(),
+        _ => operation.clone().into(),
+    }
+}
+
+// Ordered floating-point comparisons are inverted into unordered ones and vice
+// versa because of NaNs.
+fn invert_comparison(operation: &ComparisonOperation) -> ComparisonOperation {
+    let float = is_float(operation.type_());
+
+    ComparisonOperation::new(
+        operation.type_(),
+        match operation.operator() {
+            ComparisonOperator::Equal if float => ComparisonOperator::UnorderedNotEqual,
+            ComparisonOperator::NotEqual if float => ComparisonOperator::UnorderedEqual,
+            ComparisonOperator::LessThan if float => {
+                ComparisonOperator::UnorderedGreaterThanOrEqual
+            }
+            ComparisonOperator::GreaterThan if float => {
+                ComparisonOperator::UnorderedLessThanOrEqual
+            }
+            ComparisonOperator::LessThanOrEqual if float => {
+                ComparisonOperator::UnorderedGreaterThan
+            }
+            ComparisonOperator::GreaterThanOrEqual if float => {
+                ComparisonOperator::UnorderedLessThan
+            }
+            ComparisonOperator::Equal => ComparisonOperator::NotEqual,
+            ComparisonOperator::NotEqual => ComparisonOperator::Equal,
+            ComparisonOperator::LessThan => ComparisonOperator::GreaterThanOrEqual,
+            ComparisonOperator::GreaterThan => ComparisonOperator::LessThanOrEqual,
+            ComparisonOperator::LessThanOrEqual => ComparisonOperator::GreaterThan,
+            ComparisonOperator::GreaterThanOrEqual => ComparisonOperator::LessThan,
+            ComparisonOperator::Ordered => ComparisonOperator::Unordered,
+            ComparisonOperator::Unordered => ComparisonOperator::Ordered,
+            ComparisonOperator::UnorderedEqual => ComparisonOperator::NotEqual,
+            ComparisonOperator::UnorderedNotEqual => ComparisonOperator::Equal,
+            ComparisonOperator::UnorderedLessThan => ComparisonOperator::GreaterThanOrEqual,
+            ComparisonOperator::UnorderedGreaterThan => ComparisonOperator::LessThanOrEqual,
+            ComparisonOperator::UnorderedLessThanOrEqual => ComparisonOperator::GreaterThan,
+            ComparisonOperator::UnorderedGreaterThanOrEqual => ComparisonOperator::LessThan,
+        },
+        operation.lhs().clone(),
+        operation.rhs().clone(),
+    )
+}
+
+fn simplify_extract_bits(extract: &ExtractBits) -> Expression {
+    match extract.value() {
+        Expression::ExtractBits(inner) if extract.offset() + extract.width() <= inner.width() => {
+            ExtractBits::new(
+                extract.type_(),
+                inner.value().clone(),
+                inner.offset() + extract.offset(),
+                extract.width(),
+            )
+            .into()
+        }
+        _ => extract.clone().into(),
+    }
+}
+
+// Inserting bits extracted from the same value at the same range is no-op.
+fn simplify_insert_bits(insert: &InsertBits) -> Expression {
+    match insert.field() {
+        Expression::ExtractBits(extract)
+            if extract.value() == insert.value()
+                && extract.offset() == insert.offset()
+                && extract.width() == insert.width() =>
+        {
+            insert.value().clone()
+        }
+        _ => insert.clone().into(),
+    }
+}
+
+fn simplify_select(select: &Select) -> Expression {
+    match select.condition() {
+        Expression::Primitive(Primitive::Boolean(true)) => select.then().clone(),
+        Expression::Primitive(Primitive::Boolean(false)) => select.else_().clone(),
+        _ if select.then() == select.else_() => select.then().clone(),
+        _ => select.clone().into(),
+    }
+}
+
+// Floating-point zeros are not folded because `-0.0 + 0.0` is `0.0`.
+fn is_integer_zero(expression: &Expression) -> bool {
+    matches!(
+        expression,
+        Expression::Primitive(
+            Primitive::Integer8(0)
+                | Primitive::Integer16(0)
+                | Primitive::Integer32(0)
+                | Primitive::Integer64(0)
+                | Primitive::Integer128(0)
+                | Primitive::PointerInteger(0)
+        )
+    )
+}
+
+fn is_one(expression: &Expression) -> bool {
+    match expression {
+        Expression::Primitive(primitive) => match primitive {
+            Primitive::Float16(number) | Primitive::Float32(number) => *number == 1.0,
+            Primitive::Float64(number) | Primitive::Float128(number) => *number == 1.0,
+            Primitive::Integer8(number) => *number == 1,
+            Primitive::Integer16(number) => *number == 1,
+            Primitive::Integer32(number) => *number == 1,
+            Primitive::Integer64(number) => *number == 1,
+            Primitive::Integer128(number) => *number == 1,
+            Primitive::PointerInteger(number) => *number == 1,
+            Primitive::Boolean(_) => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention, Type};
+
+    fn simplify(expression: impl Into<Expression>, type_: impl Into<Type>) -> Expression {
+        let type_ = type_.into();
+
+        simplify_peepholes(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("x", type_.clone())],
+                Block::new(vec![], Return::new(type_.clone(), expression)),
+                type_,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        ))
+        .function_definitions()[0]
+            .body()
+            .terminal_instruction()
+            .to_return()
+            .unwrap()
+            .expression()
+            .clone()
+    }
+
+    #[test]
+    fn remove_identity_bit_cast() {
+        assert_eq!(
+            simplify(
+                BitCast::new(
+                    types::Primitive::Integer64,
+                    types::Primitive::Integer64,
+                    Variable::new("x")
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn collapse_bit_casts() {
+        assert_eq!(
+            simplify(
+                BitCast::new(
+                    types::Primitive::Float64,
+                    types::Primitive::PointerInteger,
+                    BitCast::new(
+                        types::Primitive::Integer64,
+                        types::Primitive::Float64,
+                        Variable::new("x")
+                    )
+                ),
+                types::Primitive::PointerInteger
+            ),
+            BitCast::new(
+                types::Primitive::Integer64,
+                types::Primitive::PointerInteger,
+                Variable::new("x")
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn remove_round_trip_bit_casts() {
+        assert_eq!(
+            simplify(
+                BitCast::new(
+                    types::Primitive::Float64,
+                    types::Primitive::Integer64,
+                    BitCast::new(
+                        types::Primitive::Integer64,
+                        types::Primitive::Float64,
+                        Variable::new("x")
+                    )
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn remove_double_bitwise_not() {
+        assert_eq!(
+            simplify(
+                BitwiseNotOperation::new(
+                    types::Primitive::Integer64,
+                    BitwiseNotOperation::new(types::Primitive::Integer64, Variable::new("x"))
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    fn create_comparison(
+        type_: types::Primitive,
+        operator: ComparisonOperator,
+        lhs: impl Into<Expression>,
+        rhs: impl Into<Expression>,
+    ) -> ComparisonOperation {
+        ComparisonOperation::new(type_, operator, lhs, rhs)
+    }
+
+    #[test]
+    fn simplify_comparison_with_true() {
+        for (operator, lhs, rhs) in [
+            (
+                ComparisonOperator::Equal,
+                Expression::from(Variable::new("x")),
+                Expression::from(Primitive::Boolean(true)),
+            ),
+            (
+                ComparisonOperator::Equal,
+                Primitive::Boolean(true).into(),
+                Variable::new("x").into(),
+            ),
+            (
+                ComparisonOperator::NotEqual,
+                Variable::new("x").into(),
+                Primitive::Boolean(false).into(),
+            ),
+        ] {
+            assert_eq!(
+                simplify(
+                    create_comparison(types::Primitive::Boolean, operator, lhs, rhs),
+                    types::Primitive::Boolean
+                ),
+                Variable::new("x").into()
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalize_comparison_with_false() {
+        assert_eq!(
+            simplify(
+                create_comparison(
+                    types::Primitive::Boolean,
+                    ComparisonOperator::NotEqual,
+                    Primitive::Boolean(true),
+                    Variable::new("x"),
+                ),
+                types::Primitive::Boolean
+            ),
+            create_comparison(
+                types::Primitive::Boolean,
+                ComparisonOperator::Equal,
+                Variable::new("x"),
+                Primitive::Boolean(false),
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn invert_comparison_compared_with_false() {
+        assert_eq!(
+            simplify(
+                create_comparison(
+                    types::Primitive::Boolean,
+                    ComparisonOperator::Equal,
+                    create_comparison(
+                        types::Primitive::Integer32,
+                        ComparisonOperator::LessThan,
+                        Variable::new("x"),
+                        Primitive::Integer32(42),
+                    ),
+                    Primitive::Boolean(false),
+                ),
+                types::Primitive::Integer32
+            ),
+            create_comparison(
+                types::Primitive::Integer32,
+                ComparisonOperator::GreaterThanOrEqual,
+                Variable::new("x"),
+                Primitive::Integer32(42),
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn invert_negated_comparison() {
+        assert_eq!(
+            simplify(
+                BitwiseNotOperation::new(
+                    types::Primitive::Boolean,
+                    create_comparison(
+                        types::Primitive::Integer32,
+                        ComparisonOperator::NotEqual,
+                        Variable::new("x"),
+                        Primitive::Integer32(42),
+                    ),
+                ),
+                types::Primitive::Integer32
+            ),
+            create_comparison(
+                types::Primitive::Integer32,
+                ComparisonOperator::Equal,
+                Variable::new("x"),
+                Primitive::Integer32(42),
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn invert_negated_float_comparison() {
+        assert_eq!(
+            simplify(
+                BitwiseNotOperation::new(
+                    types::Primitive::Boolean,
+                    create_comparison(
+                        types::Primitive::Float64,
+                        ComparisonOperator::LessThan,
+                        Variable::new("x"),
+                        Primitive::Float64(42.0),
+                    ),
+                ),
+                types::Primitive::Float64
+            ),
+            create_comparison(
+                types::Primitive::Float64,
+                ComparisonOperator::UnorderedGreaterThanOrEqual,
+                Variable::new("x"),
+                Primitive::Float64(42.0),
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn merge_nested_bit_extractions() {
+        assert_eq!(
+            simplify(
+                ExtractBits::new(
+                    types::Primitive::Integer32,
+                    ExtractBits::new(types::Primitive::Integer32, Variable::new("x"), 3, 5),
+                    1,
+                    2
+                ),
+                types::Primitive::Integer32
+            ),
+            ExtractBits::new(types::Primitive::Integer32, Variable::new("x"), 4, 2).into()
+        );
+    }
+
+    #[test]
+    fn remove_insertion_of_extracted_bits() {
+        assert_eq!(
+            simplify(
+                InsertBits::new(
+                    types::Primitive::Integer32,
+                    Variable::new("x"),
+                    ExtractBits::new(types::Primitive::Integer32, Variable::new("x"), 3, 5),
+                    3,
+                    5
+                ),
+                types::Primitive::Integer32
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn remove_addition_of_zero() {
+        assert_eq!(
+            simplify(
+                ArithmeticOperation::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperator::Add,
+                    Variable::new("x"),
+                    Primitive::Integer64(0)
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn remove_addition_to_zero() {
+        assert_eq!(
+            simplify(
+                ArithmeticOperation::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperator::Add,
+                    Primitive::Integer64(0),
+                    Variable::new("x")
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn keep_addition_of_float_zero() {
+        let operation = ArithmeticOperation::new(
+            types::Primitive::Float64,
+            ArithmeticOperator::Add,
+            Variable::new("x"),
+            Primitive::Float64(0.0),
+        );
+
+        assert_eq!(
+            simplify(operation.clone(), types::Primitive::Float64),
+            operation.into()
+        );
+    }
+
+    #[test]
+    fn remove_multiplication_by_one() {
+        assert_eq!(
+            simplify(
+                ArithmeticOperation::new(
+                    types::Primitive::Float64,
+                    ArithmeticOperator::Multiply,
+                    Variable::new("x"),
+                    Primitive::Float64(1.0)
+                ),
+                types::Primitive::Float64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn remove_multiplication_of_one() {
+        assert_eq!(
+            simplify(
+                ArithmeticOperation::new(
+                    types::Primitive::Integer32,
+                    ArithmeticOperator::Multiply,
+                    Primitive::Integer32(1),
+                    Variable::new("x")
+                ),
+                types::Primitive::Integer32
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    fn add_with_flags(
+        lhs: impl Into<Expression>,
+        rhs: impl Into<Expression>,
+        flags: FastMathFlags,
+    ) -> ArithmeticOperation {
+        ArithmeticOperation::new(types::Primitive::Float64, ArithmeticOperator::Add, lhs, rhs)
+            .with_fast_math_flags(flags)
+    }
+
+    #[test]
+    fn reassociate_integer_constants() {
+        assert_eq!(
+            simplify(
+                ArithmeticOperation::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperator::Add,
+                    ArithmeticOperation::new(
+                        types::Primitive::Integer64,
+                        ArithmeticOperator::Add,
+                        Variable::new("x"),
+                        Primitive::Integer64(1),
+                    ),
+                    Primitive::Integer64(2),
+                ),
+                types::Primitive::Integer64
+            ),
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Variable::new("x"),
+                Primitive::Integer64(3),
+            )
+            .into()
+        );
+    }
+
+    #[test]
+    fn reassociate_float_constants_with_flag() {
+        let flags = FastMathFlags {
+            allow_reassociation: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            simplify(
+                add_with_flags(
+                    add_with_flags(Variable::new("x"), Primitive::Float64(1.0), flags),
+                    Primitive::Float64(2.0),
+                    flags,
+                ),
+                types::Primitive::Float64
+            ),
+            add_with_flags(Variable::new("x"), Primitive::Float64(3.0), flags).into()
+        );
+    }
+
+    #[test]
+    fn keep_float_constants_without_reassociation_flag() {
+        let flags = FastMathFlags {
+            allow_contraction: true,
+            ..Default::default()
+        };
+        let operation = add_with_flags(
+            add_with_flags(Variable::new("x"), Primitive::Float64(1.0), flags),
+            Primitive::Float64(2.0),
+            flags,
+        );
+
+        assert_eq!(
+            simplify(operation.clone(), types::Primitive::Float64),
+            operation.into()
+        );
+    }
+
+    fn multiply_and_divide(flags: OverflowFlags) -> ArithmeticOperation {
+        ArithmeticOperation::new(
+            types::Primitive::Integer32,
+            ArithmeticOperator::Divide,
+            ArithmeticOperation::new(
+                types::Primitive::Integer32,
+                ArithmeticOperator::Multiply,
+                Variable::new("x"),
+                Primitive::Integer32(4),
+            )
+            .with_overflow_flags(flags),
+            Primitive::Integer32(4),
+        )
+    }
+
+    #[test]
+    fn reduce_division_of_multiplication_without_unsigned_wrap() {
+        assert_eq!(
+            simplify(
+                multiply_and_divide(OverflowFlags {
+                    no_unsigned_wrap: true,
+                    ..Default::default()
+                }),
+                types::Primitive::Integer32
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn keep_division_of_wrapping_multiplication() {
+        for flags in [
+            Default::default(),
+            OverflowFlags {
+                no_signed_wrap: true,
+                ..Default::default()
+            },
+        ]
+        .iter()
+        {
+            let operation = multiply_and_divide(*flags);
+
+            assert_eq!(
+                simplify(operation.clone(), types::Primitive::Integer32),
+                operation.into()
+            );
+        }
+    }
+
+    #[test]
+    fn simplify_select_with_constant_condition() {
+        assert_eq!(
+            simplify(
+                Select::new(
+                    types::Primitive::Integer64,
+                    Primitive::Boolean(false),
+                    Primitive::Integer64(1),
+                    Variable::new("x")
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn simplify_select_with_same_values() {
+        assert_eq!(
+            simplify(
+                Select::new(
+                    types::Primitive::Integer64,
+                    ComparisonOperation::new(
+                        types::Primitive::Integer64,
+                        ComparisonOperator::Equal,
+                        Variable::new("x"),
+                        Primitive::Integer64(0)
+                    ),
+                    Variable::new("x"),
+                    Variable::new("x")
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+
+    #[test]
+    fn simplify_nested_expressions() {
+        assert_eq!(
+            simplify(
+                ArithmeticOperation::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperator::Add,
+                    BitCast::new(
+                        types::Primitive::Integer64,
+                        types::Primitive::Integer64,
+                        Variable::new("x")
+                    ),
+                    ArithmeticOperation::new(
+                        types::Primitive::Integer64,
+                        ArithmeticOperator::Multiply,
+                        Primitive::Integer64(0),
+                        Primitive::Integer64(1)
+                    )
+                ),
+                types::Primitive::Integer64
+            ),
+            Variable::new("x").into()
+        );
+    }
+}
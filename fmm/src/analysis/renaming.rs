@@ -1,4 +1,5 @@
-use crate::ir::*;
+use crate::{build::NameGenerator, ir::*};
+use std::collections::{HashMap, HashSet};
 
 pub fn rename_names(module: &Module, rename: impl Fn(&str) -> String) -> Module {
     Module::new(
@@ -25,6 +26,180 @@ pub fn rename_names(module: &Module, rename: impl Fn(&str) -> String) -> Module
     )
 }
 
+// Local names shadow global ones of the same names from their binding points
+// to the ends of their blocks.
+pub fn substitute_names(module: &Module, names: &HashMap<String, String>) -> Module {
+    let rename = |name: &str| names.get(name).cloned().unwrap_or_else(|| name.into());
+
+    Module::new(
+        module
+            .variable_declarations()
+            .iter()
+            .map(|declaration| rename_variable_declaration(declaration, &rename))
+            .collect(),
+        module
+            .function_declarations()
+            .iter()
+            .map(|declaration| rename_function_declaration(declaration, &rename))
+            .collect(),
+        module
+            .variable_definitions()
+            .iter()
+            .map(|definition| rename_variable_definition(definition, &rename))
+            .collect(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                let mut names = names.clone();
+
+                for argument in definition.arguments() {
+                    names.remove(argument.name());
+                }
+
+                FunctionDefinition::new(
+                    rename(definition.name()),
+                    definition.arguments().to_vec(),
+                    rename_block_in_scope(definition.body(), &names, &mut |_| None),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+pub fn make_local_names_unique(module: &Module) -> Module {
+    let mut generate_name = create_name_generator(module, "_l");
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                let arguments = definition
+                    .arguments()
+                    .iter()
+                    .map(|argument| {
+                        Argument::with_attributes(
+                            generate_name(),
+                            argument.type_().clone(),
+                            argument.attributes(),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let names = definition
+                    .arguments()
+                    .iter()
+                    .zip(&arguments)
+                    .map(|(argument, renamed)| (argument.name().into(), renamed.name().into()))
+                    .collect();
+
+                FunctionDefinition::new(
+                    definition.name(),
+                    arguments,
+                    rename_block_in_scope(
+                        definition.body(),
+                        &names,
+                        &mut |_| Some(generate_name()),
+                    ),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+// Every binding in a block is renamed to a generated one so that the block can
+// be inlined into other blocks without shadowing their names.
+pub(crate) fn rename_bindings_in_block(
+    block: &Block,
+    generate_name: &mut impl FnMut() -> String,
+) -> Block {
+    rename_block_in_scope(block, &Default::default(), &mut |_| Some(generate_name()))
+}
+
+// Generated names collide with no names in a module.
+pub(crate) fn create_name_generator(module: &Module, prefix: &str) -> impl FnMut() -> String {
+    let names = collect_names(module);
+    let mut name_generator = NameGenerator::new(prefix);
+
+    move || loop {
+        let name = name_generator.generate();
+
+        if !names.contains(&name) {
+            return name;
+        }
+    }
+}
+
+fn collect_names(module: &Module) -> HashSet<String> {
+    module
+        .variable_declarations()
+        .iter()
+        .map(|declaration| declaration.name())
+        .chain(
+            module
+                .function_declarations()
+                .iter()
+                .map(|declaration| declaration.name()),
+        )
+        .chain(
+            module
+                .variable_definitions()
+                .iter()
+                .map(|definition| definition.name()),
+        )
+        .chain(
+            module
+                .function_definitions()
+                .iter()
+                .map(|definition| definition.name()),
+        )
+        .map(String::from)
+        .chain(
+            module
+                .function_definitions()
+                .iter()
+                .flat_map(collect_local_names),
+        )
+        .collect()
+}
+
+fn collect_local_names(definition: &FunctionDefinition) -> Vec<String> {
+    definition
+        .arguments()
+        .iter()
+        .map(|argument| argument.name().into())
+        .chain(collect_local_names_in_block(definition.body()))
+        .collect()
+}
+
+fn collect_local_names_in_block(block: &Block) -> Vec<String> {
+    block
+        .instructions()
+        .iter()
+        .flat_map(|instruction| {
+            instruction.name().map(String::from).into_iter().chain(
+                if let Instruction::If(if_) = instruction {
+                    collect_local_names_in_block(if_.then())
+                        .into_iter()
+                        .chain(collect_local_names_in_block(if_.else_()))
+                        .collect()
+                } else {
+                    vec![]
+                },
+            )
+        })
+        .collect()
+}
+
 fn rename_variable_declaration(
     declaration: &VariableDeclaration,
     rename: &impl Fn(&str) -> String,
@@ -36,7 +211,11 @@ fn rename_function_declaration(
     declaration: &FunctionDeclaration,
     rename: &impl Fn(&str) -> String,
 ) -> FunctionDeclaration {
-    FunctionDeclaration::new(rename(declaration.name()), declaration.type_().clone())
+    FunctionDeclaration::with_attributes(
+        rename(declaration.name()),
+        declaration.type_().clone(),
+        declaration.attributes(),
+    )
 }
 
 fn rename_variable_definition(
@@ -62,7 +241,13 @@ fn rename_function_definition(
         definition
             .arguments()
             .iter()
-            .map(|argument| Argument::new(rename(argument.name()), argument.type_().clone()))
+            .map(|argument| {
+                Argument::with_attributes(
+                    rename(argument.name()),
+                    argument.type_().clone(),
+                    argument.attributes(),
+                )
+            })
             .collect(),
         rename_block(definition.body(), rename),
         definition.result_type().clone(),
@@ -82,6 +267,52 @@ fn rename_block(block: &Block, rename: &impl Fn(&str) -> String) -> Block {
     )
 }
 
+// Bindings are renamed to names returned by `bind` or shadow names renamed
+// outside if it returns none.
+fn rename_block_in_scope(
+    block: &Block,
+    names: &HashMap<String, String>,
+    bind: &mut impl FnMut(&str) -> Option<String>,
+) -> Block {
+    let mut names = names.clone();
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        let rename = |name: &str| names.get(name).cloned().unwrap_or_else(|| name.into());
+        let mut renamed = if let Instruction::If(if_) = instruction {
+            If::new(
+                if_.type_().clone(),
+                rename_expression(if_.condition(), &rename),
+                rename_block_in_scope(if_.then(), &names, bind),
+                rename_block_in_scope(if_.else_(), &names, bind),
+                if_.name(),
+            )
+            .into()
+        } else {
+            rename_instruction(instruction, &rename)
+        };
+
+        if let (Some(name), Some(renamed_name)) = (instruction.name(), renamed.name_mut()) {
+            if let Some(new_name) = bind(name) {
+                names.insert(name.into(), new_name.clone());
+                *renamed_name = new_name;
+            } else {
+                names.remove(name);
+                *renamed_name = name.into();
+            }
+        }
+
+        instructions.push(renamed);
+    }
+
+    Block::new(
+        instructions,
+        rename_terminal_instruction(block.terminal_instruction(), &|name| {
+            names.get(name).cloned().unwrap_or_else(|| name.into())
+        }),
+    )
+}
+
 fn rename_instruction(instruction: &Instruction, rename: &impl Fn(&str) -> String) -> Instruction {
     let rename_expression = |expression| rename_expression(expression, rename);
 
@@ -125,6 +356,15 @@ fn rename_instruction(instruction: &Instruction, rename: &impl Fn(&str) -> Strin
             rename(call.name()),
         )
         .into(),
+        Instruction::CheckedArithmeticOperation(operation) => CheckedArithmeticOperation::new(
+            operation.type_(),
+            operation.operator(),
+            operation.signed(),
+            rename_expression(operation.lhs()),
+            rename_expression(operation.rhs()),
+            rename(operation.name()),
+        )
+        .into(),
         Instruction::CompareAndSwap(cas) => CompareAndSwap::new(
             cas.type_().clone(),
             rename_expression(cas.pointer()),
@@ -150,7 +390,19 @@ fn rename_instruction(instruction: &Instruction, rename: &impl Fn(&str) -> Strin
         )
         .into(),
         Instruction::Fence(fence) => fence.clone().into(),
+        Instruction::Fma(fma) => Fma::new(
+            fma.type_(),
+            rename_expression(fma.lhs()),
+            rename_expression(fma.rhs()),
+            rename_expression(fma.addend()),
+            rename(fma.name()),
+        )
+        .into(),
         Instruction::FreeHeap(free) => FreeHeap::new(rename_expression(free.pointer())).into(),
+        Instruction::LifetimeEnd(end) => LifetimeEnd::new(rename_expression(end.pointer())).into(),
+        Instruction::LifetimeStart(start) => {
+            LifetimeStart::new(rename_expression(start.pointer())).into()
+        }
         Instruction::If(if_) => If::new(
             if_.type_().clone(),
             rename_expression(if_.condition()),
@@ -159,12 +411,42 @@ fn rename_instruction(instruction: &Instruction, rename: &impl Fn(&str) -> Strin
             rename(if_.name()),
         )
         .into(),
+        Instruction::InlineAssembly(assembly) => InlineAssembly::new(
+            assembly.type_().clone(),
+            assembly.template(),
+            assembly.constraints(),
+            assembly.operands().iter().map(&rename_expression).collect(),
+            assembly.side_effects(),
+            assembly.align_stack(),
+            rename(assembly.name()),
+        )
+        .into(),
         Instruction::Load(load) => Load::new(
             load.type_().clone(),
             rename_expression(load.pointer()),
             rename(load.name()),
         )
         .into(),
+        Instruction::MathOperation(operation) => MathOperation::new(
+            operation.type_(),
+            operation.operator(),
+            rename_expression(operation.value()),
+            rename(operation.name()),
+        )
+        .into(),
+        Instruction::MemoryCopy(copy) => MemoryCopy::new(
+            rename_expression(copy.source()),
+            rename_expression(copy.destination()),
+            rename_expression(copy.size()),
+            copy.overlapping(),
+        )
+        .into(),
+        Instruction::MemorySet(set) => MemorySet::new(
+            rename_expression(set.pointer()),
+            rename_expression(set.value()),
+            rename_expression(set.size()),
+        )
+        .into(),
         Instruction::PassThrough(pass) => PassThrough::new(
             pass.type_().clone(),
             rename_expression(pass.expression()),
@@ -177,6 +459,17 @@ fn rename_instruction(instruction: &Instruction, rename: &impl Fn(&str) -> Strin
             rename(reallocate.name()),
         )
         .into(),
+        Instruction::SaturatingArithmeticOperation(operation) => {
+            SaturatingArithmeticOperation::new(
+                operation.type_(),
+                operation.operator(),
+                operation.signed(),
+                rename_expression(operation.lhs()),
+                rename_expression(operation.rhs()),
+                rename(operation.name()),
+            )
+            .into()
+        }
         Instruction::Store(store) => Store::new(
             store.type_().clone(),
             rename_expression(store.value()),
@@ -201,6 +494,7 @@ fn rename_terminal_instruction(
             rename_expression(return_.expression(), rename),
         )
         .into(),
+        TerminalInstruction::Trap => TerminalInstruction::Trap,
         TerminalInstruction::Unreachable => TerminalInstruction::Unreachable,
     }
 }
@@ -215,6 +509,8 @@ fn rename_expression(expression: &Expression, rename: &impl Fn(&str) -> String)
             rename_expression(operation.lhs()),
             rename_expression(operation.rhs()),
         )
+        .with_fast_math_flags(operation.fast_math_flags())
+        .with_overflow_flags(operation.overflow_flags())
         .into(),
         Expression::BitCast(bit_cast) => BitCast::new(
             bit_cast.from().clone(),
@@ -222,6 +518,12 @@ fn rename_expression(expression: &Expression, rename: &impl Fn(&str) -> String)
             rename_expression(bit_cast.expression()),
         )
         .into(),
+        Expression::BitManipulationOperation(operation) => BitManipulationOperation::new(
+            operation.type_(),
+            operation.operator(),
+            rename_expression(operation.value()),
+        )
+        .into(),
         Expression::BitwiseNotOperation(operation) => {
             BitwiseNotOperation::new(operation.type_(), rename_expression(operation.value())).into()
         }
@@ -232,6 +534,12 @@ fn rename_expression(expression: &Expression, rename: &impl Fn(&str) -> String)
             rename_expression(operation.rhs()),
         )
         .into(),
+        Expression::ByteOffset(offset) => ByteOffset::new(
+            offset.type_().clone(),
+            rename_expression(offset.pointer()),
+            rename_expression(offset.offset()),
+        )
+        .into(),
         Expression::ComparisonOperation(operation) => ComparisonOperation::new(
             operation.type_(),
             operation.operator(),
@@ -239,12 +547,33 @@ fn rename_expression(expression: &Expression, rename: &impl Fn(&str) -> String)
             rename_expression(operation.rhs()),
         )
         .into(),
+        Expression::ExtractBits(extract) => ExtractBits::new(
+            extract.type_(),
+            rename_expression(extract.value()),
+            extract.offset(),
+            extract.width(),
+        )
+        .into(),
+        Expression::InsertBits(insert) => InsertBits::new(
+            insert.type_(),
+            rename_expression(insert.value()),
+            rename_expression(insert.field()),
+            insert.offset(),
+            insert.width(),
+        )
+        .into(),
         Expression::PointerAddress(address) => PointerAddress::new(
             address.type_().clone(),
             rename_expression(address.pointer()),
             rename_expression(address.offset()),
         )
         .into(),
+        Expression::PureCall(call) => PureCall::new(
+            call.type_().clone(),
+            rename_expression(call.function()),
+            call.arguments().iter().map(rename_expression).collect(),
+        )
+        .into(),
         Expression::Record(record) => Record::new(
             record.type_().clone(),
             record
@@ -260,6 +589,13 @@ fn rename_expression(expression: &Expression, rename: &impl Fn(&str) -> String)
             address.element_index(),
         )
         .into(),
+        Expression::Select(select) => Select::new(
+            select.type_().clone(),
+            rename_expression(select.condition()),
+            rename_expression(select.then()),
+            rename_expression(select.else_()),
+        )
+        .into(),
         Expression::Union(union) => Union::new(
             union.type_().clone(),
             union.member_index(),
@@ -273,7 +609,8 @@ fn rename_expression(expression: &Expression, rename: &impl Fn(&str) -> String)
         )
         .into(),
         Expression::Variable(variable) => Variable::new(rename(variable.name())).into(),
-        Expression::AlignOf(_)
+        Expression::AbsoluteAddress(_)
+        | Expression::AlignOf(_)
         | Expression::Primitive(_)
         | Expression::SizeOf(_)
         | Expression::Undefined(_) => expression.clone(),
@@ -350,6 +687,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn keep_arithmetic_operation_flags() {
+        let create_module = |name: &str| {
+            Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![Argument::new(name, types::Primitive::Integer32)],
+                    Block::new(
+                        vec![],
+                        Return::new(
+                            types::Primitive::Integer32,
+                            ArithmeticOperation::new(
+                                types::Primitive::Integer32,
+                                ArithmeticOperator::Add,
+                                Variable::new(name),
+                                Primitive::Integer32(1),
+                            )
+                            .with_overflow_flags(OverflowFlags {
+                                no_signed_wrap: true,
+                                no_unsigned_wrap: false,
+                            }),
+                        ),
+                    ),
+                    types::Primitive::Integer32,
+                )],
+            )
+        };
+
+        assert_eq!(
+            rename_names(&create_module("x"), |name| if name == "x" {
+                "y".into()
+            } else {
+                name.into()
+            }),
+            create_module("y")
+        );
+    }
+
     #[test]
     fn rename_function_declaration() {
         let function_type = create_function_type(vec![], types::Primitive::PointerInteger);
@@ -559,4 +937,340 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn rename_argument_with_attributes() {
+        let attributes = ArgumentAttributes {
+            no_alias: true,
+            ..Default::default()
+        };
+        let create_module = |name: &str| {
+            Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![Argument::with_attributes(
+                        name,
+                        types::GENERIC_POINTER_TYPE.clone(),
+                        attributes,
+                    )],
+                    Block::new(
+                        vec![],
+                        Return::new(types::GENERIC_POINTER_TYPE.clone(), Variable::new(name)),
+                    ),
+                    types::GENERIC_POINTER_TYPE.clone(),
+                )],
+            )
+        };
+
+        assert_eq!(
+            rename_names(&create_module("x"), |name| if name == "x" {
+                "y".into()
+            } else {
+                name.into()
+            }),
+            create_module("y")
+        );
+    }
+
+    mod substitution {
+        use super::*;
+
+        fn create_module(body: Block) -> Module {
+            Module::new(
+                vec![VariableDeclaration::new(
+                    "x",
+                    types::Primitive::PointerInteger,
+                )],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    body,
+                    types::Pointer::new(types::Primitive::PointerInteger),
+                )],
+            )
+        }
+
+        fn create_names() -> HashMap<String, String> {
+            vec![("x".into(), "y".into())].into_iter().collect()
+        }
+
+        #[test]
+        fn substitute_variable_in_two_blocks() {
+            let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+            let create_body = |name| {
+                Block::new(
+                    vec![If::new(
+                        pointer_type.clone(),
+                        Primitive::Boolean(true),
+                        Block::new(
+                            vec![],
+                            Branch::new(pointer_type.clone(), Variable::new(name)),
+                        ),
+                        Block::new(
+                            vec![],
+                            Branch::new(pointer_type.clone(), Variable::new(name)),
+                        ),
+                        "z",
+                    )
+                    .into()],
+                    Return::new(pointer_type.clone(), Variable::new("z")),
+                )
+            };
+
+            assert_eq!(
+                substitute_names(&create_module(create_body("x")), &create_names()),
+                Module::new(
+                    vec![VariableDeclaration::new(
+                        "y",
+                        types::Primitive::PointerInteger,
+                    )],
+                    vec![],
+                    vec![],
+                    create_module(create_body("y"))
+                        .function_definitions()
+                        .to_vec(),
+                )
+            );
+        }
+
+        #[test]
+        fn do_not_substitute_shadowed_variable() {
+            let body = Block::new(
+                vec![AllocateStack::new(types::Primitive::PointerInteger, "x").into()],
+                Return::new(
+                    types::Pointer::new(types::Primitive::PointerInteger),
+                    Variable::new("x"),
+                ),
+            );
+
+            assert_eq!(
+                substitute_names(&create_module(body.clone()), &create_names())
+                    .function_definitions()[0]
+                    .body(),
+                &body
+            );
+        }
+
+        #[test]
+        fn substitute_variable_before_shadowing_binding() {
+            let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+            let create_body = |name| {
+                Block::new(
+                    vec![
+                        PassThrough::new(pointer_type.clone(), Variable::new(name), "z").into(),
+                        AllocateStack::new(types::Primitive::PointerInteger, "x").into(),
+                    ],
+                    Return::new(pointer_type.clone(), Variable::new("x")),
+                )
+            };
+
+            assert_eq!(
+                substitute_names(&create_module(create_body("x")), &create_names())
+                    .function_definitions()[0]
+                    .body(),
+                &create_body("y")
+            );
+        }
+
+        #[test]
+        fn substitute_variable_after_shadowing_block() {
+            let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+            let create_body = |name| {
+                Block::new(
+                    vec![If::new(
+                        pointer_type.clone(),
+                        Primitive::Boolean(true),
+                        Block::new(
+                            vec![AllocateStack::new(types::Primitive::PointerInteger, "x").into()],
+                            Branch::new(pointer_type.clone(), Variable::new("x")),
+                        ),
+                        Block::new(
+                            vec![],
+                            Branch::new(pointer_type.clone(), Variable::new(name)),
+                        ),
+                        "z",
+                    )
+                    .into()],
+                    Return::new(pointer_type.clone(), Variable::new(name)),
+                )
+            };
+
+            assert_eq!(
+                substitute_names(&create_module(create_body("x")), &create_names())
+                    .function_definitions()[0]
+                    .body(),
+                &create_body("y")
+            );
+        }
+    }
+
+    #[test]
+    fn make_shadowing_local_names_unique() {
+        let create_module = |names: [&str; 4]| {
+            Module::new(
+                vec![VariableDeclaration::new(
+                    "g",
+                    types::Primitive::PointerInteger,
+                )],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![
+                            PassThrough::new(
+                                types::Primitive::PointerInteger,
+                                Variable::new("g"),
+                                names[0],
+                            )
+                            .into(),
+                            If::new(
+                                types::Primitive::PointerInteger,
+                                Primitive::Boolean(true),
+                                Block::new(
+                                    vec![PassThrough::new(
+                                        types::Primitive::PointerInteger,
+                                        Primitive::PointerInteger(2),
+                                        names[1],
+                                    )
+                                    .into()],
+                                    Branch::new(
+                                        types::Primitive::PointerInteger,
+                                        Variable::new(names[1]),
+                                    ),
+                                ),
+                                Block::new(
+                                    vec![],
+                                    Branch::new(
+                                        types::Primitive::PointerInteger,
+                                        Variable::new(names[0]),
+                                    ),
+                                ),
+                                names[2],
+                            )
+                            .into(),
+                            PassThrough::new(
+                                types::Primitive::PointerInteger,
+                                Variable::new(names[0]),
+                                names[3],
+                            )
+                            .into(),
+                        ],
+                        Return::new(types::Primitive::PointerInteger, Variable::new(names[3])),
+                    ),
+                    types::Primitive::PointerInteger,
+                )],
+            )
+        };
+
+        assert_eq!(
+            make_local_names_unique(&create_module(["x", "x", "y", "g"])),
+            create_module(["_l0", "_l1", "_l2", "_l3"])
+        );
+    }
+
+    #[test]
+    fn make_local_names_unique_across_functions() {
+        let create_definition = |name, local_name| {
+            create_function_definition(
+                name,
+                vec![Argument::new(local_name, types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![PassThrough::new(
+                        types::Primitive::PointerInteger,
+                        Variable::new(local_name),
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                types::Primitive::PointerInteger,
+            )
+        };
+
+        assert_eq!(
+            make_local_names_unique(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_definition("f", "x"), create_definition("g", "x")]
+            )),
+            Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![
+                    create_function_definition(
+                        "f",
+                        vec![Argument::new("_l0", types::Primitive::PointerInteger)],
+                        Block::new(
+                            vec![PassThrough::new(
+                                types::Primitive::PointerInteger,
+                                Variable::new("_l0"),
+                                "_l1",
+                            )
+                            .into()],
+                            Return::new(types::Primitive::PointerInteger, Variable::new("_l1")),
+                        ),
+                        types::Primitive::PointerInteger,
+                    ),
+                    create_function_definition(
+                        "g",
+                        vec![Argument::new("_l2", types::Primitive::PointerInteger)],
+                        Block::new(
+                            vec![PassThrough::new(
+                                types::Primitive::PointerInteger,
+                                Variable::new("_l2"),
+                                "_l3",
+                            )
+                            .into()],
+                            Return::new(types::Primitive::PointerInteger, Variable::new("_l3")),
+                        ),
+                        types::Primitive::PointerInteger,
+                    )
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn rename_inline_assembly_round_trip() {
+        let module = Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![InlineAssembly::new(
+                        types::Primitive::PointerInteger,
+                        "mov $1, $0",
+                        "=r,r,~{memory}",
+                        vec![Variable::new("x").into()],
+                        true,
+                        true,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                types::Primitive::PointerInteger,
+            )],
+        );
+
+        assert_eq!(
+            rename_names(
+                &rename_names(&module, |name| format!("_{}", name)),
+                |name| name[1..].into()
+            ),
+            module
+        );
+    }
 }
@@ -0,0 +1,234 @@
+use super::renaming::create_name_generator;
+use crate::{
+    ir::*,
+    types::{self, CallingConvention, VOID_TYPE},
+};
+
+// Calls to poll functions are inserted at entries of functions and before
+// calls of the source calling convention. The poll functions use the target
+// calling convention so that the CPS transformation keeps them intact.
+pub fn insert_safepoints(module: &Module, poll_function_name: &str) -> Module {
+    let mut generate_name = create_name_generator(module, "_safepoint_");
+    let is_declared = module
+        .function_declarations()
+        .iter()
+        .any(|declaration| declaration.name() == poll_function_name);
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module
+            .function_declarations()
+            .iter()
+            .cloned()
+            .chain(if is_declared {
+                None
+            } else {
+                Some(FunctionDeclaration::new(
+                    poll_function_name,
+                    poll_function_type(),
+                ))
+            })
+            .collect(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                // Poll functions do not poll themselves.
+                if definition.name() == poll_function_name {
+                    return definition.clone();
+                }
+
+                let poll_call = create_poll_call(&mut generate_name, poll_function_name);
+
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    insert_in_block(&mut generate_name, definition.body(), poll_function_name)
+                        .with_prepended([poll_call]),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn insert_in_block(
+    generate_name: &mut impl FnMut() -> String,
+    block: &Block,
+    poll_function_name: &str,
+) -> Block {
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        match instruction {
+            Instruction::Call(call)
+                if call.type_().calling_convention() == CallingConvention::Source =>
+            {
+                instructions.push(create_poll_call(generate_name, poll_function_name));
+                instructions.push(instruction.clone());
+            }
+            Instruction::If(if_) => instructions.push(
+                If::new(
+                    if_.type_().clone(),
+                    if_.condition().clone(),
+                    insert_in_block(generate_name, if_.then(), poll_function_name),
+                    insert_in_block(generate_name, if_.else_(), poll_function_name),
+                    if_.name(),
+                )
+                .into(),
+            ),
+            _ => instructions.push(instruction.clone()),
+        }
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn create_poll_call(
+    generate_name: &mut impl FnMut() -> String,
+    poll_function_name: &str,
+) -> Instruction {
+    Call::new(
+        poll_function_type(),
+        Variable::new(poll_function_name),
+        vec![],
+        generate_name(),
+    )
+    .into()
+}
+
+fn poll_function_type() -> types::Function {
+    types::Function::new(vec![], VOID_TYPE.clone(), CallingConvention::Target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{check_types, transform_to_cps};
+
+    fn create_source_call(name: &str) -> Instruction {
+        Call::new(
+            types::Function::new(
+                vec![],
+                types::Primitive::PointerInteger,
+                CallingConvention::Source,
+            ),
+            Variable::new("f"),
+            vec![],
+            name,
+        )
+        .into()
+    }
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![FunctionDeclaration::new(
+                "f",
+                types::Function::new(
+                    vec![],
+                    types::Primitive::PointerInteger,
+                    CallingConvention::Source,
+                ),
+            )],
+            vec![],
+            vec![FunctionDefinition::new(
+                "g",
+                vec![],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Source,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn is_poll_call(instruction: &Instruction) -> bool {
+        matches!(
+            instruction,
+            Instruction::Call(call) if call.function() == &Variable::new("poll").into()
+        )
+    }
+
+    #[test]
+    fn insert_safepoint_at_function_entry() {
+        let module = insert_safepoints(
+            &create_module(vec![PassThrough::new(
+                types::Primitive::PointerInteger,
+                Primitive::PointerInteger(42),
+                "x",
+            )
+            .into()]),
+            "poll",
+        );
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert_eq!(instructions.len(), 2);
+        assert!(is_poll_call(&instructions[0]));
+    }
+
+    #[test]
+    fn insert_safepoint_before_source_call() {
+        let module = insert_safepoints(&create_module(vec![create_source_call("x")]), "poll");
+        let instructions = module.function_definitions()[0].body().instructions();
+
+        assert_eq!(instructions.len(), 3);
+        assert!(is_poll_call(&instructions[1]));
+        assert_eq!(instructions[2], create_source_call("x"));
+        assert_eq!(
+            module.function_declarations()[1],
+            FunctionDeclaration::new("poll", poll_function_type())
+        );
+    }
+
+    #[test]
+    fn insert_safepoint_in_if() {
+        let module = insert_safepoints(
+            &create_module(vec![If::new(
+                types::Primitive::PointerInteger,
+                Primitive::Boolean(true),
+                Block::new(
+                    vec![create_source_call("y")],
+                    Branch::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                Block::new(
+                    vec![],
+                    Branch::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(0),
+                    ),
+                ),
+                "x",
+            )
+            .into()]),
+            "poll",
+        );
+        let if_ = match &module.function_definitions()[0].body().instructions()[1] {
+            Instruction::If(if_) => if_,
+            _ => unreachable!(),
+        };
+
+        assert!(is_poll_call(&if_.then().instructions()[0]));
+        assert!(if_.else_().instructions().is_empty());
+    }
+
+    #[test]
+    fn transform_safepoints_to_cps() {
+        let module = insert_safepoints(
+            &create_module(vec![
+                create_source_call("y"),
+                PassThrough::new(types::Primitive::PointerInteger, Variable::new("y"), "x").into(),
+            ]),
+            "poll",
+        );
+
+        check_types(&module).unwrap();
+        check_types(&transform_to_cps(&module, VOID_TYPE.clone()).unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,328 @@
+use super::free_variables::{collect_free_variables, collect_from_instruction};
+use crate::ir::*;
+
+// Pure instructions used only in one arm of an `If` instruction are moved into
+// the arm so that they are not computed on the other path.
+pub fn sink(module: &Module) -> Module {
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    sink_block(definition.body()),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn sink_block(block: &Block) -> Block {
+    let mut instructions = block.instructions().to_vec();
+
+    // Instructions are visited backward so that chains of instructions sink
+    // together.
+    for index in (0..instructions.len()).rev() {
+        sink_instruction(&mut instructions, index, block.terminal_instruction());
+    }
+
+    Block::new(
+        instructions
+            .iter()
+            .map(|instruction| match instruction {
+                Instruction::If(if_) => If::new(
+                    if_.type_().clone(),
+                    if_.condition().clone(),
+                    sink_block(if_.then()),
+                    sink_block(if_.else_()),
+                    if_.name(),
+                )
+                .into(),
+                _ => instruction.clone(),
+            })
+            .collect(),
+        block.terminal_instruction().clone(),
+    )
+}
+
+fn sink_instruction(
+    instructions: &mut Vec<Instruction>,
+    index: usize,
+    terminal_instruction: &TerminalInstruction,
+) {
+    let instruction = &instructions[index];
+    let name = match instruction.name() {
+        Some(name) if is_pure(instruction) => name,
+        _ => return,
+    };
+
+    if collect_free_variables(&[], terminal_instruction).contains(name) {
+        return;
+    }
+
+    let mut users = instructions
+        .iter()
+        .enumerate()
+        .skip(index + 1)
+        .filter(|(_, instruction)| collect_from_instruction(instruction).contains(name));
+
+    let (if_index, if_) = match (users.next(), users.next()) {
+        (Some((if_index, Instruction::If(if_))), None) => (if_index, if_),
+        _ => return,
+    };
+
+    // Instructions cannot be moved across bindings of their names or free
+    // variables.
+    let mut variables = collect_from_instruction(instruction);
+    variables.insert(name.into());
+
+    if instructions[index + 1..if_index]
+        .iter()
+        .any(|instruction| matches!(instruction.name(), Some(name) if variables.contains(name)))
+    {
+        return;
+    }
+
+    // Loads cannot be moved across instructions which might write memory.
+    if matches!(instruction, Instruction::Load(_))
+        && !instructions[index + 1..if_index].iter().all(is_pure)
+    {
+        return;
+    }
+
+    let is_free = |block: &Block| {
+        collect_free_variables(block.instructions(), block.terminal_instruction()).contains(name)
+    };
+    let used_in_condition = collect_free_variables(
+        &[],
+        &Branch::new(if_.type_().clone(), if_.condition().clone()).into(),
+    )
+    .contains(name);
+
+    let if_ = match (used_in_condition, is_free(if_.then()), is_free(if_.else_())) {
+        (false, true, false) => If::new(
+            if_.type_().clone(),
+            if_.condition().clone(),
+            prepend_instruction(instruction.clone(), if_.then()),
+            if_.else_().clone(),
+            if_.name(),
+        ),
+        (false, false, true) => If::new(
+            if_.type_().clone(),
+            if_.condition().clone(),
+            if_.then().clone(),
+            prepend_instruction(instruction.clone(), if_.else_()),
+            if_.name(),
+        ),
+        _ => return,
+    };
+
+    instructions[if_index] = if_.into();
+    instructions.remove(index);
+}
+
+fn prepend_instruction(instruction: Instruction, block: &Block) -> Block {
+    Block::new(
+        vec![instruction]
+            .into_iter()
+            .chain(block.instructions().iter().cloned())
+            .collect(),
+        block.terminal_instruction().clone(),
+    )
+}
+
+pub(crate) fn is_pure(instruction: &Instruction) -> bool {
+    matches!(
+        instruction,
+        Instruction::CheckedArithmeticOperation(_)
+            | Instruction::DeconstructRecord(_)
+            | Instruction::DeconstructUnion(_)
+            | Instruction::Fma(_)
+            | Instruction::Load(_)
+            | Instruction::MathOperation(_)
+            | Instruction::PassThrough(_)
+            | Instruction::SaturatingArithmeticOperation(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("p", types::Pointer::new(types::Primitive::PointerInteger)),
+                    Argument::new("c", types::Primitive::Boolean),
+                ],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::PointerInteger, Variable::new("z")),
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_load() -> Instruction {
+        Load::new(types::Primitive::PointerInteger, Variable::new("p"), "x").into()
+    }
+
+    fn create_if(then: Vec<Instruction>, then_value: impl Into<Expression>) -> Instruction {
+        If::new(
+            types::Primitive::PointerInteger,
+            Variable::new("c"),
+            Block::new(
+                then,
+                Branch::new(types::Primitive::PointerInteger, then_value),
+            ),
+            Block::new(
+                vec![],
+                Branch::new(
+                    types::Primitive::PointerInteger,
+                    Primitive::PointerInteger(0),
+                ),
+            ),
+            "z",
+        )
+        .into()
+    }
+
+    #[test]
+    fn sink_load() {
+        assert_eq!(
+            sink(&create_module(vec![
+                create_load(),
+                create_if(vec![], Variable::new("x")),
+            ])),
+            create_module(vec![create_if(vec![create_load()], Variable::new("x"))])
+        );
+    }
+
+    #[test]
+    fn sink_chain_of_instructions() {
+        let pass_through: Instruction =
+            PassThrough::new(types::Primitive::PointerInteger, Variable::new("x"), "y").into();
+
+        assert_eq!(
+            sink(&create_module(vec![
+                create_load(),
+                pass_through.clone(),
+                create_if(vec![], Variable::new("y")),
+            ])),
+            create_module(vec![create_if(
+                vec![create_load(), pass_through],
+                Variable::new("y")
+            )])
+        );
+    }
+
+    #[test]
+    fn do_not_sink_load_across_store() {
+        let module = create_module(vec![
+            create_load(),
+            Store::new(
+                types::Primitive::PointerInteger,
+                Primitive::PointerInteger(42),
+                Variable::new("p"),
+            )
+            .into(),
+            create_if(vec![], Variable::new("x")),
+        ]);
+
+        assert_eq!(sink(&module), module);
+    }
+
+    #[test]
+    fn do_not_sink_instruction_across_rebinding_of_its_name() {
+        let module = create_module(vec![
+            PassThrough::new(
+                types::Primitive::PointerInteger,
+                Primitive::PointerInteger(42),
+                "x",
+            )
+            .into(),
+            Call::new(
+                types::Function::new(
+                    vec![],
+                    types::Primitive::PointerInteger,
+                    CallingConvention::Target,
+                ),
+                Variable::new("g"),
+                vec![],
+                "x",
+            )
+            .into(),
+            create_if(vec![], Variable::new("x")),
+        ]);
+
+        assert_eq!(sink(&module), module);
+    }
+
+    #[test]
+    fn do_not_sink_instruction_across_rebinding_of_its_free_variable() {
+        let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+        let module = create_module(vec![
+            create_load(),
+            PassThrough::new(pointer_type.clone(), Undefined::new(pointer_type), "p").into(),
+            create_if(vec![], Variable::new("x")),
+        ]);
+
+        assert_eq!(sink(&module), module);
+    }
+
+    #[test]
+    fn do_not_sink_instruction_used_in_both_arms() {
+        let module = create_module(vec![
+            create_load(),
+            If::new(
+                types::Primitive::PointerInteger,
+                Variable::new("c"),
+                Block::new(
+                    vec![],
+                    Branch::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                Block::new(
+                    vec![],
+                    Branch::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                "z",
+            )
+            .into(),
+        ]);
+
+        assert_eq!(sink(&module), module);
+    }
+
+    #[test]
+    fn do_not_sink_side_effect() {
+        let module = create_module(vec![
+            AtomicLoad::new(
+                types::Primitive::PointerInteger,
+                Variable::new("p"),
+                AtomicOrdering::SequentiallyConsistent,
+                "x",
+            )
+            .into(),
+            create_if(vec![], Variable::new("x")),
+        ]);
+
+        assert_eq!(sink(&module), module);
+    }
+}
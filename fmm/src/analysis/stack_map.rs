@@ -0,0 +1,248 @@
+use super::free_variables::collect_free_variables;
+use crate::{ir::*, types::Type};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackRoot {
+    name: String,
+    type_: Type,
+}
+
+impl StackRoot {
+    pub fn new(name: impl Into<String>, type_: impl Into<Type>) -> Self {
+        Self {
+            name: name.into(),
+            type_: type_.into(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_(&self) -> &Type {
+        &self.type_
+    }
+}
+
+// Safepoints are identified by names of their poll calls.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StackMap {
+    roots: BTreeMap<String, Vec<StackRoot>>,
+}
+
+impl StackMap {
+    pub fn roots(&self, safepoint: &str) -> Option<&[StackRoot]> {
+        self.roots.get(safepoint).map(Vec::as_slice)
+    }
+
+    pub fn safepoints(&self) -> impl Iterator<Item = &str> {
+        self.roots.keys().map(String::as_str)
+    }
+}
+
+// Roots are pointer-typed local variables live across safepoints.
+pub fn collect_stack_map(module: &Module, poll_function_name: &str) -> StackMap {
+    let mut stack_map = StackMap::default();
+
+    for definition in module.function_definitions() {
+        collect_from_block(
+            &mut stack_map,
+            definition.body(),
+            &definition
+                .arguments()
+                .iter()
+                .map(|argument| (argument.name().into(), argument.type_().clone()))
+                .collect(),
+            &Default::default(),
+            poll_function_name,
+        );
+    }
+
+    stack_map
+}
+
+fn collect_from_block(
+    stack_map: &mut StackMap,
+    block: &Block,
+    variables: &HashMap<String, Type>,
+    live_variables: &HashSet<String>,
+    poll_function_name: &str,
+) {
+    let mut variables = variables.clone();
+    let instructions = block.instructions();
+
+    for (index, instruction) in instructions.iter().enumerate() {
+        // Variables used after instructions in the current block or in their
+        // parent blocks
+        let live_after = || {
+            collect_free_variables(&instructions[index + 1..], block.terminal_instruction())
+                .into_iter()
+                .chain(
+                    live_variables
+                        .iter()
+                        .filter(|name| {
+                            instructions[..=index]
+                                .iter()
+                                .all(|instruction| instruction.name() != Some(name.as_str()))
+                        })
+                        .cloned(),
+                )
+                .collect::<HashSet<_>>()
+        };
+
+        match instruction {
+            Instruction::Call(call)
+                if call.function() == &Variable::new(poll_function_name).into() =>
+            {
+                let live_after = live_after();
+                let mut roots = variables
+                    .iter()
+                    .filter(|(name, type_)| {
+                        live_after.contains(name.as_str()) && matches!(type_, Type::Pointer(_))
+                    })
+                    .map(|(name, type_)| StackRoot::new(name, type_.clone()))
+                    .collect::<Vec<_>>();
+
+                roots.sort_by(|one, other| one.name().cmp(other.name()));
+
+                stack_map.roots.insert(call.name().into(), roots);
+            }
+            Instruction::If(if_) => {
+                let mut live_after = live_after();
+
+                live_after.remove(if_.name());
+
+                for block in [if_.then(), if_.else_()] {
+                    collect_from_block(
+                        stack_map,
+                        block,
+                        &variables,
+                        &live_after,
+                        poll_function_name,
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        if let (Some(name), Some(type_)) = (instruction.name(), instruction.result_type()) {
+            variables.insert(name.into(), type_);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        analysis::insert_safepoints,
+        types::{self, CallingConvention, GENERIC_POINTER_TYPE},
+    };
+
+    fn create_module(instructions: Vec<Instruction>, result: &str) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("p", GENERIC_POINTER_TYPE.clone()),
+                    Argument::new("q", GENERIC_POINTER_TYPE.clone()),
+                    Argument::new("n", types::Primitive::PointerInteger),
+                ],
+                Block::new(
+                    instructions,
+                    Return::new(GENERIC_POINTER_TYPE.clone(), Variable::new(result)),
+                ),
+                GENERIC_POINTER_TYPE.clone(),
+                CallingConvention::Source,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_poll_call(name: &str) -> Instruction {
+        Call::new(
+            types::Function::new(vec![], types::VOID_TYPE.clone(), CallingConvention::Target),
+            Variable::new("poll"),
+            vec![],
+            name,
+        )
+        .into()
+    }
+
+    #[test]
+    fn record_live_pointer() {
+        let stack_map = collect_stack_map(&create_module(vec![create_poll_call("s")], "p"), "poll");
+
+        assert_eq!(
+            stack_map.roots("s"),
+            Some(&[StackRoot::new("p", GENERIC_POINTER_TYPE.clone())][..])
+        );
+    }
+
+    #[test]
+    fn skip_dead_pointer() {
+        let stack_map = collect_stack_map(
+            &create_module(
+                vec![
+                    PassThrough::new(GENERIC_POINTER_TYPE.clone(), Variable::new("q"), "x").into(),
+                    create_poll_call("s"),
+                ],
+                "x",
+            ),
+            "poll",
+        );
+
+        assert_eq!(
+            stack_map.roots("s"),
+            Some(&[StackRoot::new("x", GENERIC_POINTER_TYPE.clone())][..])
+        );
+    }
+
+    #[test]
+    fn record_pointer_live_after_if() {
+        let stack_map = collect_stack_map(
+            &create_module(
+                vec![If::new(
+                    GENERIC_POINTER_TYPE.clone(),
+                    Primitive::Boolean(true),
+                    Block::new(
+                        vec![create_poll_call("s")],
+                        Branch::new(GENERIC_POINTER_TYPE.clone(), Variable::new("p")),
+                    ),
+                    Block::new(
+                        vec![],
+                        Branch::new(GENERIC_POINTER_TYPE.clone(), Variable::new("p")),
+                    ),
+                    "x",
+                )
+                .into()],
+                "q",
+            ),
+            "poll",
+        );
+
+        assert_eq!(
+            stack_map.roots("s"),
+            Some(
+                &[
+                    StackRoot::new("p", GENERIC_POINTER_TYPE.clone()),
+                    StackRoot::new("q", GENERIC_POINTER_TYPE.clone())
+                ][..]
+            )
+        );
+    }
+
+    #[test]
+    fn collect_stack_map_at_inserted_safepoints() {
+        let stack_map = collect_stack_map(
+            &insert_safepoints(&create_module(vec![], "q"), "poll"),
+            "poll",
+        );
+
+        assert_eq!(stack_map.safepoints().count(), 1);
+    }
+}
@@ -0,0 +1,230 @@
+use super::{free_variables::collect_from_instruction, renaming::create_name_generator};
+use crate::{build, ir::*};
+
+// Identical tail calls at the ends of both arms of an `If` instruction in a
+// tail position are merged into a single call after the instruction.
+pub fn merge_tail_calls(module: &Module) -> Module {
+    let mut generate_name = create_name_generator(module, "_tail_");
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    merge_block(definition.body(), &mut generate_name),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn merge_block(block: &Block, generate_name: &mut impl FnMut() -> String) -> Block {
+    let mut instructions = block
+        .instructions()
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::If(if_) => If::new(
+                if_.type_().clone(),
+                if_.condition().clone(),
+                merge_block(if_.then(), generate_name),
+                merge_block(if_.else_(), generate_name),
+                if_.name(),
+            )
+            .into(),
+            _ => instruction.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    if let (Some(Instruction::If(if_)), TerminalInstruction::Unreachable) =
+        (instructions.last(), block.terminal_instruction())
+    {
+        if let (Some((call, return_)), Some(other)) =
+            (get_tail_call(if_.then()), get_tail_call(if_.else_()))
+        {
+            if (call, return_) == other && !is_defined_in_arms(call, if_) {
+                let (call, return_) = (call.clone(), return_.clone());
+                let if_ = If::new(
+                    build::void_type(),
+                    if_.condition().clone(),
+                    remove_tail_call(if_.then()),
+                    remove_tail_call(if_.else_()),
+                    generate_name(),
+                );
+
+                instructions.pop();
+                instructions.extend(vec![if_.into(), call.into()]);
+
+                return Block::new(instructions, return_);
+            }
+        }
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn get_tail_call(block: &Block) -> Option<(&Call, &Return)> {
+    match (block.instructions().last(), block.terminal_instruction()) {
+        (Some(Instruction::Call(call)), TerminalInstruction::Return(return_))
+            if return_.expression() == &Variable::new(call.name()).into() =>
+        {
+            Some((call, return_))
+        }
+        _ => None,
+    }
+}
+
+fn is_defined_in_arms(call: &Call, if_: &If) -> bool {
+    let variables = collect_from_instruction(&call.clone().into());
+
+    [if_.then(), if_.else_()].iter().any(|block| {
+        block.instructions()[..block.instructions().len() - 1]
+            .iter()
+            .any(|instruction| {
+                instruction
+                    .name()
+                    .map(|name| variables.contains(name))
+                    .unwrap_or_default()
+            })
+    })
+}
+
+fn remove_tail_call(block: &Block) -> Block {
+    Block::new(
+        block.instructions()[..block.instructions().len() - 1].to_vec(),
+        Branch::new(build::void_type(), build::void_value()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_function_type() -> types::Function {
+        types::Function::new(
+            vec![types::Primitive::PointerInteger.into()],
+            types::Primitive::PointerInteger,
+            CallingConvention::Tail,
+        )
+    }
+
+    fn create_tail_call_block(
+        instructions: Vec<Instruction>,
+        argument: impl Into<Expression>,
+    ) -> Block {
+        Block::new(
+            instructions
+                .into_iter()
+                .chain(vec![Call::new(
+                    create_function_type(),
+                    Variable::new("k"),
+                    vec![argument.into()],
+                    "r",
+                )
+                .into()])
+                .collect(),
+            Return::new(types::Primitive::PointerInteger, Variable::new("r")),
+        )
+    }
+
+    fn create_module(then: Block, else_: Block) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("k", create_function_type()),
+                    Argument::new("x", types::Primitive::Boolean),
+                ],
+                Block::new(
+                    vec![If::new(build::void_type(), Variable::new("x"), then, else_, "y").into()],
+                    TerminalInstruction::Unreachable,
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Tail,
+                Linkage::External,
+            )],
+        )
+    }
+
+    #[test]
+    fn merge_identical_tail_calls() {
+        let store: Instruction = Store::new(
+            types::Primitive::PointerInteger,
+            Primitive::PointerInteger(42),
+            Undefined::new(types::Pointer::new(types::Primitive::PointerInteger)),
+        )
+        .into();
+
+        let module = merge_tail_calls(&create_module(
+            create_tail_call_block(vec![store.clone()], Primitive::PointerInteger(0)),
+            create_tail_call_block(vec![], Primitive::PointerInteger(0)),
+        ));
+        let body = module.function_definitions()[0].body();
+
+        assert_eq!(
+            body.instructions(),
+            &[
+                If::new(
+                    build::void_type(),
+                    Variable::new("x"),
+                    Block::new(
+                        vec![store],
+                        Branch::new(build::void_type(), build::void_value())
+                    ),
+                    Block::new(vec![], Branch::new(build::void_type(), build::void_value())),
+                    "_tail_0",
+                )
+                .into(),
+                Call::new(
+                    create_function_type(),
+                    Variable::new("k"),
+                    vec![Primitive::PointerInteger(0).into()],
+                    "r",
+                )
+                .into(),
+            ]
+        );
+        assert_eq!(
+            body.terminal_instruction(),
+            &Return::new(types::Primitive::PointerInteger, Variable::new("r")).into()
+        );
+    }
+
+    #[test]
+    fn keep_different_tail_calls() {
+        let module = create_module(
+            create_tail_call_block(vec![], Primitive::PointerInteger(0)),
+            create_tail_call_block(vec![], Primitive::PointerInteger(1)),
+        );
+
+        assert_eq!(merge_tail_calls(&module), module);
+    }
+
+    #[test]
+    fn keep_tail_calls_with_arguments_defined_in_arms() {
+        let instruction: Instruction = PassThrough::new(
+            types::Primitive::PointerInteger,
+            Primitive::PointerInteger(0),
+            "z",
+        )
+        .into();
+        let module = create_module(
+            create_tail_call_block(vec![instruction.clone()], Variable::new("z")),
+            create_tail_call_block(vec![instruction], Variable::new("z")),
+        );
+
+        assert_eq!(merge_tail_calls(&module), module);
+    }
+}
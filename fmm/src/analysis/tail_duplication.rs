@@ -0,0 +1,513 @@
+use super::{
+    cost::block_cost,
+    expression_conversion::{
+        convert_expressions_in_instruction, convert_expressions_in_terminal_instruction,
+    },
+    free_variables::collect_from_expression,
+    renaming::{create_name_generator, rename_bindings_in_block},
+    sinking::is_pure,
+    target_data_layout::TargetDataLayout,
+};
+use crate::ir::*;
+use std::collections::HashSet;
+
+// Small tails of blocks after `If` instructions are duplicated into their arms
+// so that other passes can specialize them with values of the arms.
+pub fn duplicate_tails(module: &Module, layout: &TargetDataLayout, cost_budget: u32) -> Module {
+    let mut generate_name = create_name_generator(module, "_tail_");
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    duplicate_block(definition.body(), layout, cost_budget, &mut generate_name),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn duplicate_block(
+    block: &Block,
+    layout: &TargetDataLayout,
+    cost_budget: u32,
+    generate_name: &mut impl FnMut() -> String,
+) -> Block {
+    let mut instructions = block
+        .instructions()
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::If(if_) => If::new(
+                if_.type_().clone(),
+                if_.condition().clone(),
+                duplicate_block(if_.then(), layout, cost_budget, generate_name),
+                duplicate_block(if_.else_(), layout, cost_budget, generate_name),
+                if_.name(),
+            )
+            .into(),
+            _ => instruction.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let (index, if_) = match find_last_if(&instructions) {
+        Some((index, if_)) => (index, if_.clone()),
+        None => return Block::new(instructions, block.terminal_instruction().clone()),
+    };
+    let tail = Block::new(
+        instructions[index + 1..].to_vec(),
+        block.terminal_instruction().clone(),
+    );
+
+    // Instructions with side effects are not duplicated.
+    if tail.instructions().is_empty()
+        || !tail.instructions().iter().all(is_pure)
+        || block_cost(&tail, layout) > cost_budget
+        || if_.then().terminal_instruction().to_branch().is_none()
+            && if_.else_().terminal_instruction().to_branch().is_none()
+    {
+        return Block::new(instructions, block.terminal_instruction().clone());
+    }
+
+    // The `If` instruction's value is now a value of the tail.
+    let type_ = match block.terminal_instruction() {
+        TerminalInstruction::Branch(branch) => branch.type_().clone(),
+        _ => if_.type_().clone(),
+    };
+
+    instructions.truncate(index);
+    instructions.push(
+        If::new(
+            type_.clone(),
+            if_.condition().clone(),
+            append_tail(if_.then(), if_.name(), &tail, generate_name),
+            append_tail(if_.else_(), if_.name(), &tail, generate_name),
+            if_.name(),
+        )
+        .into(),
+    );
+
+    Block::new(
+        instructions,
+        match block.terminal_instruction() {
+            TerminalInstruction::Branch(_) => Branch::new(type_, Variable::new(if_.name())).into(),
+            _ => TerminalInstruction::Unreachable,
+        },
+    )
+}
+
+fn find_last_if(instructions: &[Instruction]) -> Option<(usize, &If)> {
+    instructions
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(index, instruction)| match instruction {
+            Instruction::If(if_) => Some((index, if_)),
+            _ => None,
+        })
+}
+
+fn append_tail(
+    block: &Block,
+    name: &str,
+    tail: &Block,
+    generate_name: &mut impl FnMut() -> String,
+) -> Block {
+    if block.terminal_instruction().to_branch().is_none() {
+        return block.clone();
+    }
+
+    // Bindings in arms are renamed so that they do not shadow free variables of
+    // tails.
+    let block = rename_bindings_in_block(block, generate_name);
+    let branch = block.terminal_instruction().to_branch().unwrap();
+    let tail_names = tail
+        .instructions()
+        .iter()
+        .filter_map(|instruction| instruction.name())
+        .collect::<HashSet<_>>();
+
+    // Values of arms cannot be substituted into tails that rebind their names.
+    if tail_names.contains(name)
+        || collect_from_expression(branch.expression())
+            .iter()
+            .any(|name| tail_names.contains(name.as_str()))
+    {
+        return Block::new(
+            block
+                .instructions()
+                .iter()
+                .cloned()
+                .chain([PassThrough::new(
+                    branch.type_().clone(),
+                    branch.expression().clone(),
+                    name,
+                )
+                .into()])
+                .chain(tail.instructions().iter().cloned())
+                .collect(),
+            tail.terminal_instruction().clone(),
+        );
+    }
+
+    let substitute = |expression: &Expression| match expression {
+        Expression::Variable(variable) if variable.name() == name => branch.expression().clone(),
+        _ => expression.clone(),
+    };
+
+    Block::new(
+        block
+            .instructions()
+            .iter()
+            .cloned()
+            .chain(
+                tail.instructions().iter().map(|instruction| {
+                    convert_expressions_in_instruction(instruction, &substitute)
+                }),
+            )
+            .collect(),
+        convert_expressions_in_terminal_instruction(tail.terminal_instruction(), &substitute),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::constant_evaluation::evaluate_constant_expression, *};
+    use crate::types::{self, CallingConvention};
+
+    const LAYOUT: TargetDataLayout = TargetDataLayout::new(8, 8);
+
+    fn create_module(
+        instructions: Vec<Instruction>,
+        terminal_instruction: TerminalInstruction,
+    ) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![
+                    Argument::new("c", types::Primitive::Boolean),
+                    Argument::new("p", types::Pointer::new(types::Primitive::Integer64)),
+                ],
+                Block::new(instructions, terminal_instruction),
+                types::Primitive::Integer64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_if(then: Block, else_: Block) -> Instruction {
+        If::new(
+            types::Primitive::Integer64,
+            Variable::new("c"),
+            then,
+            else_,
+            "x",
+        )
+        .into()
+    }
+
+    fn create_branch(value: u64) -> Block {
+        Block::new(
+            vec![],
+            Branch::new(types::Primitive::Integer64, Primitive::Integer64(value)),
+        )
+    }
+
+    fn create_addition(lhs: impl Into<Expression>) -> Instruction {
+        PassThrough::new(
+            types::Primitive::Integer64,
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                lhs,
+                Primitive::Integer64(1),
+            ),
+            "y",
+        )
+        .into()
+    }
+
+    fn create_return() -> TerminalInstruction {
+        Return::new(types::Primitive::Integer64, Variable::new("y")).into()
+    }
+
+    #[test]
+    fn duplicate_tail_into_arms() {
+        assert_eq!(
+            duplicate_tails(
+                &create_module(
+                    vec![
+                        create_if(create_branch(1), create_branch(2)),
+                        create_addition(Variable::new("x")),
+                    ],
+                    create_return(),
+                ),
+                &LAYOUT,
+                10
+            ),
+            create_module(
+                vec![create_if(
+                    Block::new(
+                        vec![create_addition(Primitive::Integer64(1))],
+                        create_return(),
+                    ),
+                    Block::new(
+                        vec![create_addition(Primitive::Integer64(2))],
+                        create_return(),
+                    ),
+                )],
+                TerminalInstruction::Unreachable,
+            )
+        );
+    }
+
+    #[test]
+    fn fold_constant_after_duplication() {
+        let module = duplicate_tails(
+            &create_module(
+                vec![
+                    create_if(create_branch(1), create_branch(2)),
+                    create_addition(Variable::new("x")),
+                ],
+                create_return(),
+            ),
+            &LAYOUT,
+            10,
+        );
+        let if_ = match &module.function_definitions()[0].body().instructions()[0] {
+            Instruction::If(if_) => if_.clone(),
+            _ => unreachable!(),
+        };
+        let evaluate = |block: &Block| match &block.instructions()[0] {
+            Instruction::PassThrough(pass) => evaluate_constant_expression(pass.expression(), 8),
+            _ => None,
+        };
+
+        assert_eq!(evaluate(if_.then()), Some(Primitive::Integer64(2)));
+        assert_eq!(evaluate(if_.else_()), Some(Primitive::Integer64(3)));
+    }
+
+    #[test]
+    fn duplicate_tail_ending_with_branch() {
+        let create_outer_if = |then| -> Module {
+            create_module(
+                vec![If::new(
+                    types::Primitive::Integer64,
+                    Variable::new("c"),
+                    then,
+                    create_branch(0),
+                    "y",
+                )
+                .into()],
+                create_return(),
+            )
+        };
+        let create_branch_of_addition = |lhs| {
+            Block::new(
+                vec![PassThrough::new(
+                    types::Primitive::Integer64,
+                    ArithmeticOperation::new(
+                        types::Primitive::Integer64,
+                        ArithmeticOperator::Add,
+                        lhs,
+                        Primitive::Integer64(1),
+                    ),
+                    "z",
+                )
+                .into()],
+                Branch::new(types::Primitive::Integer64, Variable::new("z")),
+            )
+        };
+
+        assert_eq!(
+            duplicate_tails(
+                &create_outer_if(Block::new(
+                    vec![
+                        create_if(create_branch(1), create_branch(2)),
+                        PassThrough::new(
+                            types::Primitive::Integer64,
+                            ArithmeticOperation::new(
+                                types::Primitive::Integer64,
+                                ArithmeticOperator::Add,
+                                Variable::new("x"),
+                                Primitive::Integer64(1),
+                            ),
+                            "z",
+                        )
+                        .into(),
+                    ],
+                    Branch::new(types::Primitive::Integer64, Variable::new("z")),
+                )),
+                &LAYOUT,
+                10
+            ),
+            create_outer_if(Block::new(
+                vec![create_if(
+                    create_branch_of_addition(Primitive::Integer64(1)),
+                    create_branch_of_addition(Primitive::Integer64(2)),
+                )],
+                Branch::new(types::Primitive::Integer64, Variable::new("x")),
+            ))
+        );
+    }
+
+    fn create_sum(lhs: &str, rhs: &str, name: &str) -> Instruction {
+        PassThrough::new(
+            types::Primitive::Integer64,
+            ArithmeticOperation::new(
+                types::Primitive::Integer64,
+                ArithmeticOperator::Add,
+                Variable::new(lhs),
+                Variable::new(rhs),
+            ),
+            name,
+        )
+        .into()
+    }
+
+    fn create_constant(value: u64, name: &str) -> Instruction {
+        PassThrough::new(
+            types::Primitive::Integer64,
+            Primitive::Integer64(value),
+            name,
+        )
+        .into()
+    }
+
+    #[test]
+    fn rename_arm_binding_shadowing_free_variable_of_tail() {
+        assert_eq!(
+            duplicate_tails(
+                &create_module(
+                    vec![
+                        create_constant(5, "a"),
+                        create_if(
+                            Block::new(
+                                vec![create_constant(2, "a")],
+                                Branch::new(types::Primitive::Integer64, Variable::new("a")),
+                            ),
+                            create_branch(0),
+                        ),
+                        create_sum("x", "a", "y"),
+                    ],
+                    create_return(),
+                ),
+                &LAYOUT,
+                10
+            ),
+            create_module(
+                vec![
+                    create_constant(5, "a"),
+                    create_if(
+                        Block::new(
+                            vec![
+                                create_constant(2, "_tail_0"),
+                                create_sum("_tail_0", "a", "y")
+                            ],
+                            create_return(),
+                        ),
+                        Block::new(
+                            vec![PassThrough::new(
+                                types::Primitive::Integer64,
+                                ArithmeticOperation::new(
+                                    types::Primitive::Integer64,
+                                    ArithmeticOperator::Add,
+                                    Primitive::Integer64(0),
+                                    Variable::new("a"),
+                                ),
+                                "y",
+                            )
+                            .into()],
+                            create_return(),
+                        ),
+                    ),
+                ],
+                TerminalInstruction::Unreachable,
+            )
+        );
+    }
+
+    #[test]
+    fn bind_arm_value_in_tail_rebinding_its_name() {
+        assert_eq!(
+            duplicate_tails(
+                &create_module(
+                    vec![
+                        create_if(create_branch(1), create_branch(2)),
+                        create_sum("x", "x", "x"),
+                        create_sum("x", "x", "y"),
+                    ],
+                    create_return(),
+                ),
+                &LAYOUT,
+                10
+            ),
+            create_module(
+                vec![create_if(
+                    Block::new(
+                        vec![
+                            create_constant(1, "x"),
+                            create_sum("x", "x", "x"),
+                            create_sum("x", "x", "y"),
+                        ],
+                        create_return(),
+                    ),
+                    Block::new(
+                        vec![
+                            create_constant(2, "x"),
+                            create_sum("x", "x", "x"),
+                            create_sum("x", "x", "y"),
+                        ],
+                        create_return(),
+                    ),
+                )],
+                TerminalInstruction::Unreachable,
+            )
+        );
+    }
+
+    #[test]
+    fn do_not_duplicate_tail_with_store() {
+        let module = create_module(
+            vec![
+                create_if(create_branch(1), create_branch(2)),
+                create_addition(Variable::new("x")),
+                Store::new(
+                    types::Primitive::Integer64,
+                    Variable::new("y"),
+                    Variable::new("p"),
+                )
+                .into(),
+            ],
+            create_return(),
+        );
+
+        assert_eq!(duplicate_tails(&module, &LAYOUT, 10), module);
+    }
+
+    #[test]
+    fn do_not_duplicate_tail_over_budget() {
+        let module = create_module(
+            vec![
+                create_if(create_branch(1), create_branch(2)),
+                create_addition(Variable::new("x")),
+            ],
+            create_return(),
+        );
+
+        assert_eq!(duplicate_tails(&module, &LAYOUT, 1), module);
+    }
+}
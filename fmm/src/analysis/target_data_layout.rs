@@ -0,0 +1,23 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TargetDataLayout {
+    pointer_size: usize,
+    max_atomic_width: usize,
+}
+
+impl TargetDataLayout {
+    pub const fn new(pointer_size: usize, max_atomic_width: usize) -> Self {
+        Self {
+            pointer_size,
+            max_atomic_width,
+        }
+    }
+
+    pub fn pointer_size(&self) -> usize {
+        self.pointer_size
+    }
+
+    // The maximum width of atomic operations in bytes
+    pub fn max_atomic_width(&self) -> usize {
+        self.max_atomic_width
+    }
+}
@@ -1,17 +1,118 @@
+mod atomics;
+mod environment;
 mod error;
 mod names;
+mod pure_calls;
 
 use crate::{
     ir::*,
     types::{self, Type, GENERIC_POINTER_TYPE},
 };
+pub use atomics::check_atomic_types;
+pub use environment::TypeEnvironment;
 pub use error::*;
+pub(crate) use names::check_names;
 use std::collections::HashMap;
 
 pub fn check_types(module: &Module) -> Result<(), TypeCheckError> {
-    names::check_names(module)?;
+    check_names(module)?;
+    pure_calls::check_pure_calls(module, module.function_definitions())?;
 
-    let variables = module
+    let variables = collect_global_variables(module);
+
+    for declaration in module.function_declarations() {
+        check_function_type(declaration.type_())?;
+    }
+
+    for definition in module.variable_definitions() {
+        check_variable_definition(definition, &variables)?;
+    }
+
+    for definition in module.function_definitions() {
+        check_function_definition(definition, &variables)?;
+    }
+
+    Ok(())
+}
+
+// Only definitions of changed names are rechecked unless types of global
+// variables or functions differ from ones in a previous environment.
+pub fn check_types_incrementally(
+    module: &Module,
+    changed_names: &[&str],
+    environment: &TypeEnvironment,
+) -> Result<TypeEnvironment, TypeCheckError> {
+    let variables = collect_global_variables(module);
+
+    if &variables != environment.variables() {
+        check_types(module)?;
+    } else {
+        check_names(module)?;
+        pure_calls::check_pure_calls(
+            module,
+            module
+                .function_definitions()
+                .iter()
+                .filter(|definition| changed_names.contains(&definition.name())),
+        )?;
+
+        for definition in module
+            .variable_definitions()
+            .iter()
+            .filter(|definition| changed_names.contains(&definition.name()))
+        {
+            check_variable_definition(definition, &variables)?;
+        }
+
+        for definition in module
+            .function_definitions()
+            .iter()
+            .filter(|definition| changed_names.contains(&definition.name()))
+        {
+            check_function_definition(definition, &variables)?;
+        }
+    }
+
+    Ok(TypeEnvironment::new(variables))
+}
+
+// Unlike `check_types`, this continues checking the rest of a module after
+// errors in each definition.
+pub fn check_all_types(module: &Module) -> Result<(), Vec<TypeCheckError>> {
+    let variables = collect_global_variables(module);
+    let errors = check_names(module)
+        .err()
+        .into_iter()
+        .chain(pure_calls::check_pure_calls(module, module.function_definitions()).err())
+        .chain(
+            module
+                .function_declarations()
+                .iter()
+                .filter_map(|declaration| check_function_type(declaration.type_()).err()),
+        )
+        .chain(
+            module
+                .variable_definitions()
+                .iter()
+                .filter_map(|definition| check_variable_definition(definition, &variables).err()),
+        )
+        .chain(
+            module
+                .function_definitions()
+                .iter()
+                .filter_map(|definition| check_function_definition(definition, &variables).err()),
+        )
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+pub(crate) fn collect_global_variables(module: &Module) -> HashMap<String, Type> {
+    module
         .variable_declarations()
         .iter()
         .map(|declaration| {
@@ -38,23 +139,14 @@ pub fn check_types(module: &Module) -> Result<(), TypeCheckError> {
                 .iter()
                 .map(|definition| (definition.name().into(), definition.type_().clone().into())),
         )
-        .collect::<HashMap<String, Type>>();
-
-    for definition in module.variable_definitions() {
-        check_variable_definition(definition, &variables)?;
-    }
-
-    for definition in module.function_definitions() {
-        check_function_definition(definition, &variables)?;
-    }
-
-    Ok(())
+        .collect()
 }
 
 fn check_variable_definition(
     definition: &VariableDefinition,
     variables: &HashMap<String, Type>,
 ) -> Result<(), TypeCheckError> {
+    check_sized(definition.type_())?;
     check_equality(
         &check_expression(definition.body(), variables)?,
         definition.type_(),
@@ -65,6 +157,15 @@ fn check_function_definition(
     definition: &FunctionDefinition,
     variables: &HashMap<String, Type>,
 ) -> Result<(), TypeCheckError> {
+    definition
+        .arguments()
+        .iter()
+        .try_for_each(check_argument_attributes)
+        .and_then(|_| check_function_type(definition.type_()))
+        .map_err(|error| {
+            TypeCheckError::FunctionDefinition(definition.name().into(), vec![], error.into())
+        })?;
+
     check_block(
         definition.body(),
         &variables
@@ -79,7 +180,10 @@ fn check_function_definition(
             .collect(),
         definition.result_type(),
         None,
-    )?;
+    )
+    .map_err(|(path, error)| {
+        TypeCheckError::FunctionDefinition(definition.name().into(), path, error.into())
+    })?;
 
     Ok(())
 }
@@ -89,175 +193,309 @@ fn check_block(
     variables: &HashMap<String, Type>,
     return_type: &Type,
     branch_type: Option<&Type>,
-) -> Result<(), TypeCheckError> {
+) -> Result<(), (Vec<InstructionPathSegment>, TypeCheckError)> {
     let mut variables = variables.clone();
 
-    for instruction in block.instructions() {
-        match instruction {
-            Instruction::AllocateHeap(allocate) => {
-                check_equality(
-                    &check_expression(allocate.size(), &variables)?,
-                    &types::Primitive::PointerInteger.into(),
-                )?;
-            }
-            Instruction::AllocateStack(_) => {}
-            Instruction::AtomicLoad(load) => {
-                check_equality(
-                    &check_expression(load.pointer(), &variables)?,
-                    &types::Pointer::new(load.type_().clone()).clone().into(),
-                )?;
-            }
-            Instruction::AtomicOperation(operation) => {
-                check_equality(
-                    &check_expression(operation.pointer(), &variables)?,
-                    &types::Pointer::new(operation.type_()).into(),
-                )?;
-                check_equality(
-                    &check_expression(operation.value(), &variables)?,
-                    &operation.type_().into(),
+    for (index, instruction) in block.instructions().iter().enumerate() {
+        check_instruction(instruction, &variables)
+            .map_err(|error| (vec![InstructionPathSegment::Instruction(index)], error))?;
+
+        if let Instruction::If(if_) = instruction {
+            for (segment, block) in [
+                (InstructionPathSegment::Then, if_.then()),
+                (InstructionPathSegment::Else, if_.else_()),
+            ] {
+                check_block(block, &variables, return_type, Some(if_.type_())).map_err(
+                    |(path, error)| {
+                        (
+                            vec![InstructionPathSegment::Instruction(index), segment]
+                                .into_iter()
+                                .chain(path)
+                                .collect(),
+                            error,
+                        )
+                    },
                 )?;
             }
-            Instruction::AtomicStore(store) => {
-                check_equality(
-                    &check_expression(store.value(), &variables)?,
-                    &store.type_().clone(),
-                )?;
-                check_equality(
-                    &check_expression(store.pointer(), &variables)?,
-                    &types::Pointer::new(store.type_().clone()).into(),
-                )?;
+        }
+
+        if let Some(name) = instruction.name() {
+            if let Some(type_) = instruction.result_type() {
+                variables.insert(name.into(), type_.clone());
             }
-            Instruction::Call(call) => {
-                if call.arguments().len() != call.type_().arguments().len() {
-                    return Err(TypeCheckError::FunctionArguments(call.clone()));
-                }
+        }
+    }
 
-                check_equality(
-                    &call.type_().clone().into(),
-                    &check_expression(call.function(), &variables)?,
-                )?;
+    check_terminal_instruction(
+        block.terminal_instruction(),
+        &variables,
+        return_type,
+        branch_type,
+    )
+    .map_err(|error| (vec![InstructionPathSegment::TerminalInstruction], error))
+}
 
-                for (argument, type_) in call.arguments().iter().zip(call.type_().arguments()) {
-                    check_equality(&check_expression(argument, &variables)?, type_)?;
-                }
+fn check_instruction(
+    instruction: &Instruction,
+    variables: &HashMap<String, Type>,
+) -> Result<(), TypeCheckError> {
+    match instruction {
+        Instruction::AllocateHeap(allocate) => {
+            check_equality(
+                &check_expression(allocate.size(), variables)?,
+                &types::Primitive::PointerInteger.into(),
+            )?;
+        }
+        Instruction::AllocateStack(allocate) => {
+            check_sized(allocate.type_())?;
+        }
+        Instruction::AtomicLoad(load) => {
+            check_sized(load.type_())?;
+            check_equality(
+                &check_expression(load.pointer(), variables)?,
+                &types::Pointer::new(load.type_().clone()).clone().into(),
+            )?;
+        }
+        Instruction::AtomicOperation(operation) => {
+            check_equality(
+                &check_expression(operation.pointer(), variables)?,
+                &types::Pointer::new(operation.type_()).into(),
+            )?;
+            check_equality(
+                &check_expression(operation.value(), variables)?,
+                &operation.type_().into(),
+            )?;
+        }
+        Instruction::AtomicStore(store) => {
+            check_sized(store.type_())?;
+            check_equality(
+                &check_expression(store.value(), variables)?,
+                &store.type_().clone(),
+            )?;
+            check_equality(
+                &check_expression(store.pointer(), variables)?,
+                &types::Pointer::new(store.type_().clone()).into(),
+            )?;
+        }
+        Instruction::Call(call) => {
+            if call.arguments().len() != call.type_().arguments().len() {
+                return Err(TypeCheckError::FunctionArguments(call.clone()));
             }
-            Instruction::CompareAndSwap(cas) => {
-                check_equality(
-                    &check_expression(cas.pointer(), &variables)?,
-                    &types::Pointer::new(cas.type_().clone()).into(),
-                )?;
 
-                check_equality(
-                    &check_expression(cas.old_value(), &variables)?,
-                    &cas.type_().clone(),
-                )?;
+            check_function_type(call.type_())?;
 
-                check_equality(
-                    &check_expression(cas.new_value(), &variables)?,
-                    &cas.type_().clone(),
-                )?;
-            }
-            Instruction::DeconstructRecord(deconstruct) => {
-                check_equality(
-                    &check_expression(deconstruct.record(), &variables)?,
-                    &deconstruct.type_().clone().into(),
-                )?;
+            check_equality(
+                &call.type_().clone().into(),
+                &check_expression(call.function(), variables)?,
+            )?;
 
-                check_record_index(deconstruct.element_index(), deconstruct.type_())?;
+            for (argument, type_) in call.arguments().iter().zip(call.type_().arguments()) {
+                check_equality(&check_expression(argument, variables)?, type_)?;
             }
-            Instruction::DeconstructUnion(deconstruct) => {
-                check_equality(
-                    &check_expression(deconstruct.union(), &variables)?,
-                    &deconstruct.type_().clone().into(),
-                )?;
+        }
+        Instruction::CheckedArithmeticOperation(operation) => {
+            check_integer_arithmetic(operation.type_(), operation.operator())?;
+            check_equality(
+                &check_expression(operation.lhs(), variables)?,
+                &operation.type_().into(),
+            )?;
+            check_equality(
+                &check_expression(operation.rhs(), variables)?,
+                &operation.type_().into(),
+            )?;
+        }
+        Instruction::CompareAndSwap(cas) => {
+            check_equality(
+                &check_expression(cas.pointer(), variables)?,
+                &types::Pointer::new(cas.type_().clone()).into(),
+            )?;
 
-                check_union_index(deconstruct.member_index(), deconstruct.type_())?;
-            }
-            Instruction::Fence(_) => {}
-            Instruction::FreeHeap(free) => {
-                check_equality(
-                    &check_expression(free.pointer(), &variables)?,
-                    &GENERIC_POINTER_TYPE.clone(),
-                )?;
-            }
-            Instruction::If(if_) => {
-                check_equality(
-                    &check_expression(if_.condition(), &variables)?,
-                    &types::Primitive::Boolean.into(),
-                )?;
+            check_equality(
+                &check_expression(cas.old_value(), variables)?,
+                &cas.type_().clone(),
+            )?;
+
+            check_equality(
+                &check_expression(cas.new_value(), variables)?,
+                &cas.type_().clone(),
+            )?;
+        }
+        Instruction::DeconstructRecord(deconstruct) => {
+            check_equality(
+                &check_expression(deconstruct.record(), variables)?,
+                &deconstruct.type_().clone().into(),
+            )?;
+
+            check_record_index(deconstruct.element_index(), deconstruct.type_())?;
+        }
+        Instruction::DeconstructUnion(deconstruct) => {
+            check_equality(
+                &check_expression(deconstruct.union(), variables)?,
+                &deconstruct.type_().clone().into(),
+            )?;
 
-                check_block(if_.then(), &variables, return_type, Some(if_.type_()))?;
-                check_block(if_.else_(), &variables, return_type, Some(if_.type_()))?;
+            check_union_index(deconstruct.member_index(), deconstruct.type_())?;
+        }
+        Instruction::Fence(_) => {}
+        Instruction::Fma(fma) => {
+            if !is_float(fma.type_()) {
+                return Err(TypeCheckError::InvalidFma(fma.type_()));
             }
-            Instruction::Load(load) => {
+
+            for expression in [fma.lhs(), fma.rhs(), fma.addend()].iter() {
                 check_equality(
-                    &check_expression(load.pointer(), &variables)?,
-                    &types::Pointer::new(load.type_().clone()).into(),
+                    &check_expression(expression, variables)?,
+                    &fma.type_().into(),
                 )?;
             }
-            Instruction::PassThrough(pass) => {
-                check_equality(
-                    &check_expression(pass.expression(), &variables)?,
-                    pass.type_(),
-                )?;
+        }
+        Instruction::FreeHeap(free) => {
+            check_equality(
+                &check_expression(free.pointer(), variables)?,
+                &GENERIC_POINTER_TYPE.clone(),
+            )?;
+        }
+        Instruction::LifetimeEnd(end) => check_pointer(end.pointer(), variables)?,
+        Instruction::LifetimeStart(start) => check_pointer(start.pointer(), variables)?,
+        // Blocks of if instructions are checked by the caller.
+        Instruction::If(if_) => {
+            check_equality(
+                &check_expression(if_.condition(), variables)?,
+                &types::Primitive::Boolean.into(),
+            )?;
+        }
+        Instruction::InlineAssembly(assembly) => {
+            // Only the number of operands is validated as constraints are
+            // target-specific.
+            if assembly.operands().len() != assembly.input_constraint_count() {
+                return Err(TypeCheckError::InvalidInlineAssembly(assembly.clone()));
             }
-            Instruction::ReallocateHeap(reallocate) => {
-                check_equality(
-                    &check_expression(reallocate.pointer(), &variables)?,
-                    &GENERIC_POINTER_TYPE,
-                )?;
 
-                check_equality(
-                    &check_expression(reallocate.size(), &variables)?,
-                    &types::Primitive::PointerInteger.into(),
-                )?;
+            for operand in assembly.operands() {
+                check_expression(operand, variables)?;
             }
-            Instruction::Store(store) => {
-                check_equality(&check_expression(store.value(), &variables)?, store.type_())?;
-                check_equality(
-                    &check_expression(store.pointer(), &variables)?,
-                    &types::Pointer::new(store.type_().clone()).into(),
-                )?;
+        }
+        Instruction::Load(load) => {
+            check_sized(load.type_())?;
+            check_equality(
+                &check_expression(load.pointer(), variables)?,
+                &types::Pointer::new(load.type_().clone()).into(),
+            )?;
+        }
+        Instruction::MathOperation(operation) => {
+            if !is_float(operation.type_()) {
+                return Err(TypeCheckError::InvalidMathOperation(
+                    operation.operator(),
+                    operation.type_(),
+                ));
             }
+
+            check_equality(
+                &check_expression(operation.value(), variables)?,
+                &operation.type_().into(),
+            )?;
         }
+        Instruction::MemoryCopy(copy) => {
+            check_pointer(copy.source(), variables)?;
+            check_pointer(copy.destination(), variables)?;
+            check_equality(
+                &check_expression(copy.size(), variables)?,
+                &types::Primitive::PointerInteger.into(),
+            )?;
+        }
+        Instruction::MemorySet(set) => {
+            check_pointer(set.pointer(), variables)?;
+            check_equality(
+                &check_expression(set.value(), variables)?,
+                &types::Primitive::Integer8.into(),
+            )?;
+            check_equality(
+                &check_expression(set.size(), variables)?,
+                &types::Primitive::PointerInteger.into(),
+            )?;
+        }
+        Instruction::PassThrough(pass) => {
+            check_sized(pass.type_())?;
+            check_equality(
+                &check_expression(pass.expression(), variables)?,
+                pass.type_(),
+            )?;
+        }
+        Instruction::ReallocateHeap(reallocate) => {
+            check_equality(
+                &check_expression(reallocate.pointer(), variables)?,
+                &GENERIC_POINTER_TYPE,
+            )?;
 
-        if let Some(name) = instruction.name() {
-            if let Some(type_) = instruction.result_type() {
-                variables.insert(name.into(), type_.clone());
-            }
+            check_equality(
+                &check_expression(reallocate.size(), variables)?,
+                &types::Primitive::PointerInteger.into(),
+            )?;
+        }
+        Instruction::SaturatingArithmeticOperation(operation) => {
+            check_integer_arithmetic(operation.type_(), operation.operator())?;
+            check_equality(
+                &check_expression(operation.lhs(), variables)?,
+                &operation.type_().into(),
+            )?;
+            check_equality(
+                &check_expression(operation.rhs(), variables)?,
+                &operation.type_().into(),
+            )?;
+        }
+        Instruction::Store(store) => {
+            check_sized(store.type_())?;
+            check_equality(&check_expression(store.value(), variables)?, store.type_())?;
+            check_equality(
+                &check_expression(store.pointer(), variables)?,
+                &types::Pointer::new(store.type_().clone()).into(),
+            )?;
         }
     }
 
-    match block.terminal_instruction() {
+    Ok(())
+}
+
+fn check_terminal_instruction(
+    instruction: &TerminalInstruction,
+    variables: &HashMap<String, Type>,
+    return_type: &Type,
+    branch_type: Option<&Type>,
+) -> Result<(), TypeCheckError> {
+    match instruction {
         TerminalInstruction::Branch(branch) => {
             let branch_type =
                 branch_type.ok_or_else(|| TypeCheckError::InvalidBranch(branch.clone()))?;
 
             check_equality(branch.type_(), branch_type)?;
             check_equality(
-                &check_expression(branch.expression(), &variables)?,
+                &check_expression(branch.expression(), variables)?,
                 branch_type,
             )?;
         }
         TerminalInstruction::Return(return_) => {
             check_equality(return_.type_(), return_type)?;
             check_equality(
-                &check_expression(return_.expression(), &variables)?,
+                &check_expression(return_.expression(), variables)?,
                 return_type,
             )?;
         }
-        TerminalInstruction::Unreachable => {}
+        TerminalInstruction::Trap | TerminalInstruction::Unreachable => {}
     }
 
     Ok(())
 }
 
-fn check_expression(
+pub(crate) fn check_expression(
     expression: &Expression,
     variables: &HashMap<String, Type>,
 ) -> Result<Type, TypeCheckError> {
     Ok(match expression {
-        Expression::AlignOf(_) => AlignOf::RESULT_TYPE.into(),
+        Expression::AlignOf(align_of) => {
+            check_sized(align_of.type_())?;
+
+            AlignOf::RESULT_TYPE.into()
+        }
         Expression::ArithmeticOperation(operation) => {
             check_equality(
                 &check_expression(operation.lhs(), variables)?,
@@ -271,6 +509,8 @@ fn check_expression(
             operation.type_().into()
         }
         Expression::BitCast(bit_cast) => {
+            check_sized(bit_cast.from())?;
+            check_sized(bit_cast.to())?;
             check_equality(
                 &check_expression(bit_cast.expression(), variables)?,
                 bit_cast.from(),
@@ -278,6 +518,15 @@ fn check_expression(
 
             bit_cast.to().clone()
         }
+        Expression::BitManipulationOperation(operation) => {
+            check_bit_manipulation_operation(operation)?;
+            check_equality(
+                &check_expression(operation.value(), variables)?,
+                &operation.type_().into(),
+            )?;
+
+            operation.type_().into()
+        }
         Expression::BitwiseNotOperation(operation) => {
             check_equality(
                 &check_expression(operation.value(), variables)?,
@@ -298,7 +547,21 @@ fn check_expression(
 
             operation.type_().into()
         }
+        Expression::ByteOffset(offset) => {
+            check_equality(
+                &check_expression(offset.pointer(), variables)?,
+                &offset.type_().clone().into(),
+            )?;
+
+            check_equality(
+                &check_expression(offset.offset(), variables)?,
+                &types::Primitive::PointerInteger.into(),
+            )?;
+
+            offset.type_().clone().into()
+        }
         Expression::ComparisonOperation(operation) => {
+            check_comparison_operator(operation)?;
             check_equality(
                 &check_expression(operation.lhs(), variables)?,
                 &operation.type_().into(),
@@ -310,6 +573,28 @@ fn check_expression(
 
             ComparisonOperation::RESULT_TYPE.into()
         }
+        Expression::ExtractBits(extract) => {
+            check_bit_range(extract.type_(), extract.offset(), extract.width())?;
+            check_equality(
+                &check_expression(extract.value(), variables)?,
+                &extract.type_().into(),
+            )?;
+
+            extract.type_().into()
+        }
+        Expression::InsertBits(insert) => {
+            check_bit_range(insert.type_(), insert.offset(), insert.width())?;
+            check_equality(
+                &check_expression(insert.value(), variables)?,
+                &insert.type_().into(),
+            )?;
+            check_equality(
+                &check_expression(insert.field(), variables)?,
+                &insert.type_().into(),
+            )?;
+
+            insert.type_().into()
+        }
         Expression::PointerAddress(address) => {
             check_equality(
                 &check_expression(address.pointer(), variables)?,
@@ -324,6 +609,24 @@ fn check_expression(
             address.type_().clone().into()
         }
         Expression::Primitive(primitive) => primitive.type_().into(),
+        Expression::PureCall(call) => {
+            if call.arguments().len() != call.type_().arguments().len() {
+                return Err(TypeCheckError::PureCallArguments(call.clone()));
+            }
+
+            check_function_type(call.type_())?;
+
+            check_equality(
+                &call.type_().clone().into(),
+                &check_expression(call.function(), variables)?,
+            )?;
+
+            for (argument, type_) in call.arguments().iter().zip(call.type_().arguments()) {
+                check_equality(&check_expression(argument, variables)?, type_)?;
+            }
+
+            call.type_().result().clone()
+        }
         Expression::Record(record) => {
             if record.elements().len() != record.type_().elements().len() {
                 return Err(TypeCheckError::RecordElements(record.clone()));
@@ -345,16 +648,60 @@ fn check_expression(
 
             types::Pointer::new(address.type_().elements()[address.element_index()].clone()).into()
         }
-        Expression::SizeOf(_) => SizeOf::RESULT_TYPE.into(),
-        Expression::Undefined(undefined) => undefined.type_().clone(),
-        Expression::Union(union) => {
+        Expression::Select(select) => {
             check_equality(
-                &check_expression(union.member(), variables)?,
-                union
+                &check_expression(select.condition(), variables)?,
+                &types::Primitive::Boolean.into(),
+            )?;
+            check_equality(&check_expression(select.then(), variables)?, select.type_())?;
+            check_equality(
+                &check_expression(select.else_(), variables)?,
+                select.type_(),
+            )?;
+
+            select.type_().clone()
+        }
+        Expression::SizeOf(size_of) => {
+            check_sized(size_of.type_())?;
+
+            SizeOf::RESULT_TYPE.into()
+        }
+        Expression::AbsoluteAddress(address) => {
+            if !matches!(address.type_(), Type::Pointer(_)) {
+                return Err(TypeCheckError::TypesNotMatched(
+                    address.type_().clone(),
+                    GENERIC_POINTER_TYPE.clone(),
+                ));
+            } else if !matches!(
+                address.address(),
+                Primitive::Integer8(_)
+                    | Primitive::Integer16(_)
+                    | Primitive::Integer32(_)
+                    | Primitive::Integer64(_)
+                    | Primitive::Integer128(_)
+                    | Primitive::PointerInteger(_)
+            ) {
+                return Err(TypeCheckError::TypesNotMatched(
+                    address.address().type_().into(),
+                    types::Primitive::PointerInteger.into(),
+                ));
+            }
+
+            address.type_().clone()
+        }
+        Expression::Undefined(undefined) => {
+            check_sized(undefined.type_())?;
+
+            undefined.type_().clone()
+        }
+        Expression::Union(union) => {
+            check_equality(
+                &check_expression(union.member(), variables)?,
+                union
                     .type_()
                     .members()
                     .get(union.member_index())
-                    .ok_or(TypeCheckError::IndexOutOfRange)?,
+                    .ok_or_else(|| TypeCheckError::UnionMember(union.clone()))?,
             )?;
 
             union.type_().clone().into()
@@ -376,6 +723,149 @@ fn check_expression(
     })
 }
 
+// Overflow of integer division is not supported.
+fn check_integer_arithmetic(
+    type_: types::Primitive,
+    operator: ArithmeticOperator,
+) -> Result<(), TypeCheckError> {
+    match (operator, type_) {
+        (
+            ArithmeticOperator::Add | ArithmeticOperator::Subtract | ArithmeticOperator::Multiply,
+            types::Primitive::Integer8
+            | types::Primitive::Integer16
+            | types::Primitive::Integer32
+            | types::Primitive::Integer64
+            | types::Primitive::Integer128
+            | types::Primitive::PointerInteger,
+        ) => Ok(()),
+        _ => Err(TypeCheckError::InvalidIntegerArithmetic(type_, operator)),
+    }
+}
+
+// Byte swaps need at least two bytes.
+fn check_bit_manipulation_operation(
+    operation: &BitManipulationOperation,
+) -> Result<(), TypeCheckError> {
+    match (operation.operator(), operation.type_()) {
+        (BitManipulationOperator::ByteSwap, types::Primitive::Integer8) => {
+            Err(TypeCheckError::InvalidBitManipulation(operation.clone()))
+        }
+        (
+            _,
+            types::Primitive::Integer8
+            | types::Primitive::Integer16
+            | types::Primitive::Integer32
+            | types::Primitive::Integer64
+            | types::Primitive::Integer128
+            | types::Primitive::PointerInteger,
+        ) => Ok(()),
+        (
+            _,
+            types::Primitive::Boolean
+            | types::Primitive::Float16
+            | types::Primitive::Float32
+            | types::Primitive::Float64
+            | types::Primitive::Float128,
+        ) => Err(TypeCheckError::InvalidBitManipulation(operation.clone())),
+    }
+}
+
+fn is_float(type_: types::Primitive) -> bool {
+    match type_ {
+        types::Primitive::Float16
+        | types::Primitive::Float32
+        | types::Primitive::Float64
+        | types::Primitive::Float128 => true,
+        types::Primitive::Boolean
+        | types::Primitive::Integer8
+        | types::Primitive::Integer16
+        | types::Primitive::Integer32
+        | types::Primitive::Integer64
+        | types::Primitive::Integer128
+        | types::Primitive::PointerInteger => false,
+    }
+}
+
+fn check_comparison_operator(operation: &ComparisonOperation) -> Result<(), TypeCheckError> {
+    match operation.operator() {
+        ComparisonOperator::Equal | ComparisonOperator::NotEqual => Ok(()),
+        ComparisonOperator::LessThan
+        | ComparisonOperator::LessThanOrEqual
+        | ComparisonOperator::GreaterThan
+        | ComparisonOperator::GreaterThanOrEqual => match operation.type_() {
+            types::Primitive::Boolean => Err(TypeCheckError::InvalidComparison(operation.clone())),
+            types::Primitive::Float16
+            | types::Primitive::Float32
+            | types::Primitive::Float64
+            | types::Primitive::Float128
+            | types::Primitive::Integer8
+            | types::Primitive::Integer16
+            | types::Primitive::Integer32
+            | types::Primitive::Integer64
+            | types::Primitive::Integer128
+            | types::Primitive::PointerInteger => Ok(()),
+        },
+        ComparisonOperator::Ordered
+        | ComparisonOperator::Unordered
+        | ComparisonOperator::UnorderedEqual
+        | ComparisonOperator::UnorderedNotEqual
+        | ComparisonOperator::UnorderedLessThan
+        | ComparisonOperator::UnorderedLessThanOrEqual
+        | ComparisonOperator::UnorderedGreaterThan
+        | ComparisonOperator::UnorderedGreaterThanOrEqual => {
+            if is_float(operation.type_()) {
+                Ok(())
+            } else {
+                Err(TypeCheckError::InvalidComparison(operation.clone()))
+            }
+        }
+    }
+}
+
+// Pointer integers are not allowed as their bit widths depend on targets.
+fn check_bit_range(type_: types::Primitive, offset: u32, width: u32) -> Result<(), TypeCheckError> {
+    let bits = match type_ {
+        types::Primitive::Integer8 => 8,
+        types::Primitive::Integer16 => 16,
+        types::Primitive::Integer32 => 32,
+        types::Primitive::Integer64 => 64,
+        types::Primitive::Integer128 => 128,
+        types::Primitive::Boolean
+        | types::Primitive::Float16
+        | types::Primitive::Float32
+        | types::Primitive::Float64
+        | types::Primitive::Float128
+        | types::Primitive::PointerInteger => {
+            return Err(TypeCheckError::InvalidBitRange(type_, offset, width))
+        }
+    };
+
+    if width > 0 && offset.checked_add(width).map(|end| end <= bits) == Some(true) {
+        Ok(())
+    } else {
+        Err(TypeCheckError::InvalidBitRange(type_, offset, width))
+    }
+}
+
+fn check_argument_attributes(argument: &Argument) -> Result<(), TypeCheckError> {
+    let attributes = argument.attributes();
+
+    // Attributes are applicable only to pointers.
+    let valid = match argument.type_() {
+        Type::Pointer(_) => attributes
+            .alignment
+            .map(usize::is_power_of_two)
+            .unwrap_or(true),
+        _ => attributes == Default::default(),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(TypeCheckError::InvalidArgumentAttributes(argument.clone()))
+    }
+}
+
 fn check_record_index(index: usize, type_: &types::Record) -> Result<(), TypeCheckError> {
     if index < type_.elements().len() {
         Ok(())
@@ -392,6 +882,34 @@ fn check_union_index(index: usize, type_: &types::Union) -> Result<(), TypeCheck
     }
 }
 
+// Values of opaque types cannot exist while pointers to them can.
+fn check_sized(type_: &Type) -> Result<(), TypeCheckError> {
+    match type_ {
+        Type::Opaque(opaque) => Err(TypeCheckError::OpaqueType(opaque.clone())),
+        Type::Record(record) => record.elements().iter().try_for_each(check_sized),
+        Type::Union(union) => union.members().iter().try_for_each(check_sized),
+        Type::Function(_) | Type::Pointer(_) | Type::Primitive(_) => Ok(()),
+    }
+}
+
+fn check_function_type(type_: &types::Function) -> Result<(), TypeCheckError> {
+    type_.arguments().iter().try_for_each(check_sized)?;
+    check_sized(type_.result())
+}
+
+fn check_pointer(
+    expression: &Expression,
+    variables: &HashMap<String, Type>,
+) -> Result<(), TypeCheckError> {
+    match check_expression(expression, variables)? {
+        Type::Pointer(_) => Ok(()),
+        type_ => Err(TypeCheckError::TypesNotMatched(
+            type_,
+            GENERIC_POINTER_TYPE.clone(),
+        )),
+    }
+}
+
 fn check_equality(one: &Type, other: &Type) -> Result<(), TypeCheckError> {
     if one == other {
         Ok(())
@@ -403,7 +921,7 @@ fn check_equality(one: &Type, other: &Type) -> Result<(), TypeCheckError> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::CallingConvention;
+    use crate::{build, types::CallingConvention};
 
     fn create_function_type(arguments: Vec<Type>, result: impl Into<Type>) -> types::Function {
         types::Function::new(arguments, result, CallingConvention::Target)
@@ -430,6 +948,127 @@ mod tests {
         check_types(&Module::new(vec![], vec![], vec![], vec![]))
     }
 
+    mod incremental {
+        use super::*;
+
+        fn create_module(f_body: Block, g_result_type: types::Primitive) -> Module {
+            Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![
+                    create_function_definition(
+                        "f",
+                        vec![Argument::new("x", types::Primitive::Integer32)],
+                        f_body,
+                        types::Primitive::Integer32,
+                    ),
+                    create_function_definition(
+                        "g",
+                        vec![Argument::new("x", types::Primitive::Integer32)],
+                        Block::new(vec![], Return::new(g_result_type, Variable::new("x"))),
+                        g_result_type,
+                    ),
+                ],
+            )
+        }
+
+        fn create_valid_body() -> Block {
+            Block::new(
+                vec![],
+                Return::new(types::Primitive::Integer32, Variable::new("x")),
+            )
+        }
+
+        fn create_invalid_body() -> Block {
+            Block::new(
+                vec![],
+                Return::new(types::Primitive::Integer32, Primitive::Integer64(0)),
+            )
+        }
+
+        #[test]
+        fn check_all_functions_first() {
+            assert!(check_types_incrementally(
+                &create_module(create_invalid_body(), types::Primitive::Integer32),
+                &[],
+                &TypeEnvironment::default(),
+            )
+            .is_err());
+        }
+
+        #[test]
+        fn check_changed_functions() -> Result<(), TypeCheckError> {
+            let environment = check_types_incrementally(
+                &create_module(create_valid_body(), types::Primitive::Integer32),
+                &[],
+                &TypeEnvironment::default(),
+            )?;
+
+            assert!(check_types_incrementally(
+                &create_module(create_invalid_body(), types::Primitive::Integer32),
+                &["f"],
+                &environment,
+            )
+            .is_err());
+
+            Ok(())
+        }
+
+        #[test]
+        fn skip_unchanged_functions() -> Result<(), TypeCheckError> {
+            let environment = check_types_incrementally(
+                &create_module(create_valid_body(), types::Primitive::Integer32),
+                &[],
+                &TypeEnvironment::default(),
+            )?;
+
+            let module = create_module(create_invalid_body(), types::Primitive::Integer32);
+
+            // The ill-typed function is not rechecked as it is not marked as changed.
+            assert!(check_types(&module).is_err());
+            assert_eq!(
+                check_types_incrementally(&module, &["g"], &environment),
+                Ok(environment)
+            );
+
+            Ok(())
+        }
+
+        #[test]
+        fn check_all_functions_on_signature_change() -> Result<(), TypeCheckError> {
+            let environment = check_types_incrementally(
+                &create_module(create_valid_body(), types::Primitive::Integer32),
+                &[],
+                &TypeEnvironment::default(),
+            )?;
+
+            assert!(check_types_incrementally(
+                &create_module(create_invalid_body(), types::Primitive::Integer64),
+                &["g"],
+                &environment,
+            )
+            .is_err());
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn check_void_return() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(vec![], Return::new(build::void_type(), build::void_value())),
+                build::void_type(),
+            )],
+        ))
+    }
+
     #[test]
     fn check_variable_declaration() -> Result<(), TypeCheckError> {
         check_types(&Module::new(
@@ -669,6 +1308,37 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn fail_to_check_call_with_unmatched_calling_convention() {
+        let function_type = create_function_type(vec![], types::Primitive::Float64);
+
+        assert!(matches!(
+            check_types(&Module::new(
+                vec![],
+                vec![FunctionDeclaration::new(
+                    "g",
+                    types::Function::new(
+                        vec![],
+                        types::Primitive::Float64,
+                        CallingConvention::Source
+                    ),
+                )],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![Call::new(function_type, Variable::new("g"), vec![], "x").into()],
+                        Return::new(types::Primitive::Float64, Variable::new("x")),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if matches!(*error, TypeCheckError::TypesNotMatched(_, _))
+        ));
+    }
+
     #[test]
     fn check_if() -> Result<(), TypeCheckError> {
         check_types(&Module::new(
@@ -837,8 +1507,8 @@ mod tests {
     }
 
     #[test]
-    fn check_record_address() -> Result<(), TypeCheckError> {
-        let record_type = types::Record::new(vec![types::Primitive::PointerInteger.into()]);
+    fn check_byte_offset() -> Result<(), TypeCheckError> {
+        let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
 
         check_types(&Module::new(
             vec![],
@@ -846,22 +1516,26 @@ mod tests {
             vec![],
             vec![create_function_definition(
                 "f",
-                vec![Argument::new("x", types::Pointer::new(record_type.clone()))],
+                vec![Argument::new("x", pointer_type.clone())],
                 Block::new(
                     vec![],
                     Return::new(
-                        types::Pointer::new(types::Primitive::PointerInteger),
-                        RecordAddress::new(record_type, Variable::new("x"), 0),
+                        pointer_type.clone(),
+                        ByteOffset::new(
+                            pointer_type.clone(),
+                            Variable::new("x"),
+                            Primitive::PointerInteger(1),
+                        ),
                     ),
                 ),
-                types::Pointer::new(types::Primitive::PointerInteger),
+                pointer_type,
             )],
         ))
     }
 
     #[test]
-    fn check_union_address() -> Result<(), TypeCheckError> {
-        let union_type = types::Union::new(vec![types::Primitive::PointerInteger.into()]);
+    fn check_record_address() -> Result<(), TypeCheckError> {
+        let record_type = types::Record::new(vec![types::Primitive::PointerInteger.into()]);
 
         check_types(&Module::new(
             vec![],
@@ -869,12 +1543,12 @@ mod tests {
             vec![],
             vec![create_function_definition(
                 "f",
-                vec![Argument::new("x", types::Pointer::new(union_type.clone()))],
+                vec![Argument::new("x", types::Pointer::new(record_type.clone()))],
                 Block::new(
                     vec![],
                     Return::new(
                         types::Pointer::new(types::Primitive::PointerInteger),
-                        UnionAddress::new(union_type, Variable::new("x"), 0),
+                        RecordAddress::new(record_type, Variable::new("x"), 0),
                     ),
                 ),
                 types::Pointer::new(types::Primitive::PointerInteger),
@@ -882,78 +1556,101 @@ mod tests {
         ))
     }
 
-    #[test]
-    fn check_align_of() -> Result<(), TypeCheckError> {
+    fn check_argument(argument: Argument) -> Result<(), TypeCheckError> {
         check_types(&Module::new(
             vec![],
             vec![],
-            vec![VariableDefinition::new(
-                "x",
-                AlignOf::new(types::Primitive::Float64),
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![argument],
+                Block::new(vec![], TerminalInstruction::Unreachable),
                 types::Primitive::PointerInteger,
-                false,
-                Linkage::External,
-                None,
             )],
-            vec![],
         ))
     }
 
     #[test]
-    fn check_size_of() -> Result<(), TypeCheckError> {
-        check_types(&Module::new(
-            vec![],
-            vec![],
-            vec![VariableDefinition::new(
+    fn check_struct_return_argument() -> Result<(), TypeCheckError> {
+        check_argument(Argument::with_attributes(
+            "x",
+            types::Pointer::new(types::Record::new(vec![
+                types::Primitive::PointerInteger.into(),
+                types::Primitive::PointerInteger.into(),
+            ])),
+            ArgumentAttributes {
+                alignment: Some(8),
+                no_alias: true,
+                non_null: true,
+                struct_return: true,
+                ..Default::default()
+            },
+        ))
+    }
+
+    #[test]
+    fn fail_to_check_by_value_attribute_of_non_pointer() {
+        assert!(matches!(
+            check_argument(Argument::with_attributes(
                 "x",
-                SizeOf::new(types::Primitive::Float64),
                 types::Primitive::PointerInteger,
-                false,
-                Linkage::External,
-                None,
-            )],
-            vec![],
-        ))
+                ArgumentAttributes {
+                    by_value: true,
+                    ..Default::default()
+                },
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if matches!(*error, TypeCheckError::InvalidArgumentAttributes(_))
+        ));
     }
 
     #[test]
-    fn check_bit_cast() -> Result<(), TypeCheckError> {
-        check_types(&Module::new(
-            vec![],
-            vec![],
-            vec![VariableDefinition::new(
+    fn fail_to_check_invalid_argument_alignment() {
+        assert!(matches!(
+            check_argument(Argument::with_attributes(
                 "x",
-                BitCast::new(
-                    types::Primitive::Integer64,
-                    types::Primitive::Float64,
-                    Primitive::Integer64(42),
-                ),
-                types::Primitive::Float64,
-                false,
-                Linkage::External,
-                None,
-            )],
-            vec![],
-        ))
+                GENERIC_POINTER_TYPE.clone(),
+                ArgumentAttributes {
+                    alignment: Some(3),
+                    ..Default::default()
+                },
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if matches!(*error, TypeCheckError::InvalidArgumentAttributes(_))
+        ));
     }
 
     #[test]
-    fn check_pass_through() -> Result<(), TypeCheckError> {
+    fn check_pure_call() -> Result<(), TypeCheckError> {
+        let function_type = create_function_type(
+            vec![types::Primitive::PointerInteger.into()],
+            types::Primitive::PointerInteger,
+        );
+
         check_types(&Module::new(
             vec![],
-            vec![],
+            vec![FunctionDeclaration::with_attributes(
+                "f",
+                function_type.clone(),
+                FunctionAttributes {
+                    read_none: true,
+                    ..Default::default()
+                },
+            )],
             vec![],
             vec![create_function_definition(
-                "f",
+                "g",
                 vec![],
                 Block::new(
-                    vec![PassThrough::new(
+                    vec![],
+                    Return::new(
                         types::Primitive::PointerInteger,
-                        Primitive::PointerInteger(42),
-                        "x",
-                    )
-                    .into()],
-                    Return::new(types::Primitive::PointerInteger, Variable::new("x")),
+                        PureCall::new(
+                            function_type,
+                            Variable::new("f"),
+                            vec![Primitive::PointerInteger(42).into()],
+                        ),
+                    ),
                 ),
                 types::Primitive::PointerInteger,
             )],
@@ -961,63 +1658,1127 @@ mod tests {
     }
 
     #[test]
-    fn check_atomic_operation() -> Result<(), TypeCheckError> {
-        check_types(&Module::new(
-            vec![],
-            vec![],
-            vec![],
-            vec![create_function_definition(
-                "f",
-                vec![Argument::new(
-                    "x",
-                    types::Pointer::new(types::Primitive::PointerInteger),
+    fn fail_to_check_pure_call_with_wrong_number_of_arguments() {
+        let function_type = create_function_type(
+            vec![types::Primitive::PointerInteger.into()],
+            types::Primitive::PointerInteger,
+        );
+
+        assert!(matches!(
+            check_types(&Module::new(
+                vec![],
+                vec![FunctionDeclaration::with_attributes(
+                    "f",
+                    function_type.clone(),
+                    FunctionAttributes {
+                        read_none: true,
+                        ..Default::default()
+                    },
                 )],
-                Block::new(
-                    vec![AtomicOperation::new(
-                        types::Primitive::PointerInteger,
-                        AtomicOperator::Add,
-                        Variable::new("x"),
-                        Primitive::PointerInteger(42),
-                        AtomicOrdering::Relaxed,
-                        "y",
-                    )
-                    .into()],
-                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
-                ),
-                types::Primitive::PointerInteger,
-            )],
-        ))
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![],
+                    Block::new(
+                        vec![],
+                        Return::new(
+                            types::Primitive::PointerInteger,
+                            PureCall::new(function_type, Variable::new("f"), vec![]),
+                        ),
+                    ),
+                    types::Primitive::PointerInteger,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if matches!(*error, TypeCheckError::PureCallArguments(_))
+        ));
     }
 
     #[test]
-    fn check_free_heap() -> Result<(), TypeCheckError> {
+    fn check_empty_record() -> Result<(), TypeCheckError> {
+        let record_type = types::Record::new(vec![]);
+
         check_types(&Module::new(
             vec![],
             vec![],
             vec![],
             vec![create_function_definition(
                 "f",
-                vec![Argument::new("x", GENERIC_POINTER_TYPE.clone())],
+                vec![],
                 Block::new(
-                    vec![FreeHeap::new(Variable::new("x")).into()],
+                    vec![],
                     Return::new(
-                        types::Primitive::PointerInteger,
-                        Primitive::PointerInteger(0),
+                        record_type.clone(),
+                        Record::new(record_type.clone(), vec![]),
                     ),
                 ),
-                types::Primitive::PointerInteger,
+                record_type,
             )],
         ))
     }
 
-    #[test]
-    fn check_bitwise_operation() -> Result<(), TypeCheckError> {
-        check_types(&Module::new(
-            vec![],
-            vec![],
-            vec![],
-            vec![create_function_definition(
-                "f",
+    mod multiple_results {
+        use super::*;
+
+        fn create_module(result_index: usize, result_type: types::Primitive) -> Module {
+            let record_type = types::Record::new(vec![
+                types::Primitive::Float64.into(),
+                types::Primitive::PointerInteger.into(),
+            ]);
+            let function_type = create_function_type(vec![], record_type.clone());
+
+            Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("f", function_type.clone())],
+                vec![],
+                vec![create_function_definition(
+                    "g",
+                    vec![],
+                    Block::new(
+                        vec![
+                            Call::new(function_type, Variable::new("f"), vec![], "r").into(),
+                            DeconstructRecord::new(
+                                record_type.clone(),
+                                Variable::new("r"),
+                                result_index,
+                                "x",
+                            )
+                            .into(),
+                        ],
+                        Return::new(result_type, Variable::new("x")),
+                    ),
+                    result_type,
+                )],
+            )
+        }
+
+        #[test]
+        fn check_call_with_multiple_results() -> Result<(), TypeCheckError> {
+            check_types(&create_module(0, types::Primitive::Float64))?;
+            check_types(&create_module(1, types::Primitive::PointerInteger))
+        }
+
+        #[test]
+        fn fail_to_check_result_of_wrong_type() {
+            assert!(matches!(
+                check_types(&create_module(1, types::Primitive::Float64)),
+                Err(TypeCheckError::FunctionDefinition(_, _, error))
+                    if matches!(*error, TypeCheckError::TypesNotMatched(_, _))
+            ));
+        }
+
+        #[test]
+        fn fail_to_check_result_out_of_range() {
+            assert!(matches!(
+                check_types(&create_module(2, types::Primitive::Float64)),
+                Err(TypeCheckError::FunctionDefinition(_, _, error))
+                    if matches!(*error, TypeCheckError::IndexOutOfRange)
+            ));
+        }
+    }
+
+    #[test]
+    fn fail_to_check_record_address_of_empty_record() {
+        let record_type = types::Record::new(vec![]);
+
+        assert!(matches!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![Argument::new("x", types::Pointer::new(record_type.clone()))],
+                    Block::new(
+                        vec![],
+                        Return::new(
+                            types::Pointer::new(record_type.clone()),
+                            RecordAddress::new(record_type.clone(), Variable::new("x"), 0),
+                        ),
+                    ),
+                    types::Pointer::new(record_type),
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if matches!(*error, TypeCheckError::IndexOutOfRange)
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_record_with_too_few_elements() {
+        let record_type = types::Record::new(vec![
+            types::Primitive::PointerInteger.into(),
+            types::Primitive::PointerInteger.into(),
+        ]);
+        let record = Record::new(
+            record_type.clone(),
+            vec![Primitive::PointerInteger(42).into()],
+        );
+
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(vec![], Return::new(record_type.clone(), record.clone())),
+                    record_type,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(
+                "f".into(),
+                vec![InstructionPathSegment::TerminalInstruction],
+                TypeCheckError::RecordElements(record).into()
+            ))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_union_with_wrong_member_index() {
+        let union_type = types::Union::new(vec![types::Primitive::PointerInteger.into()]);
+        let union = Union::new(union_type.clone(), 1, Primitive::PointerInteger(42));
+
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(vec![], Return::new(union_type.clone(), union.clone())),
+                    union_type,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(
+                "f".into(),
+                vec![InstructionPathSegment::TerminalInstruction],
+                TypeCheckError::UnionMember(union).into()
+            ))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_union_with_wrong_member_type() {
+        let union_type = types::Union::new(vec![
+            types::Primitive::PointerInteger.into(),
+            types::Primitive::Float64.into(),
+        ]);
+
+        assert!(matches!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![],
+                        Return::new(
+                            union_type.clone(),
+                            Union::new(union_type.clone(), 1, Primitive::PointerInteger(42)),
+                        ),
+                    ),
+                    union_type,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if matches!(*error, TypeCheckError::TypesNotMatched(_, _))
+        ));
+    }
+
+    #[test]
+    fn check_union_address() -> Result<(), TypeCheckError> {
+        let union_type = types::Union::new(vec![types::Primitive::PointerInteger.into()]);
+
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Pointer::new(union_type.clone()))],
+                Block::new(
+                    vec![],
+                    Return::new(
+                        types::Pointer::new(types::Primitive::PointerInteger),
+                        UnionAddress::new(union_type, Variable::new("x"), 0),
+                    ),
+                ),
+                types::Pointer::new(types::Primitive::PointerInteger),
+            )],
+        ))
+    }
+
+    #[test]
+    fn check_align_of() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![VariableDefinition::new(
+                "x",
+                AlignOf::new(types::Primitive::Float64),
+                types::Primitive::PointerInteger,
+                false,
+                Linkage::External,
+                None,
+            )],
+            vec![],
+        ))
+    }
+
+    #[test]
+    fn check_size_of() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![VariableDefinition::new(
+                "x",
+                SizeOf::new(types::Primitive::Float64),
+                types::Primitive::PointerInteger,
+                false,
+                Linkage::External,
+                None,
+            )],
+            vec![],
+        ))
+    }
+
+    #[test]
+    fn check_bit_cast() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![VariableDefinition::new(
+                "x",
+                BitCast::new(
+                    types::Primitive::Integer64,
+                    types::Primitive::Float64,
+                    Primitive::Integer64(42),
+                ),
+                types::Primitive::Float64,
+                false,
+                Linkage::External,
+                None,
+            )],
+            vec![],
+        ))
+    }
+
+    #[test]
+    fn check_pass_through() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![PassThrough::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(42),
+                        "x",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                types::Primitive::PointerInteger,
+            )],
+        ))
+    }
+
+    #[test]
+    fn check_atomic_operation() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new(
+                    "x",
+                    types::Pointer::new(types::Primitive::PointerInteger),
+                )],
+                Block::new(
+                    vec![AtomicOperation::new(
+                        types::Primitive::PointerInteger,
+                        AtomicOperator::Add,
+                        Variable::new("x"),
+                        Primitive::PointerInteger(42),
+                        AtomicOrdering::Relaxed,
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::PointerInteger, Variable::new("y")),
+                ),
+                types::Primitive::PointerInteger,
+            )],
+        ))
+    }
+
+    fn check_integer_arithmetic_instruction(
+        instruction: impl Into<Instruction>,
+        result_type: impl Into<Type>,
+    ) -> Result<(), TypeCheckError> {
+        let result_type = result_type.into();
+
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::Integer32)],
+                Block::new(
+                    vec![instruction.into()],
+                    Return::new(result_type.clone(), Variable::new("y")),
+                ),
+                result_type,
+            )],
+        ))
+    }
+
+    #[test]
+    fn check_saturating_arithmetic_operation() -> Result<(), TypeCheckError> {
+        for &signed in &[false, true] {
+            check_integer_arithmetic_instruction(
+                SaturatingArithmeticOperation::new(
+                    types::Primitive::Integer32,
+                    ArithmeticOperator::Add,
+                    signed,
+                    Variable::new("x"),
+                    Primitive::Integer32(42),
+                    "y",
+                ),
+                types::Primitive::Integer32,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_checked_arithmetic_operation() -> Result<(), TypeCheckError> {
+        let operation = CheckedArithmeticOperation::new(
+            types::Primitive::Integer32,
+            ArithmeticOperator::Multiply,
+            true,
+            Variable::new("x"),
+            Primitive::Integer32(42),
+            "y",
+        );
+
+        check_integer_arithmetic_instruction(operation.clone(), operation.result_type())
+    }
+
+    #[test]
+    fn fail_to_check_saturating_division() {
+        assert!(matches!(
+            check_integer_arithmetic_instruction(
+                SaturatingArithmeticOperation::new(
+                    types::Primitive::Integer32,
+                    ArithmeticOperator::Divide,
+                    false,
+                    Variable::new("x"),
+                    Primitive::Integer32(42),
+                    "y",
+                ),
+                types::Primitive::Integer32,
+            ),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if *error == TypeCheckError::InvalidIntegerArithmetic(
+                    types::Primitive::Integer32,
+                    ArithmeticOperator::Divide,
+                )
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_saturating_arithmetic_of_floats() {
+        assert!(check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![SaturatingArithmeticOperation::new(
+                        types::Primitive::Float64,
+                        ArithmeticOperator::Add,
+                        true,
+                        Primitive::Float64(1.0),
+                        Primitive::Float64(2.0),
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::Float64, Variable::new("y")),
+                ),
+                types::Primitive::Float64,
+            )],
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn check_fma() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::Float64)],
+                Block::new(
+                    vec![Fma::new(
+                        types::Primitive::Float64,
+                        Variable::new("x"),
+                        Primitive::Float64(2.0),
+                        Primitive::Float64(3.0),
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::Float64, Variable::new("y")),
+                ),
+                types::Primitive::Float64,
+            )],
+        ))
+    }
+
+    #[test]
+    fn check_square_root() -> Result<(), TypeCheckError> {
+        for &type_ in &[types::Primitive::Float32, types::Primitive::Float64] {
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![Argument::new("x", type_)],
+                    Block::new(
+                        vec![MathOperation::new(
+                            type_,
+                            MathOperator::SquareRoot,
+                            Variable::new("x"),
+                            "y",
+                        )
+                        .into()],
+                        Return::new(type_, Variable::new("y")),
+                    ),
+                    type_,
+                )],
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fail_to_check_square_root_of_integer() {
+        assert!(matches!(
+            check_integer_arithmetic_instruction(
+                MathOperation::new(
+                    types::Primitive::Integer32,
+                    MathOperator::SquareRoot,
+                    Variable::new("x"),
+                    "y",
+                ),
+                types::Primitive::Integer32,
+            ),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if *error == TypeCheckError::InvalidMathOperation(
+                    MathOperator::SquareRoot,
+                    types::Primitive::Integer32,
+                )
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_square_root_with_mismatched_result() {
+        assert!(check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::Float32)],
+                Block::new(
+                    vec![MathOperation::new(
+                        types::Primitive::Float64,
+                        MathOperator::SquareRoot,
+                        Variable::new("x"),
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::Float64, Variable::new("y")),
+                ),
+                types::Primitive::Float64,
+            )],
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn fail_to_check_fma_of_integers() {
+        assert!(matches!(
+            check_integer_arithmetic_instruction(
+                Fma::new(
+                    types::Primitive::Integer32,
+                    Variable::new("x"),
+                    Variable::new("x"),
+                    Variable::new("x"),
+                    "y",
+                ),
+                types::Primitive::Integer32,
+            ),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if *error == TypeCheckError::InvalidFma(types::Primitive::Integer32)
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_fma_with_mismatched_operand() {
+        assert!(check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![Fma::new(
+                        types::Primitive::Float64,
+                        Primitive::Float64(1.0),
+                        Primitive::Float32(2.0),
+                        Primitive::Float64(3.0),
+                        "y",
+                    )
+                    .into()],
+                    Return::new(types::Primitive::Float64, Variable::new("y")),
+                ),
+                types::Primitive::Float64,
+            )],
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn check_inline_assembly() -> Result<(), TypeCheckError> {
+        check_integer_arithmetic_instruction(
+            InlineAssembly::new(
+                types::Primitive::Integer32,
+                "",
+                "=r,0,~{memory}",
+                vec![Variable::new("x").into()],
+                true,
+                false,
+                "y",
+            ),
+            types::Primitive::Integer32,
+        )
+    }
+
+    #[test]
+    fn fail_to_check_inline_assembly_with_wrong_number_of_operands() {
+        let assembly = InlineAssembly::new(
+            types::Primitive::Integer32,
+            "",
+            "=r,r,r",
+            vec![Variable::new("x").into()],
+            true,
+            false,
+            "y",
+        );
+
+        assert!(matches!(
+            check_integer_arithmetic_instruction(assembly.clone(), types::Primitive::Integer32),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if *error == TypeCheckError::InvalidInlineAssembly(assembly)
+        ));
+    }
+
+    fn check_absolute_address(address: AbsoluteAddress) -> Result<(), TypeCheckError> {
+        let type_ = address.type_().clone();
+
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(vec![], Return::new(type_.clone(), address)),
+                type_,
+            )],
+        ))
+    }
+
+    #[test]
+    fn check_absolute_address_of_pointer() -> Result<(), TypeCheckError> {
+        check_absolute_address(AbsoluteAddress::new(
+            types::Pointer::new(types::Primitive::Integer16),
+            Primitive::PointerInteger(0xB8000),
+        ))
+    }
+
+    #[test]
+    fn fail_to_check_absolute_address_of_non_pointer() {
+        assert!(check_absolute_address(AbsoluteAddress::new(
+            types::Primitive::PointerInteger,
+            Primitive::PointerInteger(0xB8000),
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn fail_to_check_absolute_address_of_non_integer() {
+        assert!(check_absolute_address(AbsoluteAddress::new(
+            types::Pointer::new(types::Primitive::Integer16),
+            Primitive::Float64(42.0),
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn check_free_heap() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new("x", GENERIC_POINTER_TYPE.clone())],
+                Block::new(
+                    vec![FreeHeap::new(Variable::new("x")).into()],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(0),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+            )],
+        ))
+    }
+
+    #[test]
+    fn check_lifetime_markers() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![
+                        AllocateStack::new(types::Primitive::PointerInteger, "x").into(),
+                        LifetimeStart::new(Variable::new("x")).into(),
+                        LifetimeEnd::new(Variable::new("x")).into(),
+                    ],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(0),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+            )],
+        ))
+    }
+
+    #[test]
+    fn fail_to_check_lifetime_marker_of_non_pointer() {
+        assert!(matches!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![LifetimeStart::new(Primitive::PointerInteger(0)).into()],
+                        Return::new(
+                            types::Primitive::PointerInteger,
+                            Primitive::PointerInteger(0),
+                        ),
+                    ),
+                    types::Primitive::PointerInteger,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if matches!(*error, TypeCheckError::TypesNotMatched(_, _))
+        ));
+    }
+
+    fn check_comparison_operation(
+        type_: types::Primitive,
+        operator: ComparisonOperator,
+        value: Primitive,
+    ) -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![VariableDefinition::new(
+                "x",
+                ComparisonOperation::new(type_, operator, value, value),
+                types::Primitive::Boolean,
+                false,
+                Linkage::External,
+                None,
+            )],
+            vec![],
+        ))
+    }
+
+    #[test]
+    fn check_equality_of_booleans() -> Result<(), TypeCheckError> {
+        check_comparison_operation(
+            types::Primitive::Boolean,
+            ComparisonOperator::Equal,
+            Primitive::Boolean(true),
+        )?;
+        check_comparison_operation(
+            types::Primitive::Boolean,
+            ComparisonOperator::NotEqual,
+            Primitive::Boolean(true),
+        )
+    }
+
+    #[test]
+    fn check_order_of_numbers() -> Result<(), TypeCheckError> {
+        check_comparison_operation(
+            types::Primitive::PointerInteger,
+            ComparisonOperator::LessThan,
+            Primitive::PointerInteger(42),
+        )?;
+        check_comparison_operation(
+            types::Primitive::Float64,
+            ComparisonOperator::GreaterThanOrEqual,
+            Primitive::Float64(42.0),
+        )?;
+        check_comparison_operation(
+            types::Primitive::Integer128,
+            ComparisonOperator::LessThan,
+            Primitive::Integer128(42),
+        )?;
+        check_comparison_operation(
+            types::Primitive::Integer16,
+            ComparisonOperator::GreaterThan,
+            Primitive::Integer16(42),
+        )?;
+        check_comparison_operation(
+            types::Primitive::Float16,
+            ComparisonOperator::LessThanOrEqual,
+            Primitive::Float16(42.0),
+        )?;
+        check_comparison_operation(
+            types::Primitive::Float128,
+            ComparisonOperator::GreaterThanOrEqual,
+            Primitive::Float128(42.0),
+        )
+    }
+
+    #[test]
+    fn check_unordered_comparison_of_floats() -> Result<(), TypeCheckError> {
+        check_comparison_operation(
+            types::Primitive::Float64,
+            ComparisonOperator::UnorderedEqual,
+            Primitive::Float64(42.0),
+        )?;
+        check_comparison_operation(
+            types::Primitive::Float32,
+            ComparisonOperator::Unordered,
+            Primitive::Float32(42.0),
+        )
+    }
+
+    #[test]
+    fn fail_to_check_unordered_comparison_of_integers() {
+        assert_eq!(
+            check_comparison_operation(
+                types::Primitive::Integer32,
+                ComparisonOperator::UnorderedEqual,
+                Primitive::Integer32(42),
+            ),
+            Err(TypeCheckError::InvalidComparison(ComparisonOperation::new(
+                types::Primitive::Integer32,
+                ComparisonOperator::UnorderedEqual,
+                Primitive::Integer32(42),
+                Primitive::Integer32(42),
+            )))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_order_of_booleans() {
+        for operator in [
+            ComparisonOperator::LessThan,
+            ComparisonOperator::LessThanOrEqual,
+            ComparisonOperator::GreaterThan,
+            ComparisonOperator::GreaterThanOrEqual,
+        ] {
+            assert_eq!(
+                check_comparison_operation(
+                    types::Primitive::Boolean,
+                    operator,
+                    Primitive::Boolean(true),
+                ),
+                Err(TypeCheckError::InvalidComparison(ComparisonOperation::new(
+                    types::Primitive::Boolean,
+                    operator,
+                    Primitive::Boolean(true),
+                    Primitive::Boolean(true),
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn fail_to_check_instruction_in_if() {
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![If::new(
+                            types::Primitive::Float64,
+                            Primitive::Boolean(true),
+                            Block::new(
+                                vec![PassThrough::new(
+                                    types::Primitive::Float64,
+                                    Primitive::Float64(42.0),
+                                    "x",
+                                )
+                                .into()],
+                                Branch::new(
+                                    types::Primitive::Float64,
+                                    Primitive::PointerInteger(42),
+                                ),
+                            ),
+                            Block::new(vec![], TerminalInstruction::Unreachable),
+                            "y",
+                        )
+                        .into()],
+                        Return::new(types::Primitive::Float64, Variable::new("y")),
+                    ),
+                    types::Primitive::Float64,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(
+                "f".into(),
+                vec![
+                    InstructionPathSegment::Instruction(0),
+                    InstructionPathSegment::Then,
+                    InstructionPathSegment::TerminalInstruction,
+                ],
+                TypeCheckError::TypesNotMatched(
+                    types::Primitive::PointerInteger.into(),
+                    types::Primitive::Float64.into(),
+                )
+                .into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_all_types_of_functions() {
+        let create_function_definition = |name: &str| {
+            create_function_definition(
+                name,
+                vec![],
+                Block::new(
+                    vec![],
+                    Return::new(types::Primitive::Float64, Primitive::PointerInteger(42)),
+                ),
+                types::Primitive::Float64,
+            )
+        };
+        let create_error = |name: &str| {
+            TypeCheckError::FunctionDefinition(
+                name.into(),
+                vec![InstructionPathSegment::TerminalInstruction],
+                TypeCheckError::TypesNotMatched(
+                    types::Primitive::PointerInteger.into(),
+                    types::Primitive::Float64.into(),
+                )
+                .into(),
+            )
+        };
+
+        assert_eq!(
+            check_all_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![
+                    create_function_definition("f"),
+                    create_function_definition("g")
+                ],
+            )),
+            Err(vec![create_error("f"), create_error("g")])
+        );
+    }
+
+    #[test]
+    fn check_all_types_of_empty_module() {
+        assert_eq!(
+            check_all_types(&Module::new(vec![], vec![], vec![], vec![])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fail_to_check_allocate_stack_of_opaque_type() {
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![AllocateStack::new(types::Opaque::new("foo"), "x").into()],
+                        Return::new(
+                            types::Pointer::new(types::Opaque::new("foo")),
+                            Variable::new("x")
+                        ),
+                    ),
+                    types::Pointer::new(types::Opaque::new("foo")),
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(
+                "f".into(),
+                vec![InstructionPathSegment::Instruction(0)],
+                TypeCheckError::OpaqueType(types::Opaque::new("foo")).into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_function_definition_with_opaque_argument() {
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![Argument::new("x", types::Opaque::new("foo"))],
+                    Block::new(vec![], Return::new(build::void_type(), build::void_value())),
+                    build::void_type(),
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(
+                "f".into(),
+                vec![],
+                TypeCheckError::OpaqueType(types::Opaque::new("foo")).into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_function_declaration_with_opaque_result() {
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![FunctionDeclaration::new(
+                    "f",
+                    create_function_type(vec![], types::Opaque::new("foo")),
+                )],
+                vec![],
+                vec![],
+            )),
+            Err(TypeCheckError::OpaqueType(types::Opaque::new("foo")))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_variable_definition_of_opaque_type() {
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    Undefined::new(types::Opaque::new("foo")),
+                    types::Opaque::new("foo"),
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            )),
+            Err(TypeCheckError::OpaqueType(types::Opaque::new("foo")))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_size_of_opaque_type() {
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    SizeOf::new(types::Opaque::new("foo")),
+                    types::Primitive::PointerInteger,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            )),
+            Err(TypeCheckError::OpaqueType(types::Opaque::new("foo")))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_pass_through_of_opaque_type() {
+        assert_eq!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![PassThrough::new(
+                            types::Opaque::new("foo"),
+                            Undefined::new(types::Opaque::new("foo")),
+                            "x",
+                        )
+                        .into()],
+                        Return::new(build::void_type(), build::void_value()),
+                    ),
+                    build::void_type(),
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(
+                "f".into(),
+                vec![InstructionPathSegment::Instruction(0)],
+                TypeCheckError::OpaqueType(types::Opaque::new("foo")).into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn check_bitwise_operation() -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
                 vec![],
                 Block::new(
                     vec![],
@@ -1035,4 +2796,187 @@ mod tests {
             )],
         ))
     }
+
+    fn check_integer32_expression(expression: impl Into<Expression>) -> Result<(), TypeCheckError> {
+        check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![Argument::new("x", types::Primitive::Integer32)],
+                Block::new(vec![], Return::new(types::Primitive::Integer32, expression)),
+                types::Primitive::Integer32,
+            )],
+        ))
+    }
+
+    #[test]
+    fn check_extract_bits() -> Result<(), TypeCheckError> {
+        check_integer32_expression(ExtractBits::new(
+            types::Primitive::Integer32,
+            Variable::new("x"),
+            3,
+            5,
+        ))
+    }
+
+    #[test]
+    fn check_insert_bits() -> Result<(), TypeCheckError> {
+        check_integer32_expression(InsertBits::new(
+            types::Primitive::Integer32,
+            Variable::new("x"),
+            Primitive::Integer32(1),
+            3,
+            5,
+        ))
+    }
+
+    #[test]
+    fn check_select() -> Result<(), TypeCheckError> {
+        check_integer32_expression(Select::new(
+            types::Primitive::Integer32,
+            Primitive::Boolean(true),
+            Variable::new("x"),
+            Primitive::Integer32(1),
+        ))
+    }
+
+    #[test]
+    fn fail_to_check_select_with_non_boolean_condition() {
+        assert!(matches!(
+            check_integer32_expression(Select::new(
+                types::Primitive::Integer32,
+                Variable::new("x"),
+                Variable::new("x"),
+                Primitive::Integer32(1),
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if *error == TypeCheckError::TypesNotMatched(
+                    types::Primitive::Integer32.into(),
+                    types::Primitive::Boolean.into(),
+                )
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_extract_bits_out_of_range() {
+        assert!(matches!(
+            check_integer32_expression(ExtractBits::new(
+                types::Primitive::Integer32,
+                Variable::new("x"),
+                30,
+                5,
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if *error == TypeCheckError::InvalidBitRange(types::Primitive::Integer32, 30, 5)
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_extract_bits_of_zero_width() {
+        assert!(check_integer32_expression(ExtractBits::new(
+            types::Primitive::Integer32,
+            Variable::new("x"),
+            3,
+            0,
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn fail_to_check_extract_bits_of_float() {
+        assert!(check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![],
+                    Return::new(
+                        types::Primitive::Float64,
+                        ExtractBits::new(types::Primitive::Float64, Primitive::Float64(1.0), 0, 1),
+                    ),
+                ),
+                types::Primitive::Float64,
+            )],
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn check_bit_manipulation_operations() -> Result<(), TypeCheckError> {
+        for operator in [
+            BitManipulationOperator::ByteSwap,
+            BitManipulationOperator::CountLeadingZeros,
+            BitManipulationOperator::CountTrailingZeros,
+            BitManipulationOperator::PopulationCount,
+        ]
+        .iter()
+        {
+            check_integer32_expression(BitManipulationOperation::new(
+                types::Primitive::Integer32,
+                *operator,
+                Variable::new("x"),
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fail_to_check_byte_swap_of_byte() {
+        let operation = BitManipulationOperation::new(
+            types::Primitive::Integer8,
+            BitManipulationOperator::ByteSwap,
+            Primitive::Integer8(42),
+        );
+
+        assert!(matches!(
+            check_types(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![],
+                        Return::new(types::Primitive::Integer8, operation.clone()),
+                    ),
+                    types::Primitive::Integer8,
+                )],
+            )),
+            Err(TypeCheckError::FunctionDefinition(_, _, error))
+                if *error == TypeCheckError::InvalidBitManipulation(operation)
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_population_count_of_float() {
+        assert!(check_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![create_function_definition(
+                "f",
+                vec![],
+                Block::new(
+                    vec![],
+                    Return::new(
+                        types::Primitive::Float64,
+                        BitManipulationOperation::new(
+                            types::Primitive::Float64,
+                            BitManipulationOperator::PopulationCount,
+                            Primitive::Float64(1.0),
+                        ),
+                    ),
+                ),
+                types::Primitive::Float64,
+            )],
+        ))
+        .is_err());
+    }
 }
@@ -0,0 +1,141 @@
+use super::TypeCheckError;
+use crate::{
+    analysis::{target_data_layout::TargetDataLayout, type_size::type_size},
+    ir::*,
+    types::Type,
+};
+
+// Atomic operations are available only for scalar types within the maximum
+// atomic width of a target.
+pub fn check_atomic_types(
+    module: &Module,
+    layout: &TargetDataLayout,
+) -> Result<(), TypeCheckError> {
+    for definition in module.function_definitions() {
+        check_block(definition.body(), layout)?;
+    }
+
+    Ok(())
+}
+
+fn check_block(block: &Block, layout: &TargetDataLayout) -> Result<(), TypeCheckError> {
+    for instruction in block.instructions() {
+        match instruction {
+            Instruction::AtomicLoad(load) => check_type(load.type_(), layout)?,
+            Instruction::AtomicOperation(operation) => {
+                check_type(&operation.type_().into(), layout)?
+            }
+            Instruction::AtomicStore(store) => check_type(store.type_(), layout)?,
+            Instruction::CompareAndSwap(cas) => check_type(cas.type_(), layout)?,
+            Instruction::If(if_) => {
+                check_block(if_.then(), layout)?;
+                check_block(if_.else_(), layout)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn check_type(type_: &Type, layout: &TargetDataLayout) -> Result<(), TypeCheckError> {
+    let size = type_size(type_, layout.pointer_size()).ok();
+
+    match type_ {
+        Type::Opaque(_) | Type::Record(_) | Type::Union(_) => {
+            Err(TypeCheckError::InvalidAtomicType(type_.clone(), size))
+        }
+        Type::Function(_) | Type::Pointer(_) | Type::Primitive(_) => {
+            if size.unwrap_or_default() > layout.max_atomic_width() {
+                Err(TypeCheckError::InvalidAtomicType(type_.clone(), size))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    const LAYOUT: TargetDataLayout = TargetDataLayout::new(8, 8);
+
+    fn check_atomic_load(type_: impl Into<Type>) -> Result<(), TypeCheckError> {
+        let type_ = type_.into();
+
+        check_atomic_types(
+            &Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![FunctionDefinition::new(
+                    "f",
+                    vec![Argument::new("x", types::Pointer::new(type_.clone()))],
+                    Block::new(
+                        vec![AtomicLoad::new(
+                            type_.clone(),
+                            Variable::new("x"),
+                            AtomicOrdering::SequentiallyConsistent,
+                            "y",
+                        )
+                        .into()],
+                        Return::new(type_.clone(), Variable::new("y")),
+                    ),
+                    type_,
+                    CallingConvention::Target,
+                    Linkage::External,
+                )],
+            ),
+            &LAYOUT,
+        )
+    }
+
+    #[test]
+    fn check_atomic_integer() {
+        assert_eq!(check_atomic_load(types::Primitive::Integer64), Ok(()));
+    }
+
+    #[test]
+    fn check_atomic_pointer() {
+        assert_eq!(
+            check_atomic_load(types::Pointer::new(types::Primitive::Integer8)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fail_to_check_atomic_record() {
+        let type_ = types::Record::new(vec![
+            types::Primitive::Integer64.into(),
+            types::Primitive::Integer64.into(),
+        ]);
+
+        assert_eq!(
+            check_atomic_load(type_.clone()),
+            Err(TypeCheckError::InvalidAtomicType(type_.into(), Some(16)))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_atomic_small_record() {
+        let type_ = types::Record::new(vec![types::Primitive::Integer8.into()]);
+
+        assert_eq!(
+            check_atomic_load(type_.clone()),
+            Err(TypeCheckError::InvalidAtomicType(type_.into(), Some(1)))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_atomic_integer_wider_than_target() {
+        assert_eq!(
+            check_atomic_load(types::Primitive::Integer128),
+            Err(TypeCheckError::InvalidAtomicType(
+                types::Primitive::Integer128.into(),
+                Some(16)
+            ))
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use crate::types::Type;
+use std::collections::HashMap;
+
+// Types of global variables and functions in a module checked previously
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypeEnvironment {
+    variables: HashMap<String, Type>,
+}
+
+impl TypeEnvironment {
+    pub(super) fn new(variables: HashMap<String, Type>) -> Self {
+        Self { variables }
+    }
+
+    pub(super) fn variables(&self) -> &HashMap<String, Type> {
+        &self.variables
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{ir::*, types::Type};
+use crate::{
+    analysis::{format_arithmetic_operator, format_expression, format_math_operator, format_type},
+    ir::*,
+    types::{self, Type},
+};
 use std::{
     error::Error,
     fmt::{self, Display, Formatter},
@@ -7,18 +11,197 @@ use std::{
 #[derive(Clone, Debug, PartialEq)]
 pub enum TypeCheckError {
     DuplicateNames(String),
+    DuplicateTypeNames(String),
     FunctionArguments(Call),
+    FunctionDefinition(String, InstructionPath, Box<TypeCheckError>),
+    ImpureFunctionCall(PureCall),
     IndexOutOfRange,
+    InvalidArgumentAttributes(Argument),
+    InvalidAtomicType(Type, Option<usize>),
+    InvalidBitManipulation(BitManipulationOperation),
+    InvalidBitRange(types::Primitive, u32, u32),
     InvalidBranch(Branch),
+    InvalidComparison(ComparisonOperation),
+    InvalidFma(types::Primitive),
+    InvalidInlineAssembly(InlineAssembly),
+    InvalidIntegerArithmetic(types::Primitive, ArithmeticOperator),
+    InvalidMathOperation(MathOperator, types::Primitive),
+    OpaqueType(types::Opaque),
+    PureCallArguments(PureCall),
     RecordElements(Record),
     TypesNotMatched(Type, Type),
+    UnionMember(Union),
     VariableNotFound(Variable),
 }
 
 impl Display for TypeCheckError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        write!(formatter, "{:?}", self)
+        match self {
+            Self::DuplicateNames(name) => write!(formatter, "duplicate name: {}", name),
+            Self::DuplicateTypeNames(name) => write!(formatter, "duplicate type name: {}", name),
+            Self::FunctionArguments(call) => write!(
+                formatter,
+                "invalid number of arguments in call to {}",
+                format_expression(call.function())
+            ),
+            Self::FunctionDefinition(name, path, error) => {
+                write!(formatter, "{} in function {}", error, name)?;
+
+                if path.is_empty() {
+                    Ok(())
+                } else {
+                    write!(formatter, " at {}", format_path(path))
+                }
+            }
+            Self::ImpureFunctionCall(call) => write!(
+                formatter,
+                "call to impure function {} in expression",
+                format_expression(call.function())
+            ),
+            Self::IndexOutOfRange => write!(formatter, "index out of range"),
+            Self::InvalidArgumentAttributes(argument) => write!(
+                formatter,
+                "invalid attributes of argument {} of {}",
+                argument.name(),
+                format_type(argument.type_())
+            ),
+            Self::InvalidAtomicType(type_, size) => {
+                write!(formatter, "invalid atomic type: {}", format_type(type_))?;
+
+                if let Some(size) = size {
+                    write!(formatter, " of {} bytes", size)
+                } else {
+                    Ok(())
+                }
+            }
+            Self::InvalidBitManipulation(operation) => write!(
+                formatter,
+                "invalid bit manipulation of {}",
+                format_type(&operation.type_().into())
+            ),
+            Self::InvalidBitRange(type_, offset, width) => write!(
+                formatter,
+                "invalid bit range of {} bits at offset {} in {}",
+                width,
+                offset,
+                format_type(&(*type_).into())
+            ),
+            Self::InvalidBranch(_) => write!(formatter, "branch outside of if instruction"),
+            Self::InvalidComparison(operation) => write!(
+                formatter,
+                "invalid comparison of {}",
+                format_type(&operation.type_().into())
+            ),
+            Self::InvalidFma(type_) => write!(
+                formatter,
+                "invalid fused multiply-add of {}",
+                format_type(&(*type_).into())
+            ),
+            Self::InvalidInlineAssembly(assembly) => write!(
+                formatter,
+                "invalid number of operands in inline assembly: {:?}",
+                assembly.template()
+            ),
+            Self::InvalidIntegerArithmetic(type_, operator) => write!(
+                formatter,
+                "invalid integer arithmetic ({}) of {}",
+                format_arithmetic_operator(*operator),
+                format_type(&(*type_).into())
+            ),
+            Self::InvalidMathOperation(operator, type_) => write!(
+                formatter,
+                "invalid math operation ({}) of {}",
+                format_math_operator(*operator),
+                format_type(&(*type_).into())
+            ),
+            Self::OpaqueType(opaque) => {
+                write!(formatter, "unsized opaque type: {}", opaque.name())
+            }
+            Self::PureCallArguments(call) => write!(
+                formatter,
+                "invalid number of arguments in pure call to {}",
+                format_expression(call.function())
+            ),
+            Self::RecordElements(record) => write!(
+                formatter,
+                "invalid number of elements in record of {} (expected {} but got {})",
+                format_type(&record.type_().clone().into()),
+                record.type_().elements().len(),
+                record.elements().len()
+            ),
+            Self::TypesNotMatched(one, other) => write!(
+                formatter,
+                "types not matched: {} and {}",
+                format_type(one),
+                format_type(other)
+            ),
+            Self::UnionMember(union) => write!(
+                formatter,
+                "invalid member index {} in union of {}",
+                union.member_index(),
+                format_type(&union.type_().clone().into())
+            ),
+            Self::VariableNotFound(variable) => {
+                write!(formatter, "variable not found: {}", variable.name())
+            }
+        }
     }
 }
 
 impl Error for TypeCheckError {}
+
+fn format_path(path: &[InstructionPathSegment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            InstructionPathSegment::Instruction(index) => format!("instruction {}", index),
+            InstructionPathSegment::TerminalInstruction => "terminal instruction".into(),
+            InstructionPathSegment::Then => "then".into(),
+            InstructionPathSegment::Else => "else".into(),
+        })
+        .collect::<Vec<_>>()
+        .join(" > ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_variable_not_found() {
+        assert_eq!(
+            TypeCheckError::VariableNotFound(Variable::new("x")).to_string(),
+            "variable not found: x"
+        );
+    }
+
+    #[test]
+    fn display_error_in_function_definition() {
+        assert_eq!(
+            TypeCheckError::FunctionDefinition(
+                "f".into(),
+                vec![
+                    InstructionPathSegment::Instruction(1),
+                    InstructionPathSegment::Then,
+                    InstructionPathSegment::TerminalInstruction,
+                ],
+                TypeCheckError::TypesNotMatched(
+                    types::Primitive::Integer64.into(),
+                    types::Primitive::Boolean.into(),
+                )
+                .into()
+            )
+            .to_string(),
+            "types not matched: integer64 and boolean in function f \
+             at instruction 1 > then > terminal instruction"
+        );
+    }
+
+    #[test]
+    fn display_invalid_atomic_type() {
+        assert_eq!(
+            TypeCheckError::InvalidAtomicType(types::Primitive::Integer128.into(), Some(16))
+                .to_string(),
+            "invalid atomic type: integer128 of 16 bytes"
+        );
+    }
+}
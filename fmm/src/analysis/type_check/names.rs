@@ -1,5 +1,5 @@
 use super::TypeCheckError;
-use crate::ir::*;
+use crate::{analysis::type_collection::collect_named_records, ir::*};
 use std::collections::HashSet;
 
 pub fn check_names(module: &Module) -> Result<(), TypeCheckError> {
@@ -35,6 +35,19 @@ pub fn check_names(module: &Module) -> Result<(), TypeCheckError> {
         names.insert(name);
     }
 
+    let mut type_names = HashSet::new();
+
+    // Records of the same name but different elements are distinct types.
+    for record in collect_named_records(module) {
+        let name = record.name().unwrap_or_default().to_owned();
+
+        if type_names.contains(&name) {
+            return Err(TypeCheckError::DuplicateTypeNames(name));
+        }
+
+        type_names.insert(name);
+    }
+
     Ok(())
 }
 
@@ -165,4 +178,40 @@ mod tests {
             Err(TypeCheckError::DuplicateNames("f".into()))
         );
     }
+
+    #[test]
+    fn check_duplicate_type_names() {
+        let create_definition = |name: &str, type_: types::Record| {
+            FunctionDefinition::new(
+                name,
+                vec![],
+                Block::new(
+                    vec![],
+                    Return::new(types::Primitive::PointerInteger, SizeOf::new(type_)),
+                ),
+                types::Primitive::PointerInteger,
+                types::CallingConvention::Source,
+                Linkage::External,
+            )
+        };
+
+        assert_eq!(
+            check_names(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![
+                    create_definition(
+                        "f",
+                        types::Record::named("foo", vec![types::Primitive::Integer8.into()]),
+                    ),
+                    create_definition(
+                        "g",
+                        types::Record::named("foo", vec![types::Primitive::Integer64.into()]),
+                    ),
+                ],
+            )),
+            Err(TypeCheckError::DuplicateTypeNames("foo".into()))
+        );
+    }
 }
@@ -0,0 +1,174 @@
+use super::TypeCheckError;
+use crate::{
+    analysis::{
+        convert_expressions_in_expression, convert_expressions_in_instruction,
+        convert_expressions_in_terminal_instruction,
+    },
+    ir::*,
+};
+use std::{cell::RefCell, collections::HashSet};
+
+// Only functions declared with the read-none attribute can be called in
+// expressions.
+pub fn check_pure_calls<'a>(
+    module: &Module,
+    definitions: impl IntoIterator<Item = &'a FunctionDefinition>,
+) -> Result<(), TypeCheckError> {
+    let names = module
+        .function_declarations()
+        .iter()
+        .filter(|declaration| declaration.attributes().read_none)
+        .map(|declaration| declaration.name())
+        .collect::<HashSet<_>>();
+
+    for definition in definitions {
+        // Arguments can shadow the declarations.
+        let mut names = names.clone();
+
+        for argument in definition.arguments() {
+            names.remove(argument.name());
+        }
+
+        check_block(definition.body(), &names)?;
+    }
+
+    Ok(())
+}
+
+fn check_block(block: &Block, names: &HashSet<&str>) -> Result<(), TypeCheckError> {
+    let mut names = names.clone();
+
+    for instruction in block.instructions() {
+        if let Instruction::If(if_) = instruction {
+            check_expressions(&names, |check| {
+                convert_expressions_in_expression(if_.condition(), &check);
+            })?;
+            check_block(if_.then(), &names)?;
+            check_block(if_.else_(), &names)?;
+        } else {
+            check_expressions(&names, |check| {
+                convert_expressions_in_instruction(instruction, &check);
+            })?;
+        }
+
+        if let Some(name) = instruction.name() {
+            names.remove(name);
+        }
+    }
+
+    check_expressions(&names, |check| {
+        convert_expressions_in_terminal_instruction(block.terminal_instruction(), &check);
+    })
+}
+
+fn check_expressions(
+    names: &HashSet<&str>,
+    visit: impl FnOnce(&dyn Fn(&Expression) -> Expression),
+) -> Result<(), TypeCheckError> {
+    let error = RefCell::new(None);
+
+    visit(&|expression| {
+        if let Expression::PureCall(call) = expression {
+            if !matches!(
+                call.function(),
+                Expression::Variable(variable) if names.contains(variable.name())
+            ) {
+                error
+                    .borrow_mut()
+                    .get_or_insert_with(|| TypeCheckError::ImpureFunctionCall(call.clone()));
+            }
+        }
+
+        expression.clone()
+    });
+
+    error.into_inner().map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_function_type() -> types::Function {
+        types::Function::new(
+            vec![],
+            types::Primitive::PointerInteger,
+            CallingConvention::Target,
+        )
+    }
+
+    fn create_module(attributes: FunctionAttributes, arguments: Vec<Argument>) -> Module {
+        Module::new(
+            vec![],
+            vec![FunctionDeclaration::with_attributes(
+                "f",
+                create_function_type(),
+                attributes,
+            )],
+            vec![],
+            vec![FunctionDefinition::new(
+                "g",
+                arguments,
+                Block::new(
+                    vec![],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        PureCall::new(create_function_type(), Variable::new("f"), vec![]),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn check_module(module: &Module) -> Result<(), TypeCheckError> {
+        check_pure_calls(module, module.function_definitions())
+    }
+
+    #[test]
+    fn check_call_to_read_none_function() {
+        assert_eq!(
+            check_module(&create_module(
+                FunctionAttributes {
+                    read_none: true,
+                    ..Default::default()
+                },
+                vec![],
+            )),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fail_to_check_call_to_impure_function() {
+        for attributes in [
+            Default::default(),
+            FunctionAttributes {
+                read_only: true,
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                check_module(&create_module(attributes, vec![])),
+                Err(TypeCheckError::ImpureFunctionCall(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn fail_to_check_call_to_shadowed_function() {
+        assert!(matches!(
+            check_module(&create_module(
+                FunctionAttributes {
+                    read_none: true,
+                    ..Default::default()
+                },
+                vec![Argument::new("f", create_function_type())],
+            )),
+            Err(TypeCheckError::ImpureFunctionCall(_))
+        ));
+    }
+}
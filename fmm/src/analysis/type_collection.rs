@@ -1,4 +1,11 @@
-use crate::{ir::*, types::Type};
+mod debug_type;
+
+use super::{type_alignment, type_size, TargetDataLayout, TypeSizeError};
+use crate::{
+    ir::*,
+    types::{self, Type},
+};
+pub use debug_type::*;
 use std::collections::{HashMap, HashSet};
 
 pub fn collect_types(module: &Module) -> Vec<Type> {
@@ -27,15 +34,103 @@ pub fn collect_types(module: &Module) -> Vec<Type> {
     ))
 }
 
+// Named records are sorted so that their definitions precede their uses.
+pub fn collect_named_records(module: &Module) -> Vec<types::Record> {
+    collect_types(module)
+        .into_iter()
+        .filter_map(|type_| match type_ {
+            Type::Record(record) if record.name().is_some() => Some(record),
+            _ => None,
+        })
+        .collect()
+}
+
+// Debug types are sorted in the same order as collected types.
+pub fn debug_type_info(
+    module: &Module,
+    layout: &TargetDataLayout,
+) -> Result<Vec<DebugType>, TypeSizeError> {
+    let pointer_size = layout.pointer_size();
+
+    collect_types(module)
+        .iter()
+        .filter_map(|type_| match type_ {
+            Type::Record(record) => Some(debug_record_type(record, pointer_size)),
+            Type::Union(union) => Some(debug_union_type(union, pointer_size)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn debug_record_type(
+    record: &types::Record,
+    pointer_size: usize,
+) -> Result<DebugType, TypeSizeError> {
+    let mut members = vec![];
+    let mut offset = 0usize;
+
+    for (index, element) in record.elements().iter().enumerate() {
+        let alignment = type_alignment(element, pointer_size)?;
+        let size = type_size(element, pointer_size)?;
+
+        offset = offset.div_ceil(alignment) * alignment;
+        members.push(DebugMember::new(
+            index.to_string(),
+            element.clone(),
+            offset,
+            size,
+        ));
+        offset += size;
+    }
+
+    let type_ = record.clone().into();
+
+    Ok(DebugType::new(
+        record.name().map(From::from),
+        DebugTypeKind::Record,
+        type_size(&type_, pointer_size)?,
+        type_alignment(&type_, pointer_size)?,
+        members,
+    ))
+}
+
+fn debug_union_type(union: &types::Union, pointer_size: usize) -> Result<DebugType, TypeSizeError> {
+    let type_ = union.clone().into();
+
+    Ok(DebugType::new(
+        None,
+        DebugTypeKind::Union,
+        type_size(&type_, pointer_size)?,
+        type_alignment(&type_, pointer_size)?,
+        union
+            .members()
+            .iter()
+            .enumerate()
+            .map(|(index, member)| {
+                Ok(DebugMember::new(
+                    index.to_string(),
+                    member.clone(),
+                    0,
+                    type_size(member, pointer_size)?,
+                ))
+            })
+            .collect::<Result<_, _>>()?,
+    ))
+}
+
 fn sort_types(types: &HashSet<Type>) -> Vec<Type> {
     let mut graph = petgraph::graph::Graph::<&Type, ()>::new();
     let mut indices = HashMap::<&Type, _>::new();
 
-    for type_ in types {
+    // Sort types first to make a result deterministic.
+    let mut types = types.iter().collect::<Vec<_>>();
+    types.sort_by_cached_key(|type_| format!("{:?}", type_));
+
+    for &type_ in &types {
         indices.insert(type_, graph.add_node(type_));
     }
 
-    for type_ in types {
+    for &type_ in &types {
         for child_type in collect_child_types(type_) {
             graph.add_edge(indices[&child_type], indices[type_], ());
         }
@@ -67,6 +162,10 @@ fn collect_from_expression(expression: &Expression) -> HashSet<Type> {
             .into_iter()
             .chain(collect_from_expression(bit_cast.expression()))
             .collect(),
+        Expression::BitManipulationOperation(operation) => vec![operation.type_().into()]
+            .into_iter()
+            .chain(collect_from_expression(operation.value()))
+            .collect(),
         Expression::BitwiseNotOperation(operation) => vec![operation.type_().into()]
             .into_iter()
             .chain(collect_from_expression(operation.value()))
@@ -76,14 +175,34 @@ fn collect_from_expression(expression: &Expression) -> HashSet<Type> {
             .chain(collect_from_expression(operation.lhs()))
             .chain(collect_from_expression(operation.rhs()))
             .collect(),
+        Expression::ByteOffset(offset) => vec![offset.type_().clone().into()]
+            .into_iter()
+            .chain(collect_from_expression(offset.pointer()))
+            .chain(collect_from_expression(offset.offset()))
+            .collect(),
         Expression::ComparisonOperation(operation) => vec![operation.type_().into()]
             .into_iter()
             .chain(collect_from_expression(operation.lhs()))
             .chain(collect_from_expression(operation.rhs()))
             .collect(),
+        Expression::ExtractBits(extract) => vec![extract.type_().into()]
+            .into_iter()
+            .chain(collect_from_expression(extract.value()))
+            .collect(),
+        Expression::InsertBits(insert) => vec![insert.type_().into()]
+            .into_iter()
+            .chain(collect_from_expression(insert.value()))
+            .chain(collect_from_expression(insert.field()))
+            .collect(),
         Expression::PointerAddress(address) => vec![address.type_().clone().into()]
             .into_iter()
             .chain(collect_from_expression(address.pointer()))
+            .chain(collect_from_expression(address.offset()))
+            .collect(),
+        Expression::PureCall(call) => vec![call.type_().clone().into()]
+            .into_iter()
+            .chain(collect_from_expression(call.function()))
+            .chain(call.arguments().iter().flat_map(collect_from_expression))
             .collect(),
         Expression::Record(record) => vec![record.type_().clone().into()]
             .into_iter()
@@ -93,6 +212,12 @@ fn collect_from_expression(expression: &Expression) -> HashSet<Type> {
             .into_iter()
             .chain(collect_from_expression(address.pointer()))
             .collect(),
+        Expression::Select(select) => vec![select.type_().clone()]
+            .into_iter()
+            .chain(collect_from_expression(select.condition()))
+            .chain(collect_from_expression(select.then()))
+            .chain(collect_from_expression(select.else_()))
+            .collect(),
         Expression::SizeOf(size_of) => vec![size_of.type_().clone()].into_iter().collect(),
         Expression::Union(union) => vec![union.type_().clone().into()]
             .into_iter()
@@ -103,6 +228,7 @@ fn collect_from_expression(expression: &Expression) -> HashSet<Type> {
             .chain(collect_from_expression(address.pointer()))
             .collect(),
         Expression::Undefined(undefined) => vec![undefined.type_().clone()].into_iter().collect(),
+        Expression::AbsoluteAddress(address) => vec![address.type_().clone()].into_iter().collect(),
         Expression::Primitive(_) | Expression::Variable(_) => Default::default(),
     }
 }
@@ -132,19 +258,36 @@ fn collect_from_instruction(instruction: &Instruction) -> HashSet<Type> {
         Instruction::AllocateStack(allocate) => {
             vec![allocate.type_().clone()].into_iter().collect()
         }
-        Instruction::AtomicLoad(load) => vec![load.type_().clone()].into_iter().collect(),
+        Instruction::AtomicLoad(load) => vec![load.type_().clone()]
+            .into_iter()
+            .chain(collect_from_expression(load.pointer()))
+            .collect(),
         Instruction::AtomicOperation(operation) => vec![operation.type_().into()]
             .into_iter()
             .chain(collect_from_expression(operation.pointer()))
             .chain(collect_from_expression(operation.value()))
             .collect(),
-        Instruction::AtomicStore(store) => vec![store.type_().clone()].into_iter().collect(),
+        Instruction::AtomicStore(store) => vec![store.type_().clone()]
+            .into_iter()
+            .chain(collect_from_expression(store.value()))
+            .chain(collect_from_expression(store.pointer()))
+            .collect(),
         Instruction::Call(call) => vec![call.type_().clone().into()]
             .into_iter()
             .chain(collect_from_expression(call.function()))
             .chain(call.arguments().iter().flat_map(collect_from_expression))
             .collect(),
-        Instruction::CompareAndSwap(cas) => vec![cas.type_().clone()].into_iter().collect(),
+        Instruction::CheckedArithmeticOperation(operation) => vec![operation.type_().into()]
+            .into_iter()
+            .chain(collect_from_expression(operation.lhs()))
+            .chain(collect_from_expression(operation.rhs()))
+            .collect(),
+        Instruction::CompareAndSwap(cas) => vec![cas.type_().clone()]
+            .into_iter()
+            .chain(collect_from_expression(cas.pointer()))
+            .chain(collect_from_expression(cas.old_value()))
+            .chain(collect_from_expression(cas.new_value()))
+            .collect(),
         Instruction::DeconstructRecord(deconstruct) => vec![deconstruct.type_().clone().into()]
             .into_iter()
             .chain(collect_from_expression(deconstruct.record()))
@@ -154,17 +297,41 @@ fn collect_from_instruction(instruction: &Instruction) -> HashSet<Type> {
             .chain(collect_from_expression(deconstruct.union()))
             .collect(),
         Instruction::Fence(_) => Default::default(),
+        Instruction::Fma(fma) => vec![fma.type_().into()]
+            .into_iter()
+            .chain(collect_from_expression(fma.lhs()))
+            .chain(collect_from_expression(fma.rhs()))
+            .chain(collect_from_expression(fma.addend()))
+            .collect(),
         Instruction::FreeHeap(free) => collect_from_expression(free.pointer()),
+        Instruction::LifetimeEnd(end) => collect_from_expression(end.pointer()),
+        Instruction::LifetimeStart(start) => collect_from_expression(start.pointer()),
         Instruction::If(if_) => vec![if_.type_().clone()]
             .into_iter()
             .chain(collect_from_expression(if_.condition()))
             .chain(collect_from_block(if_.then()))
             .chain(collect_from_block(if_.else_()))
             .collect(),
+        Instruction::InlineAssembly(assembly) => vec![assembly.type_().clone()]
+            .into_iter()
+            .chain(assembly.operands().iter().flat_map(collect_from_expression))
+            .collect(),
         Instruction::Load(load) => vec![load.type_().clone()]
             .into_iter()
             .chain(collect_from_expression(load.pointer()))
             .collect(),
+        Instruction::MathOperation(operation) => vec![operation.type_().into()]
+            .into_iter()
+            .chain(collect_from_expression(operation.value()))
+            .collect(),
+        Instruction::MemoryCopy(copy) => [copy.source(), copy.destination(), copy.size()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(expression))
+            .collect(),
+        Instruction::MemorySet(set) => [set.pointer(), set.value(), set.size()]
+            .iter()
+            .flat_map(|expression| collect_from_expression(expression))
+            .collect(),
         Instruction::PassThrough(pass) => vec![pass.type_().clone()]
             .into_iter()
             .chain(collect_from_expression(pass.expression()))
@@ -173,6 +340,11 @@ fn collect_from_instruction(instruction: &Instruction) -> HashSet<Type> {
             .into_iter()
             .flat_map(collect_from_expression)
             .collect(),
+        Instruction::SaturatingArithmeticOperation(operation) => vec![operation.type_().into()]
+            .into_iter()
+            .chain(collect_from_expression(operation.lhs()))
+            .chain(collect_from_expression(operation.rhs()))
+            .collect(),
         Instruction::Store(store) => vec![store.type_().clone()]
             .into_iter()
             .chain(collect_from_expression(store.value()))
@@ -183,9 +355,15 @@ fn collect_from_instruction(instruction: &Instruction) -> HashSet<Type> {
 
 fn collect_from_terminal_instruction(instruction: &TerminalInstruction) -> HashSet<Type> {
     match instruction {
-        TerminalInstruction::Branch(branch) => vec![branch.type_().clone()].into_iter().collect(),
-        TerminalInstruction::Return(return_) => vec![return_.type_().clone()].into_iter().collect(),
-        TerminalInstruction::Unreachable => Default::default(),
+        TerminalInstruction::Branch(branch) => vec![branch.type_().clone()]
+            .into_iter()
+            .chain(collect_from_expression(branch.expression()))
+            .collect(),
+        TerminalInstruction::Return(return_) => vec![return_.type_().clone()]
+            .into_iter()
+            .chain(collect_from_expression(return_.expression()))
+            .collect(),
+        TerminalInstruction::Trap | TerminalInstruction::Unreachable => Default::default(),
     }
 }
 
@@ -202,7 +380,7 @@ fn collect_child_types(type_: &Type) -> HashSet<Type> {
             .into_iter()
             .chain(function.arguments().iter().flat_map(collect_from_type))
             .collect(),
-        Type::Primitive(_) => Default::default(),
+        Type::Opaque(_) | Type::Primitive(_) => Default::default(),
         Type::Record(record) => record
             .elements()
             .iter()
@@ -216,7 +394,7 @@ fn collect_child_types(type_: &Type) -> HashSet<Type> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{self, CallingConvention};
+    use crate::types::{self, CallingConvention, GENERIC_POINTER_TYPE};
 
     #[test]
     fn sort_types() {
@@ -237,6 +415,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn collect_named_records_in_order() {
+        let inner_type = types::Record::named("foo", vec![types::Primitive::Float64.into()]);
+        let outer_type = types::Record::named(
+            "bar",
+            vec![inner_type.clone().into(), types::Record::new(vec![]).into()],
+        );
+
+        assert_eq!(
+            collect_named_records(&Module::new(
+                vec![VariableDeclaration::new("x", outer_type.clone())],
+                vec![],
+                vec![],
+                vec![]
+            )),
+            vec![inner_type, outer_type]
+        );
+    }
+
     #[test]
     fn collect_from_nested_function_types() {
         assert_eq!(
@@ -278,4 +475,151 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn collect_from_function_declaration() {
+        let record_type = types::Record::new(vec![types::Primitive::Float64.into()]);
+
+        assert!(collect_types(&Module::new(
+            vec![],
+            vec![FunctionDeclaration::new(
+                "f",
+                types::Function::new(
+                    vec![types::Pointer::new(record_type.clone()).into()],
+                    types::Primitive::PointerInteger,
+                    CallingConvention::Target
+                )
+            )],
+            vec![],
+            vec![]
+        ))
+        .contains(&record_type.into()));
+    }
+
+    #[test]
+    fn collect_from_atomic_store() {
+        let record_type = types::Record::new(vec![types::Primitive::Float64.into()]);
+        let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+        assert!(collect_types(&Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("x", GENERIC_POINTER_TYPE.clone())],
+                Block::new(
+                    vec![AtomicStore::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::PointerInteger(42),
+                        BitCast::new(
+                            types::Pointer::new(record_type.clone()),
+                            pointer_type,
+                            BitCast::new(
+                                GENERIC_POINTER_TYPE.clone(),
+                                types::Pointer::new(record_type.clone()),
+                                Variable::new("x")
+                            )
+                        ),
+                        AtomicOrdering::Relaxed
+                    )
+                    .into()],
+                    TerminalInstruction::Unreachable
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+                Linkage::Internal
+            )]
+        ))
+        .contains(&record_type.into()));
+    }
+
+    #[test]
+    fn debug_record_with_two_fields() {
+        let record_type = types::Record::named(
+            "foo",
+            vec![
+                types::Primitive::Integer8.into(),
+                types::Primitive::Integer32.into(),
+            ],
+        );
+
+        assert_eq!(
+            debug_type_info(
+                &Module::new(
+                    vec![VariableDeclaration::new("x", record_type)],
+                    vec![],
+                    vec![],
+                    vec![],
+                ),
+                &TargetDataLayout::new(8, 8),
+            ),
+            Ok(vec![DebugType::new(
+                Some("foo".into()),
+                DebugTypeKind::Record,
+                8,
+                4,
+                vec![
+                    DebugMember::new("0", types::Primitive::Integer8, 0, 1),
+                    DebugMember::new("1", types::Primitive::Integer32, 4, 4),
+                ],
+            )])
+        );
+    }
+
+    #[test]
+    fn debug_union() {
+        assert_eq!(
+            debug_type_info(
+                &Module::new(
+                    vec![VariableDeclaration::new(
+                        "x",
+                        types::Union::new(vec![
+                            types::Primitive::Integer8.into(),
+                            types::Primitive::PointerInteger.into(),
+                        ])
+                    )],
+                    vec![],
+                    vec![],
+                    vec![],
+                ),
+                &TargetDataLayout::new(4, 4),
+            ),
+            Ok(vec![DebugType::new(
+                None,
+                DebugTypeKind::Union,
+                4,
+                4,
+                vec![
+                    DebugMember::new("0", types::Primitive::Integer8, 0, 1),
+                    DebugMember::new("1", types::Primitive::PointerInteger, 0, 4),
+                ],
+            )])
+        );
+    }
+
+    #[test]
+    fn sort_types_deterministically() {
+        let module = Module::new(
+            (0..8)
+                .map(|index| {
+                    VariableDeclaration::new(
+                        format!("x{}", index),
+                        types::Record::new(vec![types::Record::new(
+                            vec![types::Primitive::PointerInteger.into(); index],
+                        )
+                        .into()]),
+                    )
+                })
+                .collect(),
+            vec![],
+            vec![],
+            vec![],
+        );
+        let types = collect_types(&module);
+
+        for _ in 0..8 {
+            assert_eq!(collect_types(&module), types);
+        }
+    }
 }
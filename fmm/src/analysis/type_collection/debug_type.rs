@@ -0,0 +1,96 @@
+use crate::types::Type;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DebugTypeKind {
+    Record,
+    Union,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugType {
+    name: Option<String>,
+    kind: DebugTypeKind,
+    size: usize,
+    alignment: usize,
+    members: Vec<DebugMember>,
+}
+
+impl DebugType {
+    pub fn new(
+        name: Option<String>,
+        kind: DebugTypeKind,
+        size: usize,
+        alignment: usize,
+        members: Vec<DebugMember>,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            size,
+            alignment,
+            members,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn kind(&self) -> DebugTypeKind {
+        self.kind
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    pub fn members(&self) -> &[DebugMember] {
+        &self.members
+    }
+}
+
+// Members are named after their indices as record elements and union members
+// have no names.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugMember {
+    name: String,
+    type_: Type,
+    offset: usize,
+    size: usize,
+}
+
+impl DebugMember {
+    pub fn new(
+        name: impl Into<String>,
+        type_: impl Into<Type>,
+        offset: usize,
+        size: usize,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            type_: type_.into(),
+            offset,
+            size,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_(&self) -> &Type {
+        &self.type_
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
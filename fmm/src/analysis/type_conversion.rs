@@ -30,6 +30,22 @@ pub fn convert_types(module: &Module, convert: &impl Fn(&Type) -> Type) -> Modul
     )
 }
 
+pub fn equal_types_ignoring_calling_conventions(one: &Type, other: &Type) -> bool {
+    normalize_calling_conventions(one) == normalize_calling_conventions(other)
+}
+
+fn normalize_calling_conventions(type_: &Type) -> Type {
+    convert_type(type_, &|type_| match type_ {
+        Type::Function(function) => types::Function::new(
+            function.arguments().to_vec(),
+            function.result().clone(),
+            types::CallingConvention::Source,
+        )
+        .into(),
+        _ => type_.clone(),
+    })
+}
+
 fn convert_variable_declaration(
     declaration: &VariableDeclaration,
     convert: &impl Fn(&Type) -> Type,
@@ -41,12 +57,13 @@ fn convert_function_declaration(
     declaration: &FunctionDeclaration,
     convert: &impl Fn(&Type) -> Type,
 ) -> FunctionDeclaration {
-    FunctionDeclaration::new(
+    FunctionDeclaration::with_attributes(
         declaration.name(),
         convert(&declaration.type_().clone().into())
             .to_function()
             .unwrap()
             .clone(),
+        declaration.attributes(),
     )
 }
 
@@ -73,7 +90,13 @@ fn convert_function_definition(
         definition
             .arguments()
             .iter()
-            .map(|argument| Argument::new(argument.name(), convert(argument.type_())))
+            .map(|argument| {
+                Argument::with_attributes(
+                    argument.name(),
+                    convert(argument.type_()),
+                    argument.attributes(),
+                )
+            })
             .collect(),
         convert_block(definition.body(), convert),
         convert(definition.result_type()),
@@ -136,6 +159,15 @@ fn convert_instruction(instruction: &Instruction, convert: &impl Fn(&Type) -> Ty
             call.name(),
         )
         .into(),
+        Instruction::CheckedArithmeticOperation(operation) => CheckedArithmeticOperation::new(
+            convert(&operation.type_().into()).to_primitive().unwrap(),
+            operation.operator(),
+            operation.signed(),
+            convert_expression(operation.lhs()),
+            convert_expression(operation.rhs()),
+            operation.name(),
+        )
+        .into(),
         Instruction::CompareAndSwap(cas) => CompareAndSwap::new(
             convert(cas.type_()),
             convert_expression(cas.pointer()),
@@ -167,7 +199,19 @@ fn convert_instruction(instruction: &Instruction, convert: &impl Fn(&Type) -> Ty
         )
         .into(),
         Instruction::Fence(fence) => fence.clone().into(),
+        Instruction::Fma(fma) => Fma::new(
+            convert(&fma.type_().into()).to_primitive().unwrap(),
+            convert_expression(fma.lhs()),
+            convert_expression(fma.rhs()),
+            convert_expression(fma.addend()),
+            fma.name(),
+        )
+        .into(),
         Instruction::FreeHeap(free) => FreeHeap::new(convert_expression(free.pointer())).into(),
+        Instruction::LifetimeEnd(end) => LifetimeEnd::new(convert_expression(end.pointer())).into(),
+        Instruction::LifetimeStart(start) => {
+            LifetimeStart::new(convert_expression(start.pointer())).into()
+        }
         Instruction::If(if_) => If::new(
             convert(if_.type_()),
             convert_expression(if_.condition()),
@@ -176,12 +220,42 @@ fn convert_instruction(instruction: &Instruction, convert: &impl Fn(&Type) -> Ty
             if_.name(),
         )
         .into(),
+        Instruction::InlineAssembly(assembly) => InlineAssembly::new(
+            convert(assembly.type_()),
+            assembly.template(),
+            assembly.constraints(),
+            assembly.operands().iter().map(convert_expression).collect(),
+            assembly.side_effects(),
+            assembly.align_stack(),
+            assembly.name(),
+        )
+        .into(),
         Instruction::Load(load) => Load::new(
             convert(load.type_()),
             convert_expression(load.pointer()),
             load.name(),
         )
         .into(),
+        Instruction::MathOperation(operation) => MathOperation::new(
+            convert(&operation.type_().into()).to_primitive().unwrap(),
+            operation.operator(),
+            convert_expression(operation.value()),
+            operation.name(),
+        )
+        .into(),
+        Instruction::MemoryCopy(copy) => MemoryCopy::new(
+            convert_expression(copy.source()),
+            convert_expression(copy.destination()),
+            convert_expression(copy.size()),
+            copy.overlapping(),
+        )
+        .into(),
+        Instruction::MemorySet(set) => MemorySet::new(
+            convert_expression(set.pointer()),
+            convert_expression(set.value()),
+            convert_expression(set.size()),
+        )
+        .into(),
         Instruction::PassThrough(pass) => PassThrough::new(
             convert(pass.type_()),
             convert_expression(pass.expression()),
@@ -194,6 +268,17 @@ fn convert_instruction(instruction: &Instruction, convert: &impl Fn(&Type) -> Ty
             reallocate.name(),
         )
         .into(),
+        Instruction::SaturatingArithmeticOperation(operation) => {
+            SaturatingArithmeticOperation::new(
+                convert(&operation.type_().into()).to_primitive().unwrap(),
+                operation.operator(),
+                operation.signed(),
+                convert_expression(operation.lhs()),
+                convert_expression(operation.rhs()),
+                operation.name(),
+            )
+            .into()
+        }
         Instruction::Store(store) => Store::new(
             convert(store.type_()),
             convert_expression(store.value()),
@@ -220,6 +305,7 @@ fn convert_terminal_instruction(
             convert_expression(return_.expression()),
         )
         .into(),
+        TerminalInstruction::Trap => TerminalInstruction::Trap,
         TerminalInstruction::Unreachable => TerminalInstruction::Unreachable,
     }
 }
@@ -241,6 +327,14 @@ fn convert_expression(expression: &Expression, convert: &impl Fn(&Type) -> Type)
             convert_expression(operation.lhs()),
             convert_expression(operation.rhs()),
         )
+        .with_fast_math_flags(operation.fast_math_flags())
+        .with_overflow_flags(operation.overflow_flags())
+        .into(),
+        Expression::BitManipulationOperation(operation) => BitManipulationOperation::new(
+            convert(&operation.type_().into()).to_primitive().unwrap(),
+            operation.operator(),
+            convert_expression(operation.value()),
+        )
         .into(),
         Expression::BitwiseNotOperation(operation) => BitwiseNotOperation::new(
             convert(&operation.type_().into()).to_primitive().unwrap(),
@@ -254,6 +348,15 @@ fn convert_expression(expression: &Expression, convert: &impl Fn(&Type) -> Type)
             convert_expression(operation.rhs()),
         )
         .into(),
+        Expression::ByteOffset(offset) => ByteOffset::new(
+            convert(&offset.type_().clone().into())
+                .to_pointer()
+                .unwrap()
+                .clone(),
+            convert_expression(offset.pointer()),
+            convert_expression(offset.offset()),
+        )
+        .into(),
         Expression::ComparisonOperation(operation) => ComparisonOperation::new(
             convert(&operation.type_().into()).to_primitive().unwrap(),
             operation.operator(),
@@ -261,6 +364,21 @@ fn convert_expression(expression: &Expression, convert: &impl Fn(&Type) -> Type)
             convert_expression(operation.rhs()),
         )
         .into(),
+        Expression::ExtractBits(extract) => ExtractBits::new(
+            convert(&extract.type_().into()).to_primitive().unwrap(),
+            convert_expression(extract.value()),
+            extract.offset(),
+            extract.width(),
+        )
+        .into(),
+        Expression::InsertBits(insert) => InsertBits::new(
+            convert(&insert.type_().into()).to_primitive().unwrap(),
+            convert_expression(insert.value()),
+            convert_expression(insert.field()),
+            insert.offset(),
+            insert.width(),
+        )
+        .into(),
         Expression::PointerAddress(address) => PointerAddress::new(
             convert(&address.type_().clone().into())
                 .to_pointer()
@@ -270,6 +388,15 @@ fn convert_expression(expression: &Expression, convert: &impl Fn(&Type) -> Type)
             convert_expression(address.offset()),
         )
         .into(),
+        Expression::PureCall(call) => PureCall::new(
+            convert(&call.type_().clone().into())
+                .to_function()
+                .unwrap()
+                .clone(),
+            convert_expression(call.function()),
+            call.arguments().iter().map(convert_expression).collect(),
+        )
+        .into(),
         Expression::Record(record) => Record::new(
             convert(&record.type_().clone().into())
                 .to_record()
@@ -287,6 +414,13 @@ fn convert_expression(expression: &Expression, convert: &impl Fn(&Type) -> Type)
             address.element_index(),
         )
         .into(),
+        Expression::Select(select) => Select::new(
+            convert(select.type_()),
+            convert_expression(select.condition()),
+            convert_expression(select.then()),
+            convert_expression(select.else_()),
+        )
+        .into(),
         Expression::SizeOf(size_of) => SizeOf::new(convert(size_of.type_())).into(),
         Expression::Union(union) => Union::new(
             convert(&union.type_().clone().into())
@@ -307,6 +441,9 @@ fn convert_expression(expression: &Expression, convert: &impl Fn(&Type) -> Type)
         )
         .into(),
         Expression::Undefined(undefined) => Undefined::new(convert(undefined.type_())).into(),
+        Expression::AbsoluteAddress(address) => {
+            AbsoluteAddress::new(convert(address.type_()), address.address()).into()
+        }
         Expression::Primitive(_) | Expression::Variable(_) => expression.clone(),
     }
 }
@@ -326,14 +463,93 @@ fn convert_type(type_: &Type, convert: &impl Fn(&Type) -> Type) -> Type {
                 function.calling_convention(),
             )
             .into(),
-            Type::Primitive(_) => type_.clone(),
+            Type::Opaque(_) | Type::Primitive(_) => type_.clone(),
             Type::Record(record) => {
-                types::Record::new(record.elements().iter().map(convert).collect()).into()
+                let elements = record.elements().iter().map(convert).collect();
+
+                if let Some(name) = record.name() {
+                    types::Record::named(name, elements)
+                } else {
+                    types::Record::new(elements)
+                }
+                .into()
+            }
+            Type::Pointer(pointer) => if let Some(length) = pointer.length() {
+                types::Pointer::bounded(convert(pointer.element()), length)
+            } else {
+                types::Pointer::new(convert(pointer.element()))
             }
-            Type::Pointer(pointer) => types::Pointer::new(convert(pointer.element())).into(),
+            .into(),
             Type::Union(union) => {
                 types::Union::new(union.members().iter().map(convert).collect()).into()
             }
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CallingConvention;
+
+    #[test]
+    fn equal_functions_with_different_calling_conventions() {
+        assert!(equal_types_ignoring_calling_conventions(
+            &types::Function::new(
+                vec![types::Primitive::PointerInteger.into()],
+                types::Primitive::Float64,
+                CallingConvention::Source,
+            )
+            .into(),
+            &types::Function::new(
+                vec![types::Primitive::PointerInteger.into()],
+                types::Primitive::Float64,
+                CallingConvention::Tail,
+            )
+            .into(),
+        ));
+    }
+
+    #[test]
+    fn distinguish_named_records() {
+        assert!(!equal_types_ignoring_calling_conventions(
+            &types::Record::named("foo", vec![types::Primitive::Float64.into()]).into(),
+            &types::Record::new(vec![types::Primitive::Float64.into()]).into(),
+        ));
+    }
+
+    #[test]
+    fn equal_nested_functions_with_different_calling_conventions() {
+        let create_type = |calling_convention| -> Type {
+            types::Pointer::new(types::Record::new(vec![types::Function::new(
+                vec![
+                    types::Function::new(vec![], types::Primitive::Float64, calling_convention)
+                        .into(),
+                ],
+                types::Primitive::Float64,
+                calling_convention,
+            )
+            .into()]))
+            .into()
+        };
+
+        assert!(equal_types_ignoring_calling_conventions(
+            &create_type(CallingConvention::Source),
+            &create_type(CallingConvention::Tail),
+        ));
+    }
+
+    #[test]
+    fn not_equal_functions_with_different_results() {
+        assert!(!equal_types_ignoring_calling_conventions(
+            &types::Function::new(vec![], types::Primitive::Float64, CallingConvention::Source)
+                .into(),
+            &types::Function::new(
+                vec![],
+                types::Primitive::PointerInteger,
+                CallingConvention::Tail,
+            )
+            .into(),
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+mod error;
+
+use crate::types::{self, Type};
+pub use error::*;
+
+pub fn type_size(type_: &Type, pointer_size: usize) -> Result<usize, TypeSizeError> {
+    Ok(match type_ {
+        Type::Function(_) | Type::Pointer(_) => pointer_size,
+        Type::Opaque(opaque) => return Err(TypeSizeError::OpaqueType(opaque.clone())),
+        Type::Primitive(primitive) => primitive_size(*primitive, pointer_size),
+        Type::Record(record) => align(
+            record.elements().iter().try_fold(0, |size, element| {
+                Ok(align(size, type_alignment(element, pointer_size)?)
+                    + type_size(element, pointer_size)?)
+            })?,
+            type_alignment(type_, pointer_size)?,
+        ),
+        Type::Union(union) => align(
+            union
+                .members()
+                .iter()
+                .map(|member| type_size(member, pointer_size))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .max()
+                .unwrap_or_default(),
+            type_alignment(type_, pointer_size)?,
+        ),
+    })
+}
+
+pub fn type_alignment(type_: &Type, pointer_size: usize) -> Result<usize, TypeSizeError> {
+    Ok(match type_ {
+        Type::Function(_) | Type::Pointer(_) => pointer_size,
+        Type::Opaque(opaque) => return Err(TypeSizeError::OpaqueType(opaque.clone())),
+        Type::Primitive(primitive) => primitive_size(*primitive, pointer_size),
+        Type::Record(record) => record
+            .elements()
+            .iter()
+            .map(|element| type_alignment(element, pointer_size))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .max()
+            .unwrap_or(1),
+        Type::Union(union) => union
+            .members()
+            .iter()
+            .map(|member| type_alignment(member, pointer_size))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .max()
+            .unwrap_or(1),
+    })
+}
+
+pub(crate) fn field_offset(
+    record: &types::Record,
+    index: usize,
+    pointer_size: usize,
+) -> Result<usize, TypeSizeError> {
+    let elements = record.elements();
+
+    Ok(align(
+        elements[..index].iter().try_fold(0, |size, element| {
+            Ok(align(size, type_alignment(element, pointer_size)?)
+                + type_size(element, pointer_size)?)
+        })?,
+        type_alignment(&elements[index], pointer_size)?,
+    ))
+}
+
+fn primitive_size(primitive: types::Primitive, pointer_size: usize) -> usize {
+    match primitive {
+        types::Primitive::Boolean | types::Primitive::Integer8 => 1,
+        types::Primitive::Float16 | types::Primitive::Integer16 => 2,
+        types::Primitive::Float32 | types::Primitive::Integer32 => 4,
+        types::Primitive::Float64 | types::Primitive::Integer64 => 8,
+        types::Primitive::Float128 | types::Primitive::Integer128 => 16,
+        types::Primitive::PointerInteger => pointer_size,
+    }
+}
+
+fn align(size: usize, alignment: usize) -> usize {
+    size.div_ceil(alignment) * alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculate_primitive_sizes() {
+        assert_eq!(type_size(&types::Primitive::Integer16.into(), 8), Ok(2));
+        assert_eq!(
+            type_size(&types::Primitive::PointerInteger.into(), 4),
+            Ok(4)
+        );
+        assert_eq!(
+            type_size(&types::Primitive::PointerInteger.into(), 8),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn calculate_record_size() {
+        let type_ = types::Record::new(vec![
+            types::Primitive::Integer8.into(),
+            types::Primitive::Integer16.into(),
+            types::Primitive::Integer32.into(),
+        ])
+        .into();
+
+        assert_eq!(type_size(&type_, 8), Ok(8));
+        assert_eq!(type_alignment(&type_, 8), Ok(4));
+    }
+
+    #[test]
+    fn calculate_record_size_with_trailing_padding() {
+        let type_ = types::Record::new(vec![
+            types::Pointer::new(types::Primitive::Integer8).into(),
+            types::Primitive::Integer8.into(),
+        ])
+        .into();
+
+        assert_eq!(type_size(&type_, 8), Ok(16));
+        assert_eq!(type_size(&type_, 4), Ok(8));
+    }
+
+    #[test]
+    fn calculate_empty_record_size() {
+        assert_eq!(type_size(&types::Record::new(vec![]).into(), 8), Ok(0));
+    }
+
+    #[test]
+    fn calculate_empty_record_alignment() {
+        assert_eq!(type_alignment(&types::Record::new(vec![]).into(), 8), Ok(1));
+    }
+
+    #[test]
+    fn calculate_record_size_with_empty_record() {
+        assert_eq!(
+            type_size(
+                &types::Record::new(vec![
+                    types::Record::new(vec![]).into(),
+                    types::Primitive::Integer8.into(),
+                    types::Record::new(vec![]).into(),
+                ])
+                .into(),
+                8
+            ),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn calculate_opaque_pointer_size() {
+        assert_eq!(
+            type_size(&types::Pointer::new(types::Opaque::new("foo")).into(), 8),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn fail_to_calculate_opaque_size() {
+        assert_eq!(
+            type_size(&types::Opaque::new("foo").into(), 8),
+            Err(TypeSizeError::OpaqueType(types::Opaque::new("foo")))
+        );
+        assert_eq!(
+            type_size(
+                &types::Record::new(vec![types::Opaque::new("foo").into()]).into(),
+                8
+            ),
+            Err(TypeSizeError::OpaqueType(types::Opaque::new("foo")))
+        );
+    }
+
+    #[test]
+    fn calculate_union_size() {
+        let type_ = types::Union::new(vec![
+            types::Primitive::Integer8.into(),
+            types::Record::new(vec![
+                types::Primitive::Integer32.into(),
+                types::Primitive::Integer8.into(),
+            ])
+            .into(),
+        ])
+        .into();
+
+        assert_eq!(type_size(&type_, 8), Ok(8));
+        assert_eq!(type_alignment(&type_, 8), Ok(4));
+    }
+}
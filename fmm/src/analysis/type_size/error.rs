@@ -0,0 +1,22 @@
+use crate::types;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeSizeError {
+    OpaqueType(types::Opaque),
+}
+
+impl Display for TypeSizeError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::OpaqueType(opaque) => {
+                write!(formatter, "size of opaque type {} unknown", opaque.name())
+            }
+        }
+    }
+}
+
+impl Error for TypeSizeError {}
@@ -0,0 +1,232 @@
+use crate::ir::*;
+use std::collections::HashSet;
+
+pub fn prune_unreachable_code(module: &Module) -> Module {
+    let no_return_functions = module
+        .function_declarations()
+        .iter()
+        .filter(|declaration| declaration.attributes().no_return)
+        .map(|declaration| declaration.name())
+        .collect::<HashSet<_>>();
+
+    Module::new(
+        module.variable_declarations().to_vec(),
+        module.function_declarations().to_vec(),
+        module.variable_definitions().to_vec(),
+        module
+            .function_definitions()
+            .iter()
+            .map(|definition| {
+                // Arguments can shadow the declarations.
+                let mut no_return_functions = no_return_functions.clone();
+
+                for argument in definition.arguments() {
+                    no_return_functions.remove(argument.name());
+                }
+
+                FunctionDefinition::new(
+                    definition.name(),
+                    definition.arguments().to_vec(),
+                    prune_block(definition.body(), &no_return_functions),
+                    definition.result_type().clone(),
+                    definition.calling_convention(),
+                    definition.linkage(),
+                )
+            })
+            .collect(),
+    )
+}
+
+fn prune_block(block: &Block, no_return_functions: &HashSet<&str>) -> Block {
+    let mut no_return_functions = no_return_functions.clone();
+    let mut instructions = vec![];
+
+    for instruction in block.instructions() {
+        instructions.push(prune_instruction(instruction, &no_return_functions));
+
+        if is_no_return_call(instruction, &no_return_functions) {
+            return Block::new(instructions, TerminalInstruction::Unreachable);
+        }
+
+        if let Some(name) = instruction.name() {
+            no_return_functions.remove(name);
+        }
+    }
+
+    Block::new(instructions, block.terminal_instruction().clone())
+}
+
+fn prune_instruction(
+    instruction: &Instruction,
+    no_return_functions: &HashSet<&str>,
+) -> Instruction {
+    match instruction {
+        Instruction::If(if_) => If::new(
+            if_.type_().clone(),
+            if_.condition().clone(),
+            prune_block(if_.then(), no_return_functions),
+            prune_block(if_.else_(), no_return_functions),
+            if_.name(),
+        )
+        .into(),
+        _ => instruction.clone(),
+    }
+}
+
+fn is_no_return_call(instruction: &Instruction, no_return_functions: &HashSet<&str>) -> bool {
+    if let Instruction::Call(call) = instruction {
+        if let Expression::Variable(variable) = call.function() {
+            return no_return_functions.contains(variable.name());
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![FunctionDeclaration::with_attributes(
+                "abort",
+                types::Function::new(vec![], types::Primitive::Boolean, CallingConvention::Target),
+                FunctionAttributes {
+                    no_return: true,
+                    ..Default::default()
+                },
+            )],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::Float64, Primitive::Float64(42.0)),
+                ),
+                types::Primitive::Float64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_abort_call(name: &str) -> Instruction {
+        Call::new(
+            types::Function::new(vec![], types::Primitive::Boolean, CallingConvention::Target),
+            Variable::new("abort"),
+            vec![],
+            name,
+        )
+        .into()
+    }
+
+    #[test]
+    fn prune_instructions_after_no_return_call() {
+        let module = prune_unreachable_code(&create_module(vec![
+            create_abort_call("x"),
+            create_abort_call("y"),
+        ]));
+
+        assert_eq!(
+            module.function_definitions()[0].body(),
+            &Block::new(
+                vec![create_abort_call("x")],
+                TerminalInstruction::Unreachable
+            )
+        );
+    }
+
+    #[test]
+    fn prune_instructions_after_no_return_call_in_if() {
+        let module = prune_unreachable_code(&create_module(vec![If::new(
+            types::Primitive::Float64,
+            Primitive::Boolean(true),
+            Block::new(
+                vec![create_abort_call("x"), create_abort_call("y")],
+                Branch::new(types::Primitive::Float64, Primitive::Float64(42.0)),
+            ),
+            Block::new(
+                vec![],
+                Branch::new(types::Primitive::Float64, Primitive::Float64(42.0)),
+            ),
+            "z",
+        )
+        .into()]));
+
+        if let Instruction::If(if_) = &module.function_definitions()[0].body().instructions()[0] {
+            assert_eq!(
+                if_.then(),
+                &Block::new(
+                    vec![create_abort_call("x")],
+                    TerminalInstruction::Unreachable
+                )
+            );
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn keep_instructions_after_call_of_shadowing_argument() {
+        let module = create_module(vec![create_abort_call("x"), create_abort_call("y")]);
+        let module = Module::new(
+            vec![],
+            module.function_declarations().to_vec(),
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new(
+                    "abort",
+                    types::Function::new(
+                        vec![],
+                        types::Primitive::Boolean,
+                        CallingConvention::Target,
+                    ),
+                )],
+                module.function_definitions()[0].body().clone(),
+                types::Primitive::Float64,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        );
+
+        assert_eq!(prune_unreachable_code(&module), module);
+    }
+
+    #[test]
+    fn keep_instructions_after_call_of_shadowing_local() {
+        let module = create_module(vec![
+            PassThrough::new(
+                types::Function::new(vec![], types::Primitive::Boolean, CallingConvention::Target),
+                Variable::new("g"),
+                "abort",
+            )
+            .into(),
+            create_abort_call("x"),
+            create_abort_call("y"),
+        ]);
+
+        assert_eq!(prune_unreachable_code(&module), module);
+    }
+
+    #[test]
+    fn keep_instructions_after_normal_call() {
+        let module = Module::new(
+            vec![],
+            vec![FunctionDeclaration::new(
+                "abort",
+                types::Function::new(vec![], types::Primitive::Boolean, CallingConvention::Target),
+            )],
+            vec![],
+            create_module(vec![create_abort_call("x"), create_abort_call("y")])
+                .function_definitions()
+                .to_vec(),
+        );
+
+        assert_eq!(prune_unreachable_code(&module), module);
+    }
+}
@@ -0,0 +1,31 @@
+mod constant;
+mod error;
+mod memory_copy;
+mod symbol;
+mod tail_call;
+mod undefined;
+mod warning;
+
+use self::{
+    constant::check_constants, memory_copy::check_memory_copies, symbol::check_symbols,
+    tail_call::check_tail_calls, undefined::check_undefined_values,
+};
+use crate::ir::*;
+pub use error::*;
+pub use warning::*;
+
+pub fn validate(module: &Module) -> Result<(), ValidationError> {
+    check_symbols(module)?;
+    check_constants(module)?;
+    check_tail_calls(module)?;
+
+    Ok(())
+}
+
+// Warnings do not make modules invalid.
+pub fn collect_warnings(module: &Module) -> Vec<ValidationWarning> {
+    check_memory_copies(module)
+        .into_iter()
+        .chain(check_undefined_values(module))
+        .collect()
+}
@@ -0,0 +1,164 @@
+use super::error::ValidationError;
+use crate::ir::*;
+
+// Initializers of global variables are evaluated at compile time.
+pub fn check_constants(module: &Module) -> Result<(), ValidationError> {
+    for definition in module.variable_definitions() {
+        check_expression(definition.body(), true)?;
+    }
+
+    Ok(())
+}
+
+// Global addresses are constant only when they are not used as numbers.
+fn check_expression(expression: &Expression, address_allowed: bool) -> Result<(), ValidationError> {
+    match expression {
+        Expression::AbsoluteAddress(_)
+        | Expression::AlignOf(_)
+        | Expression::Primitive(_)
+        | Expression::SizeOf(_)
+        | Expression::Undefined(_) => {}
+        Expression::ArithmeticOperation(operation) => {
+            check_expression(operation.lhs(), false)?;
+            check_expression(operation.rhs(), false)?;
+        }
+        Expression::BitCast(bit_cast) => check_expression(bit_cast.expression(), address_allowed)?,
+        Expression::BitManipulationOperation(operation) => {
+            check_expression(operation.value(), false)?
+        }
+        Expression::BitwiseNotOperation(operation) => check_expression(operation.value(), false)?,
+        Expression::BitwiseOperation(operation) => {
+            check_expression(operation.lhs(), false)?;
+            check_expression(operation.rhs(), false)?;
+        }
+        Expression::ByteOffset(offset) => {
+            check_expression(offset.pointer(), address_allowed)?;
+            check_expression(offset.offset(), false)?;
+        }
+        Expression::ComparisonOperation(operation) => {
+            check_expression(operation.lhs(), false)?;
+            check_expression(operation.rhs(), false)?;
+        }
+        Expression::ExtractBits(extract) => check_expression(extract.value(), false)?,
+        Expression::InsertBits(insert) => {
+            check_expression(insert.value(), false)?;
+            check_expression(insert.field(), false)?;
+        }
+        Expression::PointerAddress(address) => {
+            check_expression(address.pointer(), address_allowed)?;
+            check_expression(address.offset(), false)?;
+        }
+        Expression::PureCall(_) => {
+            return Err(ValidationError::NonConstantExpression(expression.clone()))
+        }
+        Expression::Record(record) => {
+            for element in record.elements() {
+                check_expression(element, address_allowed)?;
+            }
+        }
+        Expression::RecordAddress(address) => check_expression(address.pointer(), address_allowed)?,
+        Expression::Select(select) => {
+            check_expression(select.condition(), false)?;
+            check_expression(select.then(), address_allowed)?;
+            check_expression(select.else_(), address_allowed)?;
+        }
+        Expression::Union(union) => check_expression(union.member(), address_allowed)?,
+        Expression::UnionAddress(address) => check_expression(address.pointer(), address_allowed)?,
+        Expression::Variable(_) => {
+            if !address_allowed {
+                return Err(ValidationError::NonConstantExpression(expression.clone()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types;
+
+    fn create_module(body: impl Into<Expression>, type_: impl Into<types::Type>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![
+                VariableDefinition::new(
+                    "x",
+                    Primitive::PointerInteger(42),
+                    types::Primitive::PointerInteger,
+                    false,
+                    Linkage::Internal,
+                    None,
+                ),
+                VariableDefinition::new("y", body, type_, false, Linkage::Internal, None),
+            ],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn check_constant_record() {
+        let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+        assert_eq!(
+            check_constants(&create_module(
+                Record::new(
+                    types::Record::new(vec![
+                        types::Primitive::PointerInteger.into(),
+                        pointer_type.clone().into(),
+                    ]),
+                    vec![
+                        ArithmeticOperation::new(
+                            types::Primitive::PointerInteger,
+                            ArithmeticOperator::Multiply,
+                            SizeOf::new(types::Primitive::Integer64),
+                            Primitive::PointerInteger(2),
+                        )
+                        .into(),
+                        PointerAddress::new(
+                            pointer_type.clone(),
+                            Variable::new("x"),
+                            Primitive::PointerInteger(1),
+                        )
+                        .into(),
+                    ],
+                ),
+                types::Record::new(vec![
+                    types::Primitive::PointerInteger.into(),
+                    pointer_type.into(),
+                ]),
+            )),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fail_to_check_arithmetic_operation_on_global_address() {
+        let pointer_type = types::Pointer::new(types::Primitive::PointerInteger);
+
+        assert_eq!(
+            check_constants(&create_module(
+                Record::new(
+                    types::Record::new(vec![types::Primitive::PointerInteger.into()]),
+                    vec![ArithmeticOperation::new(
+                        types::Primitive::PointerInteger,
+                        ArithmeticOperator::Add,
+                        BitCast::new(
+                            pointer_type,
+                            types::Primitive::PointerInteger,
+                            Variable::new("x"),
+                        ),
+                        Primitive::PointerInteger(1),
+                    )
+                    .into()],
+                ),
+                types::Record::new(vec![types::Primitive::PointerInteger.into()]),
+            )),
+            Err(ValidationError::NonConstantExpression(
+                Variable::new("x").into()
+            ))
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{analysis::format_expression, ir::*};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    NonConstantExpression(Expression),
+    SymbolNotFound(Variable),
+    TailCallNotInTailPosition(Call),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NonConstantExpression(expression) => write!(
+                formatter,
+                "non-constant expression: {}",
+                format_expression(expression)
+            ),
+            Self::SymbolNotFound(variable) => {
+                write!(formatter, "symbol {} not found", variable.name())
+            }
+            Self::TailCallNotInTailPosition(call) => write!(
+                formatter,
+                "tail call {} to {} not in tail position",
+                call.name(),
+                format_expression(call.function())
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
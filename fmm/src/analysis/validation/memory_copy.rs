@@ -0,0 +1,139 @@
+use super::warning::ValidationWarning;
+use crate::{analysis::alias::decompose_bytes, ir::*};
+
+// Regions of memory copies not marked as overlapping are checked only if they
+// are derived from the same pointers by constant offsets. They are reported
+// only if they overlap for both 32 and 64-bit pointers so that warnings do not
+// depend on targets.
+pub fn check_memory_copies(module: &Module) -> Vec<ValidationWarning> {
+    module
+        .function_definitions()
+        .iter()
+        .filter(|definition| {
+            definition
+                .instructions_recursive()
+                .any(|(_, instruction)| match instruction {
+                    Instruction::MemoryCopy(copy) => {
+                        !copy.overlapping()
+                            && [4, 8]
+                                .iter()
+                                .all(|&pointer_size| is_overlapping(copy, pointer_size))
+                    }
+                    _ => false,
+                })
+        })
+        .map(|definition| ValidationWarning::OverlappingMemoryCopy(definition.name().into()))
+        .collect()
+}
+
+fn is_overlapping(copy: &MemoryCopy, pointer_size: usize) -> bool {
+    let (source, source_offset) = decompose_bytes(copy.source(), pointer_size);
+    let (destination, destination_offset) = decompose_bytes(copy.destination(), pointer_size);
+
+    if source != destination {
+        return false;
+    }
+
+    let distance = match source_offset
+        .zip(destination_offset)
+        .and_then(|(one, other)| one.checked_sub(other))
+    {
+        Some(distance) => distance.unsigned_abs(),
+        None => return false,
+    };
+
+    match copy.size() {
+        Expression::Primitive(Primitive::PointerInteger(size)) => distance < *size as u64,
+        _ => distance == 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_module(source: impl Into<Expression>, destination: impl Into<Expression>) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new(
+                    "p",
+                    types::Pointer::new(types::Primitive::Integer32),
+                )],
+                Block::new(
+                    vec![
+                        MemoryCopy::new(source, destination, Primitive::PointerInteger(8), false)
+                            .into(),
+                    ],
+                    TerminalInstruction::Unreachable,
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_element_address(index: i64) -> PointerAddress {
+        PointerAddress::new(
+            types::Pointer::new(types::Primitive::Integer32),
+            Variable::new("p"),
+            Primitive::PointerInteger(index),
+        )
+    }
+
+    #[test]
+    fn check_overlapping_copy() {
+        let module = create_module(Variable::new("p"), create_element_address(1));
+
+        assert_eq!(
+            check_memory_copies(&module),
+            vec![ValidationWarning::OverlappingMemoryCopy("f".into())]
+        );
+    }
+
+    #[test]
+    fn check_overlapping_copy_through_record_address() {
+        let record_type = types::Record::new(vec![
+            types::Primitive::Integer32.into(),
+            types::Primitive::Integer32.into(),
+        ]);
+        let module = create_module(
+            RecordAddress::new(
+                record_type.clone(),
+                BitCast::new(
+                    types::Pointer::new(types::Primitive::Integer32),
+                    types::Pointer::new(record_type),
+                    Variable::new("p"),
+                ),
+                1,
+            ),
+            create_element_address(2),
+        );
+
+        assert_eq!(check_memory_copies(&module).len(), 1);
+    }
+
+    #[test]
+    fn check_disjoint_copy() {
+        assert_eq!(
+            check_memory_copies(&create_module(
+                create_element_address(2),
+                Variable::new("p")
+            )),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn check_copy_of_different_pointers() {
+        assert_eq!(
+            check_memory_copies(&create_module(Variable::new("p"), Variable::new("q"))),
+            vec![]
+        );
+    }
+}
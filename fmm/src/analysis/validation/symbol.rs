@@ -0,0 +1,153 @@
+use super::error::ValidationError;
+use crate::{
+    analysis::free_variables::{collect_free_variables, collect_from_expression},
+    ir::*,
+};
+use std::collections::{BTreeSet, HashSet};
+
+// Local variables are resolved by the type checker and so only names not bound
+// anywhere in definitions are checked.
+pub fn check_symbols(module: &Module) -> Result<(), ValidationError> {
+    let symbols = module
+        .variable_declarations()
+        .iter()
+        .map(|declaration| declaration.name())
+        .chain(
+            module
+                .function_declarations()
+                .iter()
+                .map(|declaration| declaration.name()),
+        )
+        .chain(
+            module
+                .variable_definitions()
+                .iter()
+                .map(|definition| definition.name()),
+        )
+        .chain(
+            module
+                .function_definitions()
+                .iter()
+                .map(|definition| definition.name()),
+        )
+        .collect::<HashSet<_>>();
+
+    let mut names = BTreeSet::new();
+
+    for definition in module.variable_definitions() {
+        names.extend(collect_from_expression(definition.body()));
+    }
+
+    for definition in module.function_definitions() {
+        let local_names = definition
+            .arguments()
+            .iter()
+            .map(|argument| argument.name())
+            .chain(
+                definition
+                    .instructions_recursive()
+                    .filter_map(|(_, instruction)| instruction.name()),
+            )
+            .collect::<HashSet<_>>();
+
+        names.extend(
+            collect_free_variables(
+                definition.body().instructions(),
+                definition.body().terminal_instruction(),
+            )
+            .into_iter()
+            .filter(|name| !local_names.contains(name.as_str())),
+        );
+    }
+
+    if let Some(name) = names
+        .into_iter()
+        .find(|name| !symbols.contains(name.as_str()))
+    {
+        return Err(ValidationError::SymbolNotFound(Variable::new(name)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_module(function_declarations: Vec<FunctionDeclaration>) -> Module {
+        let function_type = types::Function::new(
+            vec![],
+            types::Primitive::PointerInteger,
+            CallingConvention::Target,
+        );
+
+        Module::new(
+            vec![],
+            function_declarations,
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![Argument::new("x", types::Primitive::PointerInteger)],
+                Block::new(
+                    vec![Call::new(function_type, Variable::new("g"), vec![], "y").into()],
+                    Return::new(
+                        types::Primitive::PointerInteger,
+                        ArithmeticOperation::new(
+                            types::Primitive::PointerInteger,
+                            ArithmeticOperator::Add,
+                            Variable::new("x"),
+                            Variable::new("y"),
+                        ),
+                    ),
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+                Linkage::External,
+            )],
+        )
+    }
+
+    #[test]
+    fn check_call_to_declared_function() {
+        assert_eq!(
+            check_symbols(&create_module(vec![FunctionDeclaration::new(
+                "g",
+                types::Function::new(
+                    vec![],
+                    types::Primitive::PointerInteger,
+                    CallingConvention::Target
+                ),
+            )])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fail_to_check_call_to_undeclared_function() {
+        assert_eq!(
+            check_symbols(&create_module(vec![])),
+            Err(ValidationError::SymbolNotFound(Variable::new("g")))
+        );
+    }
+
+    #[test]
+    fn fail_to_check_undeclared_variable_in_variable_definition() {
+        assert_eq!(
+            check_symbols(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    Variable::new("y"),
+                    types::Primitive::PointerInteger,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            )),
+            Err(ValidationError::SymbolNotFound(Variable::new("y")))
+        );
+    }
+}
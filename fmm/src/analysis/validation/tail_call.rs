@@ -0,0 +1,191 @@
+use super::error::ValidationError;
+use crate::{ir::*, types::CallingConvention};
+
+// Calls of the tail calling convention must be followed immediately by returns
+// of their results as their callers' frames are reused.
+pub fn check_tail_calls(module: &Module) -> Result<(), ValidationError> {
+    for definition in module.function_definitions() {
+        check_block(definition.body())?;
+    }
+
+    Ok(())
+}
+
+fn check_block(block: &Block) -> Result<(), ValidationError> {
+    for (index, instruction) in block.instructions().iter().enumerate() {
+        match instruction {
+            Instruction::Call(call)
+                if call.type_().calling_convention() == CallingConvention::Tail
+                    && (index + 1 != block.instructions().len()
+                        || !is_return_of(block.terminal_instruction(), call.name())) =>
+            {
+                return Err(ValidationError::TailCallNotInTailPosition(call.clone()));
+            }
+            Instruction::If(if_) => {
+                check_block(if_.then())?;
+                check_block(if_.else_())?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn is_return_of(instruction: &TerminalInstruction, name: &str) -> bool {
+    matches!(
+        instruction,
+        TerminalInstruction::Return(return_)
+            if return_.expression() == &Variable::new(name).into()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{analysis::transform_to_cps, types};
+
+    fn create_module(instructions: Vec<Instruction>) -> Module {
+        Module::new(
+            vec![],
+            vec![FunctionDeclaration::new(
+                "f",
+                types::Function::new(
+                    vec![],
+                    types::Primitive::PointerInteger,
+                    CallingConvention::Tail,
+                ),
+            )],
+            vec![],
+            vec![FunctionDefinition::new(
+                "g",
+                vec![],
+                Block::new(
+                    instructions,
+                    Return::new(types::Primitive::PointerInteger, Variable::new("x")),
+                ),
+                types::Primitive::PointerInteger,
+                CallingConvention::Tail,
+                Linkage::External,
+            )],
+        )
+    }
+
+    fn create_call(name: &str) -> Instruction {
+        Call::new(
+            types::Function::new(
+                vec![],
+                types::Primitive::PointerInteger,
+                CallingConvention::Tail,
+            ),
+            Variable::new("f"),
+            vec![],
+            name,
+        )
+        .into()
+    }
+
+    #[test]
+    fn check_tail_call() {
+        assert_eq!(
+            check_tail_calls(&create_module(vec![create_call("x")])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fail_to_check_tail_call_not_in_tail_position() {
+        assert!(matches!(
+            check_tail_calls(&create_module(vec![
+                create_call("y"),
+                create_call("x"),
+            ])),
+            Err(ValidationError::TailCallNotInTailPosition(call)) if call.name() == "y"
+        ));
+    }
+
+    #[test]
+    fn fail_to_check_tail_call_of_other_result() {
+        assert!(matches!(
+            check_tail_calls(&create_module(vec![
+                PassThrough::new(
+                    types::Primitive::PointerInteger,
+                    Primitive::PointerInteger(0),
+                    "x"
+                )
+                .into(),
+                create_call("y"),
+            ])),
+            Err(ValidationError::TailCallNotInTailPosition(_))
+        ));
+    }
+
+    #[test]
+    fn check_tail_calls_in_cps_module() {
+        let function_type = types::Function::new(
+            vec![],
+            types::Primitive::PointerInteger,
+            CallingConvention::Source,
+        );
+
+        assert_eq!(
+            check_tail_calls(
+                &transform_to_cps(
+                    &Module::new(
+                        vec![],
+                        vec![FunctionDeclaration::new("f", function_type.clone())],
+                        vec![],
+                        vec![FunctionDefinition::new(
+                            "g",
+                            vec![Argument::new("c", types::Primitive::Boolean)],
+                            Block::new(
+                                vec![
+                                    Call::new(
+                                        function_type.clone(),
+                                        Variable::new("f"),
+                                        vec![],
+                                        "x"
+                                    )
+                                    .into(),
+                                    If::new(
+                                        types::Primitive::PointerInteger,
+                                        Variable::new("c"),
+                                        Block::new(
+                                            vec![Call::new(
+                                                function_type,
+                                                Variable::new("f"),
+                                                vec![],
+                                                "y"
+                                            )
+                                            .into()],
+                                            Branch::new(
+                                                types::Primitive::PointerInteger,
+                                                Variable::new("y")
+                                            )
+                                        ),
+                                        Block::new(
+                                            vec![],
+                                            Branch::new(
+                                                types::Primitive::PointerInteger,
+                                                Variable::new("x")
+                                            )
+                                        ),
+                                        "z"
+                                    )
+                                    .into(),
+                                ],
+                                Return::new(types::Primitive::PointerInteger, Variable::new("z")),
+                            ),
+                            types::Primitive::PointerInteger,
+                            CallingConvention::Source,
+                            Linkage::External,
+                        )],
+                    ),
+                    types::VOID_TYPE.clone(),
+                )
+                .unwrap()
+            ),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use super::warning::ValidationWarning;
+use crate::ir::*;
+
+// Undefined values are legitimate in local slots but suspicious where they
+// escape to other modules.
+pub fn check_undefined_values(module: &Module) -> Vec<ValidationWarning> {
+    module
+        .variable_definitions()
+        .iter()
+        .filter(|definition| contains_undefined(definition.body()))
+        .map(|definition| ValidationWarning::UndefinedInitializer(definition.name().into()))
+        .chain(
+            module
+                .function_definitions()
+                .iter()
+                .filter(|definition| {
+                    definition.linkage() != Linkage::Internal
+                        && returns_undefined(definition.body())
+                })
+                .map(|definition| ValidationWarning::UndefinedReturn(definition.name().into())),
+        )
+        .collect()
+}
+
+fn returns_undefined(block: &Block) -> bool {
+    block
+        .instructions()
+        .iter()
+        .any(|instruction| match instruction {
+            Instruction::If(if_) => returns_undefined(if_.then()) || returns_undefined(if_.else_()),
+            _ => false,
+        })
+        || match block.terminal_instruction() {
+            TerminalInstruction::Return(return_) => contains_undefined(return_.expression()),
+            _ => false,
+        }
+}
+
+fn contains_undefined(expression: &Expression) -> bool {
+    match expression {
+        Expression::BitCast(bit_cast) => contains_undefined(bit_cast.expression()),
+        Expression::Record(record) => record.elements().iter().any(contains_undefined),
+        Expression::Undefined(_) => true,
+        Expression::Select(select) => {
+            contains_undefined(select.then()) || contains_undefined(select.else_())
+        }
+        Expression::Union(union) => contains_undefined(union.member()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{self, CallingConvention};
+
+    fn create_function_definition(linkage: Linkage) -> FunctionDefinition {
+        FunctionDefinition::new(
+            "f",
+            vec![],
+            Block::new(
+                vec![],
+                Return::new(
+                    types::Primitive::PointerInteger,
+                    Undefined::new(types::Primitive::PointerInteger),
+                ),
+            ),
+            types::Primitive::PointerInteger,
+            CallingConvention::Target,
+            linkage,
+        )
+    }
+
+    #[test]
+    fn check_undefined_initializer() {
+        assert_eq!(
+            check_undefined_values(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    Undefined::new(types::Primitive::PointerInteger),
+                    types::Primitive::PointerInteger,
+                    false,
+                    Linkage::Internal,
+                    None,
+                )],
+                vec![],
+            )),
+            vec![ValidationWarning::UndefinedInitializer("x".into())]
+        );
+    }
+
+    #[test]
+    fn check_undefined_element_in_initializer() {
+        let type_ = types::Record::new(vec![types::Primitive::PointerInteger.into()]);
+
+        assert_eq!(
+            check_undefined_values(&Module::new(
+                vec![],
+                vec![],
+                vec![VariableDefinition::new(
+                    "x",
+                    Record::new(
+                        type_.clone(),
+                        vec![Undefined::new(types::Primitive::PointerInteger).into()]
+                    ),
+                    type_,
+                    false,
+                    Linkage::External,
+                    None,
+                )],
+                vec![],
+            )),
+            vec![ValidationWarning::UndefinedInitializer("x".into())]
+        );
+    }
+
+    #[test]
+    fn check_undefined_return() {
+        assert_eq!(
+            check_undefined_values(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(Linkage::External)],
+            )),
+            vec![ValidationWarning::UndefinedReturn("f".into())]
+        );
+    }
+
+    #[test]
+    fn check_undefined_return_in_if() {
+        assert_eq!(
+            check_undefined_values(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![FunctionDefinition::new(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![If::new(
+                            types::Primitive::PointerInteger,
+                            Primitive::Boolean(true),
+                            Block::new(
+                                vec![],
+                                Return::new(
+                                    types::Primitive::PointerInteger,
+                                    Undefined::new(types::Primitive::PointerInteger),
+                                ),
+                            ),
+                            Block::new(vec![], TerminalInstruction::Unreachable),
+                            "x",
+                        )
+                        .into()],
+                        TerminalInstruction::Unreachable,
+                    ),
+                    types::Primitive::PointerInteger,
+                    CallingConvention::Target,
+                    Linkage::External,
+                )],
+            )),
+            vec![ValidationWarning::UndefinedReturn("f".into())]
+        );
+    }
+
+    #[test]
+    fn skip_undefined_return_of_internal_function() {
+        assert_eq!(
+            check_undefined_values(&Module::new(
+                vec![],
+                vec![],
+                vec![],
+                vec![create_function_definition(Linkage::Internal)],
+            )),
+            vec![]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationWarning {
+    OverlappingMemoryCopy(String),
+    UndefinedInitializer(String),
+    UndefinedReturn(String),
+}
+
+impl Display for ValidationWarning {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::OverlappingMemoryCopy(name) => write!(
+                formatter,
+                "memory copy of overlapping regions in function {}",
+                name
+            ),
+            Self::UndefinedInitializer(name) => {
+                write!(
+                    formatter,
+                    "undefined value in initializer of variable {}",
+                    name
+                )
+            }
+            Self::UndefinedReturn(name) => {
+                write!(formatter, "undefined value returned from function {}", name)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_overlapping_memory_copy() {
+        assert_eq!(
+            ValidationWarning::OverlappingMemoryCopy("f".into()).to_string(),
+            "memory copy of overlapping regions in function f"
+        );
+    }
+
+    #[test]
+    fn display_undefined_initializer() {
+        assert_eq!(
+            ValidationWarning::UndefinedInitializer("x".into()).to_string(),
+            "undefined value in initializer of variable x"
+        );
+    }
+}
@@ -1,12 +1,15 @@
 mod error;
 mod expressions;
+mod function_builder;
 mod instruction_builder;
 mod module_builder;
 mod name_generator;
+mod operators;
 mod typed_expression;
 
 pub use error::*;
 pub use expressions::*;
+pub use function_builder::*;
 pub use instruction_builder::*;
 pub use module_builder::*;
 pub use name_generator::*;
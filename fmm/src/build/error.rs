@@ -1,4 +1,4 @@
-use crate::types::Type;
+use crate::{analysis::format_type, types::Type};
 use std::{
     error::Error,
     fmt::{self, Display, Formatter},
@@ -6,17 +6,62 @@ use std::{
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum BuildError {
+    DuplicateNames(String),
+    DuplicateTypeNames(String),
     FunctionExpected(Type),
     PointerExpected(Type),
     PrimitiveExpected(Type),
     RecordExpected(Type),
+    TypesNotMatched(Type, Type),
     UnionExpected(Type),
 }
 
 impl Display for BuildError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        write!(formatter, "{:?}", self)
+        match self {
+            Self::DuplicateNames(name) => write!(formatter, "duplicate name: {}", name),
+            Self::DuplicateTypeNames(name) => write!(formatter, "duplicate type name: {}", name),
+            Self::FunctionExpected(type_) => {
+                write!(formatter, "function expected: {}", format_type(type_))
+            }
+            Self::PointerExpected(type_) => {
+                write!(formatter, "pointer expected: {}", format_type(type_))
+            }
+            Self::PrimitiveExpected(type_) => {
+                write!(formatter, "primitive expected: {}", format_type(type_))
+            }
+            Self::RecordExpected(type_) => {
+                write!(formatter, "record expected: {}", format_type(type_))
+            }
+            Self::TypesNotMatched(one, other) => write!(
+                formatter,
+                "types not matched: {} and {}",
+                format_type(one),
+                format_type(other)
+            ),
+            Self::UnionExpected(type_) => {
+                write!(formatter, "union expected: {}", format_type(type_))
+            }
+        }
     }
 }
 
 impl Error for BuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types;
+
+    #[test]
+    fn display_types_not_matched() {
+        assert_eq!(
+            BuildError::TypesNotMatched(
+                types::Primitive::Integer64.into(),
+                types::Pointer::new(types::Primitive::Float64).into()
+            )
+            .to_string(),
+            "types not matched: integer64 and (pointer float64)"
+        );
+    }
+}
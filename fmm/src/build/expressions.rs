@@ -4,6 +4,10 @@ use crate::{
     types::{self, Type},
 };
 
+pub fn absolute_address(type_: impl Into<Type>, address: i64) -> AbsoluteAddress {
+    AbsoluteAddress::new(type_, Primitive::PointerInteger(address))
+}
+
 pub fn align_of(type_: impl Into<Type>) -> TypedExpression {
     AlignOf::new(type_.into()).into()
 }
@@ -16,6 +20,8 @@ pub fn arithmetic_operation(
     let lhs = lhs.into();
     let rhs = rhs.into();
 
+    check_equality(lhs.type_(), rhs.type_())?;
+
     Ok(ArithmeticOperation::new(
         lhs.type_()
             .to_primitive()
@@ -36,6 +42,22 @@ pub fn bit_cast(to: impl Into<Type>, expression: impl Into<TypedExpression>) ->
     )
 }
 
+pub fn bit_manipulation_operation(
+    operator: BitManipulationOperator,
+    value: impl Into<TypedExpression>,
+) -> Result<BitManipulationOperation, BuildError> {
+    let value = value.into();
+
+    Ok(BitManipulationOperation::new(
+        value
+            .type_()
+            .to_primitive()
+            .ok_or_else(|| BuildError::PrimitiveExpected(value.type_().clone()))?,
+        operator,
+        value.expression().clone(),
+    ))
+}
+
 pub fn bitwise_not_operation(
     value: impl Into<TypedExpression>,
 ) -> Result<BitwiseNotOperation, BuildError> {
@@ -68,6 +90,25 @@ pub fn bitwise_operation(
     ))
 }
 
+pub fn byte_offset(
+    pointer: impl Into<TypedExpression>,
+    offset: impl Into<TypedExpression>,
+) -> Result<ByteOffset, BuildError> {
+    let pointer = pointer.into();
+    let offset = offset.into();
+    let type_ = pointer
+        .type_()
+        .to_pointer()
+        .ok_or_else(|| BuildError::PointerExpected(pointer.type_().clone()))?
+        .clone();
+
+    Ok(ByteOffset::new(
+        type_,
+        pointer.expression().clone(),
+        offset.expression().clone(),
+    ))
+}
+
 pub fn comparison_operation(
     operator: ComparisonOperator,
     lhs: impl Into<TypedExpression>,
@@ -76,6 +117,8 @@ pub fn comparison_operation(
     let lhs = lhs.into();
     let rhs = rhs.into();
 
+    check_equality(lhs.type_(), rhs.type_())?;
+
     Ok(ComparisonOperation::new(
         lhs.type_()
             .to_primitive()
@@ -86,6 +129,47 @@ pub fn comparison_operation(
     ))
 }
 
+pub fn extract_bits(
+    value: impl Into<TypedExpression>,
+    offset: u32,
+    width: u32,
+) -> Result<ExtractBits, BuildError> {
+    let value = value.into();
+
+    Ok(ExtractBits::new(
+        value
+            .type_()
+            .to_primitive()
+            .ok_or_else(|| BuildError::PrimitiveExpected(value.type_().clone()))?,
+        value.expression().clone(),
+        offset,
+        width,
+    ))
+}
+
+pub fn insert_bits(
+    value: impl Into<TypedExpression>,
+    field: impl Into<TypedExpression>,
+    offset: u32,
+    width: u32,
+) -> Result<InsertBits, BuildError> {
+    let value = value.into();
+    let field = field.into();
+
+    check_equality(value.type_(), field.type_())?;
+
+    Ok(InsertBits::new(
+        value
+            .type_()
+            .to_primitive()
+            .ok_or_else(|| BuildError::PrimitiveExpected(value.type_().clone()))?,
+        value.expression().clone(),
+        field.expression().clone(),
+        offset,
+        width,
+    ))
+}
+
 pub fn pointer_address(
     pointer: impl Into<TypedExpression>,
     offset: impl Into<TypedExpression>,
@@ -142,6 +226,26 @@ pub fn record_address(
     ))
 }
 
+pub fn select(
+    condition: impl Into<TypedExpression>,
+    then: impl Into<TypedExpression>,
+    else_: impl Into<TypedExpression>,
+) -> Result<Select, BuildError> {
+    let condition = condition.into();
+    let then = then.into();
+    let else_ = else_.into();
+
+    check_equality(condition.type_(), &types::Primitive::Boolean.into())?;
+    check_equality(then.type_(), else_.type_())?;
+
+    Ok(Select::new(
+        then.type_().clone(),
+        condition.expression().clone(),
+        then.expression().clone(),
+        else_.expression().clone(),
+    ))
+}
+
 pub fn size_of(type_: impl Into<Type>) -> TypedExpression {
     SizeOf::new(type_.into()).into()
 }
@@ -171,3 +275,19 @@ pub fn union_address(
 pub fn variable(name: impl Into<String>, type_: impl Into<Type>) -> TypedExpression {
     TypedExpression::new(Variable::new(name), type_)
 }
+
+pub fn void_type() -> types::Record {
+    types::VOID_TYPE.clone()
+}
+
+pub fn void_value() -> Record {
+    VOID_VALUE.clone()
+}
+
+fn check_equality(one: &Type, other: &Type) -> Result<(), BuildError> {
+    if one == other {
+        Ok(())
+    } else {
+        Err(BuildError::TypesNotMatched(one.clone(), other.clone()))
+    }
+}
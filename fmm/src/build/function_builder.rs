@@ -0,0 +1,156 @@
+use super::{
+    instruction_builder::InstructionBuilder, name_generator::NameGenerator, typed_expression::*,
+};
+use crate::{
+    ir::*,
+    types::{CallingConvention, Type},
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Clone, Debug)]
+pub struct FunctionBuilder {
+    name: String,
+    arguments: Vec<Argument>,
+    result_type: Type,
+    calling_convention: CallingConvention,
+    linkage: Linkage,
+    instruction_builder: InstructionBuilder,
+}
+
+impl FunctionBuilder {
+    pub fn new(
+        name_generator: Rc<RefCell<NameGenerator>>,
+        name: impl Into<String>,
+        arguments: Vec<Argument>,
+        result_type: impl Into<Type>,
+        calling_convention: CallingConvention,
+        linkage: Linkage,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            arguments,
+            result_type: result_type.into(),
+            calling_convention,
+            linkage,
+            instruction_builder: InstructionBuilder::new(name_generator),
+        }
+    }
+
+    pub fn argument(&self, index: usize) -> Option<TypedExpression> {
+        self.arguments.get(index).map(|argument| {
+            TypedExpression::new(Variable::new(argument.name()), argument.type_().clone())
+        })
+    }
+
+    pub fn body(&self) -> &InstructionBuilder {
+        &self.instruction_builder
+    }
+
+    pub fn return_(self, typed_expression: impl Into<TypedExpression>) -> FunctionDefinition {
+        let block = self.instruction_builder.return_(typed_expression);
+
+        self.finalize(block)
+    }
+
+    pub fn unreachable(self) -> FunctionDefinition {
+        let block = self.instruction_builder.unreachable();
+
+        self.finalize(block)
+    }
+
+    fn finalize(self, body: Block) -> FunctionDefinition {
+        FunctionDefinition::new(
+            self.name,
+            self.arguments,
+            body,
+            self.result_type,
+            self.calling_convention,
+            self.linkage,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{analysis::check_types, build::ModuleBuilder, types};
+
+    #[test]
+    fn build_function_with_load() {
+        let module_builder = ModuleBuilder::new();
+        let builder = module_builder.function_builder(
+            "f",
+            vec![Argument::new(
+                "x",
+                types::Pointer::new(types::Primitive::Float64),
+            )],
+            types::Primitive::Float64,
+            CallingConvention::Target,
+            Linkage::External,
+        );
+
+        let value = builder.body().load(builder.argument(0).unwrap()).unwrap();
+        let definition = builder.return_(value);
+
+        assert_eq!(
+            definition,
+            FunctionDefinition::new(
+                "f",
+                vec![Argument::new(
+                    "x",
+                    types::Pointer::new(types::Primitive::Float64),
+                )],
+                Block::new(
+                    vec![Load::new(types::Primitive::Float64, Variable::new("x"), "_fmm_0").into()],
+                    Return::new(types::Primitive::Float64, Variable::new("_fmm_0")),
+                ),
+                types::Primitive::Float64,
+                CallingConvention::Target,
+                Linkage::External,
+            )
+        );
+
+        module_builder.add_function_definition(definition);
+
+        assert_eq!(check_types(&module_builder.build().unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn build_unreachable_function() {
+        assert_eq!(
+            ModuleBuilder::new()
+                .function_builder(
+                    "f",
+                    vec![],
+                    types::Primitive::Float64,
+                    CallingConvention::Target,
+                    Linkage::External,
+                )
+                .unreachable(),
+            FunctionDefinition::new(
+                "f",
+                vec![],
+                Block::new(vec![], TerminalInstruction::Unreachable),
+                types::Primitive::Float64,
+                CallingConvention::Target,
+                Linkage::External,
+            )
+        );
+    }
+
+    #[test]
+    fn share_name_generator_with_module_builder() {
+        let module_builder = ModuleBuilder::new();
+        let builder = module_builder.function_builder(
+            "f",
+            vec![],
+            types::Primitive::PointerInteger,
+            CallingConvention::Target,
+            Linkage::External,
+        );
+
+        builder.body().allocate_heap(Primitive::PointerInteger(42));
+
+        assert_eq!(module_builder.generate_name(), "_fmm_1");
+    }
+}
@@ -1,5 +1,8 @@
 use super::{
-    error::BuildError, expressions::variable, name_generator::NameGenerator, typed_expression::*,
+    error::BuildError,
+    expressions::{bit_cast, record_address, variable, void_value},
+    name_generator::NameGenerator,
+    typed_expression::*,
 };
 use crate::{
     ir::*,
@@ -58,6 +61,27 @@ impl InstructionBuilder {
         TypedExpression::new(Variable::new(name), types::Pointer::new(type_))
     }
 
+    pub fn assert(&self, condition: impl Into<TypedExpression>) -> Result<(), BuildError> {
+        let condition = condition.into();
+
+        if condition.type_() != &types::Primitive::Boolean.into() {
+            return Err(BuildError::TypesNotMatched(
+                condition.type_().clone(),
+                types::Primitive::Boolean.into(),
+            ));
+        } else if condition.expression() == &Primitive::Boolean(true).into() {
+            return Ok(());
+        }
+
+        self.if_::<BuildError>(
+            condition,
+            |builder| Ok(builder.branch(void_value())),
+            |builder| Ok(builder.trap()),
+        )?;
+
+        Ok(())
+    }
+
     pub fn atomic_load(
         &self,
         pointer: impl Into<TypedExpression>,
@@ -66,9 +90,8 @@ impl InstructionBuilder {
         let pointer = pointer.into();
         let type_ = pointer
             .type_()
-            .to_pointer()
+            .pointer_element()
             .ok_or_else(|| BuildError::PointerExpected(pointer.type_().clone()))?
-            .element()
             .clone();
         let name = self.generate_name();
 
@@ -154,6 +177,56 @@ impl InstructionBuilder {
         Ok(variable(name, type_.result().clone()))
     }
 
+    // Multiple results are returned as records and bound to names by record
+    // deconstruction as instructions have single results. So type checking of
+    // multiple results is that of records.
+    pub fn call_with_results(
+        &self,
+        function: impl Into<TypedExpression>,
+        arguments: Vec<TypedExpression>,
+    ) -> Result<Vec<TypedExpression>, BuildError> {
+        let result = self.call(function, arguments)?;
+        let type_ = result
+            .type_()
+            .to_record()
+            .ok_or_else(|| BuildError::RecordExpected(result.type_().clone()))?
+            .clone();
+
+        (0..type_.elements().len())
+            .map(|index| self.deconstruct_record(result.clone(), index))
+            .collect()
+    }
+
+    pub fn checked_arithmetic_operation(
+        &self,
+        operator: ArithmeticOperator,
+        signed: bool,
+        lhs: impl Into<TypedExpression>,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        let lhs = lhs.into();
+        let rhs = rhs.into();
+        let type_ = lhs
+            .type_()
+            .to_primitive()
+            .ok_or_else(|| BuildError::PrimitiveExpected(lhs.type_().clone()))?;
+        let name = self.generate_name();
+        let operation = CheckedArithmeticOperation::new(
+            type_,
+            operator,
+            signed,
+            lhs.expression().clone(),
+            rhs.expression().clone(),
+            &name,
+        );
+
+        let type_ = operation.result_type();
+
+        self.add_instruction(operation);
+
+        Ok(variable(name, type_))
+    }
+
     pub fn compare_and_swap(
         &self,
         pointer: impl Into<TypedExpression>,
@@ -230,10 +303,42 @@ impl InstructionBuilder {
         self.add_instruction(Fence::new(ordering))
     }
 
+    pub fn fma(
+        &self,
+        lhs: impl Into<TypedExpression>,
+        rhs: impl Into<TypedExpression>,
+        addend: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        let lhs = lhs.into();
+        let type_ = lhs
+            .type_()
+            .to_primitive()
+            .ok_or_else(|| BuildError::PrimitiveExpected(lhs.type_().clone()))?;
+        let name = self.generate_name();
+
+        self.add_instruction(Fma::new(
+            type_,
+            lhs.expression().clone(),
+            rhs.into().expression().clone(),
+            addend.into().expression().clone(),
+            &name,
+        ));
+
+        Ok(variable(name, type_))
+    }
+
     pub fn free_heap(&self, pointer: impl Into<TypedExpression>) {
         self.add_instruction(FreeHeap::new(pointer.into().expression().clone()));
     }
 
+    pub fn lifetime_start(&self, pointer: impl Into<TypedExpression>) {
+        self.add_instruction(LifetimeStart::new(pointer.into().expression().clone()));
+    }
+
+    pub fn lifetime_end(&self, pointer: impl Into<TypedExpression>) {
+        self.add_instruction(LifetimeEnd::new(pointer.into().expression().clone()));
+    }
+
     pub fn if_<E>(
         &self,
         condition: impl Into<TypedExpression>,
@@ -268,9 +373,8 @@ impl InstructionBuilder {
         let pointer = pointer.into();
         let type_ = pointer
             .type_()
-            .to_pointer()
+            .pointer_element()
             .ok_or_else(|| BuildError::PointerExpected(pointer.type_().clone()))?
-            .element()
             .clone();
         let name = self.generate_name();
 
@@ -283,6 +387,44 @@ impl InstructionBuilder {
         Ok(TypedExpression::new(Variable::new(name), type_))
     }
 
+    pub fn load_as(
+        &self,
+        pointer: impl Into<TypedExpression>,
+        type_: impl Into<Type>,
+    ) -> Result<TypedExpression, BuildError> {
+        self.load(bit_cast(types::Pointer::new(type_), pointer))
+    }
+
+    pub fn load_field(
+        &self,
+        pointer: impl Into<TypedExpression>,
+        element_index: usize,
+    ) -> Result<TypedExpression, BuildError> {
+        self.load(record_address(pointer, element_index)?)
+    }
+
+    pub fn math_operation(
+        &self,
+        operator: MathOperator,
+        value: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        let value = value.into();
+        let type_ = value
+            .type_()
+            .to_primitive()
+            .ok_or_else(|| BuildError::PrimitiveExpected(value.type_().clone()))?;
+        let name = self.generate_name();
+
+        self.add_instruction(MathOperation::new(
+            type_,
+            operator,
+            value.expression().clone(),
+            &name,
+        ));
+
+        Ok(variable(name, type_))
+    }
+
     pub fn pass_through(&self, value: impl Into<TypedExpression>) -> TypedExpression {
         let value = value.into();
         let name = self.generate_name();
@@ -296,6 +438,32 @@ impl InstructionBuilder {
         TypedExpression::new(Variable::new(name), value.type_().clone())
     }
 
+    pub fn saturating_arithmetic_operation(
+        &self,
+        operator: ArithmeticOperator,
+        signed: bool,
+        lhs: impl Into<TypedExpression>,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        let lhs = lhs.into();
+        let rhs = rhs.into();
+        let type_ = lhs
+            .type_()
+            .to_primitive()
+            .ok_or_else(|| BuildError::PrimitiveExpected(lhs.type_().clone()))?;
+        let name = self.generate_name();
+        self.add_instruction(SaturatingArithmeticOperation::new(
+            type_,
+            operator,
+            signed,
+            lhs.expression().clone(),
+            rhs.expression().clone(),
+            &name,
+        ));
+
+        Ok(variable(name, type_))
+    }
+
     pub fn store(&self, value: impl Into<TypedExpression>, pointer: impl Into<TypedExpression>) {
         let value = value.into();
         let pointer = pointer.into();
@@ -331,6 +499,13 @@ impl InstructionBuilder {
         )
     }
 
+    pub fn trap(&self) -> Block {
+        Block::new(
+            self.instructions.borrow().clone(),
+            TerminalInstruction::Trap,
+        )
+    }
+
     pub fn unreachable(&self) -> Block {
         Block::new(
             self.instructions.borrow().clone(),
@@ -350,3 +525,164 @@ impl InstructionBuilder {
         self.name_generator.borrow_mut().generate()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build::{self, NameGenerator};
+
+    fn create_builder() -> InstructionBuilder {
+        InstructionBuilder::new(Rc::new(NameGenerator::new("x").into()))
+    }
+
+    #[test]
+    fn call_with_results() {
+        let builder = create_builder();
+        let results = builder
+            .call_with_results(
+                build::variable(
+                    "f",
+                    types::Function::new(
+                        vec![],
+                        types::Record::new(vec![
+                            types::Primitive::Float64.into(),
+                            types::Primitive::PointerInteger.into(),
+                        ]),
+                        types::CallingConvention::Target,
+                    ),
+                ),
+                vec![],
+            )
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                build::variable("x1", types::Primitive::Float64),
+                build::variable("x2", types::Primitive::PointerInteger),
+            ]
+        );
+        assert_eq!(builder.into_instructions().len(), 3);
+    }
+
+    #[test]
+    fn fail_to_call_with_results_of_non_record() {
+        assert_eq!(
+            create_builder().call_with_results(
+                build::variable(
+                    "f",
+                    types::Function::new(
+                        vec![],
+                        types::Primitive::Float64,
+                        types::CallingConvention::Target,
+                    ),
+                ),
+                vec![],
+            ),
+            Err(BuildError::RecordExpected(types::Primitive::Float64.into()))
+        );
+    }
+
+    #[test]
+    fn load_as() {
+        let pointer = build::variable("p", GENERIC_POINTER_TYPE.clone());
+
+        let builder = create_builder();
+        let value = builder
+            .load_as(pointer.clone(), types::Primitive::Float64)
+            .unwrap();
+
+        let other_builder = create_builder();
+        let other_value = other_builder
+            .load(build::bit_cast(
+                types::Pointer::new(types::Primitive::Float64),
+                pointer,
+            ))
+            .unwrap();
+
+        assert_eq!(value, other_value);
+        assert_eq!(value.type_(), &types::Primitive::Float64.into());
+        assert_eq!(
+            builder.into_instructions(),
+            other_builder.into_instructions()
+        );
+    }
+
+    #[test]
+    fn load_field() {
+        let pointer = build::variable(
+            "p",
+            types::Pointer::new(types::Record::new(vec![
+                types::Primitive::Float64.into(),
+                types::Primitive::PointerInteger.into(),
+            ])),
+        );
+
+        let builder = create_builder();
+        let value = builder.load_field(pointer.clone(), 1).unwrap();
+
+        let other_builder = create_builder();
+        let other_value = other_builder
+            .load(build::record_address(pointer, 1).unwrap())
+            .unwrap();
+
+        assert_eq!(value, other_value);
+        assert_eq!(value.type_(), &types::Primitive::PointerInteger.into());
+        assert_eq!(
+            builder.into_instructions(),
+            other_builder.into_instructions()
+        );
+    }
+
+    #[test]
+    fn fail_to_load_field_of_non_record() {
+        assert_eq!(
+            create_builder().load_field(
+                build::variable("p", types::Pointer::new(types::Primitive::Float64)),
+                0
+            ),
+            Err(BuildError::RecordExpected(types::Primitive::Float64.into()))
+        );
+    }
+
+    #[test]
+    fn assert() {
+        let builder = create_builder();
+
+        builder
+            .assert(build::variable("x", types::Primitive::Boolean))
+            .unwrap();
+
+        assert_eq!(
+            builder.into_instructions(),
+            vec![If::new(
+                VOID_TYPE.clone(),
+                Variable::new("x"),
+                Block::new(vec![], Branch::new(VOID_TYPE.clone(), build::void_value())),
+                Block::new(vec![], TerminalInstruction::Trap),
+                "x0",
+            )
+            .into()]
+        );
+    }
+
+    #[test]
+    fn assert_true() {
+        let builder = create_builder();
+
+        builder.assert(Primitive::Boolean(true)).unwrap();
+
+        assert_eq!(builder.into_instructions(), vec![]);
+    }
+
+    #[test]
+    fn fail_to_assert_non_boolean() {
+        assert_eq!(
+            create_builder().assert(Primitive::Float64(42.0)),
+            Err(BuildError::TypesNotMatched(
+                types::Primitive::Float64.into(),
+                types::Primitive::Boolean.into()
+            ))
+        );
+    }
+}
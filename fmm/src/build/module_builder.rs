@@ -1,12 +1,17 @@
 use super::{
-    instruction_builder::InstructionBuilder, name_generator::NameGenerator, typed_expression::*,
+    error::BuildError, function_builder::FunctionBuilder, instruction_builder::InstructionBuilder,
+    name_generator::NameGenerator, typed_expression::*,
 };
 use crate::{
+    analysis::{check_names, TypeCheckError},
     ir::*,
     types::{self, CallingConvention, Type},
 };
 use std::{cell::RefCell, rc::Rc};
 
+// A body, alignment, and variable of an interned constant
+type Constant = (TypedExpression, Option<usize>, TypedExpression);
+
 #[derive(Clone, Debug, Default)]
 pub struct ModuleBuilder {
     name_generator: Rc<RefCell<NameGenerator>>,
@@ -14,6 +19,8 @@ pub struct ModuleBuilder {
     function_declarations: Rc<RefCell<Vec<FunctionDeclaration>>>,
     variable_definitions: Rc<RefCell<Vec<VariableDefinition>>>,
     function_definitions: Rc<RefCell<Vec<FunctionDefinition>>>,
+    // Expressions may contain floating point numbers which cannot be hashed.
+    constants: Rc<RefCell<Vec<Constant>>>,
 }
 
 impl ModuleBuilder {
@@ -24,6 +31,7 @@ impl ModuleBuilder {
             function_declarations: RefCell::new(vec![]).into(),
             variable_definitions: RefCell::new(vec![]).into(),
             function_definitions: RefCell::new(vec![]).into(),
+            constants: Default::default(),
         }
     }
 
@@ -36,19 +44,66 @@ impl ModuleBuilder {
         )
     }
 
+    pub fn build(&self) -> Result<Module, BuildError> {
+        let module = self.as_module();
+
+        check_names(&module).map_err(|error| match error {
+            TypeCheckError::DuplicateNames(name) => BuildError::DuplicateNames(name),
+            TypeCheckError::DuplicateTypeNames(name) => BuildError::DuplicateTypeNames(name),
+            _ => unreachable!(),
+        })?;
+
+        Ok(module)
+    }
+
+    pub fn add_variable_declaration(&self, declaration: VariableDeclaration) -> TypedExpression {
+        let variable = TypedExpression::new(
+            Variable::new(declaration.name()),
+            types::Pointer::new(declaration.type_().clone()),
+        );
+
+        self.variable_declarations.borrow_mut().push(declaration);
+
+        variable
+    }
+
+    pub fn add_function_declaration(&self, declaration: FunctionDeclaration) -> TypedExpression {
+        let function = TypedExpression::new(
+            Variable::new(declaration.name()),
+            declaration.type_().clone(),
+        );
+
+        self.function_declarations.borrow_mut().push(declaration);
+
+        function
+    }
+
+    pub fn add_variable_definition(&self, definition: VariableDefinition) -> TypedExpression {
+        let variable = TypedExpression::new(
+            Variable::new(definition.name()),
+            types::Pointer::new(definition.type_().clone()),
+        );
+
+        self.variable_definitions.borrow_mut().push(definition);
+
+        variable
+    }
+
+    pub fn add_function_definition(&self, definition: FunctionDefinition) -> TypedExpression {
+        let function =
+            TypedExpression::new(Variable::new(definition.name()), definition.type_().clone());
+
+        self.function_definitions.borrow_mut().push(definition);
+
+        function
+    }
+
     pub fn declare_variable(
         &self,
         name: impl Into<String>,
         type_: impl Into<Type>,
     ) -> TypedExpression {
-        let name = name.into();
-        let type_ = type_.into();
-
-        self.variable_declarations
-            .borrow_mut()
-            .push(VariableDeclaration::new(&name, type_.clone()));
-
-        TypedExpression::new(Variable::new(name), types::Pointer::new(type_))
+        self.add_variable_declaration(VariableDeclaration::new(name, type_))
     }
 
     pub fn declare_function(
@@ -56,13 +111,7 @@ impl ModuleBuilder {
         name: impl Into<String>,
         type_: types::Function,
     ) -> TypedExpression {
-        let name = name.into();
-
-        self.function_declarations
-            .borrow_mut()
-            .push(FunctionDeclaration::new(&name, type_.clone()));
-
-        TypedExpression::new(Variable::new(name), type_)
+        self.add_function_declaration(FunctionDeclaration::new(name, type_))
     }
 
     pub fn define_variable(
@@ -73,24 +122,16 @@ impl ModuleBuilder {
         linkage: Linkage,
         alignment: impl Into<Option<usize>>,
     ) -> TypedExpression {
-        let name = name.into();
         let body = body.into();
 
-        self.variable_definitions
-            .borrow_mut()
-            .push(VariableDefinition::new(
-                &name,
-                body.expression().clone(),
-                body.type_().clone(),
-                mutable,
-                linkage,
-                alignment,
-            ));
-
-        TypedExpression::new(
-            Variable::new(name),
-            types::Pointer::new(body.type_().clone()),
-        )
+        self.add_variable_definition(VariableDefinition::new(
+            name,
+            body.expression().clone(),
+            body.type_().clone(),
+            mutable,
+            linkage,
+            alignment,
+        ))
     }
 
     pub fn define_anonymous_variable(
@@ -108,6 +149,35 @@ impl ModuleBuilder {
         )
     }
 
+    // Constants of the same contents share the same immutable global variables.
+    pub fn intern_constant(
+        &self,
+        body: impl Into<TypedExpression>,
+        alignment: impl Into<Option<usize>>,
+    ) -> TypedExpression {
+        let body = body.into();
+        let alignment = alignment.into();
+
+        if let Some((_, _, constant)) =
+            self.constants
+                .borrow()
+                .iter()
+                .find(|(other_body, other_alignment, _)| {
+                    other_body == &body && other_alignment == &alignment
+                })
+        {
+            return constant.clone();
+        }
+
+        let constant = self.define_anonymous_variable(body.clone(), false, alignment);
+
+        self.constants
+            .borrow_mut()
+            .push((body, alignment, constant.clone()));
+
+        constant
+    }
+
     pub fn define_function<E>(
         &self,
         name: impl Into<String>,
@@ -117,32 +187,34 @@ impl ModuleBuilder {
         calling_convention: CallingConvention,
         linkage: Linkage,
     ) -> Result<TypedExpression, E> {
-        let result_type = result_type.into();
-        let name = name.into();
         let body = body(InstructionBuilder::new(self.name_generator.clone()))?;
 
-        self.function_definitions
-            .borrow_mut()
-            .push(FunctionDefinition::new(
-                &name,
-                arguments.clone(),
-                body,
-                result_type.clone(),
-                calling_convention,
-                linkage,
-            ));
-
-        Ok(TypedExpression::new(
-            Variable::new(name),
-            types::Function::new(
-                arguments
-                    .iter()
-                    .map(|argument| argument.type_().clone())
-                    .collect(),
-                result_type,
-                calling_convention,
-            ),
-        ))
+        Ok(self.add_function_definition(FunctionDefinition::new(
+            name,
+            arguments,
+            body,
+            result_type,
+            calling_convention,
+            linkage,
+        )))
+    }
+
+    pub fn function_builder(
+        &self,
+        name: impl Into<String>,
+        arguments: Vec<Argument>,
+        result_type: impl Into<Type>,
+        calling_convention: CallingConvention,
+        linkage: Linkage,
+    ) -> FunctionBuilder {
+        FunctionBuilder::new(
+            self.name_generator.clone(),
+            name,
+            arguments,
+            result_type,
+            calling_convention,
+            linkage,
+        )
     }
 
     pub fn define_anonymous_function<E>(
@@ -166,3 +238,121 @@ impl ModuleBuilder {
         self.name_generator.borrow_mut().generate()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build;
+
+    fn create_string(string: &str) -> Record {
+        build::record(
+            string
+                .bytes()
+                .map(|byte| Primitive::Integer8(byte).into())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn build_module() {
+        let builder = ModuleBuilder::new();
+
+        let x = builder.add_variable_declaration(VariableDeclaration::new(
+            "x",
+            types::Primitive::PointerInteger,
+        ));
+        let f = builder.add_function_declaration(FunctionDeclaration::new(
+            "f",
+            types::Function::new(
+                vec![],
+                types::Primitive::PointerInteger,
+                CallingConvention::Target,
+            ),
+        ));
+        builder.add_variable_definition(VariableDefinition::new(
+            "y",
+            x.expression().clone(),
+            x.type_().clone(),
+            false,
+            Linkage::Internal,
+            None,
+        ));
+        builder.add_function_definition(FunctionDefinition::new(
+            "g",
+            vec![],
+            Block::new(
+                vec![],
+                Return::new(f.type_().clone(), f.expression().clone()),
+            ),
+            f.type_().clone(),
+            CallingConvention::Target,
+            Linkage::External,
+        ));
+
+        let module = builder.build().unwrap();
+
+        assert_eq!(module.variable_declarations().len(), 1);
+        assert_eq!(module.function_declarations().len(), 1);
+        assert_eq!(module.variable_definitions().len(), 1);
+        assert_eq!(module.function_definitions().len(), 1);
+        assert_eq!(crate::analysis::check_types(&module), Ok(()));
+    }
+
+    #[test]
+    fn fail_to_build_module_with_duplicate_names() {
+        let builder = ModuleBuilder::new();
+
+        builder.declare_variable("x", types::Primitive::PointerInteger);
+        builder.define_variable(
+            "x",
+            Primitive::PointerInteger(42),
+            false,
+            Linkage::External,
+            None,
+        );
+
+        assert_eq!(builder.build(), Err(BuildError::DuplicateNames("x".into())));
+    }
+
+    #[test]
+    fn build_module_with_anonymous_variables() {
+        let builder = ModuleBuilder::new();
+
+        builder.define_anonymous_variable(Primitive::PointerInteger(1), false, None);
+        builder.define_anonymous_variable(Primitive::PointerInteger(2), false, None);
+
+        assert_eq!(builder.build().unwrap().variable_definitions().len(), 2);
+    }
+
+    #[test]
+    fn intern_same_constants() {
+        let builder = ModuleBuilder::new();
+
+        let one = builder.intern_constant(create_string("foo"), None);
+        let other = builder.intern_constant(create_string("foo"), None);
+
+        assert_eq!(one, other);
+        assert_eq!(builder.as_module().variable_definitions().len(), 1);
+    }
+
+    #[test]
+    fn intern_different_constants() {
+        let builder = ModuleBuilder::new();
+
+        let one = builder.intern_constant(create_string("foo"), None);
+        let other = builder.intern_constant(create_string("bar"), None);
+
+        assert_ne!(one, other);
+        assert_eq!(builder.as_module().variable_definitions().len(), 2);
+    }
+
+    #[test]
+    fn intern_constants_with_different_alignments() {
+        let builder = ModuleBuilder::new();
+
+        builder.intern_constant(create_string("foo"), None);
+        builder.intern_constant(create_string("foo"), 8);
+
+        assert_eq!(builder.as_module().variable_definitions().len(), 2);
+    }
+}
@@ -0,0 +1,277 @@
+use super::{
+    error::BuildError,
+    expressions::{arithmetic_operation, comparison_operation},
+    typed_expression::TypedExpression,
+};
+use crate::ir::{ArithmeticOperator, ComparisonOperator};
+use std::ops::{Add, Div, Mul, Sub};
+
+impl<T: Into<TypedExpression>> Add<T> for TypedExpression {
+    type Output = Result<Self, BuildError>;
+
+    fn add(self, rhs: T) -> Self::Output {
+        operate(ArithmeticOperator::Add, self, rhs)
+    }
+}
+
+impl<T: Into<TypedExpression>> Sub<T> for TypedExpression {
+    type Output = Result<Self, BuildError>;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        operate(ArithmeticOperator::Subtract, self, rhs)
+    }
+}
+
+impl<T: Into<TypedExpression>> Mul<T> for TypedExpression {
+    type Output = Result<Self, BuildError>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        operate(ArithmeticOperator::Multiply, self, rhs)
+    }
+}
+
+impl<T: Into<TypedExpression>> Div<T> for TypedExpression {
+    type Output = Result<Self, BuildError>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        operate(ArithmeticOperator::Divide, self, rhs)
+    }
+}
+
+impl TypedExpression {
+    pub fn equal(&self, rhs: impl Into<TypedExpression>) -> Result<TypedExpression, BuildError> {
+        self.compare(ComparisonOperator::Equal, rhs)
+    }
+
+    pub fn not_equal(
+        &self,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        self.compare(ComparisonOperator::NotEqual, rhs)
+    }
+
+    pub fn less_than(
+        &self,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        self.compare(ComparisonOperator::LessThan, rhs)
+    }
+
+    pub fn less_than_or_equal(
+        &self,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        self.compare(ComparisonOperator::LessThanOrEqual, rhs)
+    }
+
+    pub fn greater_than(
+        &self,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        self.compare(ComparisonOperator::GreaterThan, rhs)
+    }
+
+    pub fn greater_than_or_equal(
+        &self,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        self.compare(ComparisonOperator::GreaterThanOrEqual, rhs)
+    }
+
+    // Signedness of ordered comparisons is determined by operand types in
+    // backends.
+    fn compare(
+        &self,
+        operator: ComparisonOperator,
+        rhs: impl Into<TypedExpression>,
+    ) -> Result<TypedExpression, BuildError> {
+        Ok(comparison_operation(operator, self.clone(), rhs)?.into())
+    }
+}
+
+fn operate(
+    operator: ArithmeticOperator,
+    lhs: TypedExpression,
+    rhs: impl Into<TypedExpression>,
+) -> Result<TypedExpression, BuildError> {
+    Ok(arithmetic_operation(operator, lhs, rhs)?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build, ir::*, types};
+
+    fn create_operands() -> (TypedExpression, TypedExpression) {
+        (
+            build::variable("x", types::Primitive::PointerInteger),
+            build::variable("y", types::Primitive::PointerInteger),
+        )
+    }
+
+    fn create_operation(operator: ArithmeticOperator) -> TypedExpression {
+        ArithmeticOperation::new(
+            types::Primitive::PointerInteger,
+            operator,
+            Variable::new("x"),
+            Variable::new("y"),
+        )
+        .into()
+    }
+
+    #[test]
+    fn add() {
+        let (x, y) = create_operands();
+
+        assert_eq!(x + y, Ok(create_operation(ArithmeticOperator::Add)));
+    }
+
+    #[test]
+    fn subtract() {
+        let (x, y) = create_operands();
+
+        assert_eq!(x - y, Ok(create_operation(ArithmeticOperator::Subtract)));
+    }
+
+    #[test]
+    fn multiply() {
+        let (x, y) = create_operands();
+
+        assert_eq!(x * y, Ok(create_operation(ArithmeticOperator::Multiply)));
+    }
+
+    #[test]
+    fn divide() {
+        let (x, y) = create_operands();
+
+        assert_eq!(x / y, Ok(create_operation(ArithmeticOperator::Divide)));
+    }
+
+    #[test]
+    fn add_primitive() {
+        assert_eq!(
+            build::variable("x", types::Primitive::PointerInteger) + Primitive::PointerInteger(1),
+            Ok(ArithmeticOperation::new(
+                types::Primitive::PointerInteger,
+                ArithmeticOperator::Add,
+                Variable::new("x"),
+                Primitive::PointerInteger(1),
+            )
+            .into())
+        );
+    }
+
+    #[test]
+    fn fail_to_build_arithmetic_operation_with_different_types() {
+        assert_eq!(
+            build::arithmetic_operation(
+                ArithmeticOperator::Add,
+                build::variable("x", types::Primitive::PointerInteger),
+                Primitive::Float64(1.0),
+            ),
+            Err(BuildError::TypesNotMatched(
+                types::Primitive::PointerInteger.into(),
+                types::Primitive::Float64.into()
+            ))
+        );
+    }
+
+    fn create_comparison(operator: ComparisonOperator) -> TypedExpression {
+        ComparisonOperation::new(
+            types::Primitive::PointerInteger,
+            operator,
+            Variable::new("x"),
+            Variable::new("y"),
+        )
+        .into()
+    }
+
+    #[test]
+    fn compare_equal() {
+        let (x, y) = create_operands();
+
+        assert_eq!(x.equal(y), Ok(create_comparison(ComparisonOperator::Equal)));
+    }
+
+    #[test]
+    fn compare_not_equal() {
+        let (x, y) = create_operands();
+
+        assert_eq!(
+            x.not_equal(y),
+            Ok(create_comparison(ComparisonOperator::NotEqual))
+        );
+    }
+
+    #[test]
+    fn compare_less_than() {
+        let (x, y) = create_operands();
+
+        assert_eq!(
+            x.less_than(y),
+            Ok(create_comparison(ComparisonOperator::LessThan))
+        );
+    }
+
+    #[test]
+    fn compare_less_than_or_equal() {
+        let (x, y) = create_operands();
+
+        assert_eq!(
+            x.less_than_or_equal(y),
+            Ok(create_comparison(ComparisonOperator::LessThanOrEqual))
+        );
+    }
+
+    #[test]
+    fn compare_greater_than() {
+        let (x, y) = create_operands();
+
+        assert_eq!(
+            x.greater_than(y),
+            Ok(create_comparison(ComparisonOperator::GreaterThan))
+        );
+    }
+
+    #[test]
+    fn compare_greater_than_or_equal() {
+        let (x, y) = create_operands();
+
+        assert_eq!(
+            x.greater_than_or_equal(y),
+            Ok(create_comparison(ComparisonOperator::GreaterThanOrEqual))
+        );
+    }
+
+    #[test]
+    fn compare_with_boolean_result() {
+        let (x, y) = create_operands();
+
+        assert_eq!(
+            x.less_than(y).unwrap().type_(),
+            &types::Primitive::Boolean.into()
+        );
+    }
+
+    #[test]
+    fn fail_to_compare_different_types() {
+        assert_eq!(
+            build::variable("x", types::Primitive::PointerInteger).equal(Primitive::Float64(1.0)),
+            Err(BuildError::TypesNotMatched(
+                types::Primitive::PointerInteger.into(),
+                types::Primitive::Float64.into()
+            ))
+        );
+    }
+
+    #[test]
+    fn fail_to_add_different_types() {
+        assert_eq!(
+            build::variable("x", types::Primitive::PointerInteger) + Primitive::Float64(1.0),
+            Err(BuildError::TypesNotMatched(
+                types::Primitive::PointerInteger.into(),
+                types::Primitive::Float64.into()
+            ))
+        );
+    }
+}
@@ -26,6 +26,14 @@ impl TypedExpression {
     }
 }
 
+impl From<AbsoluteAddress> for TypedExpression {
+    fn from(address: AbsoluteAddress) -> Self {
+        let type_ = address.type_().clone();
+
+        Self::new(address, type_)
+    }
+}
+
 impl From<AlignOf> for TypedExpression {
     fn from(align_of: AlignOf) -> Self {
         Self::new(align_of, AlignOf::RESULT_TYPE)
@@ -44,6 +52,12 @@ impl From<BitCast> for TypedExpression {
     }
 }
 
+impl From<BitManipulationOperation> for TypedExpression {
+    fn from(operation: BitManipulationOperation) -> Self {
+        Self::new(operation.clone(), operation.type_())
+    }
+}
+
 impl From<BitwiseNotOperation> for TypedExpression {
     fn from(operation: BitwiseNotOperation) -> Self {
         Self::new(operation.clone(), operation.type_())
@@ -56,12 +70,30 @@ impl From<BitwiseOperation> for TypedExpression {
     }
 }
 
+impl From<ByteOffset> for TypedExpression {
+    fn from(offset: ByteOffset) -> Self {
+        Self::new(offset.clone(), offset.type_().clone())
+    }
+}
+
 impl From<ComparisonOperation> for TypedExpression {
     fn from(operation: ComparisonOperation) -> Self {
         Self::new(operation, ComparisonOperation::RESULT_TYPE)
     }
 }
 
+impl From<ExtractBits> for TypedExpression {
+    fn from(extract: ExtractBits) -> Self {
+        Self::new(extract.clone(), extract.type_())
+    }
+}
+
+impl From<InsertBits> for TypedExpression {
+    fn from(insert: InsertBits) -> Self {
+        Self::new(insert.clone(), insert.type_())
+    }
+}
+
 impl From<PointerAddress> for TypedExpression {
     fn from(address: PointerAddress) -> Self {
         Self::new(address.clone(), address.type_().clone())
@@ -89,6 +121,12 @@ impl From<RecordAddress> for TypedExpression {
     }
 }
 
+impl From<Select> for TypedExpression {
+    fn from(select: Select) -> Self {
+        Self::new(select.clone(), select.type_().clone())
+    }
+}
+
 impl From<SizeOf> for TypedExpression {
     fn from(size_of: SizeOf) -> Self {
         Self::new(size_of, SizeOf::RESULT_TYPE)
@@ -0,0 +1,284 @@
+use crate::{build::NameGenerator, ir::*, types};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+const MAX_FUNCTION_COUNT: usize = 4;
+const MAX_ARGUMENT_COUNT: usize = 3;
+const MAX_INSTRUCTION_COUNT: usize = 4;
+const MAX_BLOCK_DEPTH: usize = 2;
+
+const PRIMITIVE_TYPES: &[types::Primitive] = &[
+    types::Primitive::Boolean,
+    types::Primitive::Integer64,
+    types::Primitive::Float64,
+];
+
+// A module wrapper generating type-correct modules for fuzz targets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArbitraryModule(pub Module);
+
+impl<'a> Arbitrary<'a> for ArbitraryModule {
+    fn arbitrary(unstructured: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self(arbitrary_module(unstructured)?))
+    }
+}
+
+struct Context {
+    functions: Vec<(String, types::Function)>,
+    name_generator: NameGenerator,
+}
+
+pub fn arbitrary_module(unstructured: &mut Unstructured) -> Result<Module> {
+    let mut context = Context {
+        functions: vec![],
+        name_generator: NameGenerator::new("_x_"),
+    };
+    let mut function_definitions = vec![];
+
+    for index in 0..unstructured.int_in_range(0..=MAX_FUNCTION_COUNT)? {
+        let definition = arbitrary_function_definition(&mut context, unstructured, index)?;
+
+        context
+            .functions
+            .push((definition.name().into(), definition.type_().clone()));
+        function_definitions.push(definition);
+    }
+
+    Ok(Module::new(vec![], vec![], vec![], function_definitions))
+}
+
+fn arbitrary_function_definition(
+    context: &mut Context,
+    unstructured: &mut Unstructured,
+    index: usize,
+) -> Result<FunctionDefinition> {
+    let arguments = (0..unstructured.int_in_range(0..=MAX_ARGUMENT_COUNT)?)
+        .map(|index| {
+            Ok(Argument::new(
+                format!("a{}", index),
+                arbitrary_type(unstructured)?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let result_type = arbitrary_type(unstructured)?;
+    let variables = arguments
+        .iter()
+        .map(|argument| (argument.name().into(), argument.type_().clone()))
+        .collect::<Vec<_>>();
+
+    Ok(FunctionDefinition::new(
+        format!("f{}", index),
+        arguments,
+        arbitrary_block(context, unstructured, &variables, &result_type, None, 0)?,
+        result_type,
+        *unstructured.choose(&[
+            types::CallingConvention::Source,
+            types::CallingConvention::Target,
+        ])?,
+        *unstructured.choose(&[Linkage::Internal, Linkage::External])?,
+    ))
+}
+
+// Variables are kept in a list in definition order so that choices are
+// deterministic for the same input.
+fn arbitrary_block(
+    context: &mut Context,
+    unstructured: &mut Unstructured,
+    variables: &[(String, types::Type)],
+    result_type: &types::Type,
+    branch_type: Option<&types::Type>,
+    depth: usize,
+) -> Result<Block> {
+    let mut variables = variables.to_vec();
+    let mut instructions = vec![];
+
+    for _ in 0..unstructured.int_in_range(0..=MAX_INSTRUCTION_COUNT)? {
+        let instruction =
+            arbitrary_instruction(context, unstructured, &variables, result_type, depth)?;
+
+        if let (Some(name), Some(type_)) = (instruction.name(), instruction.result_type()) {
+            variables.push((name.into(), type_));
+        }
+
+        instructions.push(instruction);
+    }
+
+    let terminal_instruction: TerminalInstruction = match branch_type {
+        Some(type_) if unstructured.arbitrary()? => Branch::new(
+            type_.clone(),
+            arbitrary_expression(unstructured, &variables, type_)?,
+        )
+        .into(),
+        _ => Return::new(
+            result_type.clone(),
+            arbitrary_expression(unstructured, &variables, result_type)?,
+        )
+        .into(),
+    };
+
+    Ok(Block::new(instructions, terminal_instruction))
+}
+
+fn arbitrary_instruction(
+    context: &mut Context,
+    unstructured: &mut Unstructured,
+    variables: &[(String, types::Type)],
+    result_type: &types::Type,
+    depth: usize,
+) -> Result<Instruction> {
+    let name = context.name_generator.generate();
+
+    Ok(match unstructured.int_in_range(0..=4)? {
+        0 if depth < MAX_BLOCK_DEPTH => {
+            let type_ = arbitrary_type(unstructured)?;
+
+            If::new(
+                type_.clone(),
+                arbitrary_expression(unstructured, variables, &types::Primitive::Boolean.into())?,
+                arbitrary_block(
+                    context,
+                    unstructured,
+                    variables,
+                    result_type,
+                    Some(&type_),
+                    depth + 1,
+                )?,
+                arbitrary_block(
+                    context,
+                    unstructured,
+                    variables,
+                    result_type,
+                    Some(&type_),
+                    depth + 1,
+                )?,
+                name,
+            )
+            .into()
+        }
+        1 if !context.functions.is_empty() => {
+            let (function, type_) = unstructured.choose(&context.functions)?.clone();
+
+            Call::new(
+                type_.clone(),
+                Variable::new(function),
+                type_
+                    .arguments()
+                    .iter()
+                    .map(|type_| arbitrary_expression(unstructured, variables, type_))
+                    .collect::<Result<_>>()?,
+                name,
+            )
+            .into()
+        }
+        2 => {
+            let type_ = *unstructured.choose(&PRIMITIVE_TYPES[1..])?;
+
+            PassThrough::new(
+                type_,
+                ArithmeticOperation::new(
+                    type_,
+                    *unstructured.choose(&[
+                        ArithmeticOperator::Add,
+                        ArithmeticOperator::Subtract,
+                        ArithmeticOperator::Multiply,
+                        ArithmeticOperator::Divide,
+                    ])?,
+                    arbitrary_expression(unstructured, variables, &type_.into())?,
+                    arbitrary_expression(unstructured, variables, &type_.into())?,
+                ),
+                name,
+            )
+            .into()
+        }
+        3 => {
+            let type_ = *unstructured.choose(&PRIMITIVE_TYPES[1..])?;
+
+            PassThrough::new(
+                ComparisonOperation::RESULT_TYPE,
+                ComparisonOperation::new(
+                    type_,
+                    *unstructured.choose(&[
+                        ComparisonOperator::Equal,
+                        ComparisonOperator::NotEqual,
+                        ComparisonOperator::LessThan,
+                        ComparisonOperator::GreaterThan,
+                        ComparisonOperator::LessThanOrEqual,
+                        ComparisonOperator::GreaterThanOrEqual,
+                    ])?,
+                    arbitrary_expression(unstructured, variables, &type_.into())?,
+                    arbitrary_expression(unstructured, variables, &type_.into())?,
+                ),
+                name,
+            )
+            .into()
+        }
+        _ => {
+            let type_ = arbitrary_type(unstructured)?;
+
+            PassThrough::new(
+                type_.clone(),
+                arbitrary_expression(unstructured, variables, &type_)?,
+                name,
+            )
+            .into()
+        }
+    })
+}
+
+fn arbitrary_expression(
+    unstructured: &mut Unstructured,
+    variables: &[(String, types::Type)],
+    type_: &types::Type,
+) -> Result<Expression> {
+    let candidates = variables
+        .iter()
+        .filter(|(_, variable_type)| variable_type == type_)
+        .collect::<Vec<_>>();
+
+    Ok(if !candidates.is_empty() && unstructured.arbitrary()? {
+        Variable::new(&unstructured.choose(&candidates)?.0).into()
+    } else {
+        arbitrary_primitive(unstructured, type_)?.into()
+    })
+}
+
+fn arbitrary_primitive(unstructured: &mut Unstructured, type_: &types::Type) -> Result<Primitive> {
+    Ok(match type_ {
+        types::Type::Primitive(types::Primitive::Boolean) => {
+            Primitive::Boolean(unstructured.arbitrary()?)
+        }
+        types::Type::Primitive(types::Primitive::Integer64) => {
+            Primitive::Integer64(unstructured.arbitrary()?)
+        }
+        types::Type::Primitive(types::Primitive::Float64) => {
+            Primitive::Float64(unstructured.arbitrary()?)
+        }
+        _ => unreachable!(),
+    })
+}
+
+fn arbitrary_type(unstructured: &mut Unstructured) -> Result<types::Type> {
+    Ok((*unstructured.choose(PRIMITIVE_TYPES)?).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::check_types;
+
+    #[test]
+    fn generate_type_correct_modules() {
+        for seed in 0..256u32 {
+            let data = (0..1024u32)
+                .map(|index| (index.wrapping_mul(seed * 2 + 1) ^ seed).to_le_bytes()[0])
+                .collect::<Vec<_>>();
+            let module = arbitrary_module(&mut Unstructured::new(&data)).unwrap();
+
+            check_types(&module).unwrap();
+        }
+    }
+
+    #[test]
+    fn generate_module_from_empty_data() {
+        check_types(&arbitrary_module(&mut Unstructured::new(&[])).unwrap()).unwrap();
+    }
+}
@@ -1,39 +1,60 @@
+mod absolute_address;
 mod align_of;
 mod allocate_heap;
 mod allocate_stack;
 mod argument;
+mod argument_attributes;
 mod arithmetic_operation;
 mod atomic_load;
 mod atomic_operation;
 mod atomic_ordering;
 mod atomic_store;
 mod bit_cast;
+mod bit_manipulation_operation;
 mod bitwise_not_operation;
 mod bitwise_operation;
 mod block;
 mod branch;
+mod byte_offset;
 mod call;
+mod checked_arithmetic_operation;
 mod compare_and_swap;
 mod comparison_operation;
 mod deconstruct_record;
 mod deconstruct_union;
 mod expression;
+mod extract_bits;
+mod fast_math_flags;
 mod fence;
+mod fma;
 mod free_heap;
+mod function_attributes;
 mod function_declaration;
 mod function_definition;
 mod if_;
+mod inline_assembly;
+mod insert_bits;
 mod instruction;
+mod instruction_path;
+mod lifetime_end;
+mod lifetime_start;
 mod linkage;
 mod load;
+mod math_operation;
+mod memory_copy;
+mod memory_set;
 mod module;
+mod overflow_flags;
 mod pass_through;
 mod pointer_address;
 mod primitive;
+mod pure_call;
 mod reallocate_heap;
 mod record;
 mod record_address;
 mod return_;
+mod saturating_arithmetic_operation;
+mod select;
 mod size_of;
 mod store;
 mod terminal_instruction;
@@ -45,42 +66,63 @@ mod variable;
 mod variable_declaration;
 mod variable_definition;
 
+pub use absolute_address::*;
 pub use align_of::*;
 pub use allocate_heap::*;
 pub use allocate_stack::*;
 pub use argument::*;
+pub use argument_attributes::*;
 pub use arithmetic_operation::*;
 pub use atomic_load::*;
 pub use atomic_operation::*;
 pub use atomic_ordering::*;
 pub use atomic_store::*;
 pub use bit_cast::*;
+pub use bit_manipulation_operation::*;
 pub use bitwise_not_operation::*;
 pub use bitwise_operation::*;
 pub use block::*;
 pub use branch::*;
+pub use byte_offset::*;
 pub use call::*;
+pub use checked_arithmetic_operation::*;
 pub use compare_and_swap::*;
 pub use comparison_operation::*;
 pub use deconstruct_record::*;
 pub use deconstruct_union::*;
 pub use expression::*;
+pub use extract_bits::*;
+pub use fast_math_flags::*;
 pub use fence::*;
+pub use fma::*;
 pub use free_heap::*;
+pub use function_attributes::*;
 pub use function_declaration::*;
 pub use function_definition::*;
 pub use if_::*;
+pub use inline_assembly::*;
+pub use insert_bits::*;
 pub use instruction::*;
+pub use instruction_path::*;
+pub use lifetime_end::*;
+pub use lifetime_start::*;
 pub use linkage::*;
 pub use load::*;
+pub use math_operation::*;
+pub use memory_copy::*;
+pub use memory_set::*;
 pub use module::*;
+pub use overflow_flags::*;
 pub use pass_through::*;
 pub use pointer_address::*;
 pub use primitive::*;
+pub use pure_call::*;
 pub use reallocate_heap::*;
 pub use record::*;
 pub use record_address::*;
 pub use return_::*;
+pub use saturating_arithmetic_operation::*;
+pub use select::*;
 pub use size_of::*;
 pub use store::*;
 pub use terminal_instruction::*;
@@ -0,0 +1,26 @@
+use super::primitive::Primitive;
+use crate::types::Type;
+
+// A constant pointer to a fixed address, e.g. for memory-mapped IO
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbsoluteAddress {
+    type_: Type,
+    address: Primitive,
+}
+
+impl AbsoluteAddress {
+    pub fn new(type_: impl Into<Type>, address: impl Into<Primitive>) -> Self {
+        Self {
+            type_: type_.into(),
+            address: address.into(),
+        }
+    }
+
+    pub fn type_(&self) -> &Type {
+        &self.type_
+    }
+
+    pub fn address(&self) -> Primitive {
+        self.address
+    }
+}
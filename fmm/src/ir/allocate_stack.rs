@@ -21,4 +21,8 @@ impl AllocateStack {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
 }
@@ -1,16 +1,27 @@
+use super::argument_attributes::ArgumentAttributes;
 use crate::types::Type;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Argument {
     name: String,
     type_: Type,
+    attributes: ArgumentAttributes,
 }
 
 impl Argument {
     pub fn new(name: impl Into<String>, type_: impl Into<Type>) -> Self {
+        Self::with_attributes(name, type_, Default::default())
+    }
+
+    pub fn with_attributes(
+        name: impl Into<String>,
+        type_: impl Into<Type>,
+        attributes: ArgumentAttributes,
+    ) -> Self {
         Self {
             name: name.into(),
             type_: type_.into(),
+            attributes,
         }
     }
 
@@ -21,4 +32,8 @@ impl Argument {
     pub fn type_(&self) -> &Type {
         &self.type_
     }
+
+    pub fn attributes(&self) -> ArgumentAttributes {
+        self.attributes
+    }
 }
@@ -0,0 +1,8 @@
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ArgumentAttributes {
+    pub alignment: Option<usize>,
+    pub by_value: bool,
+    pub no_alias: bool,
+    pub non_null: bool,
+    pub struct_return: bool,
+}
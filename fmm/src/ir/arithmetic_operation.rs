@@ -1,8 +1,10 @@
-use super::expression::Expression;
+use super::{
+    expression::Expression, fast_math_flags::FastMathFlags, overflow_flags::OverflowFlags,
+};
 use crate::types;
 use std::sync::Arc;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ArithmeticOperator {
     Add,
     Subtract,
@@ -16,6 +18,8 @@ pub struct ArithmeticOperation {
     operator: ArithmeticOperator,
     lhs: Arc<Expression>,
     rhs: Arc<Expression>,
+    fast_math_flags: FastMathFlags,
+    overflow_flags: OverflowFlags,
 }
 
 impl ArithmeticOperation {
@@ -30,6 +34,22 @@ impl ArithmeticOperation {
             operator,
             lhs: Arc::new(lhs.into()),
             rhs: Arc::new(rhs.into()),
+            fast_math_flags: Default::default(),
+            overflow_flags: Default::default(),
+        }
+    }
+
+    pub fn with_fast_math_flags(self, fast_math_flags: FastMathFlags) -> Self {
+        Self {
+            fast_math_flags,
+            ..self
+        }
+    }
+
+    pub fn with_overflow_flags(self, overflow_flags: OverflowFlags) -> Self {
+        Self {
+            overflow_flags,
+            ..self
         }
     }
 
@@ -48,4 +68,12 @@ impl ArithmeticOperation {
     pub fn rhs(&self) -> &Expression {
         &self.rhs
     }
+
+    pub fn fast_math_flags(&self) -> FastMathFlags {
+        self.fast_math_flags
+    }
+
+    pub fn overflow_flags(&self) -> OverflowFlags {
+        self.overflow_flags
+    }
 }
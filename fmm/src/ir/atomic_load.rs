@@ -40,4 +40,8 @@ impl AtomicLoad {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
 }
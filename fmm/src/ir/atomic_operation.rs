@@ -60,4 +60,8 @@ impl AtomicOperation {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
 }
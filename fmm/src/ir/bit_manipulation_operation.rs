@@ -0,0 +1,45 @@
+use super::expression::Expression;
+use crate::types;
+use std::sync::Arc;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum BitManipulationOperator {
+    ByteSwap,
+    CountLeadingZeros,
+    CountTrailingZeros,
+    PopulationCount,
+}
+
+// Results have the same types as operands including bit counts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BitManipulationOperation {
+    type_: types::Primitive,
+    operator: BitManipulationOperator,
+    value: Arc<Expression>,
+}
+
+impl BitManipulationOperation {
+    pub fn new(
+        type_: types::Primitive,
+        operator: BitManipulationOperator,
+        value: impl Into<Expression>,
+    ) -> Self {
+        Self {
+            type_,
+            operator,
+            value: Arc::new(value.into()),
+        }
+    }
+
+    pub fn type_(&self) -> types::Primitive {
+        self.type_
+    }
+
+    pub fn operator(&self) -> BitManipulationOperator {
+        self.operator
+    }
+
+    pub fn value(&self) -> &Expression {
+        &self.value
+    }
+}
@@ -24,4 +24,91 @@ impl Block {
     pub fn terminal_instruction(&self) -> &TerminalInstruction {
         &self.terminal_instruction
     }
+
+    pub fn is_terminated_by_unreachable(&self) -> bool {
+        matches!(self.terminal_instruction, TerminalInstruction::Unreachable)
+    }
+
+    pub fn with_prepended(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions = instructions.into_iter().chain(self.instructions).collect();
+        self
+    }
+
+    pub fn with_appended(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    pub fn with_terminal(mut self, terminal_instruction: impl Into<TerminalInstruction>) -> Self {
+        self.terminal_instruction = terminal_instruction.into();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ir::*,
+        types::{self, Type},
+    };
+
+    fn create_instruction(name: &str) -> Instruction {
+        PassThrough::new(
+            types::Primitive::PointerInteger,
+            Primitive::PointerInteger(42),
+            name,
+        )
+        .into()
+    }
+
+    fn create_return(name: &str) -> Return {
+        Return::new(
+            Type::from(types::Primitive::PointerInteger),
+            Variable::new(name),
+        )
+    }
+
+    #[test]
+    fn prepend_instructions() {
+        assert_eq!(
+            Block::new(vec![create_instruction("y")], create_return("y"))
+                .with_prepended(vec![create_instruction("x")]),
+            Block::new(
+                vec![create_instruction("x"), create_instruction("y")],
+                create_return("y")
+            )
+        );
+    }
+
+    #[test]
+    fn append_instructions() {
+        assert_eq!(
+            Block::new(vec![create_instruction("x")], create_return("y"))
+                .with_appended(vec![create_instruction("y")]),
+            Block::new(
+                vec![create_instruction("x"), create_instruction("y")],
+                create_return("y")
+            )
+        );
+    }
+
+    #[test]
+    fn replace_terminal_instruction() {
+        assert_eq!(
+            Block::new(vec![create_instruction("x")], create_return("x"))
+                .with_terminal(TerminalInstruction::Unreachable),
+            Block::new(
+                vec![create_instruction("x")],
+                TerminalInstruction::Unreachable
+            )
+        );
+    }
+
+    #[test]
+    fn check_unreachable_termination() {
+        assert!(Block::new(vec![], TerminalInstruction::Unreachable).is_terminated_by_unreachable());
+        assert!(!Block::new(vec![], TerminalInstruction::Trap).is_terminated_by_unreachable());
+        assert!(!Block::new(vec![], create_return("x")).is_terminated_by_unreachable());
+    }
 }
@@ -37,7 +37,49 @@ impl Call {
         &self.arguments
     }
 
+    pub fn arguments_mut(&mut self) -> &mut Vec<Expression> {
+        &mut self.arguments
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::*;
+
+    #[test]
+    fn insert_argument() {
+        let mut call = Call::new(
+            types::Function::new(
+                vec![
+                    types::Primitive::PointerInteger.into(),
+                    types::Primitive::Float64.into(),
+                ],
+                types::Primitive::Float64,
+                types::CallingConvention::Target,
+            ),
+            Variable::new("f"),
+            vec![Primitive::Float64(42.0).into()],
+            "x",
+        );
+
+        call.arguments_mut()
+            .insert(0, Primitive::PointerInteger(42).into());
+
+        assert_eq!(
+            call.arguments(),
+            &[
+                Primitive::PointerInteger(42).into(),
+                Primitive::Float64(42.0).into()
+            ]
+        );
+    }
 }
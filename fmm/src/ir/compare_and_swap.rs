@@ -61,4 +61,8 @@ impl CompareAndSwap {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
 }
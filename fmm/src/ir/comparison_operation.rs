@@ -2,6 +2,9 @@ use super::expression::Expression;
 use crate::types;
 use std::sync::Arc;
 
+// Comparisons of floating-point numbers are ordered by default. Unordered ones
+// are true if any of operands is NaN and available only for floating-point
+// numbers.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ComparisonOperator {
     Equal,
@@ -10,6 +13,14 @@ pub enum ComparisonOperator {
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
+    Ordered,
+    Unordered,
+    UnorderedEqual,
+    UnorderedNotEqual,
+    UnorderedLessThan,
+    UnorderedGreaterThan,
+    UnorderedLessThanOrEqual,
+    UnorderedGreaterThanOrEqual,
 }
 
 #[derive(Clone, Debug, PartialEq)]
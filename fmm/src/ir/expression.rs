@@ -1,23 +1,34 @@
 use super::{
-    align_of::AlignOf, arithmetic_operation::ArithmeticOperation, bit_cast::BitCast,
+    absolute_address::AbsoluteAddress, align_of::AlignOf,
+    arithmetic_operation::ArithmeticOperation, bit_cast::BitCast,
+    bit_manipulation_operation::BitManipulationOperation,
     bitwise_not_operation::BitwiseNotOperation, bitwise_operation::BitwiseOperation,
-    comparison_operation::ComparisonOperation, pointer_address::PointerAddress,
-    primitive::Primitive, record::Record, record_address::RecordAddress, size_of::SizeOf,
-    undefined::Undefined, union::Union, union_address::UnionAddress, variable::Variable,
+    byte_offset::ByteOffset, comparison_operation::ComparisonOperation, extract_bits::ExtractBits,
+    insert_bits::InsertBits, pointer_address::PointerAddress, primitive::Primitive,
+    pure_call::PureCall, record::Record, record_address::RecordAddress, select::Select,
+    size_of::SizeOf, undefined::Undefined, union::Union, union_address::UnionAddress,
+    variable::Variable,
 };
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Expression {
+    AbsoluteAddress(AbsoluteAddress),
     AlignOf(AlignOf),
     ArithmeticOperation(ArithmeticOperation),
     BitCast(BitCast),
+    BitManipulationOperation(BitManipulationOperation),
     BitwiseNotOperation(BitwiseNotOperation),
     BitwiseOperation(BitwiseOperation),
+    ByteOffset(ByteOffset),
     ComparisonOperation(ComparisonOperation),
+    ExtractBits(ExtractBits),
+    InsertBits(InsertBits),
     PointerAddress(PointerAddress),
     Primitive(Primitive),
+    PureCall(PureCall),
     Record(Record),
     RecordAddress(RecordAddress),
+    Select(Select),
     SizeOf(SizeOf),
     Undefined(Undefined),
     Union(Union),
@@ -25,6 +36,190 @@ pub enum Expression {
     Variable(Variable),
 }
 
+impl Expression {
+    pub fn to_absolute_address(&self) -> Option<&AbsoluteAddress> {
+        if let Expression::AbsoluteAddress(address) = self {
+            Some(address)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_align_of(&self) -> Option<&AlignOf> {
+        if let Expression::AlignOf(align_of) = self {
+            Some(align_of)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_arithmetic_operation(&self) -> Option<&ArithmeticOperation> {
+        if let Expression::ArithmeticOperation(operation) = self {
+            Some(operation)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_bit_cast(&self) -> Option<&BitCast> {
+        if let Expression::BitCast(bit_cast) = self {
+            Some(bit_cast)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_bit_manipulation_operation(&self) -> Option<&BitManipulationOperation> {
+        if let Expression::BitManipulationOperation(operation) = self {
+            Some(operation)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_bitwise_not_operation(&self) -> Option<&BitwiseNotOperation> {
+        if let Expression::BitwiseNotOperation(operation) = self {
+            Some(operation)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_bitwise_operation(&self) -> Option<&BitwiseOperation> {
+        if let Expression::BitwiseOperation(operation) = self {
+            Some(operation)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_byte_offset(&self) -> Option<&ByteOffset> {
+        if let Expression::ByteOffset(offset) = self {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_comparison_operation(&self) -> Option<&ComparisonOperation> {
+        if let Expression::ComparisonOperation(operation) = self {
+            Some(operation)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_extract_bits(&self) -> Option<&ExtractBits> {
+        if let Expression::ExtractBits(extract) = self {
+            Some(extract)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_insert_bits(&self) -> Option<&InsertBits> {
+        if let Expression::InsertBits(insert) = self {
+            Some(insert)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_pointer_address(&self) -> Option<&PointerAddress> {
+        if let Expression::PointerAddress(address) = self {
+            Some(address)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_primitive(&self) -> Option<Primitive> {
+        if let Expression::Primitive(primitive) = self {
+            Some(*primitive)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_pure_call(&self) -> Option<&PureCall> {
+        if let Expression::PureCall(call) = self {
+            Some(call)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_record(&self) -> Option<&Record> {
+        if let Expression::Record(record) = self {
+            Some(record)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_record_address(&self) -> Option<&RecordAddress> {
+        if let Expression::RecordAddress(address) = self {
+            Some(address)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_select(&self) -> Option<&Select> {
+        if let Expression::Select(select) = self {
+            Some(select)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_size_of(&self) -> Option<&SizeOf> {
+        if let Expression::SizeOf(size_of) = self {
+            Some(size_of)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_undefined(&self) -> Option<&Undefined> {
+        if let Expression::Undefined(undefined) = self {
+            Some(undefined)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_union(&self) -> Option<&Union> {
+        if let Expression::Union(union) = self {
+            Some(union)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_union_address(&self) -> Option<&UnionAddress> {
+        if let Expression::UnionAddress(address) = self {
+            Some(address)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_variable(&self) -> Option<&Variable> {
+        if let Expression::Variable(variable) = self {
+            Some(variable)
+        } else {
+            None
+        }
+    }
+}
+
+impl From<AbsoluteAddress> for Expression {
+    fn from(address: AbsoluteAddress) -> Self {
+        Self::AbsoluteAddress(address)
+    }
+}
+
 impl From<AlignOf> for Expression {
     fn from(align_of: AlignOf) -> Self {
         Self::AlignOf(align_of)
@@ -43,6 +238,12 @@ impl From<BitCast> for Expression {
     }
 }
 
+impl From<BitManipulationOperation> for Expression {
+    fn from(operation: BitManipulationOperation) -> Self {
+        Self::BitManipulationOperation(operation)
+    }
+}
+
 impl From<BitwiseNotOperation> for Expression {
     fn from(operation: BitwiseNotOperation) -> Self {
         Self::BitwiseNotOperation(operation)
@@ -55,12 +256,30 @@ impl From<BitwiseOperation> for Expression {
     }
 }
 
+impl From<ByteOffset> for Expression {
+    fn from(offset: ByteOffset) -> Self {
+        Self::ByteOffset(offset)
+    }
+}
+
 impl From<ComparisonOperation> for Expression {
     fn from(operation: ComparisonOperation) -> Self {
         Self::ComparisonOperation(operation)
     }
 }
 
+impl From<ExtractBits> for Expression {
+    fn from(extract: ExtractBits) -> Self {
+        Self::ExtractBits(extract)
+    }
+}
+
+impl From<InsertBits> for Expression {
+    fn from(insert: InsertBits) -> Self {
+        Self::InsertBits(insert)
+    }
+}
+
 impl From<PointerAddress> for Expression {
     fn from(address: PointerAddress) -> Self {
         Self::PointerAddress(address)
@@ -73,6 +292,12 @@ impl From<Primitive> for Expression {
     }
 }
 
+impl From<PureCall> for Expression {
+    fn from(call: PureCall) -> Self {
+        Self::PureCall(call)
+    }
+}
+
 impl From<Record> for Expression {
     fn from(record: Record) -> Self {
         Self::Record(record)
@@ -85,6 +310,12 @@ impl From<RecordAddress> for Expression {
     }
 }
 
+impl From<Select> for Expression {
+    fn from(select: Select) -> Self {
+        Self::Select(select)
+    }
+}
+
 impl From<SizeOf> for Expression {
     fn from(size_of: SizeOf) -> Self {
         Self::SizeOf(size_of)
@@ -114,3 +345,67 @@ impl From<Variable> for Expression {
         Self::Variable(variable)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types;
+
+    #[test]
+    fn convert_to_variable() {
+        assert_eq!(
+            Expression::from(Variable::new("x")).to_variable(),
+            Some(&Variable::new("x"))
+        );
+        assert_eq!(
+            Expression::from(Primitive::Float64(42.0)).to_variable(),
+            None
+        );
+    }
+
+    #[test]
+    fn convert_to_primitive() {
+        assert_eq!(
+            Expression::from(Primitive::Float64(42.0)).to_primitive(),
+            Some(Primitive::Float64(42.0))
+        );
+        assert_eq!(Expression::from(Variable::new("x")).to_primitive(), None);
+    }
+
+    #[test]
+    fn convert_to_record() {
+        let record = Record::new(types::Record::new(vec![]), vec![]);
+
+        assert_eq!(Expression::from(record.clone()).to_record(), Some(&record));
+        assert_eq!(Expression::from(Variable::new("x")).to_record(), None);
+    }
+
+    #[test]
+    fn convert_to_undefined() {
+        let undefined = Undefined::new(types::Primitive::Float64);
+
+        assert_eq!(
+            Expression::from(undefined.clone()).to_undefined(),
+            Some(&undefined)
+        );
+        assert_eq!(Expression::from(Variable::new("x")).to_undefined(), None);
+    }
+
+    #[test]
+    fn convert_to_other_expressions() {
+        let expression = Expression::from(SizeOf::new(types::Primitive::Float64));
+
+        assert!(expression.to_size_of().is_some());
+        assert!(expression.to_align_of().is_none());
+        assert!(expression.to_arithmetic_operation().is_none());
+        assert!(expression.to_bit_cast().is_none());
+        assert!(expression.to_bitwise_not_operation().is_none());
+        assert!(expression.to_bitwise_operation().is_none());
+        assert!(expression.to_byte_offset().is_none());
+        assert!(expression.to_comparison_operation().is_none());
+        assert!(expression.to_pointer_address().is_none());
+        assert!(expression.to_record_address().is_none());
+        assert!(expression.to_union().is_none());
+        assert!(expression.to_union_address().is_none());
+    }
+}
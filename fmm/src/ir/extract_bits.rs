@@ -0,0 +1,43 @@
+use super::expression::Expression;
+use crate::types;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtractBits {
+    type_: types::Primitive,
+    value: Arc<Expression>,
+    offset: u32,
+    width: u32,
+}
+
+impl ExtractBits {
+    pub fn new(
+        type_: types::Primitive,
+        value: impl Into<Expression>,
+        offset: u32,
+        width: u32,
+    ) -> Self {
+        Self {
+            type_,
+            value: Arc::new(value.into()),
+            offset,
+            width,
+        }
+    }
+
+    pub fn type_(&self) -> types::Primitive {
+        self.type_
+    }
+
+    pub fn value(&self) -> &Expression {
+        &self.value
+    }
+
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+}
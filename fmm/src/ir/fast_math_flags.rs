@@ -0,0 +1,27 @@
+// Flags are meaningful only for floating-point operands.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct FastMathFlags {
+    pub no_nans: bool,
+    pub no_infinities: bool,
+    pub no_signed_zeros: bool,
+    pub allow_reciprocal: bool,
+    pub allow_contraction: bool,
+    pub allow_reassociation: bool,
+}
+
+impl FastMathFlags {
+    pub fn fast() -> Self {
+        Self {
+            no_nans: true,
+            no_infinities: true,
+            no_signed_zeros: true,
+            allow_reciprocal: true,
+            allow_contraction: true,
+            allow_reassociation: true,
+        }
+    }
+
+    pub fn is_fast(&self) -> bool {
+        *self == Self::fast()
+    }
+}
@@ -0,0 +1,55 @@
+use super::expression::Expression;
+use crate::types;
+use std::sync::Arc;
+
+// A fused multiply-add computes `lhs * rhs + addend` with a single rounding.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fma {
+    type_: types::Primitive,
+    lhs: Arc<Expression>,
+    rhs: Arc<Expression>,
+    addend: Arc<Expression>,
+    name: String,
+}
+
+impl Fma {
+    pub fn new(
+        type_: types::Primitive,
+        lhs: impl Into<Expression>,
+        rhs: impl Into<Expression>,
+        addend: impl Into<Expression>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            type_,
+            lhs: lhs.into().into(),
+            rhs: rhs.into().into(),
+            addend: addend.into().into(),
+            name: name.into(),
+        }
+    }
+
+    pub fn type_(&self) -> types::Primitive {
+        self.type_
+    }
+
+    pub fn lhs(&self) -> &Expression {
+        &self.lhs
+    }
+
+    pub fn rhs(&self) -> &Expression {
+        &self.rhs
+    }
+
+    pub fn addend(&self) -> &Expression {
+        &self.addend
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
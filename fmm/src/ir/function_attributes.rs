@@ -0,0 +1,6 @@
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FunctionAttributes {
+    pub no_return: bool,
+    pub read_only: bool,
+    pub read_none: bool,
+}
@@ -1,16 +1,27 @@
+use super::function_attributes::FunctionAttributes;
 use crate::types;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FunctionDeclaration {
     name: String,
     type_: types::Function,
+    attributes: FunctionAttributes,
 }
 
 impl FunctionDeclaration {
     pub fn new(name: impl Into<String>, type_: types::Function) -> Self {
+        Self::with_attributes(name, type_, Default::default())
+    }
+
+    pub fn with_attributes(
+        name: impl Into<String>,
+        type_: types::Function,
+        attributes: FunctionAttributes,
+    ) -> Self {
         Self {
             name: name.into(),
             type_,
+            attributes,
         }
     }
 
@@ -21,4 +32,8 @@ impl FunctionDeclaration {
     pub fn type_(&self) -> &types::Function {
         &self.type_
     }
+
+    pub fn attributes(&self) -> FunctionAttributes {
+        self.attributes
+    }
 }
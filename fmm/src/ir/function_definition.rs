@@ -1,4 +1,10 @@
-use super::{argument::Argument, block::Block, linkage::Linkage};
+use super::{
+    argument::Argument,
+    block::Block,
+    instruction::Instruction,
+    instruction_path::{InstructionPath, InstructionPathSegment},
+    linkage::Linkage,
+};
 use crate::types::{self, CallingConvention, Type};
 
 #[derive(Clone, Debug, PartialEq)]
@@ -66,4 +72,113 @@ impl FunctionDefinition {
     pub fn linkage(&self) -> Linkage {
         self.linkage
     }
+
+    // Instructions are visited in pre-order with instructions in `If` arms
+    // following their `If` instructions.
+    pub fn instructions_recursive(&self) -> impl Iterator<Item = (InstructionPath, &Instruction)> {
+        let mut instructions = vec![];
+
+        collect_instructions(&self.body, &[], &mut instructions);
+
+        instructions.into_iter()
+    }
+}
+
+fn collect_instructions<'a>(
+    block: &'a Block,
+    path: &[InstructionPathSegment],
+    instructions: &mut Vec<(InstructionPath, &'a Instruction)>,
+) {
+    for (index, instruction) in block.instructions().iter().enumerate() {
+        let path = path
+            .iter()
+            .copied()
+            .chain([InstructionPathSegment::Instruction(index)])
+            .collect::<Vec<_>>();
+
+        instructions.push((path.clone(), instruction));
+
+        if let Instruction::If(if_) = instruction {
+            for (segment, block) in [
+                (InstructionPathSegment::Then, if_.then()),
+                (InstructionPathSegment::Else, if_.else_()),
+            ] {
+                collect_instructions(
+                    block,
+                    &path.iter().copied().chain([segment]).collect::<Vec<_>>(),
+                    instructions,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::*;
+
+    #[test]
+    fn iterate_instructions_recursively() {
+        let load = |name| Load::new(types::Primitive::PointerInteger, Variable::new("x"), name);
+        let definition = FunctionDefinition::new(
+            "f",
+            vec![Argument::new(
+                "x",
+                types::Pointer::new(types::Primitive::PointerInteger),
+            )],
+            Block::new(
+                vec![
+                    load("a").into(),
+                    If::new(
+                        types::Primitive::PointerInteger,
+                        Primitive::Boolean(true),
+                        Block::new(
+                            vec![load("b").into()],
+                            Branch::new(types::Primitive::PointerInteger, Variable::new("b")),
+                        ),
+                        Block::new(
+                            vec![load("c").into()],
+                            Branch::new(types::Primitive::PointerInteger, Variable::new("c")),
+                        ),
+                        "d",
+                    )
+                    .into(),
+                    load("e").into(),
+                ],
+                Return::new(types::Primitive::PointerInteger, Variable::new("e")),
+            ),
+            types::Primitive::PointerInteger,
+            CallingConvention::Target,
+            Linkage::External,
+        );
+
+        assert_eq!(
+            definition
+                .instructions_recursive()
+                .map(|(path, instruction)| (path, instruction.name().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                (vec![InstructionPathSegment::Instruction(0)], "a"),
+                (vec![InstructionPathSegment::Instruction(1)], "d"),
+                (
+                    vec![
+                        InstructionPathSegment::Instruction(1),
+                        InstructionPathSegment::Then,
+                        InstructionPathSegment::Instruction(0)
+                    ],
+                    "b"
+                ),
+                (
+                    vec![
+                        InstructionPathSegment::Instruction(1),
+                        InstructionPathSegment::Else,
+                        InstructionPathSegment::Instruction(0)
+                    ],
+                    "c"
+                ),
+                (vec![InstructionPathSegment::Instruction(2)], "e"),
+            ]
+        );
+    }
 }
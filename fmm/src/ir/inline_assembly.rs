@@ -0,0 +1,82 @@
+use super::expression::Expression;
+use crate::types::Type;
+
+// Inline assembly is opaque to every analysis and always treated as an
+// instruction with side effects.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InlineAssembly {
+    type_: Type,
+    template: String,
+    constraints: String,
+    operands: Vec<Expression>,
+    side_effects: bool,
+    align_stack: bool,
+    name: String,
+}
+
+impl InlineAssembly {
+    pub fn new(
+        type_: impl Into<Type>,
+        template: impl Into<String>,
+        constraints: impl Into<String>,
+        operands: Vec<Expression>,
+        side_effects: bool,
+        align_stack: bool,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            type_: type_.into(),
+            template: template.into(),
+            constraints: constraints.into(),
+            operands,
+            side_effects,
+            align_stack,
+            name: name.into(),
+        }
+    }
+
+    pub fn type_(&self) -> &Type {
+        &self.type_
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    pub fn constraints(&self) -> &str {
+        &self.constraints
+    }
+
+    pub fn operands(&self) -> &[Expression] {
+        &self.operands
+    }
+
+    pub fn side_effects(&self) -> bool {
+        self.side_effects
+    }
+
+    pub fn align_stack(&self) -> bool {
+        self.align_stack
+    }
+
+    // Constraints of input operands exclude outputs and clobbers.
+    pub fn input_constraint_count(&self) -> usize {
+        self.constraints
+            .split(',')
+            .map(str::trim)
+            .filter(|constraint| {
+                !constraint.is_empty()
+                    && !constraint.starts_with('=')
+                    && !constraint.starts_with('~')
+            })
+            .count()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
@@ -1,9 +1,13 @@
 use super::{
     allocate_heap::AllocateHeap, allocate_stack::AllocateStack, atomic_load::AtomicLoad,
     atomic_operation::AtomicOperation, atomic_store::AtomicStore, call::Call,
-    compare_and_swap::CompareAndSwap, deconstruct_record::DeconstructRecord,
-    deconstruct_union::DeconstructUnion, fence::Fence, free_heap::FreeHeap, if_::If, load::Load,
-    pass_through::PassThrough, reallocate_heap::ReallocateHeap, store::Store,
+    checked_arithmetic_operation::CheckedArithmeticOperation, compare_and_swap::CompareAndSwap,
+    deconstruct_record::DeconstructRecord, deconstruct_union::DeconstructUnion, fence::Fence,
+    fma::Fma, free_heap::FreeHeap, if_::If, inline_assembly::InlineAssembly,
+    lifetime_end::LifetimeEnd, lifetime_start::LifetimeStart, load::Load,
+    math_operation::MathOperation, memory_copy::MemoryCopy, memory_set::MemorySet,
+    pass_through::PassThrough, reallocate_heap::ReallocateHeap,
+    saturating_arithmetic_operation::SaturatingArithmeticOperation, store::Store,
 };
 use crate::types::{self, Type, GENERIC_POINTER_TYPE};
 
@@ -15,15 +19,24 @@ pub enum Instruction {
     AtomicOperation(AtomicOperation),
     AtomicStore(AtomicStore),
     Call(Call),
+    CheckedArithmeticOperation(CheckedArithmeticOperation),
     CompareAndSwap(CompareAndSwap),
     DeconstructRecord(DeconstructRecord),
     DeconstructUnion(DeconstructUnion),
     Fence(Fence),
+    Fma(Fma),
     FreeHeap(FreeHeap),
     If(If),
+    InlineAssembly(InlineAssembly),
+    LifetimeEnd(LifetimeEnd),
+    LifetimeStart(LifetimeStart),
     Load(Load),
+    MathOperation(MathOperation),
+    MemoryCopy(MemoryCopy),
+    MemorySet(MemorySet),
     PassThrough(PassThrough),
     ReallocateHeap(ReallocateHeap),
+    SaturatingArithmeticOperation(SaturatingArithmeticOperation),
     Store(Store),
 }
 
@@ -35,14 +48,56 @@ impl Instruction {
             Self::AtomicLoad(load) => Some(load.name()),
             Self::AtomicOperation(operation) => Some(operation.name()),
             Self::Call(call) => Some(call.name()),
+            Self::CheckedArithmeticOperation(operation) => Some(operation.name()),
             Self::CompareAndSwap(cas) => Some(cas.name()),
             Self::DeconstructRecord(deconstruct) => Some(deconstruct.name()),
             Self::DeconstructUnion(deconstruct) => Some(deconstruct.name()),
+            Self::Fma(fma) => Some(fma.name()),
             Self::If(if_) => Some(if_.name()),
+            Self::InlineAssembly(assembly) => Some(assembly.name()),
             Self::Load(load) => Some(load.name()),
+            Self::MathOperation(operation) => Some(operation.name()),
             Self::PassThrough(pass) => Some(pass.name()),
             Self::ReallocateHeap(reallocate) => Some(reallocate.name()),
-            Self::AtomicStore(_) | Self::Fence(_) | Self::FreeHeap(_) | Self::Store(_) => None,
+            Self::SaturatingArithmeticOperation(operation) => Some(operation.name()),
+            Self::AtomicStore(_)
+            | Self::Fence(_)
+            | Self::FreeHeap(_)
+            | Self::LifetimeEnd(_)
+            | Self::LifetimeStart(_)
+            | Self::MemoryCopy(_)
+            | Self::MemorySet(_)
+            | Self::Store(_) => None,
+        }
+    }
+
+    pub fn name_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Self::AllocateHeap(allocate) => Some(allocate.name_mut()),
+            Self::AllocateStack(allocate) => Some(allocate.name_mut()),
+            Self::AtomicLoad(load) => Some(load.name_mut()),
+            Self::AtomicOperation(operation) => Some(operation.name_mut()),
+            Self::Call(call) => Some(call.name_mut()),
+            Self::CheckedArithmeticOperation(operation) => Some(operation.name_mut()),
+            Self::CompareAndSwap(cas) => Some(cas.name_mut()),
+            Self::DeconstructRecord(deconstruct) => Some(deconstruct.name_mut()),
+            Self::DeconstructUnion(deconstruct) => Some(deconstruct.name_mut()),
+            Self::Fma(fma) => Some(fma.name_mut()),
+            Self::If(if_) => Some(if_.name_mut()),
+            Self::InlineAssembly(assembly) => Some(assembly.name_mut()),
+            Self::Load(load) => Some(load.name_mut()),
+            Self::MathOperation(operation) => Some(operation.name_mut()),
+            Self::PassThrough(pass) => Some(pass.name_mut()),
+            Self::ReallocateHeap(reallocate) => Some(reallocate.name_mut()),
+            Self::SaturatingArithmeticOperation(operation) => Some(operation.name_mut()),
+            Self::AtomicStore(_)
+            | Self::Fence(_)
+            | Self::FreeHeap(_)
+            | Self::LifetimeEnd(_)
+            | Self::LifetimeStart(_)
+            | Self::MemoryCopy(_)
+            | Self::MemorySet(_)
+            | Self::Store(_) => None,
         }
     }
 
@@ -55,6 +110,7 @@ impl Instruction {
             Self::AtomicLoad(load) => Some(load.type_().clone()),
             Self::AtomicOperation(operation) => Some(operation.type_().into()),
             Self::Call(call) => Some(call.type_().result().clone()),
+            Self::CheckedArithmeticOperation(operation) => Some(operation.result_type().into()),
             Self::CompareAndSwap(_) => Some(types::Primitive::Boolean.into()),
             Self::DeconstructRecord(deconstruct) => {
                 Some(deconstruct.type_().elements()[deconstruct.element_index()].clone())
@@ -62,11 +118,22 @@ impl Instruction {
             Self::DeconstructUnion(deconstruct) => {
                 Some(deconstruct.type_().members()[deconstruct.member_index()].clone())
             }
+            Self::Fma(fma) => Some(fma.type_().into()),
             Self::If(if_) => Some(if_.type_().clone()),
+            Self::InlineAssembly(assembly) => Some(assembly.type_().clone()),
             Self::Load(load) => Some(load.type_().clone()),
+            Self::MathOperation(operation) => Some(operation.type_().into()),
             Self::PassThrough(pass) => Some(pass.type_().clone()),
             Self::ReallocateHeap(_) => Some(GENERIC_POINTER_TYPE.clone()),
-            Self::AtomicStore(_) | Self::Fence(_) | Self::FreeHeap(_) | Self::Store(_) => None,
+            Self::SaturatingArithmeticOperation(operation) => Some(operation.type_().into()),
+            Self::AtomicStore(_)
+            | Self::Fence(_)
+            | Self::FreeHeap(_)
+            | Self::LifetimeEnd(_)
+            | Self::LifetimeStart(_)
+            | Self::MemoryCopy(_)
+            | Self::MemorySet(_)
+            | Self::Store(_) => None,
         }
     }
 }
@@ -107,6 +174,12 @@ impl From<Call> for Instruction {
     }
 }
 
+impl From<CheckedArithmeticOperation> for Instruction {
+    fn from(operation: CheckedArithmeticOperation) -> Self {
+        Self::CheckedArithmeticOperation(operation)
+    }
+}
+
 impl From<CompareAndSwap> for Instruction {
     fn from(compare_and_swap: CompareAndSwap) -> Self {
         Self::CompareAndSwap(compare_and_swap)
@@ -131,6 +204,12 @@ impl From<Fence> for Instruction {
     }
 }
 
+impl From<Fma> for Instruction {
+    fn from(fma: Fma) -> Self {
+        Self::Fma(fma)
+    }
+}
+
 impl From<FreeHeap> for Instruction {
     fn from(free: FreeHeap) -> Self {
         Self::FreeHeap(free)
@@ -143,12 +222,48 @@ impl From<If> for Instruction {
     }
 }
 
+impl From<InlineAssembly> for Instruction {
+    fn from(assembly: InlineAssembly) -> Self {
+        Self::InlineAssembly(assembly)
+    }
+}
+
+impl From<LifetimeEnd> for Instruction {
+    fn from(end: LifetimeEnd) -> Self {
+        Self::LifetimeEnd(end)
+    }
+}
+
+impl From<LifetimeStart> for Instruction {
+    fn from(start: LifetimeStart) -> Self {
+        Self::LifetimeStart(start)
+    }
+}
+
 impl From<Load> for Instruction {
     fn from(load: Load) -> Self {
         Self::Load(load)
     }
 }
 
+impl From<MathOperation> for Instruction {
+    fn from(operation: MathOperation) -> Self {
+        Self::MathOperation(operation)
+    }
+}
+
+impl From<MemoryCopy> for Instruction {
+    fn from(copy: MemoryCopy) -> Self {
+        Self::MemoryCopy(copy)
+    }
+}
+
+impl From<MemorySet> for Instruction {
+    fn from(set: MemorySet) -> Self {
+        Self::MemorySet(set)
+    }
+}
+
 impl From<PassThrough> for Instruction {
     fn from(pass: PassThrough) -> Self {
         Self::PassThrough(pass)
@@ -161,6 +276,12 @@ impl From<ReallocateHeap> for Instruction {
     }
 }
 
+impl From<SaturatingArithmeticOperation> for Instruction {
+    fn from(operation: SaturatingArithmeticOperation) -> Self {
+        Self::SaturatingArithmeticOperation(operation)
+    }
+}
+
 impl From<Store> for Instruction {
     fn from(store: Store) -> Self {
         Self::Store(store)
@@ -0,0 +1,9 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InstructionPathSegment {
+    Instruction(usize),
+    TerminalInstruction,
+    Then,
+    Else,
+}
+
+pub type InstructionPath = Vec<InstructionPathSegment>;
@@ -0,0 +1,18 @@
+use super::expression::Expression;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LifetimeStart {
+    pointer: Expression,
+}
+
+impl LifetimeStart {
+    pub fn new(pointer: impl Into<Expression>) -> Self {
+        Self {
+            pointer: pointer.into(),
+        }
+    }
+
+    pub fn pointer(&self) -> &Expression {
+        &self.pointer
+    }
+}
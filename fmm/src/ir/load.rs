@@ -33,4 +33,36 @@ impl Load {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ir::*, types};
+
+    #[test]
+    fn rename() {
+        let mut load = Load::new(types::Primitive::Float64, Variable::new("p"), "x");
+
+        *load.name_mut() = "y".into();
+
+        assert_eq!(
+            load,
+            Load::new(types::Primitive::Float64, Variable::new("p"), "y")
+        );
+    }
+
+    #[test]
+    fn rename_instruction() {
+        let mut instruction: Instruction =
+            Load::new(types::Primitive::Float64, Variable::new("p"), "x").into();
+
+        *instruction.name_mut().unwrap() = "y".into();
+
+        assert_eq!(instruction.name(), Some("y"));
+    }
 }
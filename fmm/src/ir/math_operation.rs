@@ -0,0 +1,61 @@
+use super::expression::Expression;
+use crate::types;
+use std::sync::Arc;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum MathOperator {
+    Absolute,
+    Ceiling,
+    Cosine,
+    Exponential,
+    Floor,
+    Logarithm,
+    Sine,
+    SquareRoot,
+}
+
+// Operations on floating-point numbers whose results have the same types as
+// operands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MathOperation {
+    type_: types::Primitive,
+    operator: MathOperator,
+    value: Arc<Expression>,
+    name: String,
+}
+
+impl MathOperation {
+    pub fn new(
+        type_: types::Primitive,
+        operator: MathOperator,
+        value: impl Into<Expression>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            type_,
+            operator,
+            value: value.into().into(),
+            name: name.into(),
+        }
+    }
+
+    pub fn type_(&self) -> types::Primitive {
+        self.type_
+    }
+
+    pub fn operator(&self) -> MathOperator {
+        self.operator
+    }
+
+    pub fn value(&self) -> &Expression {
+        &self.value
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
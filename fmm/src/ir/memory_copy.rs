@@ -0,0 +1,42 @@
+use super::expression::Expression;
+
+// Regions of a copy can overlap only if it is marked as overlapping.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryCopy {
+    source: Expression,
+    destination: Expression,
+    size: Expression,
+    overlapping: bool,
+}
+
+impl MemoryCopy {
+    pub fn new(
+        source: impl Into<Expression>,
+        destination: impl Into<Expression>,
+        size: impl Into<Expression>,
+        overlapping: bool,
+    ) -> Self {
+        Self {
+            source: source.into(),
+            destination: destination.into(),
+            size: size.into(),
+            overlapping,
+        }
+    }
+
+    pub fn source(&self) -> &Expression {
+        &self.source
+    }
+
+    pub fn destination(&self) -> &Expression {
+        &self.destination
+    }
+
+    pub fn size(&self) -> &Expression {
+        &self.size
+    }
+
+    pub fn overlapping(&self) -> bool {
+        self.overlapping
+    }
+}
@@ -0,0 +1,35 @@
+use super::expression::Expression;
+
+// A memory set fills bytes with an 8-bit integer value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemorySet {
+    pointer: Expression,
+    value: Expression,
+    size: Expression,
+}
+
+impl MemorySet {
+    pub fn new(
+        pointer: impl Into<Expression>,
+        value: impl Into<Expression>,
+        size: impl Into<Expression>,
+    ) -> Self {
+        Self {
+            pointer: pointer.into(),
+            value: value.into(),
+            size: size.into(),
+        }
+    }
+
+    pub fn pointer(&self) -> &Expression {
+        &self.pointer
+    }
+
+    pub fn value(&self) -> &Expression {
+        &self.value
+    }
+
+    pub fn size(&self) -> &Expression {
+        &self.size
+    }
+}
@@ -41,4 +41,215 @@ impl Module {
     pub fn function_definitions(&self) -> &[FunctionDefinition] {
         &self.function_definitions
     }
+
+    // Unchanged sections are moved into new modules without being cloned.
+    pub fn map_variable_definitions(
+        mut self,
+        map: impl FnMut(VariableDefinition) -> VariableDefinition,
+    ) -> Self {
+        self.variable_definitions = self.variable_definitions.into_iter().map(map).collect();
+        self
+    }
+
+    pub fn map_function_definitions(
+        mut self,
+        map: impl FnMut(FunctionDefinition) -> FunctionDefinition,
+    ) -> Self {
+        self.function_definitions = self.function_definitions.into_iter().map(map).collect();
+        self
+    }
+
+    // Sections are sorted by names so that modules built in different orders
+    // are formatted identically.
+    pub fn canonicalize(mut self) -> Self {
+        self.variable_declarations
+            .sort_by(|one, other| one.name().cmp(other.name()));
+        self.function_declarations
+            .sort_by(|one, other| one.name().cmp(other.name()));
+        self.variable_definitions
+            .sort_by(|one, other| one.name().cmp(other.name()));
+        self.function_definitions
+            .sort_by(|one, other| one.name().cmp(other.name()));
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        analysis::{format_module, transform_to_cps},
+        ir::*,
+        types::{self, CallingConvention},
+    };
+
+    fn create_function_definition(name: &str) -> FunctionDefinition {
+        FunctionDefinition::new(
+            name,
+            vec![],
+            Block::new(
+                vec![],
+                Return::new(
+                    types::Primitive::PointerInteger,
+                    Primitive::PointerInteger(0),
+                ),
+            ),
+            types::Primitive::PointerInteger,
+            CallingConvention::Source,
+            Linkage::External,
+        )
+    }
+
+    fn create_variable_definition(name: &str) -> VariableDefinition {
+        VariableDefinition::new(
+            name,
+            Primitive::PointerInteger(0),
+            types::Primitive::PointerInteger,
+            false,
+            Linkage::External,
+            None,
+        )
+    }
+
+    #[test]
+    fn map_function_definitions() {
+        let module = Module::new(
+            vec![VariableDeclaration::new(
+                "a",
+                types::Primitive::PointerInteger,
+            )],
+            vec![],
+            vec![create_variable_definition("b")],
+            vec![create_function_definition("c")],
+        );
+        let variable_declarations = module.variable_declarations().as_ptr();
+        let variable_definitions = module.variable_definitions().as_ptr();
+
+        let module = module.map_function_definitions(|definition| {
+            create_function_definition(&(definition.name().to_owned() + "d"))
+        });
+
+        assert_eq!(
+            module.function_definitions(),
+            &[create_function_definition("cd")]
+        );
+        assert_eq!(
+            module.variable_declarations().as_ptr(),
+            variable_declarations
+        );
+        assert_eq!(module.variable_definitions().as_ptr(), variable_definitions);
+    }
+
+    #[test]
+    fn map_variable_definitions() {
+        let module = Module::new(
+            vec![],
+            vec![],
+            vec![create_variable_definition("a")],
+            vec![create_function_definition("b")],
+        );
+        let function_definitions = module.function_definitions().as_ptr();
+
+        let module = module.map_variable_definitions(|definition| {
+            create_variable_definition(&(definition.name().to_owned() + "c"))
+        });
+
+        assert_eq!(
+            module.variable_definitions(),
+            &[create_variable_definition("ac")]
+        );
+        assert_eq!(module.function_definitions().as_ptr(), function_definitions);
+    }
+
+    #[test]
+    fn canonicalize_differently_ordered_modules() {
+        assert_eq!(
+            format_module(
+                &Module::new(
+                    vec![
+                        VariableDeclaration::new("b", types::Primitive::PointerInteger),
+                        VariableDeclaration::new("a", types::Primitive::PointerInteger),
+                    ],
+                    vec![],
+                    vec![
+                        create_variable_definition("d"),
+                        create_variable_definition("c")
+                    ],
+                    vec![
+                        create_function_definition("f"),
+                        create_function_definition("e")
+                    ],
+                )
+                .canonicalize()
+            ),
+            format_module(
+                &Module::new(
+                    vec![
+                        VariableDeclaration::new("a", types::Primitive::PointerInteger),
+                        VariableDeclaration::new("b", types::Primitive::PointerInteger),
+                    ],
+                    vec![],
+                    vec![
+                        create_variable_definition("c"),
+                        create_variable_definition("d")
+                    ],
+                    vec![
+                        create_function_definition("e"),
+                        create_function_definition("f")
+                    ],
+                )
+                .canonicalize()
+            )
+        );
+    }
+
+    #[test]
+    fn canonicalize_continuations() {
+        let function_type = types::Function::new(
+            vec![],
+            types::Primitive::PointerInteger,
+            CallingConvention::Source,
+        );
+        let module = transform_to_cps(
+            &Module::new(
+                vec![],
+                vec![FunctionDeclaration::new("g", function_type.clone())],
+                vec![],
+                vec![FunctionDefinition::new(
+                    "f",
+                    vec![],
+                    Block::new(
+                        vec![Call::new(function_type, Variable::new("g"), vec![], "x").into()],
+                        Return::new(
+                            types::Primitive::PointerInteger,
+                            ArithmeticOperation::new(
+                                types::Primitive::PointerInteger,
+                                ArithmeticOperator::Add,
+                                Variable::new("x"),
+                                Primitive::PointerInteger(1),
+                            ),
+                        ),
+                    ),
+                    types::Primitive::PointerInteger,
+                    CallingConvention::Source,
+                    Linkage::External,
+                )],
+            ),
+            types::VOID_TYPE.clone(),
+        )
+        .unwrap()
+        .canonicalize();
+        let names = module
+            .function_definitions()
+            .iter()
+            .map(|definition| definition.name())
+            .collect::<Vec<_>>();
+        let mut sorted_names = names.clone();
+
+        sorted_names.sort_unstable();
+
+        assert!(names.len() > 1);
+        assert_eq!(names, sorted_names);
+    }
 }
@@ -0,0 +1,7 @@
+// Overflow of operations with these flags is undefined behavior. Flags are
+// meaningful only for integer operands.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct OverflowFlags {
+    pub no_signed_wrap: bool,
+    pub no_unsigned_wrap: bool,
+}
@@ -6,7 +6,7 @@ use std::sync::Arc;
 pub struct PointerAddress {
     type_: types::Pointer, // type of the pointer value
     pointer: Arc<Expression>,
-    offset: Arc<Expression>,
+    offset: Arc<Expression>, // in elements of the pointer's element type
 }
 
 impl PointerAddress {
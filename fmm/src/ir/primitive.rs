@@ -3,11 +3,19 @@ use crate::types;
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Primitive {
     Boolean(bool),
+    // Half-precision floats are represented by single-precision ones as Rust
+    // doesn't have the former.
+    Float16(f32),
     Float32(f32),
     Float64(f64),
+    // Quadruple-precision floats are limited to the precision of double-precision
+    // ones on construction.
+    Float128(f64),
     Integer8(u8),
+    Integer16(u16),
     Integer32(u32),
     Integer64(u64),
+    Integer128(u128),
     // Pointer integers are signed as it's expected to be architecture-agnostic
     // relative values.
     PointerInteger(i64),
@@ -17,11 +25,15 @@ impl Primitive {
     pub fn type_(&self) -> types::Primitive {
         match self {
             Primitive::Boolean(_) => types::Primitive::Boolean,
+            Primitive::Float16(_) => types::Primitive::Float16,
             Primitive::Float32(_) => types::Primitive::Float32,
             Primitive::Float64(_) => types::Primitive::Float64,
+            Primitive::Float128(_) => types::Primitive::Float128,
             Primitive::Integer8(_) => types::Primitive::Integer8,
+            Primitive::Integer16(_) => types::Primitive::Integer16,
             Primitive::Integer32(_) => types::Primitive::Integer32,
             Primitive::Integer64(_) => types::Primitive::Integer64,
+            Primitive::Integer128(_) => types::Primitive::Integer128,
             Primitive::PointerInteger(_) => types::Primitive::PointerInteger,
         }
     }
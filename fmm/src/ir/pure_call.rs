@@ -0,0 +1,38 @@
+use super::expression::Expression;
+use crate::types;
+use std::sync::Arc;
+
+// Calls to read-none functions in expressions. They are flattened into call
+// instructions before code generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PureCall {
+    type_: types::Function,
+    function: Arc<Expression>,
+    arguments: Vec<Expression>,
+}
+
+impl PureCall {
+    pub fn new(
+        type_: types::Function,
+        function: impl Into<Expression>,
+        arguments: Vec<Expression>,
+    ) -> Self {
+        Self {
+            type_,
+            function: function.into().into(),
+            arguments,
+        }
+    }
+
+    pub fn type_(&self) -> &types::Function {
+        &self.type_
+    }
+
+    pub fn function(&self) -> &Expression {
+        &self.function
+    }
+
+    pub fn arguments(&self) -> &[Expression] {
+        &self.arguments
+    }
+}
@@ -0,0 +1,62 @@
+use super::{arithmetic_operation::ArithmeticOperator, expression::Expression};
+use crate::types;
+use std::sync::Arc;
+
+// Results are clamped to the minimum or maximum values on overflow.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaturatingArithmeticOperation {
+    type_: types::Primitive,
+    operator: ArithmeticOperator,
+    signed: bool,
+    lhs: Arc<Expression>,
+    rhs: Arc<Expression>,
+    name: String,
+}
+
+impl SaturatingArithmeticOperation {
+    pub fn new(
+        type_: types::Primitive,
+        operator: ArithmeticOperator,
+        signed: bool,
+        lhs: impl Into<Expression>,
+        rhs: impl Into<Expression>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            type_,
+            operator,
+            signed,
+            lhs: lhs.into().into(),
+            rhs: rhs.into().into(),
+            name: name.into(),
+        }
+    }
+
+    pub fn type_(&self) -> types::Primitive {
+        self.type_
+    }
+
+    pub fn operator(&self) -> ArithmeticOperator {
+        self.operator
+    }
+
+    pub fn signed(&self) -> bool {
+        self.signed
+    }
+
+    pub fn lhs(&self) -> &Expression {
+        &self.lhs
+    }
+
+    pub fn rhs(&self) -> &Expression {
+        &self.rhs
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+}
@@ -0,0 +1,43 @@
+use super::expression::Expression;
+use crate::types::Type;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Select {
+    type_: Type,
+    condition: Arc<Expression>,
+    then: Arc<Expression>,
+    else_: Arc<Expression>,
+}
+
+impl Select {
+    pub fn new(
+        type_: impl Into<Type>,
+        condition: impl Into<Expression>,
+        then: impl Into<Expression>,
+        else_: impl Into<Expression>,
+    ) -> Self {
+        Self {
+            type_: type_.into(),
+            condition: Arc::new(condition.into()),
+            then: Arc::new(then.into()),
+            else_: Arc::new(else_.into()),
+        }
+    }
+
+    pub fn type_(&self) -> &Type {
+        &self.type_
+    }
+
+    pub fn condition(&self) -> &Expression {
+        &self.condition
+    }
+
+    pub fn then(&self) -> &Expression {
+        &self.then
+    }
+
+    pub fn else_(&self) -> &Expression {
+        &self.else_
+    }
+}
@@ -4,6 +4,7 @@ use super::{branch::Branch, return_::Return};
 pub enum TerminalInstruction {
     Branch(Branch),
     Return(Return),
+    Trap,
     Unreachable,
 }
 
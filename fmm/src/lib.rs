@@ -1,4 +1,8 @@
 pub mod analysis;
 pub mod build;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 pub mod ir;
+#[cfg(test)]
+mod test_support;
 pub mod types;
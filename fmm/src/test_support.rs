@@ -0,0 +1,107 @@
+use crate::{analysis::check_types, ir::*};
+
+// Asserts that a module is type-correct before and after a pass and returns
+// the transformed module.
+pub fn assert_preserves_types(pass: impl Fn(&Module) -> Module, module: &Module) -> Module {
+    if let Err(error) = check_types(module) {
+        panic!("invalid module before pass: {}", error);
+    }
+
+    let transformed = pass(module);
+
+    if let Err(error) = check_types(&transformed) {
+        panic!("invalid module after pass: {}", error);
+    }
+
+    transformed
+}
+
+// Asserts that applying a pass twice results in the same module as applying
+// it once.
+pub fn assert_idempotent(pass: impl Fn(&Module) -> Module, module: &Module) -> Module {
+    let transformed = assert_preserves_types(&pass, module);
+
+    pretty_assertions::assert_eq!(assert_preserves_types(&pass, &transformed), transformed);
+
+    transformed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types;
+
+    fn create_module(value: f64) -> Module {
+        Module::new(
+            vec![],
+            vec![],
+            vec![],
+            vec![FunctionDefinition::new(
+                "f",
+                vec![],
+                Block::new(
+                    vec![],
+                    Return::new(types::Primitive::Float64, Primitive::Float64(value)),
+                ),
+                types::Primitive::Float64,
+                types::CallingConvention::Target,
+                Linkage::Internal,
+            )],
+        )
+    }
+
+    #[test]
+    fn assert_identity_pass() {
+        let module = create_module(42.0);
+
+        assert_eq!(assert_idempotent(|module| module.clone(), &module), module);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_on_pass_breaking_types() {
+        assert_preserves_types(
+            |_| {
+                Module::new(
+                    vec![],
+                    vec![],
+                    vec![],
+                    vec![FunctionDefinition::new(
+                        "f",
+                        vec![],
+                        Block::new(
+                            vec![],
+                            Return::new(types::Primitive::Float64, Primitive::Boolean(true)),
+                        ),
+                        types::Primitive::Float64,
+                        types::CallingConvention::Target,
+                        Linkage::Internal,
+                    )],
+                )
+            },
+            &create_module(42.0),
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_on_non_idempotent_pass() {
+        assert_idempotent(
+            |module| {
+                let value = match module.function_definitions()[0]
+                    .body()
+                    .terminal_instruction()
+                {
+                    TerminalInstruction::Return(return_) => match return_.expression() {
+                        Expression::Primitive(Primitive::Float64(value)) => *value,
+                        _ => unreachable!(),
+                    },
+                    _ => unreachable!(),
+                };
+
+                create_module(value + 1.0)
+            },
+            &create_module(42.0),
+        );
+    }
+}
@@ -1,5 +1,6 @@
 mod calling_convention;
 mod function;
+mod opaque;
 mod pointer;
 mod primitive;
 mod record;
@@ -9,6 +10,7 @@ mod utilities;
 
 pub use calling_convention::*;
 pub use function::*;
+pub use opaque::*;
 pub use pointer::*;
 pub use primitive::*;
 pub use record::*;
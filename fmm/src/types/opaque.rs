@@ -0,0 +1,15 @@
+// Opaque types have no known bodies until they are resolved by names.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Opaque {
+    name: String,
+}
+
+impl Opaque {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
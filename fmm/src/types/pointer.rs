@@ -1,19 +1,53 @@
 use super::type_::Type;
-use std::sync::Arc;
+use std::{
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+// Lengths are metadata for bounds checking.
+#[derive(Clone, Debug)]
 pub struct Pointer {
     element: Arc<Type>,
+    length: Option<usize>,
 }
 
 impl Pointer {
     pub fn new(element: impl Into<Type>) -> Self {
         Self {
             element: element.into().into(),
+            length: None,
+        }
+    }
+
+    pub fn bounded(element: impl Into<Type>, length: usize) -> Self {
+        Self {
+            element: element.into().into(),
+            length: Some(length),
         }
     }
 
     pub fn element(&self) -> &Type {
         &self.element
     }
+
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+}
+
+// Lengths are ignored in equality and hashing so that bounded pointers can be
+// passed wherever pointers to the same elements are expected and they compile
+// to the same backend types.
+impl PartialEq for Pointer {
+    fn eq(&self, other: &Self) -> bool {
+        self.element == other.element
+    }
+}
+
+impl Eq for Pointer {}
+
+impl Hash for Pointer {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.element.hash(hasher);
+    }
 }
@@ -1,12 +1,18 @@
 // TODO Split float and integer primitive types.
 // Allow only integer types in bitwise operations.
+// 80-bit extended precision floats are not supported because they are specific
+// to x86 and C has no portable type for them.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Primitive {
     Boolean,
+    Float16,
     Float32,
     Float64,
+    Float128,
     Integer8,
+    Integer16,
     Integer32,
     Integer64,
+    Integer128,
     PointerInteger,
 }
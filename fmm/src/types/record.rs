@@ -1,13 +1,30 @@
 use super::type_::Type;
 
+// Named records are nominal and distinguished from records of the same
+// elements with different or no names.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct Record {
+    name: Option<String>,
     elements: Vec<Type>,
 }
 
 impl Record {
     pub const fn new(elements: Vec<Type>) -> Self {
-        Self { elements }
+        Self {
+            name: None,
+            elements,
+        }
+    }
+
+    pub fn named(name: impl Into<String>, elements: Vec<Type>) -> Self {
+        Self {
+            name: Some(name.into()),
+            elements,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
     }
 
     pub fn elements(&self) -> &[Type] {
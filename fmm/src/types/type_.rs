@@ -1,10 +1,13 @@
 use super::{
-    function::Function, pointer::Pointer, primitive::Primitive, record::Record, union::Union,
+    function::Function, opaque::Opaque, pointer::Pointer, primitive::Primitive, record::Record,
+    union::Union,
 };
+use crate::analysis::{type_alignment, type_size, TargetDataLayout};
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Type {
     Function(Function),
+    Opaque(Opaque),
     Record(Record),
     Primitive(Primitive),
     Pointer(Pointer),
@@ -20,6 +23,14 @@ impl Type {
         }
     }
 
+    pub fn to_opaque(&self) -> Option<&Opaque> {
+        if let Type::Opaque(opaque) = self {
+            Some(opaque)
+        } else {
+            None
+        }
+    }
+
     pub fn to_record(&self) -> Option<&Record> {
         if let Type::Record(record) = self {
             Some(record)
@@ -44,6 +55,10 @@ impl Type {
         }
     }
 
+    pub fn pointer_element(&self) -> Option<&Type> {
+        self.to_pointer().map(|pointer| pointer.element())
+    }
+
     pub fn to_union(&self) -> Option<&Union> {
         if let Type::Union(union) = self {
             Some(union)
@@ -51,6 +66,49 @@ impl Type {
             None
         }
     }
+
+    pub fn is_scalar(&self) -> bool {
+        matches!(
+            self,
+            Type::Function(_) | Type::Pointer(_) | Type::Primitive(_)
+        )
+    }
+
+    // Records are checked element by element so that large ones are rejected
+    // without calculating their whole layouts.
+    pub fn fits_in_words(&self, count: usize, layout: &TargetDataLayout) -> bool {
+        let pointer_size = layout.pointer_size();
+        let limit = count * pointer_size;
+
+        match self {
+            Type::Function(_) | Type::Pointer(_) => pointer_size <= limit,
+            Type::Opaque(_) => false,
+            Type::Primitive(_) | Type::Union(_) => {
+                type_size(self, pointer_size).is_ok_and(|size| size <= limit)
+            }
+            Type::Record(record) => {
+                let mut size = 0usize;
+
+                for element in record.elements() {
+                    size = match (
+                        type_size(element, pointer_size),
+                        type_alignment(element, pointer_size),
+                    ) {
+                        (Ok(element_size), Ok(alignment)) => {
+                            size.div_ceil(alignment) * alignment + element_size
+                        }
+                        _ => return false,
+                    };
+
+                    if size > limit {
+                        return false;
+                    }
+                }
+
+                type_size(self, pointer_size).is_ok_and(|size| size <= limit)
+            }
+        }
+    }
 }
 
 impl From<Function> for Type {
@@ -59,6 +117,12 @@ impl From<Function> for Type {
     }
 }
 
+impl From<Opaque> for Type {
+    fn from(opaque: Opaque) -> Self {
+        Self::Opaque(opaque)
+    }
+}
+
 impl From<Record> for Type {
     fn from(record: Record) -> Self {
         Self::Record(record)
@@ -82,3 +146,66 @@ impl From<Union> for Type {
         Self::Union(union)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_pointer_element() {
+        assert_eq!(
+            Type::from(Pointer::new(Primitive::Float64)).pointer_element(),
+            Some(&Primitive::Float64.into())
+        );
+    }
+
+    #[test]
+    fn check_scalar_types() {
+        assert!(Type::from(Primitive::Float64).is_scalar());
+        assert!(Type::from(Pointer::new(Primitive::Float64)).is_scalar());
+        assert!(!Type::from(Record::new(vec![Primitive::Float64.into()])).is_scalar());
+    }
+
+    #[test]
+    fn fit_primitive_in_word() {
+        assert!(Type::from(Primitive::Integer64).fits_in_words(1, &TargetDataLayout::new(8, 8)));
+        assert!(!Type::from(Primitive::Integer128).fits_in_words(1, &TargetDataLayout::new(8, 8)));
+    }
+
+    #[test]
+    fn fit_small_record_in_two_words() {
+        assert!(Type::from(Record::new(vec![
+            Primitive::Integer64.into(),
+            Primitive::Integer32.into(),
+        ]))
+        .fits_in_words(2, &TargetDataLayout::new(8, 8)));
+    }
+
+    #[test]
+    fn do_not_fit_large_record_in_two_words() {
+        assert!(!Type::from(Record::new(vec![
+            Primitive::Integer64.into(),
+            Primitive::Integer64.into(),
+            Primitive::Integer64.into(),
+        ]))
+        .fits_in_words(2, &TargetDataLayout::new(8, 8)));
+    }
+
+    #[test]
+    fn do_not_fit_opaque_type() {
+        assert!(!Type::from(Opaque::new("foo")).fits_in_words(2, &TargetDataLayout::new(8, 8)));
+    }
+
+    #[test]
+    fn get_no_pointer_element_of_non_pointer() {
+        assert_eq!(Type::from(Primitive::Float64).pointer_element(), None);
+    }
+
+    #[test]
+    fn ignore_pointer_lengths_in_equality() {
+        assert_eq!(
+            Type::from(Pointer::bounded(Primitive::Float64, 4)),
+            Pointer::new(Primitive::Float64).into()
+        );
+    }
+}